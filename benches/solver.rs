@@ -0,0 +1,52 @@
+//! How long the solver takes to rank guesses, with and without the pattern cache.
+//!
+//! Run with `cargo bench`. A hint after the first guess should stay well under
+//! 50ms, so it doesn't hold up the board.
+
+use std::time::Instant;
+
+use cl_wordle::{
+    diff,
+    solver::{self, PatternCache, Ranking, Strategy},
+    state::Guess,
+    words,
+};
+
+/// Runs `f` the given number of times, and prints the mean time it took
+fn bench<T>(name: &str, runs: u32, mut f: impl FnMut() -> T) {
+    let start = Instant::now();
+    for _ in 0..runs {
+        std::hint::black_box(f());
+    }
+    let mean = start.elapsed() / runs;
+    println!("{:<48} {:>10.2}ms", name, mean.as_secs_f64() * 1000.0);
+}
+
+fn main() {
+    bench("scoring every pair of solutions", 1, || {
+        let cache = PatternCache::new(words::FINAL).unwrap();
+        cache.precompute();
+        cache
+    });
+
+    // the opening is the slowest, and fills in every row the rest look up. It's only
+    // worked out once, so there's only one run to time
+    bench("hint for the first guess", 1, || solver::explain(&[]));
+
+    let history = [Guess("sagaz", diff("sagaz", "termo"))];
+    bench("hint after a guess", 20, || solver::explain(&history));
+    bench("suggestions after a guess", 20, || {
+        solver::suggest(&history, Ranking::Entropy, 5)
+    });
+
+    let candidates = solver::candidates(&history, words::FINAL);
+    bench("best guess after a guess", 20, || {
+        Strategy::Entropy.best_guess(&candidates)
+    });
+    // a word from outside the bundled solutions means none of them can be looked up
+    let mut uncached = candidates.clone();
+    uncached.push("xyzzy");
+    bench("best guess after a guess, without the cache", 20, || {
+        Strategy::Entropy.best_guess(&uncached)
+    });
+}
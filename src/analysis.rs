@@ -0,0 +1,217 @@
+//! Rates each guess of a finished game, like the NYT's WordleBot.
+//!
+//! A guess's skill is how much it could be expected to narrow down the solutions
+//! still possible, next to the [solver's](crate::solver::explain) best guess, and its
+//! luck is how its colours turned out next to the colours it could have got.
+//!
+//! Only the bundled solutions are counted, so a game with words from anywhere else
+//! is only rated while its guesses fit some of them, and a game of another length or
+//! won with a word that isn't bundled isn't rated at all.
+
+use std::fmt;
+
+use crate::{
+    solver::{self, Candidates},
+    state::Guess,
+    words, Match,
+};
+
+/// How one guess went
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GuessAnalysis {
+    pub word: String,
+    /// Solutions still possible before the guess
+    pub before: usize,
+    /// Solutions still possible after it, which is 1 once the solution is guessed
+    pub after: usize,
+    /// Information the guess could be expected to give, in bits
+    pub bits: f64,
+    /// The solver's best guess instead, when it could be expected to give more
+    pub better: Option<&'static str>,
+    /// The guess's bits out of the best guess's, from 0 to 100. A guess that isn't the
+    /// last solution left scores 0 then, as only guessing it could still win
+    pub skill: u8,
+    /// How many of the colours the guess could have got would have left more
+    /// solutions, from 0 to 100, with ties counted as half. 50 is as lucky as average
+    pub luck: u8,
+}
+
+/// Every guess of a game, rated
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Analysis {
+    pub guesses: Vec<GuessAnalysis>,
+    /// The average skill of the guesses, from 0 to 100
+    pub skill: u8,
+    /// The average luck of the guesses, from 0 to 100
+    pub luck: u8,
+}
+
+impl Analysis {
+    /// Rates the guesses in order. Any after the first one that leaves none of the
+    /// bundled solutions aren't rated, and none are when the words aren't
+    /// [`WORD_LEN`](crate::WORD_LEN) letters long or the game was won with a word that
+    /// isn't a bundled solution
+    pub fn new(history: &[Guess<'_>]) -> Self {
+        let bundled = history.iter().all(|Guess(word, matches)| {
+            word.chars().count() == crate::WORD_LEN
+                && (matches.0.iter().any(|&m| m != Match::Exact) || words::FINAL.contains(word))
+        });
+        if !bundled {
+            return Self::default();
+        }
+        let mut guesses = Vec::with_capacity(history.len());
+        for (i, Guess(word, matches)) in history.iter().enumerate() {
+            let remaining = solver::candidates(&history[..i], words::FINAL);
+            if remaining.is_empty() {
+                break;
+            }
+            let explanation = solver::explain(&history[..i]);
+            let total = remaining.len() as f64;
+            let buckets = Candidates::new(&remaining).buckets(word);
+            // a guess that can only get one colouring comes out as -0
+            let bits = solver::entropy_of(&buckets, total).abs();
+            let best = explanation.best().expect("there's a solution left");
+
+            let skill = if best.entropy > 0.0 {
+                (bits / best.entropy).min(1.0)
+            } else if remaining == [*word] {
+                1.0
+            } else {
+                0.0
+            };
+            let got = buckets[usize::from(solver::encode(matches))];
+            let luck = buckets
+                .iter()
+                .map(|&n| match n.cmp(&got) {
+                    std::cmp::Ordering::Greater => n as f64,
+                    std::cmp::Ordering::Equal => n as f64 / 2.0,
+                    std::cmp::Ordering::Less => 0.0,
+                })
+                .sum::<f64>()
+                / total;
+            guesses.push(GuessAnalysis {
+                word: (*word).to_owned(),
+                before: remaining.len(),
+                after: got,
+                bits,
+                better: (best.entropy > bits + 1e-9 && best.word != *word).then_some(best.word),
+                skill: (skill * 100.0).round() as u8,
+                luck: (luck * 100.0).round() as u8,
+            });
+        }
+        Self {
+            skill: average(guesses.iter().map(|guess| guess.skill)),
+            luck: average(guesses.iter().map(|guess| guess.luck)),
+            guesses,
+        }
+    }
+}
+
+/// 0 if there's nothing to average
+fn average(scores: impl ExactSizeIterator<Item = u8>) -> u8 {
+    let n = scores.len().max(1) as f64;
+    (scores.map(f64::from).sum::<f64>() / n).round() as u8
+}
+
+/// A table of the guesses with the averages below, like
+///
+/// ```text
+/// Guess  Before  After  Bits  Skill  Luck  Better
+/// SUITE    1341    107   5.1     83    29  TARSO
+/// TERMO     107      1   4.2    100    99
+///
+/// Skill 92, luck 64
+/// ```
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.guesses.iter().map(|guess| guess.word.chars().count());
+        let width = width.max().unwrap_or(0).max("Guess".len());
+        write!(
+            f,
+            "{:width$}  Before  After  Bits  Skill  Luck  Better",
+            "Guess"
+        )?;
+        for guess in &self.guesses {
+            let row = format!(
+                "{:width$}  {:>6}  {:>5}  {:>4.1}  {:>5}  {:>4}  {}",
+                guess.word.to_uppercase(),
+                guess.before,
+                guess.after,
+                guess.bits,
+                guess.skill,
+                guess.luck,
+                guess.better.map_or_else(String::new, str::to_uppercase),
+            );
+            write!(f, "\n{}", row.trim_end())?;
+        }
+        write!(f, "\n\nSkill {}, luck {}", self.skill, self.luck)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Analysis;
+    use crate::{diff, state::Guess, words};
+
+    fn analyse(guesses: &[&'static str]) -> Analysis {
+        let history = guesses
+            .iter()
+            .map(|guess| Guess(guess, diff(guess, "termo")))
+            .collect::<Vec<_>>();
+        Analysis::new(&history)
+    }
+
+    #[test]
+    fn counts_the_solutions_each_guess_left() {
+        let analysis = analyse(&["suite", "termo"]);
+        let (first, last) = (&analysis.guesses[0], &analysis.guesses[1]);
+        assert_eq!(first.before, words::FINAL.len());
+        assert_eq!(first.after, last.before);
+        assert_eq!(last.after, 1);
+        assert!(first.bits > 0.0 && first.bits >= last.bits);
+        assert!(first.skill <= 100 && last.skill <= 100);
+        assert_eq!(analysis.to_string().lines().count(), 5);
+    }
+
+    #[test]
+    fn guessing_the_solution_first_is_lucky() {
+        let analysis = analyse(&["termo"]);
+        assert_eq!(analysis.guesses[0].after, 1);
+        assert!(analysis.guesses[0].luck >= 99);
+    }
+
+    #[test]
+    fn only_the_last_solution_left_is_worth_guessing() {
+        // termo is all that's left after it's guessed, whatever comes next
+        let analysis = analyse(&["termo", "sagaz", "termo"]);
+        let skill = analysis.guesses.iter().map(|guess| guess.skill);
+        assert_eq!(skill.collect::<Vec<_>>()[1..], [0, 100]);
+        assert_eq!(analysis.guesses[1].better, None);
+
+        let analysis = analyse(&["ossos"]);
+        assert!(analysis.guesses[0].better.is_some());
+        assert!(analysis.guesses[0].skill < 100);
+    }
+
+    #[test]
+    fn stops_once_no_bundled_solution_fits() {
+        // no word is both termo and sagaz, so the guess after them can't be rated
+        let history = ["termo", "sagaz", "termo"].map(|guess| Guess(guess, diff(guess, guess)));
+        assert_eq!(Analysis::new(&history).guesses.len(), 2);
+        assert_eq!(Analysis::new(&[]).skill, 0);
+    }
+
+    #[test]
+    fn other_words_are_not_rated() {
+        let six = [
+            Guess("zenith", diff("zenith", "banana")),
+            Guess("banana", diff("banana", "banana")),
+        ];
+        assert_eq!(Analysis::new(&six), Analysis::default());
+        // a five letter win that isn't a bundled solution
+        let unlisted = [Guess("aanas", diff("aanas", "aanas"))];
+        assert!(Analysis::new(&unlisted).guesses.is_empty());
+    }
+}
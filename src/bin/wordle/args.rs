@@ -1,8 +1,19 @@
+use cl_wordle::prelude::*;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 use time::macros::format_description;
 
+#[cfg(feature = "tui")]
+use crate::controller::tui::{Align, Cells, Interrupt, Layout, Palette, RevealStyle, ShortWord};
+
+const EXIT_CODES: &str = "EXIT CODES:
+    0    The game was won
+    1    Something went wrong
+    2    The game was lost
+    3    The game was quit before it was over";
+
 #[derive(Parser)]
-#[clap(name = "wordle", author, version)]
+#[clap(name = "wordle", author, version, after_help = EXIT_CODES)]
 pub struct App {
     #[clap(long)]
     #[cfg(feature = "tui")]
@@ -11,10 +22,451 @@ pub struct App {
     /// This disables the TUI and any enriched experience
     pub no_tui: bool,
 
+    #[clap(long)]
+    /// Plays over stdin and stdout, for bots: a guess is read from each line, and
+    /// answered with a line like GYBBG, a letter for each green, yellow or black
+    /// square
+    ///
+    /// Invalid guesses get INVALID and the reason, and a lost game ends with LOST and
+    /// the solution. Nothing else is printed. It's used anyway when neither stdin nor
+    /// stdout is a terminal, unless --no-tui is given
+    pub pipe: bool,
+
+    #[clap(long, conflicts_with = "pipe")]
+    /// Plays a line at a time for screen readers, with nothing told by colour alone
+    ///
+    /// Each guess is answered with its letters spelled out and how each matched, like
+    /// "R correct, A present in the wrong spot, T absent". Press enter on an empty
+    /// line to hear the guesses so far
+    pub accessible: bool,
+
+    #[clap(long, default_value = "100")]
+    #[cfg(feature = "tui")]
+    /// Delay in milliseconds between revealing each letter of a guess
+    ///
+    /// Mimics the tile flip animation. Pressing any key skips the rest of it
+    pub reveal_delay: u64,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Reveal guesses all at once, and don't shake words that aren't accepted
+    ///
+    /// Same as --reveal-delay 0. A --reveal-style of slow is still slow
+    pub no_animation: bool,
+
+    #[clap(long, default_value = "15")]
+    #[cfg(feature = "tui")]
+    /// Ignores presses of the same letter less than this many milliseconds apart
+    ///
+    /// Stops held keys from repeating. Set to 0 to allow key repeat
+    pub debounce: u64,
+
+    #[clap(long, arg_enum, default_value = "top-left")]
+    #[cfg(feature = "tui")]
+    /// Where to place the board in the terminal
+    pub align: Align,
+
+    #[clap(long, arg_enum, default_value = "whole")]
+    #[cfg(feature = "tui")]
+    /// How to show the solution after a loss
+    pub reveal_style: RevealStyle,
+
+    #[clap(long, arg_enum, default_value = "silent")]
+    #[cfg(feature = "tui")]
+    /// What to do when enter is pressed before all 5 letters are typed
+    pub short_word: ShortWord,
+
+    #[clap(long, arg_enum, default_value = "quit")]
+    #[cfg(feature = "tui")]
+    /// What to do when Ctrl+C is pressed during a game
+    pub interrupt: Interrupt,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Starts with the on-screen keyboard hidden
+    ///
+    /// Press Ctrl+K during the game to show or hide it. The choice is saved as
+    /// hide_keyboard in the config file, so the next game starts the same way
+    pub hide_keyboard: bool,
+
+    #[clap(long, conflicts_with = "deduce-keyboard")]
+    #[cfg(feature = "tui")]
+    /// Leave the on-screen keyboard uncoloured, for an extra challenge
+    ///
+    /// What's known of each letter has to be remembered instead. The share is marked
+    /// with a `!` after the score
+    pub no_keyboard_hints: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Mark keys found in place that the solution has more of somewhere else
+    ///
+    /// They're drawn in the close colour on the exact one, such as the R of TERRA once
+    /// one R is found and another is known to be there too
+    pub split_keys: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Grey out keys that can't be in the solution, even if they haven't been guessed
+    ///
+    /// This is a significant assist, as it rules out letters based on every remaining
+    /// possible solution
+    pub deduce_keyboard: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Allows pressing `?` to get a suggested guess, with its score and alternatives
+    pub hint: bool,
+
+    #[clap(long, value_name = "N", default_value = "0")]
+    #[cfg(feature = "tui")]
+    /// Allows pressing Ctrl+R up to N times a game to give away a letter of the solution
+    ///
+    /// Each press says a letter is in the word, or where it goes once it's known to be
+    /// in it. The share says how many were used, like `💡 2 hints`
+    pub letter_hints: usize,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Keeps the 5 best next guesses beside the keyboard, updated after each guess
+    ///
+    /// This is a cheat, meant for practice
+    pub assist: bool,
+
+    #[clap(long, arg_enum, default_value = "entropy")]
+    #[cfg(feature = "tui")]
+    /// How --assist ranks its guesses: by how much they'd narrow down the solutions,
+    /// or by how common their letters are, which is quicker
+    pub assist_by: cl_wordle::solver::Ranking,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Allows pressing Tab to list every solution that's still possible, ranked by how
+    /// common their letters are
+    ///
+    /// This is a cheat, meant for practice
+    pub candidates: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Allows pressing Ctrl+L to show beside the board which letters each position
+    /// could still hold, under how many solutions are left
+    ///
+    /// This is a cheat, meant for practice
+    pub positions: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Allows pressing Ctrl+P to see how the word being typed would be coloured,
+    /// without using up a guess
+    ///
+    /// This is a cheat, so it can't be used in daily games
+    pub preview: bool,
+
+    #[clap(long, default_value = "0")]
+    #[cfg(feature = "tui")]
+    /// Blank columns between the letters on the board
+    pub cell_spacing: u16,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Draws a line between the letters on the board
+    pub cell_separators: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Type by clicking the letters on the keyboard, with enter and delete buttons
+    /// below them
+    ///
+    /// The terminal can't select text while this is on
+    pub mouse: bool,
+
+    #[clap(long, alias = "kb-layout", arg_enum, default_value = "alphabetical")]
+    #[cfg(feature = "tui")]
+    /// The order of the keys on the keyboard, to match the one you type on.
+    /// --kb-layout works too
+    pub layout: Layout,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Also writes out each guess's colours in words, like "T green, E amber", on a
+    /// line below the board for screen readers
+    pub text: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Times each game, from the start until the last guess, and adds the time to
+    /// the share
+    pub timed: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Shows a clock beside the title that starts on the first key pressed. The
+    /// time is kept in the stats, and added to the share
+    pub timer: bool,
+
+    #[clap(long)]
+    /// Leaves the time of a timed game out of the share. It's still kept in the stats
+    pub no_share_time: bool,
+
+    #[clap(long, value_name = "FILE")]
+    #[cfg(feature = "tui")]
+    /// Records every key pressed, with when it was pressed, to play the game back
+    /// with `wordle replay <FILE>`
+    pub record: Option<PathBuf>,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Doesn't copy the result to the clipboard when c is pressed once the game
+    /// is over
+    pub no_clipboard: bool,
+
+    #[clap(long)]
+    /// Uses orange and blue instead of green and yellow, on the board and in the
+    /// share, which are easier to tell apart with colour blindness. The same as
+    /// --theme colorblind
+    pub colorblind: bool,
+
+    #[clap(long, arg_enum)]
+    /// The colours matches are drawn in. Defaults to standard, or mono if NO_COLOR
+    /// is set
+    pub theme: Option<Theme>,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Shows the alphabet below the board, with every letter already guessed dimmed
+    pub alphabet: bool,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// Word list sorted from most to least common, one word per line
+    ///
+    /// Each guess is marked with a bar showing how common it is
+    pub frequencies: Option<PathBuf>,
+
+    #[clap(long)]
+    #[cfg(feature = "tui")]
+    /// More definitions to show below the solution once the game is over, with a word,
+    /// a tab and its definition on each line
+    ///
+    /// These win over the ones bundled, which only cover some of the solutions. When
+    /// there's no definition for the solution, none is shown
+    pub definitions: Option<PathBuf>,
+
+    #[clap(long)]
+    #[cfg(all(feature = "tui", feature = "net"))]
+    /// Look the solution up in Wiktionary when it has no definition bundled
+    pub online_definitions: bool,
+
+    #[clap(long, use_delimiter = true)]
+    #[cfg(feature = "tui")]
+    /// Comma separated messages to show after a win, for 1 guess, 2 guesses and so on
+    ///
+    /// The last message is also used for any guesses after it.
+    /// Defaults to Genius, Magnificent, Impressive, Splendid, Great and Phew
+    pub celebrations: Vec<String>,
+
+    #[clap(long, conflicts_with = "celebrations")]
+    #[cfg(feature = "tui")]
+    /// Don't show any message after a win
+    pub no_celebrations: bool,
+
+    #[clap(long)]
+    /// Rejects guessing the same word more than once
+    pub no_repeats: bool,
+
+    #[clap(long)]
+    /// Hard mode: green letters must stay in place and amber letters must be reused
+    pub hard: bool,
+
+    #[clap(long, overrides_with = "no-hard")]
+    /// Hard mode, and every guess has to fit what's known too: grey letters can't be
+    /// played again and amber ones can't go back where they were
+    pub ultra_hard: bool,
+
+    #[clap(long)]
+    /// Letters match whatever their accents, so `leoes` can be typed for `leões`,
+    /// and its O matches the Õ
+    pub ignore_accents: bool,
+
+    #[clap(long, overrides_with_all = &["hard", "ultra-hard"])]
+    /// Turns hard mode off, when the config file turns it on
+    pub no_hard: bool,
+
+    #[clap(long, default_value = "5")]
+    /// Letters per word in the daily games. Only 5 letter words are bundled, but
+    /// custom games can be any length
+    pub length: usize,
+
+    #[clap(long, alias = "wordlist")]
+    /// Plays the daily games with the words in this file, one per line, instead of
+    /// the bundled lists
+    ///
+    /// The words are both the solutions, in order, and the accepted guesses. They
+    /// have to be as long as --length, and repeats are dropped. The share counts
+    /// these games as custom
+    pub dict: Option<PathBuf>,
+
+    #[clap(long, value_name = "SOURCE", parse(try_from_str = parse_daily), conflicts_with = "dict")]
+    /// Where the daily solutions come from: "bundled" for the bundled list in order,
+    /// "shuffled:<seed>" for the bundled words in an order of the seed's own, or
+    /// "file:<path>" for a JSON file like {"solutions": [...], "accepted": [...]}
+    ///
+    /// Unlike --dict, these games still count as the daily. Without it, the lists from
+    /// `wordle update-words` are played if there are any, or the bundled list
+    pub daily: Option<DailySource>,
+
+    #[clap(long, value_name = "PATH", requires = "dict")]
+    /// Also accepts the guesses in this file, one per line, on top of the --dict
+    /// words. They aren't picked as solutions
+    pub accept_list: Option<PathBuf>,
+
+    #[clap(long = "guess", value_name = "WORD")]
+    /// Plays this guess instead of asking for one. Can be given more than once, and
+    /// the guesses are played in order
+    ///
+    /// Nothing is drawn or read from the terminal, only the result is printed. The
+    /// result isn't recorded in your stats
+    pub guesses: Vec<String>,
+
+    #[clap(long)]
+    #[cfg(feature = "net")]
+    /// Fetches the daily solution from this URL instead of the bundled list
+    ///
+    /// The response should be just the word. If it can't be fetched, the last solution
+    /// fetched today is used, or the bundled daily otherwise
+    pub solution_url: Option<String>,
+
+    #[clap(long, parse(try_from_str = parse_offset), allow_hyphen_values = true)]
+    /// Decides which day it is at this offset from UTC, like +05:30 or -03:00,
+    /// instead of in the local timezone
+    ///
+    /// The daily puzzle then changes at midnight at that offset, wherever this is run
+    pub offset: Option<time::UtcOffset>,
+
+    #[clap(long)]
+    /// Decides which day it is in UTC, so the daily changes at midnight UTC. Wins
+    /// over --offset and --tz
+    pub utc: bool,
+
+    #[clap(long, value_name = "ZONE")]
+    /// Decides which day it is in this timezone, like Europe/Lisbon, from the
+    /// system's timezone database. --offset wins over it
+    ///
+    /// Without any of these, it's the local timezone, or UTC if that can't be found
+    pub tz: Option<String>,
+
+    #[clap(long, alias = "guesses", default_value_t = cl_wordle::MAX_GUESSES)]
+    /// How many guesses there are before the game is lost, like 4 for a challenge or
+    /// 8 for kids. The score is out of this many, and games with more or fewer aren't
+    /// recorded in your stats. Multi gets an extra guess for each board after the first
+    pub max_guesses: usize,
+
+    #[clap(long)]
+    /// Target number of guesses. The result says whether it was beaten, met or missed
+    pub par: Option<usize>,
+
+    #[clap(long, arg_enum, default_value = "full")]
+    /// Which parts of the result to print at the end of the game
+    pub share_style: ShareStyle,
+
+    #[clap(long, value_name = "TEMPLATE")]
+    /// Lays out the result printed and copied, with {day}, {score}, {hard}, {grid},
+    /// {time} and {url} for its parts and \n for a new line, like
+    /// "Termo {day} {score}{hard}\n{grid}"
+    pub share_template: Option<cl_wordle::game::ShareTemplate>,
+
+    #[clap(long)]
+    /// Uses ⬜ for absent letters in the share, for posting on a light background
+    pub white_squares: bool,
+
+    #[clap(long, alias = "output", arg_enum, default_value = "emoji")]
+    #[cfg(feature = "serde")]
+    /// How to print the result at the end of the game. --output works too
+    pub format: Format,
+
+    #[clap(long)]
+    /// Also rates each guess at the end of the game, by how much it could be expected
+    /// to narrow down the solutions and how lucky its colours were
+    ///
+    /// The table goes on stderr, after the result, or in the JSON with --format json.
+    /// In the tui, `a` shows it once the game is over either way
+    pub analysis: bool,
+
+    #[clap(long, value_name = "N", parse(try_from_str = parse_boards))]
+    /// Plays N boards at once instead of the daily game, like 2 for Dordle or 4 for
+    /// Quordle, up to 8. Short for `multi --boards N`
+    pub boards: Option<usize>,
+
+    #[clap(long, value_name = "PATH")]
+    /// Where stats, training progress and the last share are kept, instead of the
+    /// usual data directory
+    pub data_dir: Option<PathBuf>,
+
+    #[clap(long, value_name = "NAME")]
+    /// Keeps stats, streaks and games in progress apart from other people's on the
+    /// same machine. Without one, the files shared before profiles are used
+    pub profile: Option<String>,
+
+    #[clap(long, value_name = "PATH")]
+    #[cfg(feature = "image")]
+    /// Also draws the result as a PNG image at the end of the game, in the
+    /// colorblind colours if they're picked too
+    pub png: Option<std::path::PathBuf>,
+
     #[clap(subcommand)]
     pub game_mode: Option<GameMode>,
 }
 
+#[cfg(feature = "serde")]
+#[derive(clap::ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// The share, as it would be pasted
+    Emoji,
+    /// The game type, score, grid, words guessed and time as JSON, for other tools
+    /// to read. --analysis adds how each guess was rated
+    Json,
+}
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Theme {
+    /// Green for exact matches and yellow for close ones
+    Standard,
+    /// Orange for exact matches and blue for close ones, in the share too
+    Colorblind,
+    /// No colours: exact matches are reversed, close ones underlined and wrong ones
+    /// dimmed. The share is still the usual emoji
+    Mono,
+}
+
+impl App {
+    /// Whether the board and the share use orange and blue
+    pub fn high_contrast(&self) -> bool {
+        self.colorblind || self.theme == Some(Theme::Colorblind)
+    }
+}
+
+#[cfg(feature = "tui")]
+impl App {
+    pub fn cells(&self) -> Cells {
+        Cells {
+            spacing: self.cell_spacing,
+            separators: self.cell_separators,
+            ..Cells::default()
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        match self.theme {
+            Some(Theme::Mono) => Palette::Mono,
+            // asked for by name, so NO_COLOR doesn't get a say
+            Some(_) if self.high_contrast() => Palette::HighContrast,
+            Some(_) => Palette::Standard,
+            None => Palette::detect(self.high_contrast()),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum GameMode {
     /// Play with a custom word
@@ -26,23 +478,483 @@ pub enum GameMode {
     #[cfg(feature = "rand")]
     /// Play a random day
     Random,
+    #[cfg(feature = "tui")]
+    /// Play a list of guesses against a solution and print the final board
+    Render(Render),
+    /// Play a round of a tournament, shared by everyone with the same seed
+    Tournament(Tournament),
+    /// Play random words, starting another with n after each one
+    Practice(Practice),
+    /// Play against a word that keeps changing, like Absurdle. Each guess gets the
+    /// colours that leave the most words possible, and it goes on until it's solved
+    Absurdle,
+    /// Play several words at once, like Dordle or Quordle, with every guess going to
+    /// each board
+    Multi(Multi),
+    #[cfg(feature = "tui")]
+    /// Fill in the guesses of a finished board of the day's word, like Crosswordle.
+    /// The word and the colours of each guess are shown, and any word that would have
+    /// been coloured like its row fits it. It gives the day's word away, so play the
+    /// daily first
+    Crosswordle(Crosswordle),
+    /// Play random words one after another against the clock, then see the total
+    /// and average time
+    Speedrun(Speedrun),
+    /// Play random words one after another until one isn't solved, then see how many
+    /// were and the guesses they took
+    Marathon(Marathon),
+    #[cfg(feature = "tui")]
+    /// Play back a game recorded with --record, with the keys at the pace they were
+    /// pressed, by the rules it was recorded with whatever flags are given
+    Replay(Replay),
+    /// Practise tricky words, with the ones you struggle with coming back sooner
+    Train,
+    /// Create a challenge file to send to a friend, without revealing the word
+    Challenge(CreateChallenge),
+    /// Print a short code for a word to send to a friend, who plays it with `play`.
+    /// Unlike `custom`, the word isn't left in their shell history
+    Seed(CreateSeed),
+    /// Play a seed's code or a challenge file
+    Play(Play),
+    /// Compare shared results of the same game side by side
+    Compare(Compare),
+    #[cfg(feature = "image")]
+    /// Draw a pasted share as a picture, for places that don't line up emoji. It's
+    /// in orange and blue with --theme colorblind, and light with --white-squares
+    Share(Share),
+    #[cfg(feature = "tui")]
+    /// Take turns with a friend on the same terminal: one types a word without it
+    /// showing, and the other guesses it
+    Duel(Duel),
+    #[cfg(feature = "tui")]
+    /// Wait for a friend to join over the network, then race them to the same word,
+    /// each seeing the colours of the other's guesses as they're made
+    Host(Host),
+    #[cfg(feature = "tui")]
+    /// Join a race hosted with `wordle host`
+    Join(Join),
+    #[cfg(feature = "tui")]
+    /// Offer the daily over the network, for anyone to play with telnet without
+    /// installing anything
+    Serve(Serve),
+    #[cfg(feature = "tui")]
+    /// List the solutions that match a pattern
+    Search(Search),
+    #[cfg(feature = "tui")]
+    /// List the solutions that fit known green, amber and absent letters
+    Find(Find),
+    /// Watch the solver play a word, or see how it does on every solution with --all
+    Solve(Solve),
+    /// Rank first guesses by how much they can be expected to narrow down the
+    /// solutions, or see how the ones you've opened your dailies with have done
+    Openers(Openers),
+    /// Print the day number and a hash of its solution, to check a build
+    /// matches another without revealing the word
+    Verify(Verify),
+    /// Show how your daily games have gone: wins, streaks and the spread of guesses
+    Stats(Stats),
+    /// List the profiles made with --profile, with their streaks
+    Profiles,
+    /// Rank everyone's profiles by streak, win percentage and guesses
+    Leaderboard,
+    /// Show how long until the next daily word, at midnight in the local timezone,
+    /// or in UTC with --utc, the --offset given or the --tz zone
+    Next,
+    /// Say whether today's daily has been played, for shell prompts and cron jobs
+    ///
+    /// Prints "played, 4/6" and exits with 0 once it has, or prints "not played yet"
+    /// and exits with 1
+    Status,
+    #[cfg(feature = "net")]
+    /// Download word lists to play the dailies from in place of the bundled ones,
+    /// so they follow edits to the lists without a new release
+    UpdateWords(UpdateWords),
+    #[clap(subcommand)]
+    /// Manage the config file of defaults
+    Config(Config),
+}
+
+#[derive(Subcommand)]
+pub enum Config {
+    /// Write a commented config file to fill in
+    Init {
+        #[clap(long)]
+        /// Replaces the config file if there's one already
+        force: bool,
+    },
 }
 
 #[derive(Parser)]
 pub struct Custom {
+    /// The solution. Left out, it's typed in without showing, so it isn't seen on
+    /// screen or kept in the shell's history
+    pub word: Option<String>,
+
+    #[clap(long = "alias")]
+    /// Alternative spelling that also counts as the solution. Can be repeated
+    pub aliases: Vec<String>,
+
+    #[clap(long)]
+    /// Allows a word that isn't in the word lists, such as a name
+    pub unlisted: bool,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Render {
+    #[clap(long)]
+    pub solution: String,
+
+    /// File with one guess per line. Reads from stdin if not given
+    pub guesses: Option<PathBuf>,
+
+    #[clap(long)]
+    /// Ignore guesses that aren't valid words instead of failing
+    pub skip_invalid: bool,
+}
+
+#[derive(Parser)]
+pub struct Tournament {
+    #[clap(long)]
+    pub seed: String,
+
+    #[clap(long)]
+    pub round: usize,
+}
+
+#[derive(Parser)]
+pub struct Practice {
+    #[clap(long)]
+    /// Picks the first word from this seed instead of at random. It's shown in the
+    /// header and share, so others can play the same word
+    pub seed: Option<u64>,
+}
+
+#[derive(Parser)]
+pub struct Speedrun {
+    #[clap(long, default_value_t = 5)]
+    /// Number of words to play
+    pub rounds: usize,
+
+    #[clap(long)]
+    /// Picks the words from this seed, so others can race the same ones
+    pub seed: Option<u64>,
+}
+
+#[derive(Parser)]
+pub struct Marathon {
+    #[clap(long)]
+    /// Picks the words from this seed, so others can play the same ones
+    pub seed: Option<u64>,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Replay {
+    /// The file written by --record
+    pub file: PathBuf,
+
+    #[clap(long, default_value_t = 1.0, parse(try_from_str = parse_speed))]
+    /// How many times faster than it was recorded to play it back, like 0.5 for
+    /// half speed. It's kept from 0.01 to 100
+    pub speed: f64,
+}
+
+#[derive(Parser)]
+pub struct Multi {
+    /// The solutions, one per board. Random words are picked if none are given
+    pub words: Vec<String>,
+
+    #[clap(long, default_value_t = 2, parse(try_from_str = parse_boards))]
+    /// Number of boards of random words, like 2 for Dordle or 4 for Quordle, up to 8.
+    /// There's an extra guess for each board after the first
+    pub boards: usize,
+
+    #[clap(long)]
+    /// Picks the random words from this seed, as in practice games
+    pub seed: Option<u64>,
+}
+
+#[derive(Parser)]
+pub struct Crosswordle {
+    /// The day whose board to fill in, instead of today's
+    pub day: Option<usize>,
+}
+
+#[derive(Parser)]
+pub struct CreateChallenge {
     pub word: String,
+
+    #[clap(long, short)]
+    /// Where to write the challenge. Writes to stdout if not given
+    pub output: Option<PathBuf>,
+
+    #[clap(long)]
+    /// Name of the dictionary the word was picked from
+    pub dictionary: Option<String>,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "net")]
+pub struct UpdateWords {
+    #[clap(required_unless_present = "bundled")]
+    /// Where to download the solutions from, one per line in the order they're played
+    pub solutions: Option<String>,
+
+    /// Where to download the other words that can be guessed from, one per line. The
+    /// bundled ones are accepted without it
+    pub accepted: Option<String>,
+
+    #[clap(long, conflicts_with_all = &["solutions", "accepted"])]
+    /// Deletes the downloaded lists, going back to the bundled ones
+    pub bundled: bool,
+}
+
+#[derive(Parser)]
+pub struct CreateSeed {
+    /// The word to hide in the code. A random one is picked if not given
+    pub word: Option<String>,
+}
+
+#[derive(Parser)]
+pub struct Play {
+    #[clap(required_unless_present = "challenge", conflicts_with = "challenge")]
+    /// Code made by `wordle seed`
+    pub code: Option<String>,
+
+    #[clap(long)]
+    /// Challenge file to play
+    pub challenge: Option<PathBuf>,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Duel {
+    #[clap(default_value = "player 1")]
+    /// Who types the first word
+    pub first: String,
+
+    #[clap(default_value = "player 2")]
+    /// Who guesses the first word, and types the next
+    pub second: String,
+
+    #[clap(long)]
+    /// Rounds to play, taking turns. Without it, the duel goes on until esc is pressed
+    /// between rounds
+    pub rounds: Option<usize>,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Host {
+    #[clap(long, default_value_t = crate::race::PORT)]
+    /// The port to wait on
+    pub port: u16,
+
+    #[clap(long)]
+    /// The name the other player sees, instead of the user's own
+    pub name: Option<String>,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Join {
+    /// The host's address, as `host` or `host:port`
+    pub address: String,
+
+    #[clap(long)]
+    /// The name the other player sees, instead of the user's own
+    pub name: Option<String>,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Serve {
+    #[clap(long, default_value_t = crate::serve::PORT)]
+    /// The port to take connections on
+    pub port: u16,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Search {
+    /// Letters to match, with `.` for any letter. eg `t..mo`
+    pub pattern: String,
+
+    #[clap(long)]
+    /// Show at most this many words
+    pub limit: Option<usize>,
+}
+
+#[derive(Parser)]
+#[cfg(feature = "tui")]
+pub struct Find {
+    #[clap(long, use_delimiter = true)]
+    /// Letter at a position, counting from 1. eg `c=1`
+    pub green: Vec<cl_wordle::solver::Placement>,
+
+    #[clap(long, use_delimiter = true)]
+    /// Letter in the word, optionally not at a position. eg `a` or `a=3`
+    pub amber: Vec<cl_wordle::solver::Placement>,
+
+    #[clap(long, default_value = "")]
+    /// Letters not in the word. eg `rne`
+    pub absent: String,
+
+    #[clap(long)]
+    /// Show at most this many words
+    pub limit: Option<usize>,
+}
+
+#[derive(Parser)]
+pub struct Solve {
+    #[clap(required_unless_present = "all")]
+    /// The solution for the solver to play, printing each guess it makes
+    pub word: Option<String>,
+
+    #[clap(long, conflicts_with = "word")]
+    /// Plays every solution in the bundled list instead, and prints the average
+    /// number of guesses, how many games would have been lost and the hardest words
+    pub all: bool,
+
+    #[clap(long, arg_enum, default_value = "entropy")]
+    /// How the solver picks each guess out of the words still possible
+    pub strategy: cl_wordle::solver::Strategy,
+
+    #[clap(long, default_value_t = 10)]
+    /// How many of the hardest words to list with --all
+    pub worst: usize,
+}
+
+#[derive(Parser)]
+pub struct Openers {
+    #[clap(long, default_value_t = 10)]
+    /// How many openers to list
+    pub top: usize,
+
+    #[clap(long)]
+    /// Lists the openers of your dailies instead, the most played first, with how
+    /// many were won and the average guesses of the wins
+    pub mine: bool,
+}
+
+#[derive(Parser)]
+pub struct Compare {
+    /// Files with one share each, named after the player. Reads shares one after
+    /// another from stdin if not given
+    pub shares: Vec<PathBuf>,
+}
+
+#[cfg(feature = "image")]
+#[derive(Parser)]
+pub struct Share {
+    /// The share. Reads from stdin if not given
+    pub file: Option<PathBuf>,
+
+    #[clap(long, value_name = "PATH")]
+    /// Where to save the picture: an SVG if it ends in .svg, or else a PNG
+    pub image: PathBuf,
 }
 
 #[derive(Parser)]
 pub struct Day {
-    pub day: usize,
+    /// Without one, past days are listed to pick from
+    pub day: Option<usize>,
+}
+
+#[derive(Parser)]
+pub struct Stats {
+    #[clap(subcommand)]
+    pub command: Option<StatsCommand>,
+}
+
+#[derive(Subcommand)]
+pub enum StatsCommand {
+    /// Print every daily game played, to back them up or move them to another machine
+    Export {
+        #[clap(long, arg_enum, default_value = "csv")]
+        format: StatsFormat,
+    },
+    /// Add the days from an export that aren't in your stats yet. Days already
+    /// played keep their result, and the streaks are worked out again
+    Import {
+        /// The exported games. Reads from stdin if not given
+        file: Option<PathBuf>,
+
+        #[clap(long, arg_enum)]
+        /// Told apart from the contents if not given
+        format: Option<StatsFormat>,
+    },
+    /// Add the dailies from pasted shares, this game's or official ones like
+    /// "Wordle 1,234 4/6", that aren't in your stats yet
+    ///
+    /// They're recorded by the number in the share. Use --profile to keep another
+    /// game's results apart from these dailies
+    ImportShare {
+        /// The shares, one after another. Reads from stdin if not given
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsFormat {
+    /// A line per day of the day, the guesses or X for a loss, and the seconds taken
+    Csv,
+    /// An array of objects with the day, guesses, seconds and opener. Needs the serde
+    /// feature
+    Json,
+}
+
+#[derive(Parser)]
+pub struct Verify {
+    #[clap(long, parse(try_from_str = parse_date))]
+    /// year-month-day. Defaults to today
+    pub date: Option<time::Date>,
 }
 
 #[derive(Parser)]
 pub struct Date {
     #[clap(parse(try_from_str = parse_date))]
-    /// year-month-day
-    pub date: time::Date,
+    /// year-month-day. Without one, past days are listed to pick from
+    pub date: Option<time::Date>,
+}
+
+/// Where the dailies come from, from --daily
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DailySource {
+    Bundled,
+    Shuffled(String),
+    File(PathBuf),
+}
+
+fn parse_daily(input: &str) -> Result<DailySource, String> {
+    match input.split_once(':') {
+        None if input == "bundled" => Ok(DailySource::Bundled),
+        Some(("shuffled", seed)) if !seed.is_empty() => Ok(DailySource::Shuffled(seed.to_owned())),
+        Some(("file", path)) if !path.is_empty() => Ok(DailySource::File(path.into())),
+        _ => Err("expected bundled, shuffled:<seed> or file:<path>".to_owned()),
+    }
+}
+
+fn parse_boards(input: &str) -> Result<usize, String> {
+    let max = cl_wordle::multi::MAX_BOARDS;
+    match input.parse() {
+        Ok(boards) if (1..=max).contains(&boards) => Ok(boards),
+        _ => Err(format!("expected a number of boards from 1 to {}", max)),
+    }
+}
+
+#[cfg(feature = "tui")]
+fn parse_speed(input: &str) -> Result<f64, String> {
+    match input.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed > 0.0 => Ok(speed.clamp(0.01, 100.0)),
+        _ => Err("expected a speed above 0, like 2 or 0.5".to_owned()),
+    }
+}
+
+fn parse_offset(input: &str) -> Result<time::UtcOffset, time::error::Parse> {
+    let description = format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+    time::UtcOffset::parse(input, description)
 }
 
 fn parse_date(input: &str) -> Result<time::Date, time::error::Parse> {
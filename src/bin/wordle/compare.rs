@@ -0,0 +1,160 @@
+use std::{fmt::Write, path::Path};
+
+use cl_wordle::prelude::*;
+
+/// A share to compare, and who it's from
+pub struct Entry {
+    pub name: String,
+    pub share: Result<GameShare, ParseShareError>,
+}
+
+impl Entry {
+    pub fn from_file(path: &Path) -> std::io::Result<Self> {
+        let name = path.file_stem().map_or_else(
+            || path.display().to_string(),
+            |s| s.to_string_lossy().into_owned(),
+        );
+        let share = GameShare::parse(&std::fs::read_to_string(path)?);
+        Ok(Self { name, share })
+    }
+
+    /// Splits pasted shares at each header, naming them by their position
+    pub fn split(input: &str) -> Vec<Self> {
+        let mut shares = Vec::<String>::new();
+        for line in input.lines() {
+            match shares.last_mut() {
+                Some(share) if !line.trim_start().starts_with("joguei") => {
+                    share.push('\n');
+                    share.push_str(line);
+                }
+                _ if line.trim().is_empty() => {}
+                _ => shares.push(line.to_owned()),
+            }
+        }
+        let shares = shares.iter().map(|share| GameShare::parse(share));
+        shares
+            .enumerate()
+            .map(|(i, share)| Entry {
+                name: format!("#{}", i + 1),
+                share,
+            })
+            .collect()
+    }
+}
+
+/// Number of guesses taken, with losses after every win
+fn guesses(share: &GameShare) -> usize {
+    let taken = share
+        .score()
+        .and_then(|score| score.guesses())
+        .unwrap_or(share.grid().len());
+    if share.won() {
+        taken
+    } else {
+        usize::MAX
+    }
+}
+
+/// Lays out the grids side by side, from fewest guesses to most, followed by any
+/// entries that couldn't be compared
+pub fn compare(entries: Vec<Entry>) -> String {
+    let mut out = String::new();
+    let mut errors = Vec::new();
+    let mut shares = Vec::new();
+    for Entry { name, share } in entries {
+        match share {
+            Err(err) => errors.push(format!("{}: {}", name, err)),
+            Ok(share) => {
+                let first = shares
+                    .first()
+                    .map(|(_, first): &(_, GameShare)| first.game_type());
+                match first {
+                    Some(first) if first != share.game_type() => errors.push(format!(
+                        "{}: played #{} instead of #{}",
+                        name,
+                        share.game_type(),
+                        first
+                    )),
+                    _ => shares.push((name, share)),
+                }
+            }
+        }
+    }
+    shares.sort_by_key(|(_, share)| guesses(share));
+
+    if let Some((_, first)) = shares.first() {
+        // each square is 2 columns wide
+        let grid = shares
+            .iter()
+            .flat_map(|(_, share)| share.grid())
+            .map(|row| 2 * row.0.len())
+            .max();
+        let width = shares.iter().map(|(name, _)| name.chars().count()).max();
+        let width = width.unwrap_or(0).max(grid.unwrap_or(10)) + 2;
+
+        let _ = writeln!(out, "Termo {}", first.game_type());
+        for (name, _) in &shares {
+            let _ = write!(out, "{:width$}", name, width = width);
+        }
+        end_line(&mut out);
+        for (_, share) in &shares {
+            let score = match (share.score(), share.max_guesses()) {
+                // there's no limit to be out of
+                (Some(score), usize::MAX) => score.to_string(),
+                (None, usize::MAX) => "?".to_owned(),
+                (Some(score), max) => format!("{}/{}", score, max),
+                (None, max) => format!("?/{}", max),
+            };
+            let _ = write!(out, "{:width$}", score, width = width);
+        }
+        end_line(&mut out);
+
+        let rows = shares
+            .iter()
+            .map(|(_, s)| s.grid().len())
+            .max()
+            .unwrap_or(0);
+        for i in 0..rows {
+            for (_, share) in &shares {
+                match share.grid().get(i) {
+                    Some(row) => {
+                        let pad = width - 2 * row.0.len();
+                        let _ = write!(out, "{}{:pad$}", row, "", pad = pad);
+                    }
+                    None => {
+                        let _ = write!(out, "{:width$}", "", width = width);
+                    }
+                }
+            }
+            end_line(&mut out);
+        }
+    }
+
+    for error in errors {
+        let _ = writeln!(out, "{}", error);
+    }
+    out
+}
+
+fn end_line(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, Entry};
+
+    #[test]
+    fn compare_shares() {
+        let input = "joguei term.ooo #3 X/6\n\n⬛⬛⬛⬛⬛\n\njoguei term.ooo #3 1/6\n\n🟩🟩🟩🟩🟩\n\njoguei term.ooo #4 1/6\n\njoguei term.ooo #3 1/6\n\n🟩🟩x\n";
+        let expected = "Termo 3
+#2          #1
+1/6         X/6
+🟩🟩🟩🟩🟩  ⬛⬛⬛⬛⬛
+#3: played #4 instead of #3
+#4: invalid row in grid
+";
+        assert_eq!(compare(Entry::split(input)), expected);
+    }
+}
@@ -0,0 +1,354 @@
+//! Defaults read from `config.toml` in the config directory, like `~/.config/wordle`
+//!
+//! Only flat `key = value` lines are understood, which is all the settings need.
+//! Each setting is turned into the flag it stands for, put in front of the ones
+//! given on the command line so those win.
+
+use std::{fs, io, path::PathBuf};
+
+use eyre::{bail, Result};
+
+/// Written by `wordle config init`
+pub const TEMPLATE: &str = r#"# Defaults for wordle. Flags given on the command line win over these
+# Uncomment a line to use it
+
+# Colours: "standard", "colorblind" or "mono"
+# theme = "standard"
+
+# Green letters must stay in place and amber letters must be reused.
+# --no-hard turns it off again for one game
+# hard = false
+
+# Hard mode, where guesses also have to fit everything known: grey letters can't
+# be played again and amber letters can't go back where they were
+# ultra_hard = false
+
+# Only Portuguese, "pt", is bundled. Play other languages with --dict <FILE>
+# language = "pt"
+
+# Order of the on-screen keyboard: "alphabetical", "qwerty", "qwertz", "azerty",
+# "dvorak" or "colemak"
+# layout = "alphabetical"
+
+# Letters per word in the daily games. Only 5 letter words are bundled
+# length = 5
+
+# Where the daily words come from: "bundled" in order, "shuffled:<seed>" for the
+# bundled words in an order of their own, or "file:<path>" for a JSON file like
+# {"solutions": [...], "accepted": [...]}
+# daily = "shuffled:our family"
+
+# Where stats, training progress and the last share are kept. ~/ is your home
+# data_dir = "~/.local/share/cl-wordle"
+
+# Whose stats and games in progress to use, when people share the machine
+# profile = "ana"
+
+# How the result is laid out, with {day}, {score}, {hard}, {grid}, {time}, {hints}
+# and {url} for its parts and \n for a new line
+# share_template = "Termo {day} {score}{hard}\n{grid}\n{url}"
+
+# Letters of the solution Ctrl+R can give away each game. The share says how
+# many were
+# letter_hints = 0
+
+# Start with the on-screen keyboard hidden. Ctrl+K shows or hides it, and saves
+# the choice here
+# hide_keyboard = false
+
+# Use ⬜ for absent letters in the share, for light backgrounds
+# white_squares = false
+
+# Where the daily changes at midnight, instead of the local timezone. "UTC" works
+# everywhere, and names like "Europe/Lisbon" need the system's timezone database
+# timezone = "UTC"
+"#;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Value {
+    String(String),
+    Bool(bool),
+    Integer(i64),
+}
+
+pub fn path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("wordle").join("config.toml"))
+}
+
+/// The flags the config file stands for, or none if there isn't one
+pub fn args() -> Result<Vec<String>> {
+    let path = match path() {
+        Some(path) => path,
+        None => return Ok(Vec::new()),
+    };
+    match fs::read_to_string(&path) {
+        Ok(input) => to_args(&input).map_err(|err| eyre::eyre!("{}: {}", path.display(), err)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Writes the commented template, unless there's a config file already
+pub fn init(force: bool) -> Result<PathBuf> {
+    let path = match path() {
+        Some(path) => path,
+        None => bail!("no config directory"),
+    };
+    if path.exists() && !force {
+        bail!(
+            "{} already exists, pass --force to replace it",
+            path.display()
+        );
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, TEMPLATE)?;
+    Ok(path)
+}
+
+/// Sets `key` to `value`, which is written as it is, in the config file. A line
+/// that already sets it is replaced, or else one is added at the end, and the file
+/// is made if there isn't one
+#[cfg(feature = "tui")]
+pub fn set(key: &str, value: &str) -> Result<()> {
+    let path = match path() {
+        Some(path) => path,
+        None => bail!("no config directory"),
+    };
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, with_setting(&input, key, value))?;
+    Ok(())
+}
+
+/// The config file with `key` set, as by [`set`]. Comments are left alone, so the
+/// template's commented out line stays above the one that's added
+#[cfg(feature = "tui")]
+fn with_setting(input: &str, key: &str, value: &str) -> String {
+    let setting = format!("{} = {}", key, value);
+    let mut found = false;
+    let mut lines = input
+        .lines()
+        .map(|line| {
+            let sets = !line.trim_start().starts_with('#')
+                && line
+                    .split_once('=')
+                    .is_some_and(|(name, _)| name.trim() == key);
+            found |= sets;
+            if sets {
+                setting.clone()
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>();
+    if !found {
+        lines.push(setting);
+    }
+    lines.join("\n") + "\n"
+}
+
+fn to_args(input: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    for (key, value) in parse(input)? {
+        match (key.as_str(), value) {
+            ("theme", Value::String(theme)) => args.extend(["--theme".to_owned(), theme]),
+            ("hard", Value::Bool(hard)) => {
+                if hard {
+                    args.push("--hard".to_owned());
+                }
+            }
+            ("ultra_hard", Value::Bool(ultra_hard)) => {
+                if ultra_hard {
+                    args.push("--ultra-hard".to_owned());
+                }
+            }
+            ("language", Value::String(lang)) => {
+                if lang != "pt" {
+                    eprintln!(
+                        "warning: only \"pt\" words are bundled, play \"{}\" with --dict <FILE>",
+                        lang
+                    );
+                }
+            }
+            #[cfg(feature = "tui")]
+            ("layout", Value::String(layout)) => args.extend(["--layout".to_owned(), layout]),
+            // there's no keyboard to lay out without the tui
+            #[cfg(not(feature = "tui"))]
+            ("layout", Value::String(_)) => {}
+            ("daily", Value::String(source)) => args.extend(["--daily".to_owned(), source]),
+            ("length", Value::Integer(length)) => {
+                args.extend(["--length".to_owned(), length.to_string()]);
+            }
+            #[cfg(feature = "tui")]
+            ("letter_hints", Value::Integer(hints)) => {
+                args.extend(["--letter-hints".to_owned(), hints.to_string()]);
+            }
+            // there's no key to ask for them without the tui
+            #[cfg(not(feature = "tui"))]
+            ("letter_hints", Value::Integer(_)) => {}
+            ("data_dir", Value::String(dir)) => {
+                let dir = match (dir.strip_prefix("~/"), dirs::home_dir()) {
+                    (Some(rest), Some(home)) => home.join(rest).display().to_string(),
+                    _ => dir,
+                };
+                args.extend(["--data-dir".to_owned(), dir]);
+            }
+            ("profile", Value::String(profile)) => args.extend(["--profile".to_owned(), profile]),
+            ("timezone", Value::String(zone)) => args.extend(["--tz".to_owned(), zone]),
+            ("share_template", Value::String(template)) => {
+                args.extend(["--share-template".to_owned(), template]);
+            }
+            #[cfg(feature = "tui")]
+            ("hide_keyboard", Value::Bool(hide)) => {
+                if hide {
+                    args.push("--hide-keyboard".to_owned());
+                }
+            }
+            // there's no keyboard to hide without the tui
+            #[cfg(not(feature = "tui"))]
+            ("hide_keyboard", Value::Bool(_)) => {}
+            ("white_squares", Value::Bool(white)) => {
+                if white {
+                    args.push("--white-squares".to_owned());
+                }
+            }
+            (
+                key @ ("theme" | "language" | "layout" | "daily" | "data_dir" | "profile"
+                | "share_template" | "timezone"),
+                _,
+            ) => {
+                bail!("{} should be a string", key);
+            }
+            (key @ ("hard" | "ultra_hard" | "hide_keyboard" | "white_squares"), _) => {
+                bail!("{} should be true or false", key)
+            }
+            (key @ ("length" | "letter_hints"), _) => bail!("{} should be a number", key),
+            (key, _) => eprintln!("warning: ignoring unknown config setting {}", key),
+        }
+    }
+    Ok(args)
+}
+
+fn parse(input: &str) -> Result<Vec<(String, Value)>> {
+    let mut settings = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some(setting) => setting,
+            None => bail!("line {}: expected `key = value`", i + 1),
+        };
+        let value = match parse_value(value.trim()) {
+            Some(value) => value,
+            None => bail!(
+                "line {}: expected a quoted string, true, false or a number",
+                i + 1
+            ),
+        };
+        settings.push((key.trim().to_owned(), value));
+    }
+    Ok(settings)
+}
+
+/// Parses the value and any comment after it
+fn parse_value(input: &str) -> Option<Value> {
+    if let Some(rest) = input.strip_prefix('"') {
+        let (string, rest) = rest.split_once('"')?;
+        let rest = rest.trim_start();
+        return (rest.is_empty() || rest.starts_with('#'))
+            .then(|| Value::String(string.to_owned()));
+    }
+    let value = input.split('#').next().unwrap_or_default().trim_end();
+    match value {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        value => value.parse().ok().map(Value::Integer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "tui")]
+    use super::with_setting;
+    use super::{parse, to_args, Value, TEMPLATE};
+
+    #[test]
+    fn parses_settings() {
+        let input = "# a comment\n\ntheme = \"mono\" # trailing\nhard=true\nlength = 6\n";
+        assert_eq!(
+            parse(input).unwrap(),
+            [
+                ("theme".to_owned(), Value::String("mono".to_owned())),
+                ("hard".to_owned(), Value::Bool(true)),
+                ("length".to_owned(), Value::Integer(6)),
+            ]
+        );
+        assert!(parse("theme = mono").is_err());
+        assert!(parse("[section]").is_err());
+    }
+
+    #[test]
+    fn settings_become_flags() {
+        let args = to_args("theme = \"colorblind\"\nhard = false\ndata_dir = \"/tmp/w\"").unwrap();
+        assert_eq!(args, ["--theme", "colorblind", "--data-dir", "/tmp/w"]);
+        assert!(to_args("length = \"6\"").is_err());
+        #[cfg(feature = "tui")]
+        assert_eq!(
+            to_args("letter_hints = 2").unwrap(),
+            ["--letter-hints", "2"]
+        );
+        assert!(to_args("letter_hints = true").is_err());
+        let args = to_args("timezone = \"Europe/Lisbon\"").unwrap();
+        assert_eq!(args, ["--tz", "Europe/Lisbon"]);
+        assert!(to_args("timezone = 0").is_err());
+        let args = to_args("daily = \"shuffled:our family\"").unwrap();
+        assert_eq!(args, ["--daily", "shuffled:our family"]);
+        let args = to_args("share_template = \"{day} {score}\\n{grid}\"\nwhite_squares = true");
+        assert_eq!(
+            args.unwrap(),
+            [
+                "--share-template",
+                "{day} {score}\\n{grid}",
+                "--white-squares"
+            ]
+        );
+        #[cfg(feature = "tui")]
+        assert_eq!(
+            to_args("hide_keyboard = true").unwrap(),
+            ["--hide-keyboard"]
+        );
+        assert!(to_args("hide_keyboard = 1").is_err());
+        // everything in the template is commented out
+        assert!(to_args(TEMPLATE).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn settings_are_saved_over_the_old_ones() {
+        assert_eq!(
+            with_setting("", "hide_keyboard", "true"),
+            "hide_keyboard = true\n"
+        );
+        let input = "# hide_keyboard = false\nhard = true\nhide_keyboard=true # why\n";
+        assert_eq!(
+            with_setting(input, "hide_keyboard", "false"),
+            "# hide_keyboard = false\nhard = true\nhide_keyboard = false\n"
+        );
+        let saved = with_setting(TEMPLATE, "hide_keyboard", "true");
+        assert!(saved.starts_with(TEMPLATE));
+        assert_eq!(
+            to_args(&saved).unwrap(),
+            to_args("hide_keyboard = true").unwrap()
+        );
+    }
+}
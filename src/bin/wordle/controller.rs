@@ -1,3 +1,5 @@
+pub mod accessible;
+pub mod batch;
 pub mod cli;
 #[cfg(feature = "tui")]
 pub mod tui;
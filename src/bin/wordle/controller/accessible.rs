@@ -0,0 +1,246 @@
+//! Line by line play for screen readers, with `--accessible`.
+//!
+//! Nothing is redrawn and nothing is told by colour alone. Each guess is answered
+//! with a line spelling out every letter and how it matched, like
+//! `R correct, A present in the wrong spot, T absent`, and an empty line reads back
+//! the guesses made so far.
+
+use std::io::{self, BufRead, Write};
+
+use eyre::Result;
+
+use cl_wordle::{
+    multi::{Multi, MultiShare},
+    prelude::*,
+};
+
+type OnGuess = Box<dyn FnMut(&Game)>;
+
+pub struct Controller {
+    game: Game,
+    /// Called after every accepted guess, to save the game as it goes
+    on_guess: Option<OnGuess>,
+}
+
+impl Controller {
+    pub fn new(game: Game) -> Self {
+        Self {
+            game,
+            on_guess: None,
+        }
+    }
+
+    /// Calls `on_guess` with the game after every accepted guess
+    pub fn with_on_guess(mut self, on_guess: impl FnMut(&Game) + 'static) -> Self {
+        self.on_guess = Some(Box::new(on_guess));
+        self
+    }
+
+    /// Plays on stdin and stdout until the game is over, or returns `None` if stdin
+    /// closes first
+    pub fn run(self) -> Result<Option<GameShare>> {
+        self.run_with(io::stdin().lock(), io::stdout().lock())
+    }
+
+    fn run_with(
+        mut self,
+        mut input: impl BufRead,
+        mut output: impl Write,
+    ) -> Result<Option<GameShare>> {
+        let state = self.game.state();
+        let game_type = match self.game.game_type() {
+            GameType::Daily(day) => format!("Daily game {}", day),
+            game_type => format!("Game {}", game_type),
+        };
+        writeln!(
+            output,
+            "{}. Guess the {} letter word in {} guesses. Type a guess and press enter, \
+             or press enter on its own to hear the guesses so far.",
+            game_type,
+            self.game.word_len(),
+            limit(state.max_guesses()),
+        )?;
+        if state.guesses().len() > 0 {
+            writeln!(output, "Resumed where you left off.")?;
+            self.read_back(&mut output)?;
+        }
+
+        let mut line = String::with_capacity(6);
+        let win = loop {
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let word = line.trim();
+            if word.is_empty() {
+                self.read_back(&mut output)?;
+                continue;
+            }
+
+            match self.game.guess(word) {
+                Err(err) => writeln!(output, "{} is not accepted, {}.", word.to_uppercase(), err)?,
+                Ok(matches) => {
+                    let n = self.game.state().guesses().len();
+                    writeln!(output, "{}", self.describe_guess(n, word, &matches))?;
+                    if let Some(on_guess) = &mut self.on_guess {
+                        on_guess(&self.game);
+                    }
+                    if let Some(win) = self.game.game_over() {
+                        break win;
+                    }
+                }
+            }
+        };
+
+        let state = self.game.state();
+        if win {
+            let n = state.guesses().len();
+            let plural = if n == 1 { "guess" } else { "guesses" };
+            writeln!(output, "Solved in {} {}.", n, plural)?;
+        } else {
+            writeln!(
+                output,
+                "Out of guesses. The word was {}.",
+                state.solution().to_uppercase()
+            )?;
+        }
+
+        Ok(Some(self.game.share().with_guesses(&self.game)))
+    }
+
+    /// Each guess so far, in the order they were made
+    fn read_back(&self, output: &mut impl Write) -> io::Result<()> {
+        let guesses = self.game.state().guesses();
+        if guesses.len() == 0 {
+            return writeln!(output, "No guesses yet.");
+        }
+        for (i, Guess(word, matches)) in guesses.enumerate() {
+            writeln!(output, "{}", self.describe_guess(i + 1, word, &matches))?;
+        }
+        Ok(())
+    }
+
+    /// The guess's number, the word and how each of its letters matched
+    fn describe_guess(&self, n: usize, word: &str, matches: &Matches) -> String {
+        format!(
+            "Guess {} of {}, {}: {}.",
+            n,
+            limit(self.game.state().max_guesses()),
+            word.to_uppercase(),
+            describe(word, matches)
+        )
+    }
+}
+
+/// Spells out each letter and how it matched, like
+/// `R correct, A present in the wrong spot, T absent`
+pub fn describe(word: &str, matches: &Matches) -> String {
+    let letters = word.chars().zip(&matches.0).map(|(c, m)| {
+        let matched = match m {
+            Match::Exact => "correct",
+            Match::Close => "present in the wrong spot",
+            Match::Wrong => "absent",
+        };
+        format!("{} {}", c.to_uppercase(), matched)
+    });
+    letters.collect::<Vec<_>>().join(", ")
+}
+
+/// The number of guesses allowed, in words when there's no limit
+fn limit(max_guesses: usize) -> String {
+    match max_guesses {
+        usize::MAX => "unlimited".to_owned(),
+        max => max.to_string(),
+    }
+}
+
+/// Plays every board at once, describing how each guess matched on each board
+/// that wasn't already solved
+pub fn play_multi(mut multi: Multi) -> Result<Option<MultiShare>> {
+    let stdin = io::stdin();
+    let mut line = String::with_capacity(6);
+    println!(
+        "Guess {} words of {} letters at once, in {} guesses.",
+        multi.boards().len(),
+        multi.word_len(),
+        multi.max_guesses()
+    );
+
+    while multi.game_over().is_none() {
+        line.clear();
+        if stdin.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let word = line.trim();
+        match multi.guess(word) {
+            Err(err) => println!("{} is not accepted, {}.", word.to_uppercase(), err),
+            Ok(boards) => {
+                for (i, matches) in boards.iter().enumerate() {
+                    match matches {
+                        Some(matches) if matches.0.iter().all(|&m| m == Match::Exact) => {
+                            println!("Board {}: {} solved.", i + 1, word.to_uppercase());
+                        }
+                        Some(matches) => println!("Board {}: {}.", i + 1, describe(word, matches)),
+                        None => println!("Board {}: already solved.", i + 1),
+                    }
+                }
+            }
+        }
+    }
+
+    for (i, board) in multi.boards().iter().enumerate() {
+        if board.game_over() == Some(false) {
+            println!(
+                "Board {} was not solved. The word was {}.",
+                i + 1,
+                board.state().solution().to_uppercase()
+            );
+        }
+    }
+
+    Ok(Some(multi.share()))
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::{diff, prelude::*};
+
+    use super::{describe, Controller};
+
+    #[test]
+    fn letters_are_described_in_words() {
+        assert_eq!(
+            describe("terra", &diff("terra", "termo")),
+            "T correct, E correct, R correct, R absent, A absent"
+        );
+        assert_eq!(
+            describe("sorte", &diff("sorte", "termo")),
+            "S absent, O present in the wrong spot, R correct, \
+             T present in the wrong spot, E present in the wrong spot"
+        );
+    }
+
+    #[test]
+    fn answers_each_line() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let mut output = Vec::new();
+        let share = Controller::new(game)
+            .run_with(&b"xxxxx\nterra\n\ntermo\n"[..], &mut output)
+            .unwrap()
+            .unwrap();
+        assert!(share.won());
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "XXXXX is not accepted, not in the word list.",
+                "Guess 1 of 6, TERRA: T correct, E correct, R correct, R absent, A absent.",
+                "Guess 1 of 6, TERRA: T correct, E correct, R correct, R absent, A absent.",
+                "Guess 2 of 6, TERMO: T correct, E correct, R correct, M correct, O correct.",
+                "Solved in 2 guesses.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use eyre::{bail, Result};
+
+use cl_wordle::prelude::*;
+
+/// Plays guesses given up front, without reading or drawing anything
+pub struct Controller {
+    game: Game,
+    guesses: Vec<String>,
+}
+
+impl Controller {
+    pub fn new(game: Game, guesses: Vec<String>) -> Self {
+        Self { game, guesses }
+    }
+
+    /// Plays every guess in order, or returns `None` if they run out before the game
+    /// is over. Fails on the first guess that isn't accepted
+    pub fn run(mut self) -> Result<Option<GameShare>> {
+        for word in &self.guesses {
+            let word = word.to_lowercase();
+            match self.game.guess(&word) {
+                Ok(_) => {}
+                Err(GuessError::GameOver) => {
+                    bail!("game was already over before guess '{}'", word)
+                }
+                Err(err) => bail!("invalid guess '{}': {}", word, err),
+            }
+        }
+
+        if self.game.game_over().is_none() {
+            eprintln!("the guesses ran out before the game was over");
+            return Ok(None);
+        }
+        Ok(Some(self.game.share().with_guesses(&self.game)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::prelude::*;
+
+    use super::Controller;
+
+    fn play(guesses: &[&str]) -> eyre::Result<Option<GameShare>> {
+        let game = Game::from_day(0).unwrap();
+        Controller::new(game, guesses.iter().map(|&g| g.to_owned()).collect()).run()
+    }
+
+    #[test]
+    fn plays_every_guess() {
+        let share = play(&["suite", "TERMO"]).unwrap().unwrap();
+        assert_eq!(share.score(), Some(Score::Guesses(2)));
+        assert_eq!(play(&["suite"]).unwrap(), None);
+    }
+
+    #[test]
+    fn names_the_invalid_guess() {
+        let err = play(&["suite", "xxxxx"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid guess 'xxxxx': not in the word list"
+        );
+        let err = play(&["termo", "suite"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "game was already over before guess 'suite'"
+        );
+    }
+}
@@ -1,31 +1,99 @@
-use std::io::stdin;
+use std::io::{stdin, stdout, Write};
 
 use eyre::Result;
 
-use cl_wordle::game::{Game, GameShare};
+use cl_wordle::{
+    multi::{Multi, MultiShare},
+    prelude::*,
+};
+
+type OnGuess = Box<dyn FnMut(&Game)>;
 
 pub struct Controller {
     game: Game,
+    /// Called after every accepted guess, to save the game as it goes
+    on_guess: Option<OnGuess>,
+    /// Answers in letters for bots, as with `--pipe`, instead of squares
+    pipe: bool,
+    /// Answers with the whole board in plain text, for terminals the tui can't be
+    /// drawn in
+    board: bool,
 }
 
 impl Controller {
     pub fn new(game: Game) -> Self {
-        Self { game }
+        Self {
+            game,
+            on_guess: None,
+            pipe: false,
+            board: false,
+        }
     }
 
-    pub fn run(mut self) -> Result<GameShare> {
+    /// Prints the board after each guess, with the rows left blank, and asks for the
+    /// next guess. It's all plain text, so it can be read without a terminal
+    pub fn with_board(mut self, board: bool) -> Self {
+        self.board = board;
+        self
+    }
+
+    /// Answers each guess with a letter per square, like `GYBBG`, and gives the reason
+    /// a guess is invalid
+    pub fn with_pipe(mut self, pipe: bool) -> Self {
+        self.pipe = pipe;
+        self
+    }
+
+    /// Calls `on_guess` with the game after every accepted guess
+    pub fn with_on_guess(mut self, on_guess: impl FnMut(&Game) + 'static) -> Self {
+        self.on_guess = Some(Box::new(on_guess));
+        self
+    }
+
+    /// Plays until the game is over, or returns `None` if stdin closes first.
+    ///
+    /// A resumed game's guesses so far are listed on stderr, so only new guesses
+    /// are answered on stdout
+    pub fn run(mut self) -> Result<Option<GameShare>> {
         let stdin = stdin();
         let mut line = String::with_capacity(6);
 
+        if self.game.state().guesses().len() > 0 {
+            eprintln!("resumed where you left off:");
+            for Guess(word, matches) in self.game.state().guesses() {
+                eprintln!("{} {}", word.to_uppercase(), matches);
+            }
+        }
+
+        if self.board {
+            println!("Termo {}\n", self.game.game_type());
+            self.print_board();
+        }
         let win = loop {
+            if self.board {
+                print!("guess: ");
+                stdout().flush()?;
+            }
             line.clear();
-            stdin.read_line(&mut line)?;
+            if stdin.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
 
-            match self.game.state_mut().guess(line.trim_end()) {
+            match self.game.guess(line.trim_end()) {
+                Err(err) if self.pipe || self.board => println!("INVALID {}", err),
                 Err(_) => println!("INVALID"),
                 Ok(m) => {
-                    println!("{}", m);
-                    if let Some(win) = self.game.state().game_over() {
+                    if self.pipe {
+                        println!("{:#}", m);
+                    } else if self.board {
+                        self.print_board();
+                    } else {
+                        println!("{}", m);
+                    }
+                    if let Some(on_guess) = &mut self.on_guess {
+                        on_guess(&self.game);
+                    }
+                    if let Some(win) = self.game.game_over() {
                         break win;
                     }
                 }
@@ -33,12 +101,64 @@ impl Controller {
         };
 
         if !win {
+            let solution = self.game.state().solution().to_ascii_uppercase();
+            if self.pipe {
+                println!("LOST {}", solution);
+            } else {
+                println!("GAME OVER - '{}'", solution);
+            }
+        }
+
+        Ok(Some(self.game.share().with_guesses(&self.game)))
+    }
+
+    /// Each guess with a letter for each of its matches, like `SAGAZ BYBBB`, then a
+    /// blank row for each guess left
+    fn print_board(&self) {
+        let state = self.game.state();
+        for Guess(word, matches) in state.guesses() {
+            println!("{} {:#}", word.to_uppercase(), matches);
+        }
+        let blank = "_".repeat(state.word_len());
+        for _ in state.guesses().len()..state.rows() {
+            println!("{}", blank);
+        }
+        println!();
+    }
+}
+
+/// Plays every board at once, printing how each guess matched on each board, or `-`
+/// for the ones already solved
+pub fn play_multi(mut multi: Multi) -> Result<Option<MultiShare>> {
+    let stdin = stdin();
+    let mut line = String::with_capacity(6);
+
+    while multi.game_over().is_none() {
+        line.clear();
+        if stdin.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        match multi.guess(line.trim_end()) {
+            Err(_) => println!("INVALID"),
+            Ok(boards) => {
+                let boards = boards.into_iter().map(|matches| match matches {
+                    Some(matches) => matches.to_string(),
+                    None => "-".to_owned(),
+                });
+                println!("{}", boards.collect::<Vec<_>>().join(" "));
+            }
+        }
+    }
+
+    for board in multi.boards() {
+        if board.game_over() == Some(false) {
             println!(
                 "GAME OVER - '{}'",
-                self.game.state().solution().to_ascii_uppercase()
+                board.state().solution().to_ascii_uppercase()
             );
         }
-
-        Ok(self.game.share())
     }
+
+    Ok(Some(multi.share()))
 }
@@ -1,133 +1,2282 @@
-use std::io::{self, Write};
+use std::{
+    io,
+    time::{Duration, Instant},
+};
 
 use cl_wordle::{
-    game::{Game, GameShare},
-    state::GuessError,
-    Match,
+    definitions::Definitions,
+    frequency::Frequencies,
+    frontend::{Key, Renderer, Typing},
+    game::{Elapsed, ShareTemplate},
+    prelude::*,
+    solver::{self, Ranking, Suggestions},
+    stats::Stats,
+    words,
 };
 use crossterm::{
     cursor,
-    event::{self, KeyCode},
+    event::{self, KeyCode, KeyModifiers, MouseButton, MouseEventKind},
     execute,
     terminal::{Clear, ClearType},
 };
 use eyre::Result;
-use owo_colors::{colors::Red, OwoColorize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+mod alphabet;
+mod analysis;
+pub mod archive;
+mod assist;
+mod candidates;
+pub mod crosswordle;
+pub mod duel;
 mod guess;
+mod heat;
+mod hint;
+mod histogram;
+mod history;
 mod keyboard;
+mod layout;
 mod letters;
+pub mod multi;
+pub mod opponent;
+pub mod pager;
+mod positions;
+pub mod replay;
+pub mod secret;
+pub mod snapshot;
+pub mod stream;
+mod summary;
 mod terminal;
 
-use self::{guess::Guesses, keyboard::Keyboard, letters::WordMatch, terminal::Terminal};
+use self::{
+    alphabet::AlphabetStatus,
+    assist::AssistPanel,
+    candidates::CandidatesPanel,
+    guess::{GuessRow, Guesses},
+    hint::HintPanel,
+    histogram::HistogramPanel,
+    keyboard::{Keyboard, Press},
+    letters::{LetterMatch, WordMatch},
+    opponent::{Opponent, OpponentPanel},
+    positions::PositionsPanel,
+    summary::SummaryPanel,
+    terminal::{Backend, Terminal},
+};
+pub use self::{keyboard::Layout, layout::Cells, letters::Palette, terminal::unusable};
+
+/// Columns between the board and the keyboard
+const KEYBOARD_GAP: u16 = 9;
+/// Narrowest the board can be drawn while still fitting the title
+const TITLE_WIDTH: u16 = 12;
+/// Width of the alphabet status line
+const ALPHABET_WIDTH: u16 = 26;
+/// Columns a rejected word is moved right by in each frame of the shake, ending back
+/// where it belongs
+const SHAKE: [u16; 5] = [1, 0, 1, 0, 0];
+/// How long each frame of the shake is shown for
+const SHAKE_FRAME: Duration = Duration::from_millis(40);
+/// Most words offered in place of one that isn't in the word list
+const SUGGESTIONS: usize = 3;
+/// How often the other player in a race is checked on, while waiting for a key
+const OPPONENT_POLL: Duration = Duration::from_millis(100);
+
+/// Shown after a win, by the number of guesses it took
+pub const CELEBRATIONS: [&str; 6] = [
+    "Genius",
+    "Magnificent",
+    "Impressive",
+    "Splendid",
+    "Great",
+    "Phew",
+];
+/// Height of the title, board and revealed solution, in cells, with a row for each
+/// of `guesses`. There are at least 5 rows though, to fit the keyboard beside them
+fn board_height(guesses: usize) -> u16 {
+    guesses.max(5) as u16 + 5
+}
+
+#[derive(Clone, Default)]
+pub struct Options {
+    /// Delay between revealing each letter of a submitted guess.
+    /// A zero delay reveals the whole row at once.
+    pub reveal_delay: Duration,
+    /// Shake a rejected word side to side before leaving it highlighted
+    pub shake: bool,
+    /// Where the board is placed in the terminal
+    pub align: Align,
+    /// How the solution is shown after a loss
+    pub reveal_style: RevealStyle,
+    /// What to do when enter is pressed before the word is complete
+    pub short_word: ShortWord,
+    /// What to do when Ctrl+C is pressed during the game
+    pub interrupt: Interrupt,
+    /// Whether the keyboard starts hidden. It can be toggled with Ctrl+K
+    pub hide_keyboard: bool,
+    /// Leave the keyboard uncoloured, so what's known of each letter has to be
+    /// remembered. The share is marked with a `!`
+    pub no_keyboard_hints: bool,
+    /// Draw a letter in both colours on the keyboard once it's found, if there's more
+    /// of it to place somewhere else
+    pub split_keys: bool,
+    /// Also mark letters as wrong on the keyboard once no remaining solution contains them
+    pub deduce_keyboard: bool,
+    /// Allow asking for a suggested guess, and why, with `?`
+    pub hint: bool,
+    /// Letters of the solution that can be given away with Ctrl+R each game
+    pub letter_hints: usize,
+    /// Allow previewing the colours of the word being typed with Ctrl+P, without
+    /// guessing it
+    pub preview: bool,
+    /// Spacing and separators between the letters on the board
+    pub cells: Cells,
+    /// Show the alphabet below the board, with the letters already guessed dimmed
+    pub alphabet: bool,
+    /// Show how common each guess is beside its row
+    pub frequencies: Option<Frequencies>,
+    /// Presses of the same letter closer together than this are treated as one, to
+    /// ignore held keys. A zero duration turns this off
+    pub debounce: Duration,
+    /// Allow listing every solution that's still possible with Tab
+    pub candidates: bool,
+    /// Allow showing the letters each position could still hold with Ctrl+L
+    pub positions: bool,
+    /// Messages shown after a win, by the number of guesses it took. The last one is
+    /// used for any guess counts after it, and none are shown if it's empty
+    pub celebrations: Vec<String>,
+    /// Which parts of the result are copied with `c` once the game is over
+    pub share_style: ShareStyle,
+    /// How the result copied with `c` is laid out, if not the usual way
+    pub share_template: Option<ShareTemplate>,
+    /// Copy absent letters as ⬜, for a light background
+    pub white_squares: bool,
+    /// The colours matches are drawn in, which the copied share follows too
+    pub palette: Palette,
+    /// Type by clicking on the keyboard, which also gets enter and delete buttons
+    pub mouse: bool,
+    /// The order the keys are drawn in
+    pub layout: Layout,
+    /// Spell out the last guess's matches in words, below the board, for screen readers
+    pub text: bool,
+    /// Time each game, showing how long it took when it's over and in the share
+    pub timed: bool,
+    /// Time each game from the first key pressed, with a clock ticking beside the
+    /// title while it's played. The time goes in the share too
+    pub timer: bool,
+    /// Leave the time out of the share copied with `c`
+    pub no_share_time: bool,
+    /// The offset from UTC the next daily is counted down in, or the local timezone
+    pub offset: Option<time::UtcOffset>,
+    /// Don't copy the result with `c` once the game is over
+    pub no_clipboard: bool,
+    /// Keep the best next guesses, ranked this way, beside the keyboard after each guess
+    pub assist: Option<Ranking>,
+    /// Shown below the solution once the game is over
+    pub definitions: Definitions,
+    /// Look the solution up in Wiktionary when it isn't in the definitions
+    #[cfg(feature = "net")]
+    pub online_definitions: bool,
+}
+
+impl Options {
+    /// A keyboard with no letters guessed yet, with a key for each letter the
+    /// game's word lists are spelt with
+    fn keyboard(&self, state: &State) -> Keyboard {
+        Keyboard::new(self.palette)
+            .with_alphabet(state.alphabet())
+            .with_buttons(self.mouse)
+            .with_layout(self.layout)
+            .with_hints(!self.no_keyboard_hints)
+            .with_split(self.split_keys)
+    }
+}
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RevealStyle {
+    /// Show the whole solution at once
+    #[default]
+    Whole,
+    /// Show one letter of the solution every second. Any key shows the rest
+    Slow,
+    /// Only show the letters that were never found in the right place
+    Unfound,
+}
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Interrupt {
+    /// Quit straight away, like Esc
+    #[default]
+    Quit,
+    /// Ask before quitting
+    Confirm,
+}
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShortWord {
+    /// Ignore the key press
+    #[default]
+    Silent,
+    /// Tell the player how many letters are needed
+    Message,
+}
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    TopLeft,
+    Center,
+}
+
+impl Align {
+    /// Gets the top-left cell of the board for the given terminal size
+    fn origin(
+        self,
+        (width, height): (u16, u16),
+        (board_width, board_height): (u16, u16),
+    ) -> (u16, u16) {
+        match self {
+            Align::TopLeft => (0, 0),
+            Align::Center => (
+                width.saturating_sub(board_width) / 2,
+                height.saturating_sub(board_height) / 2,
+            ),
+        }
+    }
+}
+
+type OnGuess = Box<dyn FnMut(&Game)>;
 
-pub struct Controller {
+type OnKeyboard = Box<dyn FnMut(bool)>;
+
+pub struct Controller<B = Terminal> {
     game: Game,
+    options: Options,
     keyboard: Keyboard,
-    stdout: Terminal,
+    terminal: B,
+    /// Top-left cell of the board, as of the last redraw
+    origin: (u16, u16),
+    /// Whether a message is currently shown on the status line
+    status: bool,
+    show_keyboard: bool,
+    /// Page of the candidates panel shown, if it's open
+    candidates_page: Option<usize>,
+    /// Whether the hint panel is shown beside the board
+    hint_shown: bool,
+    /// Whether the letters each position could still hold are shown beside the board
+    positions_shown: bool,
+    /// Whether a preview of the word being typed is shown below the board
+    preview_shown: bool,
+    /// Starts the next game when `n` is pressed once a game is over, for practice
+    next_round: Option<Box<dyn FnMut() -> Game>>,
+    /// Called after every accepted guess, to save the game as it goes
+    on_guess: Option<OnGuess>,
+    /// Called with whether the keyboard is shown, whenever Ctrl+K changes it
+    on_keyboard: Option<OnKeyboard>,
+    /// When the game being played started being timed. With `--timer`, that's the
+    /// first key pressed, so it's `None` until then
+    started: Option<Instant>,
+    /// How long the last game took, once it's over, if games are timed
+    elapsed: Option<Duration>,
+    /// The daily results so far, shown with this game's once it's over
+    stats: Option<Stats>,
+    /// The best next guesses as of the last guess, with `--assist`
+    suggestions: Option<Suggestions>,
+    /// What the solution means, once the game is over, if it's known
+    definition: Option<String>,
+    /// Whether the stats are shown in place of the summary, once the game is over
+    stats_shown: bool,
+    /// The other player, when racing them to the same word
+    opponent: Option<Opponent>,
+    /// The games before this one, with how each went, for `l` to look back on
+    played: Vec<(Game, GameShare)>,
+    /// Shown on the status line when the first game starts
+    notice: Option<String>,
 }
 
 impl Controller {
-    pub fn new(game: Game) -> Result<Self> {
-        Ok(Self {
+    pub fn new(game: Game, options: Options) -> Result<Self> {
+        let terminal = Terminal::new(options.mouse)?;
+        Ok(Self::with_backend(game, options, terminal))
+    }
+
+    /// Writes every key pressed to the recording, to play back with `wordle replay`.
+    /// Clicks on the keyboard aren't recorded
+    pub fn with_recording(mut self, recording: replay::Recording) -> Self {
+        self.terminal.record_to(recording);
+        self
+    }
+}
+
+impl<B: Backend> Controller<B> {
+    pub fn with_backend(game: Game, mut options: Options, terminal: B) -> Self {
+        options.cells = options.cells.fit(game.state().solution());
+        let mut keyboard = options.keyboard(game.state());
+        // a resumed game already has guesses
+        for Guess(word, matches) in game.state().guesses() {
+            keyboard.push(&game.state().fold(word), matches);
+        }
+        let mut controller = Self {
             game,
-            keyboard: Keyboard::default(),
-            stdout: Terminal::new()?,
-        })
+            keyboard,
+            terminal,
+            origin: (0, 0),
+            status: false,
+            show_keyboard: !options.hide_keyboard,
+            candidates_page: None,
+            hint_shown: false,
+            positions_shown: false,
+            preview_shown: false,
+            next_round: None,
+            on_guess: None,
+            on_keyboard: None,
+            started: None,
+            elapsed: None,
+            stats: None,
+            suggestions: None,
+            definition: None,
+            stats_shown: false,
+            opponent: None,
+            played: Vec::new(),
+            notice: None,
+            options,
+        };
+        controller.deduce_keyboard();
+        controller.suggest();
+        controller
+    }
+
+    /// Offers another game once each is over, started by `next_round`
+    pub fn with_next_round(mut self, next_round: impl FnMut() -> Game + 'static) -> Self {
+        self.next_round = Some(Box::new(next_round));
+        self
+    }
+
+    /// Shows the stats, with this game recorded in them, once a daily game is over.
+    /// Nothing is saved here, that's left to whoever keeps the stats
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Shows how the other player in a race is getting on, beside the keyboard
+    pub fn with_opponent(mut self, opponent: Opponent) -> Self {
+        self.opponent = Some(opponent);
+        self
+    }
+
+    /// Shows the message on the status line when the first game starts, until a key
+    /// is pressed
+    pub fn with_notice(mut self, notice: String) -> Self {
+        self.notice = Some(notice);
+        self
+    }
+
+    /// Calls `on_guess` with the game after every accepted guess
+    pub fn with_on_guess(mut self, on_guess: impl FnMut(&Game) + 'static) -> Self {
+        self.on_guess = Some(Box::new(on_guess));
+        self
+    }
+
+    /// Calls `on_keyboard` with whether the keyboard is shown each time Ctrl+K shows
+    /// or hides it, to keep the choice for next time
+    pub fn with_on_keyboard(mut self, on_keyboard: impl FnMut(bool) + 'static) -> Self {
+        self.on_keyboard = Some(Box::new(on_keyboard));
+        self
     }
 
     pub fn run(mut self) -> Result<Option<GameShare>> {
-        self.display_window()?;
+        loop {
+            if !self.play_timed()? {
+                return Ok(None);
+            }
+            if !self.game_over_screen()? {
+                return Ok(Some(self.share()));
+            }
+        }
+    }
 
-        let mut word = String::with_capacity(5);
+    /// Plays `rounds` games back to back, each after the first from `next_round`,
+    /// waiting for enter between them. Only the last waits on the end screen.
+    ///
+    /// Returns the result of each game finished, stopping early if one was quit
+    pub fn run_rounds(mut self, rounds: usize) -> Result<Vec<GameShare>> {
+        self.play_rounds(Some(rounds))
+    }
 
-        let win = loop {
-            self.stdout.flush()?;
-            if let event::Event::Key(key) = event::read()? {
+    /// Plays games back to back as in [`run_rounds`](Self::run_rounds), but for as
+    /// long as each one is won. The one that isn't waits on the end screen
+    pub fn run_marathon(mut self) -> Result<Vec<GameShare>> {
+        self.play_rounds(None)
+    }
+
+    /// Plays the rounds, or with `None`, until a game is lost
+    fn play_rounds(&mut self, rounds: Option<usize>) -> Result<Vec<GameShare>> {
+        let mut shares = Vec::new();
+        for round in 1.. {
+            if !self.play_timed()? {
+                break;
+            }
+            let share = self.share();
+            let last = match rounds {
+                Some(rounds) => round == rounds,
+                None => !share.won(),
+            };
+            shares.push(share);
+            if last {
+                // there's no next round to offer with n
+                self.next_round = None;
+                self.game_over_screen()?;
+                break;
+            }
+            if !self.wait_for_round(round + 1, rounds)? {
+                break;
+            }
+            let game = match &mut self.next_round {
+                Some(next_round) => next_round(),
+                None => break,
+            };
+            self.reset(game);
+        }
+        Ok(shares)
+    }
+
+    /// Plays the game and reveals how it went, timing it if games are timed.
+    /// Returns whether it was played to the end, rather than quit
+    fn play_timed(&mut self) -> Result<bool> {
+        // with --timer, the clock starts on the first key instead
+        self.started = (!self.options.timer).then(Instant::now);
+        let win = match self.play()? {
+            Some(win) => win,
+            None => return Ok(false),
+        };
+        // stopped before the reveal, so waiting on the end screen doesn't count
+        let elapsed = self
+            .started
+            .map_or_else(Duration::default, |started| started.elapsed());
+        self.elapsed = (self.options.timed || self.options.timer).then_some(elapsed);
+        if win {
+            self.write_celebration()?;
+        } else {
+            self.write_final_solution()?;
+        }
+        Ok(true)
+    }
+
+    /// Waits for enter before the next of the rounds, if there's a set number of
+    /// them, or returns false if the player left instead
+    fn wait_for_round(&mut self, next: usize, rounds: Option<usize>) -> Result<bool> {
+        execute!(self.terminal, cursor::Hide)?;
+        self.display_time()?;
+        let status = match rounds {
+            Some(rounds) => format!("enter for round {} of {}", next, rounds),
+            None => format!("enter for word {}", next),
+        };
+        self.show_status(&status)?;
+        loop {
+            self.terminal.flush()?;
+            if let event::Event::Key(key) = self.terminal.read()? {
                 match key.code {
-                    KeyCode::Esc => return Ok(None),
-                    KeyCode::Char(c) if c.is_ascii_alphabetic() && word.len() < 5 => {
-                        let c = c.to_ascii_lowercase();
-                        write!(self.stdout, "{}", c.to_ascii_uppercase())?;
-                        word.push(c);
+                    KeyCode::Enter => break,
+                    KeyCode::Esc => return Ok(false),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(false)
                     }
-                    KeyCode::Enter if word.len() == 5 => match self.guess(&*word) {
-                        Ok(()) => {
-                            self.display_window()?;
+                    _ => {}
+                }
+            }
+        }
+        self.clear_status()?;
+        execute!(self.terminal, cursor::Show)?;
+        Ok(true)
+    }
 
-                            if let Some(win) = self.game.state().game_over() {
-                                break win;
-                            }
+    /// Plays the game until it's won or lost, or returns `None` if it was quit
+    fn play(&mut self) -> Result<Option<bool>> {
+        let (_, height) = self.terminal.size()?;
+        let (_, needed) = self.needed_size();
+        if height < needed {
+            eyre::bail!(
+                "{} guesses need a terminal {} rows tall, this one is {}",
+                self.game.state().rows(),
+                needed,
+                height
+            );
+        }
+        self.display_window()?;
+        if let Some(notice) = self.notice.take() {
+            self.show_status(&notice)?;
+        } else if self.game.state().guesses().len() > 0 {
+            self.show_status("resumed where you left off")?;
+        }
+
+        let len = self.game.word_len();
+        let mut typing = Typing::new();
+        // how many guesses back the recalled word is. 0 is the word being typed
+        let mut recalled = 0;
+        // the last letter pressed, and when, for debouncing
+        let mut last_letter = None;
+
+        let win = loop {
+            let mut wake = None;
+            if self.options.timer {
+                let elapsed = self.started.map_or_else(Duration::default, |s| s.elapsed());
+                self.display_clock(elapsed)?;
+                // wake up on the next second to tick the clock, once it's running
+                let tick =
+                    Duration::from_secs(1) - Duration::from_nanos(elapsed.subsec_nanos().into());
+                wake = self.started.map(|_| tick);
+            }
+            if self.opponent.is_some() {
+                wake = Some(wake.map_or(OPPONENT_POLL, |tick| tick.min(OPPONENT_POLL)));
+            }
+            if let Some(timeout) = wake {
+                if !self.terminal.poll(timeout)? {
+                    self.update_opponent()?;
+                    continue;
+                }
+            }
+            self.terminal.flush()?;
+            let event = match self.terminal.read()? {
+                // clicks on the keyboard are played as if the key was typed
+                event::Event::Mouse(mouse) => match self.click(mouse) {
+                    Some(code) => event::Event::Key(code.into()),
+                    None => continue,
+                },
+                event => event,
+            };
+            if let event::Event::Resize(..) = event {
+                if !self.wait_for_room()? {
+                    return Ok(None);
+                }
+                typing.redraw(self)?;
+            }
+            if let event::Event::Key(key) = event {
+                let now = Instant::now();
+                if self.options.timer && self.started.is_none() {
+                    self.started = Some(now);
+                }
+                if let KeyCode::Char(c) = key.code {
+                    let bounced = matches!(last_letter, Some((last, at)) if last == c
+                        && now.duration_since(at) < self.options.debounce);
+                    if bounced {
+                        continue;
+                    }
+                    last_letter = Some((c, now));
+                } else {
+                    last_letter = None;
+                }
 
-                            word.clear();
+                self.clear_status()?;
+                self.clear_preview()?;
+                match key.code {
+                    KeyCode::Esc => {
+                        // a daily can't be replayed, so one stray press shouldn't end it
+                        let started = self.game.state().guesses().len() > 0;
+                        let daily = matches!(self.game.game_type(), GameType::Daily(_));
+                        if !(started && daily) || self.confirm("quit today's game? (y/n)")? {
+                            return Ok(None);
                         }
-                        Err(_) => self.display_invalid(&word)?,
-                    },
+                    }
+                    KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.show_keyboard = !self.show_keyboard;
+                        if let Some(on_keyboard) = &mut self.on_keyboard {
+                            on_keyboard(self.show_keyboard);
+                        }
+                        typing.redraw(self)?;
+                    }
+                    KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if !self.undoable() {
+                            self.show_status("only practice guesses can be undone")?;
+                        } else if self.undo() {
+                            typing.redraw(self)?;
+                        }
+                    }
+                    KeyCode::Char('?') if self.options.hint => self.display_hint()?,
+                    KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.reveal_letter()?;
+                    }
+                    KeyCode::Tab if self.options.candidates => self.display_candidates()?,
+                    KeyCode::Char('l')
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && self.options.positions =>
+                    {
+                        self.positions_shown = !self.positions_shown;
+                        typing.redraw(self)?;
+                    }
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        let quit = match self.options.interrupt {
+                            Interrupt::Quit => true,
+                            Interrupt::Confirm => self.confirm("quit? (y/n)")?,
+                        };
+                        if quit {
+                            return Ok(None);
+                        }
+                    }
+                    KeyCode::Char('p')
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && self.options.preview =>
+                    {
+                        self.display_preview(typing.word())?;
+                    }
+                    // shows the solution, but a daily can't be replayed, so it asks first
+                    KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        let daily = matches!(self.game.game_type(), GameType::Daily(_));
+                        if !daily || self.confirm("give up today's game? (y/n)")? {
+                            self.game.give_up();
+                            break false;
+                        }
+                    }
+                    // clears the whole word, like in a shell
+                    KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        recalled = 0;
+                        typing.press(Key::Clear, self)?;
+                    }
+                    // other shortcuts shouldn't type their letter
+                    KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {}
+                    KeyCode::Char(c) => {
+                        typing.press(Key::Letter(c), self)?;
+                    }
+                    KeyCode::Enter if typing.len() == len => {
+                        if let Some(win) = typing.press(Key::Submit, self)? {
+                            break win;
+                        }
+                        // the word is only cleared once the guess is accepted
+                        if typing.is_empty() {
+                            recalled = 0;
+                        }
+                    }
+                    KeyCode::Enter if self.options.short_word == ShortWord::Message => {
+                        self.show_status(&format!("need {} letters", len))?;
+                    }
+                    KeyCode::Up if recalled < self.game.state().guesses().len() => {
+                        recalled += 1;
+                        self.recall(&mut typing, recalled)?;
+                    }
+                    KeyCode::Down if recalled > 0 => {
+                        recalled -= 1;
+                        self.recall(&mut typing, recalled)?;
+                    }
                     KeyCode::Backspace => {
-                        word.pop();
-                        write!(self.stdout, "{back} {back}", back = cursor::MoveLeft(1))?;
+                        typing.press(Key::Delete, self)?;
                     }
                     _ => {}
                 }
             }
         };
 
-        if !win {
-            self.write_final_solution()?;
-        }
+        Ok(Some(win))
+    }
 
-        execute!(self.stdout, cursor::Hide)?;
+    /// Waits on the finished board for the player to leave, or to start another
+    /// round, which is only offered when there's a next round to play.
+    ///
+    /// Returns whether a new round was started
+    fn game_over_screen(&mut self) -> Result<bool> {
+        execute!(self.terminal, cursor::Hide)?;
+        self.definition = self.look_up_definition();
+        self.display_time()?;
+        self.display_summary()?;
+        self.display_definition()?;
 
         loop {
-            self.stdout.flush()?;
-            if let event::Event::Key(key) = event::read()? {
+            if let (GameType::Daily(_), false) = (self.game.game_type(), self.stats_shown) {
+                self.display_summary()?;
+            }
+            self.terminal.flush()?;
+
+            // wake up every second to tick the countdown
+            let timeout = match self.opponent {
+                Some(_) => OPPONENT_POLL,
+                None => Duration::from_secs(1),
+            };
+            if !self.terminal.poll(timeout)? {
+                self.update_opponent()?;
+                continue;
+            }
+            let event = self.terminal.read()?;
+            if let event::Event::Resize(..) = event {
+                if !self.wait_for_room()? {
+                    return Ok(false);
+                }
+                self.redraw_game_over()?;
+            }
+            if let event::Event::Key(key) = event {
+                self.clear_status()?;
                 match key.code {
-                    KeyCode::Enter | KeyCode::Esc => break,
+                    KeyCode::Enter | KeyCode::Esc => return Ok(false),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(false)
+                    }
+                    KeyCode::Char('s') if self.recorded_stats().is_some() => {
+                        self.stats_shown = !self.stats_shown;
+                        self.redraw_game_over()?;
+                    }
+                    KeyCode::Char('n') => {
+                        if let Some(next_round) = &mut self.next_round {
+                            let game = next_round();
+                            self.reset(game);
+                            execute!(self.terminal, cursor::Show)?;
+                            return Ok(true);
+                        }
+                    }
+                    KeyCode::Char('l') if !self.played.is_empty() => {
+                        self.browse_played()?;
+                        self.redraw_game_over()?;
+                    }
+                    KeyCode::Char('a') if self.game.state().guesses().len() > 0 => {
+                        self.show_analysis()?;
+                    }
+                    KeyCode::Char('c') if !self.options.no_clipboard => {
+                        let share = self.share();
+                        let share = if self.options.no_share_time {
+                            share.without_time()
+                        } else {
+                            share
+                        };
+                        let share = share
+                            .with_style(self.options.share_style)
+                            .with_high_contrast(self.options.palette == Palette::HighContrast)
+                            .with_white_squares(self.options.white_squares)
+                            .with_template(self.options.share_template.clone());
+                        self.show_status(copy(&share))?;
+                    }
                     _ => {}
                 }
             }
         }
+    }
+
+    /// Lists every game of the session, this one last, to look back at their boards
+    fn browse_played(&mut self) -> Result<()> {
+        let share = self.share();
+        let entries = self
+            .played
+            .iter()
+            .map(|(game, share)| history::Entry {
+                state: game.state(),
+                share,
+            })
+            .chain([history::Entry {
+                state: self.game.state(),
+                share: &share,
+            }])
+            .collect();
+        let cells = self.options.cells;
+        let palette = self.options.palette;
+        history::Controller::with_backend(entries, cells, palette, &mut self.terminal).run()
+    }
+
+    /// Shows how each guess was rated until it's left for the board again. Words that
+    /// aren't bundled can't be rated, which the status line says instead
+    fn show_analysis(&mut self) -> Result<()> {
+        let analysis = match self.share().analysis() {
+            Some(analysis) if !analysis.guesses.is_empty() => analysis,
+            _ => return Ok(self.show_status("only games of the bundled words can be rated")?),
+        };
+        analysis::Controller::with_backend(&analysis, &mut self.terminal).run()?;
+        Ok(self.redraw_game_over()?)
+    }
+
+    /// The result of the game, with the time it took if it was timed
+    fn share(&self) -> GameShare {
+        let share = self
+            .game
+            .share()
+            .with_guesses(&self.game)
+            .with_no_keyboard_hints(self.options.no_keyboard_hints);
+        match self.elapsed {
+            Some(elapsed) => share.with_time(elapsed),
+            None => share,
+        }
+    }
+
+    /// Shows how long the game took after the title, if it was timed
+    fn display_time(&mut self) -> io::Result<()> {
+        match self.elapsed {
+            Some(elapsed) => self.display_clock(elapsed),
+            None => Ok(()),
+        }
+    }
+
+    /// Draws the time after the title, leaving the cursor where it was
+    fn display_clock(&mut self, elapsed: Duration) -> io::Result<()> {
+        let (x, y) = self.origin;
+        let title = format!("Termo {}", self.game.game_type()).width() as u16;
+        write!(
+            self.terminal,
+            "{}{}⏱ {}{}",
+            cursor::SavePosition,
+            cursor::MoveTo(x + title + 2, y),
+            Elapsed(elapsed),
+            cursor::RestorePosition,
+        )
+    }
 
-        Ok(Some(self.game.share()))
+    /// The solution's definition, from the ones given or else from Wiktionary with
+    /// `--online-definitions`
+    fn look_up_definition(&self) -> Option<String> {
+        let solution = self.game.state().solution();
+        if let Some(definition) = self.options.definitions.get(solution) {
+            return Some(definition.to_owned());
+        }
+        #[cfg(feature = "net")]
+        if self.options.online_definitions {
+            return cl_wordle::definitions::lookup(solution).ok().flatten();
+        }
+        None
     }
 
-    fn guess(&mut self, word: &str) -> Result<(), GuessError> {
-        let matches = self.game.state_mut().guess(word)?;
-        self.keyboard.push(word, matches);
+    /// Writes the definition on the line below the revealed solution, cut to the
+    /// terminal's width. It's left out when that line has the exit message on it
+    fn display_definition(&mut self) -> io::Result<()> {
+        let definition = match &self.definition {
+            Some(definition) => definition,
+            None => return Ok(()),
+        };
+        let (width, height) = self.terminal.size()?;
+        let (x, row) = (self.origin.0, self.bottom());
+        if row + 1 >= height {
+            return Ok(());
+        }
+        let mut room = width.saturating_sub(x) as usize;
+        let shown = definition
+            .chars()
+            .take_while(|c| match room.checked_sub(c.width().unwrap_or(0)) {
+                Some(left) => {
+                    room = left;
+                    true
+                }
+                None => false,
+            })
+            .collect::<String>();
+        write!(
+            self.terminal,
+            "{}{}{}{}",
+            cursor::SavePosition,
+            cursor::MoveTo(x, row),
+            shown,
+            cursor::RestorePosition
+        )
+    }
+
+    /// Top-left cell of the keyboard, beside the board
+    fn keyboard_origin(&self) -> (u16, u16) {
+        let (x, y) = self.origin;
+        let board = self.options.cells.width(self.game.word_len() as u16);
+        (x + board + KEYBOARD_GAP, y + 2)
+    }
+
+    /// Finds the key clicked on the keyboard, if a key was clicked at all
+    fn click(&self, mouse: event::MouseEvent) -> Option<KeyCode> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) || !self.show_keyboard {
+            return None;
+        }
+        let (column, row) = self.keyboard_origin();
+        let press = self.keyboard.press_at(
+            mouse.column.checked_sub(column)?,
+            mouse.row.checked_sub(row)?,
+        )?;
+        Some(match press {
+            Press::Letter(c) => KeyCode::Char(c),
+            Press::Enter => KeyCode::Enter,
+            Press::Delete => KeyCode::Backspace,
+        })
+    }
+
+    /// Draws the finished game again from scratch, without animating anything
+    fn redraw_game_over(&mut self) -> io::Result<()> {
+        self.display_window()?;
+        if self.game.game_over() == Some(true) {
+            self.write_celebration()?;
+        } else {
+            // the reveal already played out, so there's no need to animate it again
+            self.write_solution()?;
+        }
+        self.display_time()?;
+        self.display_summary()?;
+        self.display_definition()?;
+        self.display_opponent()
+    }
+
+    /// Shows how the game went and the keys for what's next where the keyboard was,
+    /// or with `s`, the stats in its place
+    fn display_summary(&mut self) -> io::Result<()> {
+        let stats = self.recorded_stats();
+        if let (true, Some(stats)) = (self.stats_shown, &stats) {
+            self.show_status(&stats.summary())?;
+            return self.display_histogram();
+        }
+        let mut keys = Vec::new();
+        if cfg!(feature = "clipboard") && !self.options.no_clipboard {
+            keys.push(("c", "copy the result"));
+        }
+        if stats.is_some() {
+            keys.push(("s", "show the stats"));
+        }
+        if self.next_round.is_some() {
+            keys.push(("n", "another word"));
+        }
+        if !self.played.is_empty() {
+            keys.push(("l", "earlier games"));
+        }
+        if self.game.state().guesses().len() > 0 {
+            keys.push(("a", "rate each guess"));
+        }
+        keys.push(("enter", "exit"));
+        let next_daily = match (self.game.game_type(), cl_wordle::now(self.options.offset)) {
+            (GameType::Daily(_), Ok(now)) => {
+                Some(cl_wordle::time_until_next_day(now).whole_seconds())
+            }
+            _ => None,
+        };
+        let state = self.game.state();
+        let (column, row) = self.keyboard_origin();
+        write!(
+            self.terminal,
+            "{}",
+            SummaryPanel {
+                score: state.score(),
+                max_guesses: (!state.unlimited()).then(|| state.max_guesses()),
+                solution: state.solution(),
+                streak: stats.map(|stats| (stats.current_streak(), stats.max_streak())),
+                next_daily,
+                keys: &keys,
+                height: self.keyboard.rows() as u16,
+                column,
+                row,
+            }
+        )
+    }
+
+    /// The stats with the daily game just played recorded, if there are stats to show
+    fn recorded_stats(&self) -> Option<Stats> {
+        stats_after(self.stats.as_ref()?, &self.game.share())
+    }
+
+    /// Draws the guess distribution where the keyboard was, once a daily game is over
+    fn display_histogram(&mut self) -> io::Result<()> {
+        let stats = match self.recorded_stats() {
+            Some(stats) => stats,
+            None => return Ok(()),
+        };
+        let latest = match self.game.state().score() {
+            Score::Guesses(n) => (n as usize).clamp(1, 6) - 1,
+            Score::Failed => 6,
+        };
+        let (column, row) = self.keyboard_origin();
+        let (width, _) = self.terminal.size()?;
+        write!(
+            self.terminal,
+            "{}",
+            HistogramPanel {
+                distribution: stats.distribution(),
+                latest: Some(latest),
+                palette: self.options.palette,
+                width: width.saturating_sub(column + 1),
+                column,
+                row,
+            }
+        )
+    }
+
+    /// Swaps in a new game, forgetting everything about the last one
+    fn reset(&mut self, game: Game) {
+        self.options.cells = self.options.cells.fit(game.state().solution());
+        let share = self.share();
+        let last = std::mem::replace(&mut self.game, game);
+        self.played.push((last, share));
+        self.keyboard = self.options.keyboard(self.game.state());
+        self.started = None;
+        self.elapsed = None;
+        self.suggestions = None;
+        self.definition = None;
+        self.stats_shown = false;
+    }
+
+    /// Works out the best next guesses for the assist panel, with `--assist`. There
+    /// are none before the first guess or once the game is over
+    fn suggest(&mut self) {
+        let state = self.game.state();
+        self.suggestions = match self.options.assist {
+            Some(ranking) if state.guesses().len() > 0 && state.game_over().is_none() => {
+                let history = state.guesses().collect::<Vec<_>>();
+                Some(solver::suggest(&history, ranking, assist::SUGGESTIONS))
+            }
+            _ => None,
+        };
+    }
+
+    /// Whether guesses can be taken back, which they can in games that don't count
+    fn undoable(&self) -> bool {
+        matches!(
+            self.game.game_type(),
+            GameType::Practice { .. }
+                | GameType::Custom
+                | GameType::Absurdle
+                | GameType::Seeded { .. }
+        )
+    }
+
+    /// Takes back the last guess, colouring the keyboard from the guesses left.
+    /// Returns whether there was one to take back
+    fn undo(&mut self) -> bool {
+        if self.game.undo().is_none() {
+            return false;
+        }
+        self.keyboard = self.options.keyboard(self.game.state());
+        for Guess(word, matches) in self.game.state().guesses() {
+            self.keyboard.push(&self.game.state().fold(word), matches);
+        }
+        self.deduce_keyboard();
+        self.suggest();
+        true
+    }
+
+    /// Greys out the letters no remaining solution uses, with `--deduce-keyboard`
+    fn deduce_keyboard(&mut self) {
+        if self.options.deduce_keyboard {
+            let history = self.game.state().guesses().collect::<Vec<_>>();
+            self.keyboard
+                .deduce(&solver::candidates(&history, words::FINAL));
+        }
+    }
+
+    /// Replaces the word being typed with the guess made `back` guesses ago,
+    /// or clears it if `back` is 0
+    fn recall(&mut self, typing: &mut Typing, back: usize) -> Result<()> {
+        let guesses = self.game.state().guesses();
+        let recalled = match back.checked_sub(1) {
+            Some(n) => guesses.rev().nth(n).map_or("", |Guess(w, _)| w),
+            None => "",
+        }
+        .to_owned();
+        typing.replace(&recalled, self)
+    }
+
+    /// Column and row of the `i`th letter of the word being typed
+    fn cell(&self, i: usize) -> cursor::MoveTo {
+        let (x, y) = self.origin;
+        let row = self.game.state().guesses().len() as u16;
+        cursor::MoveTo(x + self.options.cells.offset(i), y + 2 + row)
+    }
+
+    /// Writes the word being typed into its row, leaving the cursor where the next letter goes
+    fn write_word(&mut self, word: &str, invalid: bool) -> io::Result<()> {
+        for (i, c) in word.chars().enumerate() {
+            let (cell, c) = (self.cell(i), c.to_ascii_uppercase());
+            if invalid {
+                let mut letter = String::new();
+                self.options.palette.invalid(c, &mut letter).unwrap();
+                write!(self.terminal, "{}{}", cell, letter)?;
+            } else {
+                write!(self.terminal, "{}{}", cell, c)?;
+            }
+        }
+        let next = self.cell(word.chars().count());
+        write!(self.terminal, "{}", next)
+    }
+
+    /// Reveals the colours of the last guess one letter at a time.
+    ///
+    /// Any pending input cuts the animation short. The event is left in the queue
+    /// so the main loop still handles it, and the following `display_guess`
+    /// redraws the full row regardless.
+    fn reveal_guess(&mut self) -> Result<()> {
+        if self.options.reveal_delay.is_zero() {
+            return Ok(());
+        }
+
+        let Guess(word, matches) = match self.game.state().guesses().next_back() {
+            Some(guess) => guess,
+            None => return Ok(()),
+        };
+
+        // the guess has been made, so its row is the one above the cursor's
+        let (x, y) = self.origin;
+        let row = y + 2 + self.game.state().guesses().len() as u16 - 1;
+        for (i, (c, m)) in word.chars().zip(matches.0).enumerate() {
+            let column = x + self.options.cells.offset(i);
+            write!(
+                self.terminal,
+                "{}{}",
+                cursor::MoveTo(column, row),
+                LetterMatch(c, m, self.options.palette)
+            )?;
+            self.terminal.flush()?;
+            if self.terminal.poll(self.options.reveal_delay)? {
+                break;
+            }
+        }
+
         Ok(())
     }
 
+    /// Shows the solution below the board, according to the reveal style
     pub fn write_final_solution(&mut self) -> io::Result<()> {
-        write!(self.stdout, "{}", cursor::MoveDown(1))?;
+        let x = self.origin.0;
+        let bottom = self.bottom();
+        let row = bottom - 2;
+        let solution = self.game.state().solution().to_owned();
+
+        match self.options.reveal_style {
+            RevealStyle::Whole => return self.write_solution(),
+            RevealStyle::Slow | RevealStyle::Unfound => {
+                let mut found = vec![false; solution.chars().count()];
+                if self.options.reveal_style == RevealStyle::Unfound {
+                    for Guess(_, Matches(m)) in self.game.state().guesses() {
+                        for (found, m) in found.iter_mut().zip(m) {
+                            *found |= m == Match::Exact;
+                        }
+                    }
+                }
+
+                let cells = self.options.cells;
+                write!(
+                    self.terminal,
+                    "{}{}",
+                    cursor::MoveTo(x, row),
+                    cells.blank(found.len())
+                )?;
+                let mut delay = self.options.reveal_style == RevealStyle::Slow;
+                for (i, c) in solution.chars().enumerate().filter(|&(i, _)| !found[i]) {
+                    let column = x + cells.offset(i);
+                    let letter = LetterMatch(c, Match::Exact, self.options.palette);
+                    write!(self.terminal, "{}{}", cursor::MoveTo(column, row), letter)?;
+                    self.terminal.flush()?;
+                    // like the reveal animation, a key press skips the rest of it
+                    if delay && self.terminal.poll(Duration::from_secs(1))? {
+                        delay = false;
+                    }
+                }
+            }
+        }
+
+        write!(self.terminal, "{}", cursor::MoveTo(x, bottom - 1))
+    }
+
+    /// Shows the whole solution below the board at once
+    fn write_solution(&mut self) -> io::Result<()> {
+        let x = self.origin.0;
+        let bottom = self.bottom();
+        let word = WordMatch(
+            self.game.state().solution(),
+            Match::Exact,
+            self.options.cells,
+            self.options.palette,
+        );
+        write!(
+            self.terminal,
+            "{}{}{}",
+            cursor::MoveTo(x, bottom - 2),
+            word,
+            cursor::MoveTo(x, bottom - 1)
+        )
+    }
+
+    /// Congratulates the player where the solution would have been revealed
+    fn write_celebration(&mut self) -> io::Result<()> {
+        let guesses = self.game.state().guesses().len();
+        let celebrations = &self.options.celebrations;
+        let message = match celebrations
+            .get(guesses - 1)
+            .or_else(|| celebrations.last())
+        {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+        let x = self.origin.0;
+        let bottom = self.bottom();
+        write!(
+            self.terminal,
+            "{}{}{}",
+            cursor::MoveTo(x, bottom - 2),
+            message,
+            cursor::MoveTo(x, bottom - 1),
+        )
+    }
+
+    /// Shows the solver's suggested guess next to the keyboard, until the next redraw
+    fn display_hint(&mut self) -> io::Result<()> {
+        let history = self.game.state().guesses().collect::<Vec<_>>();
+        let explanation = solver::explain(&history);
+
+        let (x, y) = self.origin;
+        let column = x + self.board_width() + 2;
+        self.hint_shown = true;
         write!(
-            self.stdout,
+            self.terminal,
             "{}",
-            WordMatch(self.game.state().solution(), Match::Exact)
+            HintPanel {
+                explanation: &explanation,
+                column,
+                row: y + 2,
+            }
+        )
+    }
+
+    /// Draws the assist panel to the right of the keyboard, or in its place if it's
+    /// hidden
+    fn display_assist(&mut self) -> io::Result<()> {
+        if self.options.assist.is_none() {
+            return Ok(());
+        }
+        let (mut column, row) = self.keyboard_origin();
+        if self.show_keyboard {
+            column += self.keyboard.width() + 2;
+        }
+        write!(
+            self.terminal,
+            "{}",
+            AssistPanel {
+                suggestions: self.suggestions.as_ref(),
+                column,
+                row,
+            }
+        )
+    }
+
+    /// Draws the other player's board to the right of the keyboard, or in its place
+    /// if it's hidden
+    fn display_opponent(&mut self) -> io::Result<()> {
+        let (mut column, row) = self.keyboard_origin();
+        if self.show_keyboard {
+            column += self.keyboard.width() + 2;
+        }
+        let opponent = match &self.opponent {
+            Some(opponent) => opponent,
+            None => return Ok(()),
+        };
+        let state = self.game.state();
+        let panel = OpponentPanel {
+            opponent,
+            max_guesses: state.max_guesses(),
+            word_len: state.word_len(),
+            palette: self.options.palette,
+            column,
+            row,
+        };
+        write!(self.terminal, "{}", panel)
+    }
+
+    /// Takes in what the other player did since last time, redrawing their board if
+    /// anything changed
+    fn update_opponent(&mut self) -> io::Result<()> {
+        if !self.opponent.as_mut().is_some_and(Opponent::receive) {
+            return Ok(());
+        }
+        self.display_opponent()?;
+        self.terminal.flush()
+    }
+
+    /// Lists the solutions still possible beside the board, most common letters first,
+    /// showing the next page each time until the next redraw
+    fn display_candidates(&mut self) -> io::Result<()> {
+        let history = self.game.state().guesses().collect::<Vec<_>>();
+        let mut candidates = solver::candidates(&history, words::FINAL);
+        solver::rank_by_letters(&mut candidates);
+
+        let (x, y) = self.origin;
+        let (width, height) = self.terminal.size()?;
+        let column = x + self.board_width() + 2;
+        // keep clear of the exit message on the last line
+        let size = (width.saturating_sub(column), height.saturating_sub(y + 3));
+        let mut panel = CandidatesPanel {
+            candidates: &candidates,
+            page: 0,
+            column,
+            row: y + 2,
+            size,
+        };
+        panel.page = match self.candidates_page {
+            Some(page) if page + 1 < panel.pages() => page + 1,
+            _ => 0,
+        };
+        self.candidates_page = Some(panel.page);
+        write!(self.terminal, "{}", panel)
+    }
+
+    /// Draws the letters each position could still hold beside the board, while the
+    /// game is played and the panel is toggled on with Ctrl+L
+    fn display_positions(&mut self) -> io::Result<()> {
+        let state = self.game.state();
+        if !self.positions_shown || state.game_over().is_some() {
+            return Ok(());
+        }
+        let history = state.guesses().collect::<Vec<_>>();
+        let candidates = solver::candidates(&history, words::FINAL);
+        let letters = solver::possible_letters(&candidates, state.word_len());
+
+        let (x, y) = self.origin;
+        write!(
+            self.terminal,
+            "{}",
+            PositionsPanel {
+                remaining: candidates.len(),
+                letters: &letters,
+                column: x + self.board_width() + 2,
+                row: y + 2,
+            }
+        )
+    }
+
+    /// Columns and rows the board takes, with the exit message below it
+    fn needed_size(&self) -> (u16, u16) {
+        (
+            self.board_width(),
+            board_height(self.game.state().rows()) + 1,
+        )
+    }
+
+    /// Once the terminal is resized smaller than the board, says how big it needs to
+    /// be instead of drawing a garbled board, and waits until it's made big enough.
+    ///
+    /// Returns false if ESC is pressed before then
+    fn wait_for_room(&mut self) -> io::Result<bool> {
+        loop {
+            let (width, height) = self.terminal.size()?;
+            let (needed_width, needed_height) = self.needed_size();
+            if width >= needed_width && height >= needed_height {
+                return Ok(true);
+            }
+            write!(
+                self.terminal,
+                "{}{}the terminal needs to be {}x{} to play, it's {}x{}. ESC to quit",
+                Clear(ClearType::All),
+                cursor::MoveTo(0, 0),
+                needed_width,
+                needed_height,
+                width,
+                height
+            )?;
+            self.terminal.flush()?;
+            if let event::Event::Key(key) = self.terminal.read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.code == KeyCode::Esc || ctrl_c {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    /// Asks a yes or no question on the status line, waiting for the answer. The board
+    /// and the word being typed are left as they were
+    fn confirm(&mut self, question: &str) -> io::Result<bool> {
+        self.show_status(question)?;
+        self.terminal.flush()?;
+        let yes = loop {
+            if let event::Event::Key(key) = self.terminal.read()? {
+                match key.code {
+                    KeyCode::Char('y' | 'Y') => break true,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        break true
+                    }
+                    _ => break false,
+                }
+            }
+        };
+        self.clear_status()?;
+        Ok(yes)
+    }
+
+    /// Shows a message on the line between the title and the board,
+    /// leaving the cursor where it was
+    fn show_status(&mut self, message: &str) -> io::Result<()> {
+        let (x, y) = self.origin;
+        write!(
+            self.terminal,
+            "{save}{status}{message}{restore}",
+            save = cursor::SavePosition,
+            status = cursor::MoveTo(x, y + 1),
+            restore = cursor::RestorePosition,
         )?;
-        write!(self.stdout, "{}", cursor::MoveTo(0, 10))
+        self.status = true;
+        Ok(())
+    }
+
+    fn clear_status(&mut self) -> io::Result<()> {
+        if self.status {
+            let (x, y) = self.origin;
+            write!(
+                self.terminal,
+                "{save}{status}{clear}{restore}",
+                save = cursor::SavePosition,
+                status = cursor::MoveTo(x, y + 1),
+                clear = Clear(ClearType::UntilNewLine),
+                restore = cursor::RestorePosition,
+            )?;
+            self.status = false;
+        }
+        Ok(())
+    }
+
+    /// Shows how the word would be coloured below the board, without guessing it.
+    /// The word being typed is left as it is
+    fn display_preview(&mut self, word: &str) -> io::Result<()> {
+        let state = self.game.state();
+        if let Err(err) = state.check(word) {
+            return self.show_status(&err.to_string());
+        }
+        let Matches(matches) = state.diff(word);
+        let palette = self.options.palette;
+        let letters = word
+            .chars()
+            .zip(matches)
+            .map(|(c, m)| LetterMatch(c, m, palette));
+        let x = self.origin.0;
+        let bottom = self.bottom();
+        write!(
+            self.terminal,
+            "{save}{start}{row}{restore}",
+            save = cursor::SavePosition,
+            start = cursor::MoveTo(x, bottom - 2),
+            row = self.options.cells.row(letters),
+            restore = cursor::RestorePosition,
+        )?;
+        self.preview_shown = true;
+        Ok(())
+    }
+
+    /// Gives away a letter of the solution on the status line, while the game has
+    /// letter hints left
+    fn reveal_letter(&mut self) -> io::Result<()> {
+        let limit = self.options.letter_hints;
+        let left = limit.saturating_sub(self.game.state().revealed().count());
+        if limit == 0 {
+            return self.show_status("letter hints are off, see --letter-hints");
+        }
+        if left == 0 {
+            return self.show_status("no letter hints left");
+        }
+        let reveal = match self.game.reveal() {
+            Some(reveal) => reveal,
+            None => return self.show_status("there's no letter left to give away"),
+        };
+        match left - 1 {
+            1 => self.show_status(&format!("{}, 1 hint left", reveal)),
+            left => self.show_status(&format!("{}, {} hints left", reveal, left)),
+        }
+    }
+
+    /// Offers words a typo away from one that isn't in the word list, below the board
+    /// where the preview goes, so the next key clears them too
+    fn display_suggestions(&mut self, word: &str) -> io::Result<()> {
+        let suggestions = self.game.state().suggestions(word, SUGGESTIONS);
+        if suggestions.is_empty() {
+            return Ok(());
+        }
+        let x = self.origin.0;
+        let bottom = self.bottom();
+        write!(
+            self.terminal,
+            "{save}{start}did you mean {words}?{restore}",
+            save = cursor::SavePosition,
+            start = cursor::MoveTo(x, bottom - 2),
+            words = suggestions.join(", ").to_uppercase(),
+            restore = cursor::RestorePosition,
+        )?;
+        self.preview_shown = true;
+        Ok(())
+    }
+
+    fn clear_preview(&mut self) -> io::Result<()> {
+        if self.preview_shown {
+            let x = self.origin.0;
+            let bottom = self.bottom();
+            write!(
+                self.terminal,
+                "{save}{start}{clear}{restore}",
+                save = cursor::SavePosition,
+                start = cursor::MoveTo(x, bottom - 2),
+                clear = Clear(ClearType::UntilNewLine),
+                restore = cursor::RestorePosition,
+            )?;
+            self.preview_shown = false;
+        }
+        Ok(())
     }
 
     fn display_invalid(&mut self, invalid: &str) -> io::Result<()> {
         self.display_window()?;
-        write!(self.stdout, "{}", invalid.to_ascii_uppercase().bg::<Red>())
+        self.shake_word(invalid)?;
+        self.write_word(invalid, true)
+    }
+
+    /// Moves the rejected word back and forth in its row a few times. Like the
+    /// reveal, any pending input cuts it short
+    fn shake_word(&mut self, word: &str) -> io::Result<()> {
+        if !self.options.shake {
+            return Ok(());
+        }
+        let cells = self.options.cells;
+        let x = self.origin.0;
+        let cursor::MoveTo(_, row) = self.cell(0);
+        for shift in SHAKE {
+            // the blank row puts back any separators the letters were moved onto,
+            // and the space clears the last letter when it's moved past the row
+            write!(
+                self.terminal,
+                "{}{} ",
+                cursor::MoveTo(x, row),
+                cells.blank(self.game.word_len())
+            )?;
+            for (i, c) in word.chars().enumerate() {
+                let mut letter = String::new();
+                self.options
+                    .palette
+                    .invalid(c.to_ascii_uppercase(), &mut letter)
+                    .unwrap();
+                let column = x + cells.offset(i) + shift;
+                write!(self.terminal, "{}{}", cursor::MoveTo(column, row), letter)?;
+            }
+            self.terminal.flush()?;
+            if self.terminal.poll(SHAKE_FRAME)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// The row just below the board and the lines under it, as of the last redraw
+    fn bottom(&self) -> u16 {
+        self.origin.1 + board_height(self.game.state().rows())
+    }
+
+    /// Width of the title, board and keyboard, in cells
+    fn board_width(&self) -> u16 {
+        let board = self.options.cells.width(self.game.word_len() as u16);
+        let width = if self.show_keyboard {
+            board + KEYBOARD_GAP + self.keyboard.width()
+        } else {
+            board.max(TITLE_WIDTH)
+        };
+        if self.options.alphabet {
+            width.max(ALPHABET_WIDTH)
+        } else {
+            width
+        }
+    }
+
+    /// Redraws only what the last guess changed: its row, the keys it coloured
+    /// and the alphabet, so the screen isn't cleared and doesn't flicker.
+    ///
+    /// Panels beside the board may no longer be accurate, and a board with unlimited
+    /// guesses moves everything below it down, so those fall back to a full redraw.
+    fn display_guess(&mut self, before: &Keyboard) -> io::Result<()> {
+        // without a limit on the guesses, the board grows a row with each one
+        let panel = self.hint_shown || self.candidates_page.is_some() || self.positions_shown;
+        if panel || self.game.state().unlimited() {
+            return self.display_window();
+        }
+
+        let (x, y) = self.origin;
+        let bottom = self.bottom();
+        let cursor = self.cell(0);
+        let state = self.game.state();
+        if let Some(guess) = state.guesses().next_back() {
+            let row = y + 2 + state.guesses().len() as u16 - 1;
+            write!(
+                self.terminal,
+                "{}{}",
+                cursor::MoveTo(x, row),
+                GuessRow {
+                    guess,
+                    cells: self.options.cells,
+                    palette: self.options.palette,
+                    frequencies: self.options.frequencies.as_ref(),
+                }
+            )?;
+        }
+        if self.show_keyboard {
+            let (column, row) = self.keyboard_origin();
+            write!(
+                self.terminal,
+                "{}",
+                self.keyboard.changes_at(before, column, row)
+            )?;
+        }
+        if self.options.alphabet {
+            write!(
+                self.terminal,
+                "{}{}",
+                cursor::MoveTo(x, bottom - 3),
+                AlphabetStatus(state)
+            )?;
+        }
+        self.display_description()?;
+        self.display_assist()?;
+        write!(self.terminal, "{}", cursor)
+    }
+
+    /// Writes out how each letter of the last guess matched, with `--text`.
+    ///
+    /// It has a line of its own below the board, so it never covers a guess
+    fn display_description(&mut self) -> io::Result<()> {
+        let state = self.game.state();
+        let last = match state.guesses().next_back() {
+            Some(Guess(word, matches)) if self.options.text => matches.describe(word),
+            _ => return Ok(()),
+        };
+        let x = self.origin.0;
+        let bottom = self.bottom();
+        write!(
+            self.terminal,
+            "{}{}{}",
+            cursor::MoveTo(x, bottom),
+            last,
+            Clear(ClearType::UntilNewLine),
+        )
     }
 
     fn display_window(&mut self) -> io::Result<()> {
-        let (_width, height) =
-            crossterm::terminal::size().map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.status = false;
+        self.candidates_page = None;
+        self.hint_shown = false;
+        self.preview_shown = false;
+        let size = self.terminal.size()?;
+        let board = (self.board_width(), board_height(self.game.state().rows()));
+        let (x, y) = self.options.align.origin(size, board);
+        self.origin = (x, y);
+        let bottom = self.bottom();
 
         write!(
-            self.stdout,
-            "{clear_all}{bottom_left}Press ESC to exit.{top_left}Termo {game_type}{down}{keyboard}{state}",
+            self.terminal,
+            "{clear_all}{bottom_left}Press ESC to exit.{top_left}Termo {game_type}",
             clear_all = Clear(ClearType::All),
-            bottom_left = cursor::MoveTo(0, height-1),
-            top_left = cursor::MoveTo(0, 0),
+            bottom_left = cursor::MoveTo(0, size.1 - 1),
+            top_left = cursor::MoveTo(x, y),
             game_type = self.game.game_type(),
-            down = cursor::MoveTo(0, 2),
-            keyboard = self.keyboard,
-            state = Guesses::from(self.game.state()),
+        )?;
+        if self.show_keyboard {
+            let (column, row) = self.keyboard_origin();
+            write!(self.terminal, "{}", self.keyboard.at(column, row))?;
+        }
+        if self.options.alphabet {
+            write!(
+                self.terminal,
+                "{}{}",
+                cursor::MoveTo(x, bottom - 3),
+                AlphabetStatus(self.game.state())
+            )?;
+        }
+        self.display_description()?;
+        self.display_assist()?;
+        self.display_positions()?;
+        self.display_opponent()?;
+        let cursor = self.cell(0);
+        write!(
+            self.terminal,
+            "{down}{guesses}{cursor}",
+            down = cursor::MoveTo(x, y + 2),
+            guesses = Guesses {
+                state: self.game.state(),
+                cells: self.options.cells,
+                palette: self.options.palette,
+                frequencies: self.options.frequencies.as_ref(),
+            },
+            cursor = cursor,
         )?;
 
         Ok(())
     }
 }
+
+/// The terminal is drawn on with crossterm commands, only redrawing what changed
+impl<B: Backend> Renderer for Controller<B> {
+    type Error = eyre::Report;
+
+    fn game(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    fn draw_window(&mut self) -> Result<()> {
+        Ok(self.display_window()?)
+    }
+
+    fn draw_word(&mut self, word: &str) -> Result<()> {
+        let width = self.options.cells.letter_width as usize;
+        for i in word.chars().count()..self.game.word_len() {
+            let cell = self.cell(i);
+            write!(self.terminal, "{}{:width$}", cell, "")?;
+        }
+        Ok(self.write_word(word, false)?)
+    }
+
+    fn draw_guess(&mut self, word: &str, matches: Matches) -> Result<()> {
+        let before = self.keyboard.clone();
+        // with accents ignored, an accented letter colours its plain key
+        self.keyboard.push(&self.game.state().fold(word), matches);
+        self.deduce_keyboard();
+        self.suggest();
+        if let Some(on_guess) = &mut self.on_guess {
+            on_guess(&self.game);
+        }
+        self.reveal_guess()?;
+        Ok(self.display_guess(&before)?)
+    }
+
+    fn show_invalid(&mut self, word: &str, err: GuessError) -> Result<()> {
+        self.display_invalid(word)?;
+        if err == GuessError::NotInWordList {
+            self.display_suggestions(word)?;
+        }
+        Ok(self.show_status(&err.to_string())?)
+    }
+}
+
+/// The stats as they'll be once the daily game in the share is recorded
+fn stats_after(stats: &Stats, share: &GameShare) -> Option<Stats> {
+    let day = match share.game_type() {
+        GameType::Daily(day) => *day,
+        _ => return None,
+    };
+    let mut stats = stats.clone();
+    let guesses = share.score().and_then(|score| score.guesses());
+    stats.record(day, guesses.map(|n| n as u8));
+    Some(stats)
+}
+
+/// Copies the share to the clipboard, returning the message saying whether it worked
+#[cfg(feature = "clipboard")]
+fn copy(share: &GameShare) -> &'static str {
+    match share.copy_to_clipboard() {
+        Ok(()) => "copied to clipboard",
+        // such as over SSH, or without a display server. It's still printed on leaving
+        Err(_) => "no clipboard available, the result is printed on leaving",
+    }
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn copy(_: &GameShare) -> &'static str {
+    "built without clipboard support"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, io, time::Duration};
+
+    use cl_wordle::{prelude::*, stats::Stats};
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    use super::{
+        stats_after,
+        terminal::{screen::Screen, Backend},
+        Align, Controller, Interrupt, Options,
+    };
+
+    /// Plays back a list of key presses, discarding everything drawn. The terminal is
+    /// 80x24 until a resize is played back
+    pub(super) struct Scripted(pub(super) VecDeque<Event>, (u16, u16));
+
+    impl Scripted {
+        pub(super) fn new(keys: &str) -> Self {
+            let events = keys.chars().map(|c| {
+                let code = match c {
+                    '\n' => KeyCode::Enter,
+                    '\x1b' => KeyCode::Esc,
+                    '\x08' => KeyCode::Backspace,
+                    c => KeyCode::Char(c),
+                };
+                Event::Key(KeyEvent::from(code))
+            });
+            Self(events.collect(), (80, 24))
+        }
+    }
+
+    impl io::Write for Scripted {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for Scripted {
+        fn size(&self) -> io::Result<(u16, u16)> {
+            Ok(self.1)
+        }
+
+        fn read(&mut self) -> io::Result<Event> {
+            let event = self.0.pop_front();
+            if let Some(Event::Resize(width, height)) = event {
+                self.1 = (width, height);
+            }
+            event.ok_or_else(|| io::ErrorKind::UnexpectedEof.into())
+        }
+
+        fn poll(&mut self, _: Duration) -> io::Result<bool> {
+            Ok(!self.0.is_empty())
+        }
+    }
+
+    /// Plays back key presses like [`Scripted`], keeping what's drawn on a screen of
+    /// cells to check where it went
+    struct Drawn(Scripted, Screen);
+
+    impl io::Write for Drawn {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.1.write(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.1.render(self.0 .1);
+            Ok(())
+        }
+    }
+
+    impl Backend for Drawn {
+        fn size(&self) -> io::Result<(u16, u16)> {
+            self.0.size()
+        }
+
+        fn read(&mut self) -> io::Result<Event> {
+            self.0.read()
+        }
+
+        fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+            self.0.poll(timeout)
+        }
+    }
+
+    fn play(keys: &str) -> Option<GameShare> {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let controller = Controller::with_backend(game, Options::default(), Scripted::new(keys));
+        controller.run().unwrap()
+    }
+
+    #[test]
+    fn win() {
+        let share = play("sagaz\ntermo\n\n").unwrap();
+        assert_eq!(share.score(), Some(Score::Guesses(2)));
+    }
+
+    #[test]
+    fn timed_share_has_the_time() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let options = Options {
+            timed: true,
+            ..Options::default()
+        };
+        let share = Controller::with_backend(game, options, Scripted::new("termo\n\n"))
+            .run()
+            .unwrap()
+            .unwrap();
+        assert_eq!(share.time(), Some(Duration::ZERO));
+        assert!(share.to_string().ends_with("\n⏱ 00:00"));
+        assert_eq!(play("termo\n\n").unwrap().time(), None);
+    }
+
+    #[test]
+    fn share_without_keyboard_hints_is_marked() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let options = Options {
+            no_keyboard_hints: true,
+            ..Options::default()
+        };
+        let share = Controller::with_backend(
+            game,
+            options,
+            Scripted::new(
+                "sagaz
+termo
+
+",
+            ),
+        )
+        .run()
+        .unwrap()
+        .unwrap();
+        assert!(share.no_keyboard_hints());
+        assert!(share
+            .to_string()
+            .starts_with("joguei term.ooo #custom 2/6!\n"));
+    }
+
+    #[test]
+    fn rounds_follow_on_after_enter() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let options = Options {
+            timer: true,
+            ..Options::default()
+        };
+        let shares = Controller::with_backend(game, options, Scripted::new("termo\n\nsagaz\n\x1b"))
+            .with_next_round(|| Game::custom("sagaz".to_owned()).unwrap())
+            .run_rounds(3)
+            .unwrap();
+        assert_eq!(shares.len(), 2);
+        assert!(shares.iter().all(|share| share.time().is_some()));
+    }
+
+    #[test]
+    fn marathons_go_on_until_a_word_isnt_solved() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let misses = ["termo", "fazer", "mundo", "livro", "carta", "porta"];
+        let keys = format!("termo\n\n{}\n\n", misses.join("\n"));
+        let shares = Controller::with_backend(game, Options::default(), Scripted::new(&keys))
+            .with_next_round(|| Game::custom("sagaz".to_owned()).unwrap())
+            .run_marathon()
+            .unwrap();
+        assert_eq!(shares.len(), 2);
+        assert!(shares[0].won() && !shares[1].won());
+    }
+
+    #[test]
+    fn earlier_games_can_be_looked_back_on() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        // l does nothing until there's an earlier game, then opens the list of them
+        let keys = Scripted::new("termo\nlnsagaz\nl\n\x1b\x1b\n");
+        let share = Controller::with_backend(game, Options::default(), keys)
+            .with_next_round(|| Game::custom("sagaz".to_owned()).unwrap())
+            .run()
+            .unwrap()
+            .unwrap();
+        assert!(share.won());
+    }
+
+    #[test]
+    fn resumed_game_saves_each_guess() {
+        let mut game = Game::custom("termo".to_owned()).unwrap();
+        assert!(game.resume("custom\nsagaz"));
+        let saves = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let saved = saves.clone();
+        let share = Controller::with_backend(game, Options::default(), Scripted::new("termo\n\n"))
+            .with_on_guess(move |game| saved.borrow_mut().push(game.save()))
+            .run()
+            .unwrap()
+            .unwrap();
+        assert_eq!(share.score(), Some(Score::Guesses(2)));
+        assert_eq!(*saves.borrow(), ["custom\nsagaz\ntermo"]);
+    }
+
+    #[test]
+    fn more_guesses_than_fit() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let game = game.with_max_guesses(10).unwrap();
+        let backend = Scripted::new("sagaz\nsagaz\ntermo\n\n");
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert!(share
+            .unwrap()
+            .unwrap()
+            .to_string()
+            .starts_with("joguei term.ooo #custom 3/10"));
+
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let game = game.with_max_guesses(20).unwrap();
+        let err = Controller::with_backend(game, Options::default(), Scripted::new("")).run();
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "20 guesses need a terminal 26 rows tall, this one is 24"
+        );
+    }
+
+    #[test]
+    fn invalid_guesses_are_ignored() {
+        let share = play("zzzzz\n\x08\x08\x08\x08\x08termo\n\n").unwrap();
+        assert_eq!(share.score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn typos_can_be_fixed_after_the_suggestions() {
+        // temro isn't a word, so termo is suggested below the board
+        let share = play("temro\n\x08\x08\x08rmo\n\n").unwrap();
+        assert_eq!(share.score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn debounce() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let options = Options {
+            debounce: Duration::from_secs(3600),
+            ..Options::default()
+        };
+        let backend = Scripted::new("tteeerrmmoo\n\n");
+        let share = Controller::with_backend(game, options, backend)
+            .run()
+            .unwrap();
+        assert_eq!(share.unwrap().score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn ctrl_c() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let mut backend = Scripted::new("sag");
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        backend.0.push_back(Event::Key(ctrl_c));
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert!(share.unwrap().is_none());
+
+        // declining keeps the word being typed
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let options = Options {
+            interrupt: Interrupt::Confirm,
+            ..Options::default()
+        };
+        let mut backend = Scripted::new("ter");
+        backend.0.push_back(Event::Key(ctrl_c));
+        backend.0.extend(Scripted::new("nmo\n\n").0);
+        let share = Controller::with_backend(game, options, backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn preview_doesnt_guess() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let options = Options {
+            preview: true,
+            ..Options::default()
+        };
+        let mut backend = Scripted::new("sagaz");
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        backend.0.push_back(Event::Key(ctrl_p));
+        // the word is still there to be guessed after the preview
+        backend.0.extend(Scripted::new("\ntermo\n\n").0);
+        let share = Controller::with_backend(game, options, backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(2)));
+    }
+
+    #[test]
+    fn the_analysis_goes_back_to_the_board() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let backend = Scripted::new("termo\na\x1b\n");
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn games_of_other_lengths_are_not_rated() {
+        let game = Game::custom_unlisted("banana".to_owned()).unwrap();
+        let backend = Scripted::new("banana\na\n");
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn letter_hints_go_in_the_share() {
+        let ctrl_r = Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        let play = |letter_hints| {
+            let game = Game::custom("termo".to_owned()).unwrap();
+            let options = Options {
+                letter_hints,
+                ..Options::default()
+            };
+            let mut backend = Scripted::new("");
+            backend.0.extend([ctrl_r; 3]);
+            backend.0.extend(Scripted::new("termo\n\n").0);
+            Controller::with_backend(game, options, backend).run()
+        };
+        // only as many are given away as allowed
+        assert_eq!(play(2).unwrap().unwrap().hints(), 2);
+        assert_eq!(play(0).unwrap().unwrap().hints(), 0);
+    }
+
+    #[test]
+    fn ctrl_g_gives_up() {
+        let ctrl_g = Event::Key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+        let give_up = |game, keys_after| {
+            let mut backend = Scripted::new("sagaz\n");
+            backend.0.push_back(ctrl_g);
+            backend.0.extend(Scripted::new(keys_after).0);
+            Controller::with_backend(game, Options::default(), backend).run()
+        };
+        let share = give_up(Game::custom("termo".to_owned()).unwrap(), "\n");
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Failed));
+
+        // a daily asks first, and carries on if it's declined
+        let share = give_up(Game::from_day(0).unwrap(), "ntermo\n\n");
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(2)));
+        let share = give_up(Game::from_day(0).unwrap(), "y\n");
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Failed));
+    }
+
+    #[test]
+    fn ctrl_u_clears_the_word() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let mut backend = Scripted::new("sagaz\ntur");
+        let ctrl_u = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+        backend.0.push_back(Event::Key(ctrl_u));
+        backend.0.extend(Scripted::new("termo\n\n").0);
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(2)));
+    }
+
+    #[test]
+    fn waits_for_the_terminal_to_fit_again() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let mut backend = Scripted::new("ter");
+        // typing is ignored while the board doesn't fit
+        backend.0.push_back(Event::Resize(80, 5));
+        backend.0.extend(Scripted::new("xx").0);
+        backend.0.push_back(Event::Resize(80, 24));
+        backend.0.extend(Scripted::new("mo\n\n").0);
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(1)));
+
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let mut backend = Scripted::new("ter");
+        backend.0.push_back(Event::Resize(10, 24));
+        backend.0.extend(Scripted::new("\x1b").0);
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert!(share.unwrap().is_none());
+    }
+
+    #[test]
+    fn undo_takes_back_a_guess() {
+        let ctrl_z = Event::Key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL));
+        let mut backend = Scripted::new("sagaz\n");
+        backend.0.push_back(ctrl_z);
+        backend.0.extend(Scripted::new("termo\n\n").0);
+        let share = Controller::with_backend(
+            Game::custom("termo".to_owned()).unwrap(),
+            Options::default(),
+            backend,
+        )
+        .run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(1)));
+
+        // a daily counts, so the guess stays
+        let mut backend = Scripted::new("sagaz\n");
+        backend.0.push_back(ctrl_z);
+        backend.0.extend(Scripted::new("tenso\n\n").0);
+        let game = Game::from_day(
+            cl_wordle::words::FINAL
+                .iter()
+                .position(|&w| w == "tenso")
+                .unwrap(),
+        )
+        .unwrap();
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(2)));
+    }
+
+    #[test]
+    fn toggling_positions_keeps_the_word_being_typed() {
+        let ctrl_l = Event::Key(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL));
+        let mut backend = Scripted::new("sag");
+        backend.0.push_back(ctrl_l);
+        backend.0.extend(Scripted::new("az\ntermo\n\n").0);
+        let options = Options {
+            positions: true,
+            ..Options::default()
+        };
+        let share =
+            Controller::with_backend(Game::custom("termo".to_owned()).unwrap(), options, backend)
+                .run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(2)));
+    }
+
+    #[test]
+    fn resize_keeps_the_word_being_typed() {
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let mut backend = Scripted::new("ter");
+        backend.0.push_back(Event::Resize(100, 30));
+        backend.0.extend(Scripted::new("mo\n").0);
+        backend.0.push_back(Event::Resize(60, 20));
+        backend.0.extend(Scripted::new("\n").0);
+        let share = Controller::with_backend(game, Options::default(), backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn resize_moves_the_board_and_the_cursor_together() {
+        let draw = |keys: Scripted| {
+            let game = Game::custom("termo".to_owned()).unwrap();
+            let options = Options {
+                align: Align::Center,
+                ..Options::default()
+            };
+            let mut drawn = Drawn(keys, Screen::default());
+            // the keys run out with the word still being typed
+            assert!(Controller::with_backend(game, options, &mut drawn)
+                .run()
+                .is_err());
+            drawn.1.render((100, 30));
+            drawn.1
+        };
+        let mut resized = Scripted::new("sagaz\nter");
+        resized.0.push_back(Event::Resize(100, 30));
+        let screen = draw(resized);
+        let mut started = Scripted::new("sagaz\nter");
+        started.1 = (100, 30);
+        let fresh = draw(started);
+        // the same screen as a game started at that size
+        for y in 0..30 {
+            assert_eq!(screen.line(y), fresh.line(y), "row {}", y);
+        }
+        assert_eq!(screen.cursor(), fresh.cursor());
+
+        let (x, y) = (0..30)
+            .find_map(|y| Some((screen.line(y).find("Termo")? as u16, y)))
+            .unwrap();
+        assert_eq!(&screen.line(y + 2)[x as usize..][..5], "SAGAZ");
+        assert_eq!(&screen.line(y + 3)[x as usize..][..5], "TER  ");
+        assert_eq!(screen.cursor(), (x + 3, y + 3));
+    }
+
+    #[test]
+    fn clicking_the_keyboard_types() {
+        let click = |column, row| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        let game = Game::custom("termo".to_owned()).unwrap();
+        let options = Options {
+            mouse: true,
+            ..Options::default()
+        };
+        // t is the 6th key of the 3rd row, beside the board
+        let mut backend = Scripted::new("");
+        backend.0.extend([click(19, 4), click(0, 0)]);
+        backend.0.extend(Scripted::new("ermo").0);
+        // then enter, on the row of buttons below the letters
+        backend.0.extend([click(15, 6)]);
+        backend.0.extend(Scripted::new("\n").0);
+        let share = Controller::with_backend(game, options, backend).run();
+        assert_eq!(share.unwrap().unwrap().score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn quit() {
+        assert!(play("sag\x1b").is_none());
+        assert!(play("sagaz\nter\x1b").is_none());
+    }
+
+    #[test]
+    fn quitting_a_started_daily_asks_first() {
+        let daily = |keys| {
+            let game = Game::from_day(0).unwrap();
+            let controller =
+                Controller::with_backend(game, Options::default(), Scripted::new(keys));
+            controller.run().unwrap()
+        };
+        assert!(daily("sag\x1b").is_none());
+        assert!(daily("sagaz\nter\x1by").is_none());
+        // declining keeps the word being typed
+        let share = daily("sagaz\nter\x1bnmo\n\n").unwrap();
+        assert_eq!(share.score(), Some(Score::Guesses(2)));
+    }
+
+    #[test]
+    fn stats_include_the_game_just_played() {
+        let stats = Stats::parse("0 X\n");
+        let mut game = Game::from_day(1).unwrap();
+        let solution = game.state().solution().to_owned();
+        game.guess(&solution).unwrap();
+        assert_eq!(
+            stats_after(&stats, &game.share()).unwrap().summary(),
+            "played 2, won 50%, current streak 1, max streak 1"
+        );
+        // only dailies are recorded
+        assert_eq!(
+            stats_after(&stats, &Game::custom("termo".to_owned()).unwrap().share()),
+            None
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt;
+
+use cl_wordle::state::{Guess, State};
+use owo_colors::OwoColorize;
+
+use super::letters;
+
+/// The whole alphabet of the word lists on one line, with every letter that's been
+/// guessed dimmed.
+///
+/// Unlike the keyboard, this doesn't say anything about how the letters matched
+pub struct AlphabetStatus<'a>(pub &'a State);
+
+impl<'a> fmt::Display for AlphabetStatus<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guessed = self
+            .0
+            .guesses()
+            .map(|Guess(word, _)| word)
+            .collect::<String>();
+        for c in self.0.alphabet() {
+            let used = guessed.contains(c);
+            let c = letters::uppercase(c);
+            if used {
+                write!(f, "{}", c.dimmed())?;
+            } else {
+                write!(f, "{}", c.bold())?;
+            }
+        }
+        Ok(())
+    }
+}
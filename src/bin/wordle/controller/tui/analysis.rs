@@ -0,0 +1,85 @@
+//! How each guess of the game just finished was rated, shown over the board.
+
+use std::io;
+
+use cl_wordle::analysis::Analysis;
+use crossterm::{
+    cursor,
+    event::{self, KeyCode, KeyModifiers},
+    execute,
+    terminal::{Clear, ClearType},
+};
+use eyre::Result;
+
+use super::terminal::{Backend, Terminal};
+
+/// Shows the table of ratings until Esc or Enter goes back to the game
+pub struct Controller<'a, B = Terminal> {
+    analysis: &'a Analysis,
+    terminal: B,
+}
+
+impl<'a, B: Backend> Controller<'a, B> {
+    pub fn with_backend(analysis: &'a Analysis, terminal: B) -> Self {
+        Self { analysis, terminal }
+    }
+
+    pub fn run(mut self) -> Result<()> {
+        loop {
+            self.display()?;
+            let key = match self.terminal.read()? {
+                event::Event::Key(key) => key,
+                _ => continue,
+            };
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn display(&mut self) -> io::Result<()> {
+        let (_, height) = self.terminal.size()?;
+        execute!(
+            self.terminal,
+            cursor::Hide,
+            cursor::MoveTo(0, 0),
+            Clear(ClearType::All),
+        )?;
+        write!(self.terminal, "How each guess went")?;
+        for (row, line) in self.analysis.to_string().lines().enumerate() {
+            write!(
+                self.terminal,
+                "{}{}",
+                cursor::MoveTo(0, row as u16 + 2),
+                line
+            )?;
+        }
+        write!(
+            self.terminal,
+            "{}Press ESC to go back.",
+            cursor::MoveTo(0, height - 1)
+        )?;
+        self.terminal.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::{analysis::Analysis, diff, state::Guess};
+
+    use super::Controller;
+    use crate::controller::tui::tests::Scripted;
+
+    #[test]
+    fn goes_back_to_the_game() {
+        let analysis = Analysis::new(&[Guess("termo", diff("termo", "termo"))]);
+        for keys in ["\x1b", "x\n", "q"] {
+            let controller = Controller::with_backend(&analysis, Scripted::new(keys));
+            controller.run().unwrap();
+        }
+    }
+}
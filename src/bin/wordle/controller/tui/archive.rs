@@ -0,0 +1,185 @@
+use std::io;
+
+use crossterm::{
+    cursor,
+    event::{self, KeyCode, KeyModifiers},
+    execute,
+    terminal::{Clear, ClearType},
+};
+use eyre::Result;
+use owo_colors::OwoColorize;
+
+use super::terminal::{Backend, Terminal};
+
+/// A past daily game, and how it went if it was played
+pub struct Entry {
+    pub day: usize,
+    pub date: time::Date,
+    /// The number of guesses, `None` for a loss, or `None` overall if not played yet
+    pub result: Option<Option<u8>>,
+}
+
+/// Lists past days to pick one to play, scrolling when they don't fit on screen
+pub struct Controller<B = Terminal> {
+    entries: Vec<Entry>,
+    terminal: B,
+    /// The entry that's highlighted
+    selected: usize,
+    /// The first entry shown
+    top: usize,
+}
+
+impl Controller {
+    pub fn new(entries: Vec<Entry>) -> Result<Self> {
+        let terminal = Terminal::new(false)?;
+        Ok(Self::with_backend(entries, terminal))
+    }
+}
+
+impl<B: Backend> Controller<B> {
+    /// Starts on the first entry that hasn't been played
+    pub fn with_backend(entries: Vec<Entry>, terminal: B) -> Self {
+        let selected = entries.iter().position(|e| e.result.is_none()).unwrap_or(0);
+        Self {
+            entries,
+            terminal,
+            selected,
+            top: 0,
+        }
+    }
+
+    /// Waits for a day to be picked with Enter, or returns `None` if the menu was left
+    pub fn run(mut self) -> Result<Option<usize>> {
+        if self.entries.is_empty() {
+            return Ok(None);
+        }
+        let last = self.entries.len() - 1;
+        loop {
+            self.display()?;
+            let key = match self.terminal.read()? {
+                event::Event::Key(key) => key,
+                _ => continue,
+            };
+            let page = self.page();
+            self.selected = match key.code {
+                KeyCode::Enter => return Ok(Some(self.entries[self.selected].day)),
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(None)
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => (self.selected + 1).min(last),
+                KeyCode::PageUp => self.selected.saturating_sub(page),
+                KeyCode::PageDown => (self.selected + page).min(last),
+                KeyCode::Home => 0,
+                KeyCode::End => last,
+                // the next day that hasn't been played, wrapping round to the top
+                KeyCode::Char('u') => (1..=self.entries.len())
+                    .map(|n| (self.selected + n) % self.entries.len())
+                    .find(|&i| self.entries[i].result.is_none())
+                    .unwrap_or(self.selected),
+                _ => self.selected,
+            };
+        }
+    }
+
+    /// Number of entries that fit below the title
+    fn page(&self) -> usize {
+        let height = self.terminal.size().map_or(24, |(_, height)| height);
+        (height as usize).saturating_sub(2).max(1)
+    }
+
+    fn display(&mut self) -> io::Result<()> {
+        let page = self.page();
+        // scroll just enough to keep the selected entry on screen
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + page {
+            self.top = self.selected + 1 - page;
+        }
+
+        execute!(
+            self.terminal,
+            cursor::Hide,
+            cursor::MoveTo(0, 0),
+            Clear(ClearType::All),
+        )?;
+        write!(
+            self.terminal,
+            "Pick a day: enter to play, u for the next unplayed, esc to leave"
+        )?;
+        let shown = self.entries.iter().enumerate().skip(self.top).take(page);
+        for (row, (i, entry)) in shown.enumerate() {
+            let line = line(entry);
+            write!(self.terminal, "{}", cursor::MoveTo(0, row as u16 + 2))?;
+            if i == self.selected {
+                write!(self.terminal, "{}", line.reversed())?;
+            } else {
+                write!(self.terminal, "{}", line)?;
+            }
+        }
+        self.terminal.flush()
+    }
+}
+
+/// The entry as one line of the menu, like `✓ #12    2022-01-13  3/6`
+fn line(entry: &Entry) -> String {
+    let (mark, score) = match entry.result {
+        Some(Some(n)) => ('✓', format!("{}/6", n)),
+        Some(None) => ('✗', "X/6".to_owned()),
+        None => (' ', String::new()),
+    };
+    let day = format!("#{}", entry.day);
+    format!("{} {:<6} {}  {}", mark, day, entry.date, score)
+        .trim_end()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::{line, Controller, Entry};
+    use crate::controller::tui::tests::Scripted;
+
+    fn entries() -> Vec<Entry> {
+        [Some(Some(3)), None, Some(None), None]
+            .into_iter()
+            .enumerate()
+            .map(|(i, result)| Entry {
+                day: 10 - i,
+                date: date!(2022 - 01 - 11) - time::Duration::days(i as i64),
+                result,
+            })
+            .collect()
+    }
+
+    fn pick(keys: &str) -> Option<usize> {
+        Controller::with_backend(entries(), Scripted::new(keys))
+            .run()
+            .unwrap()
+    }
+
+    #[test]
+    fn starts_on_the_first_unplayed_day() {
+        assert_eq!(pick("\n"), Some(9));
+        assert_eq!(pick("kk\n"), Some(10));
+        assert_eq!(pick("jjjj\n"), Some(7));
+        assert_eq!(pick("uu\n"), Some(9));
+        assert_eq!(pick("\x1b"), None);
+    }
+
+    #[test]
+    fn marks_the_results() {
+        let lines = entries().iter().map(line).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "✓ #10    2022-01-11  3/6",
+                "  #9     2022-01-10",
+                "✗ #8     2022-01-09  X/6",
+                "  #7     2022-01-08",
+            ]
+        );
+    }
+}
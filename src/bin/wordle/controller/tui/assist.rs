@@ -0,0 +1,76 @@
+use std::fmt;
+
+use cl_wordle::solver::Suggestions;
+use crossterm::{
+    cursor,
+    terminal::{Clear, ClearType},
+};
+
+/// Suggestions shown at most, below the count of solutions left
+pub const SUGGESTIONS: usize = 5;
+
+/// The best next guesses, kept up to date after each guess, with their top-left
+/// corner at the given cell.
+///
+/// Without suggestions, the lines are cleared, such as once the game is over
+pub struct AssistPanel<'a> {
+    pub suggestions: Option<&'a Suggestions>,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl<'a> AssistPanel<'a> {
+    /// The line at `i`, or an empty one past the suggestions
+    fn line(&self, i: usize) -> String {
+        let Suggestions { remaining, words } = match self.suggestions {
+            Some(suggestions) => suggestions,
+            None => return String::new(),
+        };
+        match i {
+            0 if *remaining == 1 => "1 word left".to_owned(),
+            0 => format!("{} words left", remaining),
+            i => words
+                .get(i - 1)
+                .map_or_else(String::new, |word| format!(" {}", word.to_uppercase())),
+        }
+    }
+}
+
+impl<'a> fmt::Display for AssistPanel<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", cursor::SavePosition)?;
+        for i in 0..=SUGGESTIONS {
+            let start = cursor::MoveTo(self.column, self.row + i as u16);
+            write!(
+                f,
+                "{}{}{}",
+                start,
+                Clear(ClearType::UntilNewLine),
+                self.line(i)
+            )?;
+        }
+        write!(f, "{}", cursor::RestorePosition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::solver::Suggestions;
+
+    use super::AssistPanel;
+
+    #[test]
+    fn lists_the_suggestions_under_the_count() {
+        let suggestions = Suggestions {
+            remaining: 12,
+            words: vec!["termo", "tempo"],
+        };
+        let panel = AssistPanel {
+            suggestions: Some(&suggestions),
+            column: 0,
+            row: 0,
+        };
+        let lines = (0..4).map(|i| panel.line(i)).collect::<Vec<_>>();
+        assert_eq!(lines, ["12 words left", " TERMO", " TEMPO", ""]);
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use crossterm::{
+    cursor,
+    terminal::{Clear, ClearType},
+};
+
+/// Lists one page of the remaining solutions in columns, with its top-left corner at
+/// the given cell
+pub struct CandidatesPanel<'a> {
+    pub candidates: &'a [&'a str],
+    pub page: usize,
+    pub column: u16,
+    pub row: u16,
+    /// Space available for the panel, in cells
+    pub size: (u16, u16),
+}
+
+impl CandidatesPanel<'_> {
+    /// Number of candidates that fit on one page
+    pub fn per_page(&self) -> usize {
+        let (width, height) = self.size;
+        // one line is taken by the header, and each word takes 7 columns
+        let columns = (width as usize / 7).max(1);
+        let rows = (height as usize).saturating_sub(1).max(1);
+        columns * rows
+    }
+
+    /// Number of pages needed to list every candidate
+    pub fn pages(&self) -> usize {
+        self.candidates.len().div_ceil(self.per_page()).max(1)
+    }
+}
+
+impl fmt::Display for CandidatesPanel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let columns = (self.size.0 as usize / 7).max(1);
+        let per_page = self.per_page();
+
+        write!(
+            f,
+            "{}{}{} possible, page {}/{} (Tab for more){}",
+            cursor::SavePosition,
+            cursor::MoveTo(self.column, self.row),
+            self.candidates.len(),
+            self.page + 1,
+            self.pages(),
+            Clear(ClearType::UntilNewLine),
+        )?;
+
+        let page = self
+            .candidates
+            .chunks(per_page)
+            .nth(self.page)
+            .unwrap_or_default();
+        for (i, line) in page.chunks(columns).enumerate() {
+            write!(
+                f,
+                "{}",
+                cursor::MoveTo(self.column, self.row + 1 + i as u16)
+            )?;
+            for word in line {
+                write!(f, "{:7}", word.to_uppercase())?;
+            }
+            write!(f, "{}", Clear(ClearType::UntilNewLine))?;
+        }
+        write!(f, "{}", cursor::RestorePosition)
+    }
+}
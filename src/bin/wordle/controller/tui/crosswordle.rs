@@ -0,0 +1,287 @@
+//! Filling in the guesses of a finished board from their colours, for `wordle
+//! crosswordle`.
+
+use std::{io, iter};
+
+use cl_wordle::{
+    crosswordle::{Crosswordle, CrosswordleShare, FillError},
+    Match,
+};
+use crossterm::{
+    cursor,
+    event::{self, KeyCode, KeyModifiers},
+    execute,
+    terminal::{Clear, ClearType},
+};
+use eyre::Result;
+
+use super::{
+    layout::Cells,
+    letters::{LetterMatch, Palette},
+    terminal::{Backend, Terminal},
+};
+
+/// Columns left of the board for the marker on the row being filled in
+const MARKER_WIDTH: u16 = 2;
+
+/// Shows the colours of every row with the solution below them, and takes the words
+/// that fit them one row at a time, starting from the top.
+///
+/// Up and down pick another row, including one that's already filled in to change it
+pub struct Controller<B = Terminal> {
+    board: Crosswordle,
+    cells: Cells,
+    palette: Palette,
+    terminal: B,
+    /// The word being typed
+    word: String,
+    /// The row the word goes on
+    row: usize,
+}
+
+impl Controller {
+    pub fn new(board: Crosswordle, cells: Cells, palette: Palette) -> Result<Self> {
+        let terminal = Terminal::new(false)?;
+        Ok(Self::with_backend(board, cells, palette, terminal))
+    }
+}
+
+impl<B: Backend> Controller<B> {
+    pub fn with_backend(board: Crosswordle, cells: Cells, palette: Palette, terminal: B) -> Self {
+        Self {
+            cells: cells.fit(board.solution()),
+            board,
+            palette,
+            terminal,
+            word: String::new(),
+            row: 0,
+        }
+    }
+
+    /// Plays until every row is filled in, or returns `None` if it was quit
+    pub fn run(mut self) -> Result<Option<CrosswordleShare>> {
+        let (_, height) = self.terminal.size()?;
+        let needed = self.needed_height();
+        if height < needed {
+            eyre::bail!(
+                "{} rows need a terminal {} rows tall, this one is {}",
+                self.board.target().len() + 1,
+                needed,
+                height
+            );
+        }
+
+        self.display_window()?;
+        let len = self.board.word_len();
+        loop {
+            self.terminal.flush()?;
+            let key = match self.terminal.read()? {
+                event::Event::Key(key) => key,
+                event::Event::Resize(..) => {
+                    self.display_window()?;
+                    continue;
+                }
+                _ => continue,
+            };
+            self.show_status("")?;
+            match key.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(None)
+                }
+                KeyCode::Char(c) if c.is_alphabetic() && self.word.chars().count() < len => {
+                    self.word.extend(c.to_lowercase());
+                    self.display_row(self.row)?;
+                }
+                KeyCode::Backspace => {
+                    self.word.pop();
+                    self.display_row(self.row)?;
+                }
+                KeyCode::Up if self.row > 0 => self.select(self.row - 1)?,
+                KeyCode::Down if self.row + 1 < self.board.target().len() => {
+                    self.select(self.row + 1)?
+                }
+                KeyCode::Enter if self.word.chars().count() == len => {
+                    match self.board.fill(self.row, &self.word) {
+                        Ok(()) => {
+                            self.word.clear();
+                            if self.board.solved() {
+                                break;
+                            }
+                            self.select(self.next_empty())?;
+                        }
+                        Err(FillError::DoesntFit(matches)) => {
+                            let mut squares = String::new();
+                            for &m in &matches.0 {
+                                self.palette.square(m, &mut squares)?;
+                            }
+                            let word = self.word.to_uppercase();
+                            self.show_status(&format!("{} would be {}", word, squares))?;
+                        }
+                        Err(err) => self.show_status(&err.to_string())?,
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.display_window()?;
+        let message = match self.board.misses() {
+            0 => "filled in without a miss!".to_owned(),
+            1 => "filled in with 1 miss".to_owned(),
+            misses => format!("filled in with {} misses", misses),
+        };
+        self.show_status(&message)?;
+        execute!(self.terminal, cursor::Hide)?;
+        loop {
+            if let event::Event::Key(key) = self.terminal.read()? {
+                if let KeyCode::Enter | KeyCode::Esc = key.code {
+                    return Ok(Some(self.board.share()));
+                }
+            }
+        }
+    }
+
+    /// Moves the word being typed onto another row, starting it again
+    fn select(&mut self, row: usize) -> io::Result<()> {
+        let last = self.row;
+        self.word.clear();
+        self.row = row;
+        self.display_row(last)?;
+        self.display_row(row)
+    }
+
+    /// The first row after this one without a word, going round to the top
+    fn next_empty(&self) -> usize {
+        let rows = self.board.filled().len();
+        (1..=rows)
+            .map(|i| (self.row + i) % rows)
+            .find(|&row| self.board.filled()[row].is_none())
+            .unwrap_or(self.row)
+    }
+
+    /// The title, the status line, every row, the solution and the exit message
+    fn needed_height(&self) -> u16 {
+        self.board.target().len() as u16 + 5
+    }
+
+    fn display_window(&mut self) -> io::Result<()> {
+        let (_, height) = self.terminal.size()?;
+        write!(
+            self.terminal,
+            "{clear}{bottom}Press ESC to exit.{top}Termo {game_type} crosswordle",
+            clear = Clear(ClearType::All),
+            bottom = cursor::MoveTo(0, height - 1),
+            top = cursor::MoveTo(0, 0),
+            game_type = self.board.game_type(),
+        )?;
+        let solution = self.board.solution().to_uppercase();
+        let letters = solution
+            .chars()
+            .map(|c| LetterMatch(c, Match::Exact, self.palette));
+        let y = 2 + self.board.target().len() as u16;
+        write!(
+            self.terminal,
+            "{}{}",
+            cursor::MoveTo(MARKER_WIDTH, y),
+            self.cells.row(letters)
+        )?;
+        let selected = self.row;
+        for row in (0..self.board.target().len()).filter(|&row| row != selected) {
+            self.display_row(row)?;
+        }
+        // the row being typed on goes last, to leave the cursor on it
+        self.display_row(self.row)
+    }
+
+    /// Draws the row's colours with its word on them: the one being typed on it, or
+    /// else the one it was filled in with
+    fn display_row(&mut self, row: usize) -> io::Result<()> {
+        let word = match &self.board.filled()[row] {
+            _ if row == self.row && !self.word.is_empty() => self.word.to_uppercase(),
+            Some(filled) => filled.to_uppercase(),
+            None => String::new(),
+        };
+        let palette = self.palette;
+        let letters = word
+            .chars()
+            .chain(iter::repeat(' '))
+            .zip(self.board.target()[row].0.clone())
+            .map(|(c, m)| LetterMatch(c, m, palette));
+        let y = 2 + row as u16;
+        let marker = if row == self.row { ">" } else { " " };
+        write!(
+            self.terminal,
+            "{}{}{}{}",
+            cursor::MoveTo(0, y),
+            marker,
+            cursor::MoveTo(MARKER_WIDTH, y),
+            self.cells.row(letters)
+        )?;
+        if row == self.row {
+            let typed = self.word.chars().count().min(self.board.word_len() - 1);
+            let x = MARKER_WIDTH + self.cells.offset(typed);
+            execute!(self.terminal, cursor::MoveTo(x, y), cursor::Show)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the message on the line between the title and the board,
+    /// leaving the cursor where it was
+    fn show_status(&mut self, message: &str) -> io::Result<()> {
+        write!(
+            self.terminal,
+            "{save}{status}{message}{clear}{restore}",
+            save = cursor::SavePosition,
+            status = cursor::MoveTo(0, 1),
+            clear = Clear(ClearType::UntilNewLine),
+            restore = cursor::RestorePosition,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::{
+        crosswordle::{Crosswordle, CrosswordleShare},
+        diff,
+    };
+    use crossterm::event::{Event, KeyCode};
+
+    use super::Controller;
+    use crate::controller::tui::tests::Scripted;
+
+    fn play(keys: Scripted) -> Option<CrosswordleShare> {
+        let board = Crosswordle::custom(
+            "termo".to_owned(),
+            vec![diff("sagaz", "termo"), diff("sorte", "termo")],
+        );
+        let controller =
+            Controller::with_backend(board, Default::default(), Default::default(), keys);
+        controller.run().unwrap()
+    }
+
+    #[test]
+    fn rows_are_filled_from_the_top() {
+        // sorte doesn't fit the top row, so it's typed again on the row below
+        let share = play(Scripted::new("sorte\n\x08\x08\x08\x08\x08chuva\nsorte\n\n")).unwrap();
+        assert!(share
+            .to_string()
+            .starts_with("joguei term.ooo #custom crosswordle 1 miss\n"));
+    }
+
+    #[test]
+    fn any_row_can_be_picked() {
+        let mut keys = Scripted::new("");
+        keys.0.push_back(Event::Key(KeyCode::Down.into()));
+        keys.0.extend(Scripted::new("sorte\nsagaz\n\n").0);
+        let share = play(keys).unwrap();
+        assert!(share.solved());
+        assert_eq!(share.misses(), 0);
+    }
+
+    #[test]
+    fn quit() {
+        assert!(play(Scripted::new("sag\x1b")).is_none());
+    }
+}
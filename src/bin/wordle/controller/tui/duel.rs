@@ -0,0 +1,173 @@
+//! Hot-seat duels: the players take turns picking a word, typed without showing it,
+//! for the other to guess on the usual board.
+
+use std::io;
+
+use cl_wordle::duel::Duel;
+use crossterm::event::{Event, KeyCode, KeyModifiers};
+use eyre::Result;
+
+use super::{
+    secret,
+    terminal::{Backend, Terminal},
+    Options,
+};
+
+/// Runs the rounds of a duel, with a screen between each one for the next word
+pub struct Controller<B = Terminal> {
+    duel: Duel,
+    options: Options,
+    terminal: B,
+    /// Rounds to play before the duel is over, or `None` to play until it's stopped
+    rounds: Option<usize>,
+}
+
+impl Controller {
+    pub fn new(duel: Duel, options: Options) -> Result<Self> {
+        let terminal = Terminal::new(options.mouse)?;
+        Ok(Self::with_backend(duel, options, terminal))
+    }
+}
+
+impl<B: Backend> Controller<B> {
+    pub fn with_backend(duel: Duel, options: Options, terminal: B) -> Self {
+        Self {
+            duel,
+            options,
+            terminal,
+            rounds: None,
+        }
+    }
+
+    /// Ends the duel after this many rounds, instead of when it's stopped
+    pub fn with_rounds(self, rounds: Option<usize>) -> Self {
+        Self { rounds, ..self }
+    }
+
+    /// Plays rounds until they run out or Esc is pressed between them, and returns
+    /// the duel with every round that was finished
+    pub fn run(mut self) -> Result<Duel> {
+        while self
+            .rounds
+            .is_none_or(|rounds| self.duel.rounds().len() < rounds)
+        {
+            let game = match self.pick_word()? {
+                Some(game) => game,
+                None => break,
+            };
+            let solver = &self.duel.players()[self.duel.solver()];
+            let ready = format!("Pass the keyboard to {}, then press enter to start", solver);
+            if !self.wait(&[ready.as_str()])? {
+                break;
+            }
+
+            let solution = game.state().solution().to_owned();
+            let board =
+                super::Controller::with_backend(game, self.options.clone(), &mut self.terminal);
+            let share = match board.run()? {
+                Some(share) => share,
+                // quitting part way through a game ends the duel without scoring it
+                None => break,
+            };
+            let score = share.score().expect("the game is over");
+            self.duel.record(solution, score, share.max_guesses());
+
+            let last = self.rounds == Some(self.duel.rounds().len());
+            let scoreboard = self.duel.to_string();
+            let mut lines = scoreboard.lines().collect::<Vec<_>>();
+            lines.push("");
+            lines.push(if last {
+                "Press enter to finish"
+            } else {
+                "Press enter for the next round, or esc to stop"
+            });
+            if !self.wait(&lines)? {
+                break;
+            }
+        }
+        Ok(self.duel)
+    }
+
+    /// Reads the setter's word without showing it, until it's one that can be
+    /// played, or returns `None` if Esc is pressed
+    fn pick_word(&mut self) -> Result<Option<cl_wordle::game::Game>> {
+        let [setter, solver] =
+            [self.duel.setter(), self.duel.solver()].map(|i| &self.duel.players()[i]);
+        let prompt = format!(
+            "Round {}. {}, type a word for {} to guess while they look away",
+            self.duel.rounds().len() + 1,
+            setter,
+            solver
+        );
+        let duel = &self.duel;
+        let check = |word: &str| duel.start(word).map_err(|err| secret::rejection(&err));
+        Ok(secret::read(&mut self.terminal, &prompt, check)?)
+    }
+
+    /// Shows the lines until Enter is pressed, returning `false` for Esc instead
+    fn wait(&mut self, lines: &[&str]) -> Result<bool> {
+        self.display(lines)?;
+        loop {
+            let key = match self.terminal.read()? {
+                Event::Key(key) => key,
+                Event::Resize(..) => {
+                    self.display(lines)?;
+                    continue;
+                }
+                _ => continue,
+            };
+            match key.code {
+                KeyCode::Enter => return Ok(true),
+                KeyCode::Esc => return Ok(false),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(false)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn display(&mut self, lines: &[&str]) -> io::Result<()> {
+        secret::show(&mut self.terminal, lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::{duel::Duel, game::Score};
+
+    use super::Controller;
+    use crate::controller::tui::{tests::Scripted, Options};
+
+    fn duel(keys: &str, rounds: Option<usize>) -> Duel {
+        let duel = Duel::new("ana".to_owned(), "bia".to_owned());
+        Controller::with_backend(duel, Options::default(), Scripted::new(keys))
+            .with_rounds(rounds)
+            .run()
+            .unwrap()
+    }
+
+    #[test]
+    fn players_swap_each_round() {
+        // ana picks termo, which bia gets second go, then bia picks sagaz
+        let keys = "termo\n\nsagaz\ntermo\n\n\nsagaz\n\nsagaz\n\n\n";
+        let duel = duel(keys, Some(2));
+        let rounds = duel.rounds();
+        assert_eq!(rounds.len(), 2);
+        assert_eq!((rounds[0].setter, rounds[0].score), (0, Score::Guesses(2)));
+        assert_eq!((rounds[1].setter, rounds[1].score), (1, Score::Guesses(1)));
+        assert_eq!(duel.points(), [6, 5]);
+    }
+
+    #[test]
+    fn words_that_cant_be_played_are_asked_for_again() {
+        let duel = duel("xxxxx\ntermo\n\ntermo\n\n\x1b", None);
+        assert_eq!(duel.rounds().len(), 1);
+        assert_eq!(duel.rounds()[0].solution, "termo");
+    }
+
+    #[test]
+    fn esc_before_the_word_ends_the_duel() {
+        assert!(duel("ter\x1b", None).rounds().is_empty());
+    }
+}
@@ -1,29 +1,80 @@
 use std::fmt;
 
 use cl_wordle::{
+    frequency::Frequencies,
     state::{Guess, State},
     Matches,
 };
 use crossterm::cursor;
 
-use super::letters::LetterMatch;
-
-pub struct Guesses<'a>(&'a State);
+use super::{
+    heat::Heat,
+    layout::Cells,
+    letters::{LetterMatch, Palette},
+};
 
-impl<'a> From<&'a State> for Guesses<'a> {
-    fn from(state: &'a State) -> Self {
-        Guesses(state)
-    }
+/// Draws every row of the board, with blank rows for the guesses left
+pub struct Guesses<'a> {
+    pub state: &'a State,
+    pub cells: Cells,
+    pub palette: Palette,
+    /// Shows how common each guess is beside its row
+    pub frequencies: Option<&'a Frequencies>,
 }
 
 impl<'a> fmt::Display for Guesses<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for Guess(w, Matches(m)) in self.0.guesses() {
-            for (b, m) in w.bytes().zip(m) {
-                write!(f, "{}", LetterMatch(b as char, m))?;
-            }
-            write!(f, "{}{}", cursor::MoveDown(1), cursor::MoveLeft(5))?;
+        let len = self.state.word_len();
+        let next_row = cursor::MoveLeft(self.cells.width(len as u16));
+        for guess in self.state.guesses() {
+            let row = GuessRow {
+                guess,
+                cells: self.cells,
+                palette: self.palette,
+                frequencies: self.frequencies,
+            };
+            // the row already leaves the cursor back at its start
+            write!(f, "{}{}", row, cursor::MoveDown(1))?;
+        }
+        for _ in self.state.guesses().len()..self.state.rows() {
+            write!(
+                f,
+                "{}{}{}",
+                self.cells.blank(len),
+                cursor::MoveDown(1),
+                next_row
+            )?;
         }
         Ok(())
     }
 }
+
+/// Draws a single guess, leaving the cursor back at the start of its row
+pub struct GuessRow<'a> {
+    pub guess: Guess<'a>,
+    pub cells: Cells,
+    pub palette: Palette,
+    /// Shows how common the guess is beside its row
+    pub frequencies: Option<&'a Frequencies>,
+}
+
+impl<'a> fmt::Display for GuessRow<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Guess(w, Matches(m)) = &self.guess;
+        let width = self.cells.width(m.len() as u16);
+        let letters = w
+            .chars()
+            .zip(m)
+            .map(|(c, &m)| LetterMatch(c, m, self.palette));
+        write!(f, "{}", self.cells.row(letters))?;
+        match self.frequencies {
+            Some(frequencies) => write!(
+                f,
+                " {}{}",
+                Heat(frequencies.rank(w)),
+                cursor::MoveLeft(width + 2)
+            ),
+            None => write!(f, "{}", cursor::MoveLeft(width)),
+        }
+    }
+}
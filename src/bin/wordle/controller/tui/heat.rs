@@ -0,0 +1,18 @@
+use std::fmt;
+
+use owo_colors::OwoColorize;
+
+/// A bar showing how common a word is, taller for more common words
+pub struct Heat(pub Option<usize>);
+
+impl fmt::Display for Heat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(rank) if rank < 1000 => write!(f, "▇"),
+            Some(rank) if rank < 5000 => write!(f, "▅"),
+            Some(rank) if rank < 20000 => write!(f, "{}", '▃'.dimmed()),
+            Some(_) => write!(f, "{}", '▁'.dimmed()),
+            None => write!(f, "{}", '·'.dimmed()),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+
+use cl_wordle::solver::HintExplanation;
+use crossterm::cursor;
+
+/// Shows the suggested guess and why, with its top-left corner at the given cell
+pub struct HintPanel<'a> {
+    pub explanation: &'a HintExplanation,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl<'a> fmt::Display for HintPanel<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let HintExplanation { remaining, ranked } = self.explanation;
+        let mut lines = Vec::with_capacity(ranked.len() + 3);
+
+        match ranked.split_first() {
+            Some((best, alternatives)) => {
+                lines.push(format!("Try {}", best.word.to_ascii_uppercase()));
+                lines.push(format!(
+                    "{:.2} bits, ~{:.1} of {} left",
+                    best.entropy, best.expected_remaining, remaining
+                ));
+                if !alternatives.is_empty() {
+                    lines.push("Alternatives:".to_owned());
+                }
+                for alt in alternatives {
+                    lines.push(format!(
+                        " {} {:.2} bits",
+                        alt.word.to_ascii_uppercase(),
+                        alt.entropy
+                    ));
+                }
+            }
+            None => lines.push("No candidates left".to_owned()),
+        }
+
+        write!(f, "{}", cursor::SavePosition)?;
+        for (i, line) in lines.iter().enumerate() {
+            write!(
+                f,
+                "{}{}",
+                cursor::MoveTo(self.column, self.row + i as u16),
+                line
+            )?;
+        }
+        write!(f, "{}", cursor::RestorePosition)
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::{self, Write};
+
+use crossterm::{
+    cursor,
+    terminal::{Clear, ClearType},
+};
+
+use super::letters::Palette;
+
+/// Rows in the panel: wins in 1 to 6 guesses, then losses
+pub const ROWS: u16 = 7;
+
+/// The guess distribution as a bar chart, with its top-left corner at the given cell.
+///
+/// Each bar ends in its count, and the longest is as wide as the panel allows. The
+/// bar for the game just played is drawn in the exact match colour
+pub struct HistogramPanel {
+    /// Wins in each number of guesses, followed by the losses
+    pub distribution: [usize; 7],
+    /// Index into the distribution of the game just played
+    pub latest: Option<usize>,
+    pub palette: Palette,
+    /// Columns the panel can use, including the label in front of each bar
+    pub width: u16,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl HistogramPanel {
+    fn line(&self, i: usize, mut f: impl Write) -> fmt::Result {
+        let label = match i {
+            6 => 'X',
+            i => char::from(b'1' + i as u8),
+        };
+        let count = self.distribution[i];
+        let most = self.distribution.iter().copied().max().unwrap_or(0).max(1);
+        let digits = count.to_string().len();
+        let widest = (self.width as usize).saturating_sub(2).max(digits + 2);
+        // every bar is wide enough for its count, so an empty row still reads as a row
+        let bar = (count * widest / most).max(digits + 2);
+        let text = format!("{:>1$} ", count, bar - 1);
+        write!(f, "{} ", label)?;
+        self.palette.bar(text, self.latest == Some(i), f)
+    }
+}
+
+impl fmt::Display for HistogramPanel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", cursor::SavePosition)?;
+        for i in 0..ROWS {
+            let start = cursor::MoveTo(self.column, self.row + i);
+            write!(f, "{}{}", start, Clear(ClearType::UntilNewLine))?;
+            self.line(i as usize, &mut *f)?;
+        }
+        write!(f, "{}", cursor::RestorePosition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HistogramPanel, Palette};
+
+    #[test]
+    fn bars_fill_the_width() {
+        let panel = HistogramPanel {
+            distribution: [0, 1, 4, 2, 0, 0, 1],
+            latest: Some(2),
+            palette: Palette::Plain,
+            width: 22,
+            column: 0,
+            row: 0,
+        };
+        let line = |i| {
+            let mut line = String::new();
+            panel.line(i, &mut line).unwrap();
+            line
+        };
+        assert_eq!(line(2), format!("3 {:>19} ", 4));
+        assert_eq!(line(3), format!("4 {:>9} ", 2));
+        assert_eq!(line(0), "1  0 ");
+        assert_eq!(line(6), format!("X {:>4} ", 1));
+    }
+}
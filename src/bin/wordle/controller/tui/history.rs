@@ -0,0 +1,266 @@
+//! The games finished so far in a session, to look back over their boards once
+//! another word has been played.
+
+use std::io;
+
+use cl_wordle::{
+    game::{Elapsed, GameShare},
+    state::State,
+};
+use crossterm::{
+    cursor,
+    event::{self, KeyCode, KeyModifiers},
+    execute,
+    terminal::{Clear, ClearType},
+};
+use eyre::Result;
+use owo_colors::OwoColorize;
+
+use super::{
+    guess::Guesses,
+    layout::Cells,
+    letters::Palette,
+    terminal::{Backend, Terminal},
+};
+
+/// A finished game: its board, and the result it was shared as
+pub struct Entry<'a> {
+    pub state: &'a State,
+    pub share: &'a GameShare,
+}
+
+/// Lists the games, scrolling when they don't fit on screen, and shows the board of
+/// the one picked with Enter until Esc goes back to the list
+pub struct Controller<'a, B = Terminal> {
+    entries: Vec<Entry<'a>>,
+    cells: Cells,
+    palette: Palette,
+    terminal: B,
+    /// The entry that's highlighted
+    selected: usize,
+    /// The first entry shown
+    top: usize,
+}
+
+impl<'a, B: Backend> Controller<'a, B> {
+    /// Starts on the last game, the one just finished
+    pub fn with_backend(
+        entries: Vec<Entry<'a>>,
+        cells: Cells,
+        palette: Palette,
+        terminal: B,
+    ) -> Self {
+        Self {
+            selected: entries.len().saturating_sub(1),
+            entries,
+            cells,
+            palette,
+            terminal,
+            top: 0,
+        }
+    }
+
+    /// Browses the games until the list is left
+    pub fn run(mut self) -> Result<()> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+        let last = self.entries.len() - 1;
+        loop {
+            self.display_list()?;
+            let key = match self.terminal.read()? {
+                event::Event::Key(key) => key,
+                _ => continue,
+            };
+            let page = self.page();
+            self.selected = match key.code {
+                KeyCode::Enter => {
+                    if !self.show_board()? {
+                        return Ok(());
+                    }
+                    self.selected
+                }
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => (self.selected + 1).min(last),
+                KeyCode::PageUp => self.selected.saturating_sub(page),
+                KeyCode::PageDown => (self.selected + page).min(last),
+                KeyCode::Home => 0,
+                KeyCode::End => last,
+                _ => self.selected,
+            };
+        }
+    }
+
+    /// Number of entries that fit below the title
+    fn page(&self) -> usize {
+        let height = self.terminal.size().map_or(24, |(_, height)| height);
+        (height as usize).saturating_sub(2).max(1)
+    }
+
+    fn display_list(&mut self) -> io::Result<()> {
+        let page = self.page();
+        // scroll just enough to keep the selected entry on screen
+        if self.selected < self.top {
+            self.top = self.selected;
+        } else if self.selected >= self.top + page {
+            self.top = self.selected + 1 - page;
+        }
+
+        execute!(
+            self.terminal,
+            cursor::Hide,
+            cursor::MoveTo(0, 0),
+            Clear(ClearType::All),
+        )?;
+        write!(
+            self.terminal,
+            "Games this session: enter to see the board, esc to go back"
+        )?;
+        let shown = self.entries.iter().enumerate().skip(self.top).take(page);
+        for (row, (i, entry)) in shown.enumerate() {
+            let line = line(i, entry);
+            write!(self.terminal, "{}", cursor::MoveTo(0, row as u16 + 2))?;
+            if i == self.selected {
+                write!(self.terminal, "{}", line.reversed())?;
+            } else {
+                write!(self.terminal, "{}", line)?;
+            }
+        }
+        self.terminal.flush()
+    }
+
+    /// Shows the selected game's board until Esc or Enter goes back to the list.
+    /// Returns `false` if ctrl-c was pressed to leave altogether
+    fn show_board(&mut self) -> Result<bool> {
+        loop {
+            self.display_board()?;
+            let key = match self.terminal.read()? {
+                event::Event::Key(key) => key,
+                _ => continue,
+            };
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => return Ok(true),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(false)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn display_board(&mut self) -> io::Result<()> {
+        let entry = &self.entries[self.selected];
+        let cells = self.cells.fit(entry.state.solution());
+        let (_, height) = self.terminal.size()?;
+        execute!(
+            self.terminal,
+            cursor::Hide,
+            cursor::MoveTo(0, 0),
+            Clear(ClearType::All),
+        )?;
+        write!(
+            self.terminal,
+            "Termo {}{}{}{}The word was {}{}Press ESC to go back.",
+            entry.share.game_type(),
+            cursor::MoveTo(0, 2),
+            Guesses {
+                state: entry.state,
+                cells,
+                palette: self.palette,
+                frequencies: None,
+            },
+            cursor::MoveTo(0, 3 + entry.state.rows() as u16),
+            entry.state.solution().to_uppercase(),
+            cursor::MoveTo(0, height - 1),
+        )?;
+        self.terminal.flush()
+    }
+}
+
+/// The entry as one line of the list, like `✓ 2  practice 7   TERMO  3/6  00:42`
+fn line(i: usize, entry: &Entry) -> String {
+    let share = entry.share;
+    let mark = if share.won() { '✓' } else { '✗' };
+    let score = share
+        .score()
+        .map_or_else(|| "?".to_owned(), |score| score.to_string());
+    let time = share
+        .time()
+        .map_or_else(String::new, |time| Elapsed(time).to_string());
+    format!(
+        "{} {:<3}{:<16} {}  {}/{}  {}",
+        mark,
+        i + 1,
+        share.game_type().to_string(),
+        entry.state.solution().to_uppercase(),
+        score,
+        share.max_guesses(),
+        time
+    )
+    .trim_end()
+    .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use cl_wordle::game::Game;
+
+    use super::{line, Controller, Entry};
+    use crate::controller::tui::tests::Scripted;
+
+    fn played(guesses: &[&str]) -> Game {
+        let mut game = Game::custom("termo".to_owned())
+            .unwrap()
+            .allow_repeated_guesses(true);
+        for guess in guesses {
+            game.guess(guess).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn lists_how_each_game_went() {
+        let (won, lost) = (played(&["sagaz", "termo"]), played(&["sagaz"; 6]));
+        let won_share = won.share().with_time(Duration::from_secs(42));
+        let lost_share = lost.share();
+        let won = Entry {
+            state: won.state(),
+            share: &won_share,
+        };
+        let lost = Entry {
+            state: lost.state(),
+            share: &lost_share,
+        };
+        assert_eq!(line(0, &won), "✓ 1  custom           TERMO  2/6  00:42");
+        assert_eq!(line(1, &lost), "✗ 2  custom           TERMO  X/6");
+    }
+
+    #[test]
+    fn boards_go_back_to_the_list() {
+        let game = played(&["termo"]);
+        let share = game.share();
+        let entries = || {
+            let entry = || Entry {
+                state: game.state(),
+                share: &share,
+            };
+            vec![entry(), entry(), entry()]
+        };
+        for keys in ["\x1b", "k\n\x1bjj\n\n\x1b", "\n\x1b\x1b"] {
+            Controller::with_backend(
+                entries(),
+                Default::default(),
+                Default::default(),
+                Scripted::new(keys),
+            )
+            .run()
+            .unwrap();
+        }
+    }
+}
@@ -3,60 +3,701 @@ use std::fmt::Display;
 use cl_wordle::{Match, Matches};
 use crossterm::cursor;
 
-use super::letters::LetterMatch;
+use super::letters::{self, LetterMatch, Palette};
 
+/// The row of buttons below the letters, for clicking instead of typing
+const BUTTONS: &str = "ENT DEL";
+
+/// Finds the button at the given column of the row of buttons
+fn button_at(column: impl Into<usize>) -> Option<Press> {
+    match column.into() {
+        0..=2 => Some(Press::Enter),
+        4..=6 => Some(Press::Delete),
+        _ => None,
+    }
+}
+
+/// The order the keys are drawn in
+#[derive(clap::ArgEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// A to Z, seven keys a row
+    #[default]
+    Alphabetical,
+    Qwerty,
+    Qwertz,
+    Azerty,
+    Dvorak,
+    Colemak,
+}
+
+impl Layout {
+    /// The letters and indent of each row of keys. Every layout has each letter once
+    fn rows(self) -> &'static [(&'static str, usize)] {
+        match self {
+            Layout::Alphabetical => &[("ABCDEFG", 0), ("HIJKLMN", 0), ("OPQRSTU", 0), ("VWXYZ", 1)],
+            Layout::Qwerty => &[("QWERTYUIOP", 0), ("ASDFGHJKL", 0), ("ZXCVBNM", 1)],
+            Layout::Qwertz => &[("QWERTZUIOP", 0), ("ASDFGHJKL", 0), ("YXCVBNM", 1)],
+            Layout::Azerty => &[("AZERTYUIOP", 0), ("QSDFGHJKLM", 0), ("WXCVBN", 2)],
+            // the punctuation keys on the left of the top and bottom rows are left as gaps
+            Layout::Dvorak => &[("PYFGCRL", 3), ("AOEUIDHTNS", 0), ("QJKXBMWVZ", 1)],
+            Layout::Colemak => &[("QWFPGJLUY", 0), ("ARSTDHNEIO", 0), ("ZXCVBKM", 1)],
+        }
+    }
+}
+
+/// What clicking on the keyboard does
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Press {
+    Letter(char),
+    Enter,
+    Delete,
+}
+
+/// What the guesses so far say about a letter
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Hint {
+    /// Each position the letter was found in, as a bit
+    exact: u32,
+    /// Copies the solution has at least, from the guess that found the most
+    copies: usize,
+    /// Whether it's been guessed at all
+    guessed: bool,
+}
+
+impl Hint {
+    /// The key's colour: exact once it's been found anywhere, then close once it's
+    /// known to be in the solution, and wrong only if it's never matched
+    fn best(self) -> Option<Match> {
+        match self {
+            Hint { exact: 1.., .. } => Some(Match::Exact),
+            Hint { copies: 1.., .. } => Some(Match::Close),
+            Hint { guessed: true, .. } => Some(Match::Wrong),
+            _ => None,
+        }
+    }
+
+    /// Whether it's been found, but the solution has more of it than were found
+    fn split(self) -> bool {
+        self.exact != 0 && self.copies > self.exact.count_ones() as usize
+    }
+}
+
+/// Keys a row of a keyboard made for an alphabet has at most, before the rest go on
+/// the next row
+const GENERATED_WIDTH: usize = 10;
+
+#[derive(Clone)]
 pub struct Keyboard {
-    arangement: String,
-    letters: [Option<Match>; 26],
+    layout: Layout,
+    /// The letters of the word lists, in order, if they aren't just a to z. They're
+    /// laid out in that order, whatever the layout
+    alphabet: Option<Vec<char>>,
+    /// What's known of each letter so far, in alphabetical order whatever the layout
+    letters: Vec<Hint>,
+    /// Letters that don't appear in any remaining candidate
+    impossible: Vec<bool>,
+    palette: Palette,
+    /// Whether to draw the enter and delete buttons below the letters
+    buttons: bool,
+    /// Whether guesses colour the keys, or they're all left plain
+    hints: bool,
+    /// Whether a letter that's been found with more of it left to place is drawn
+    /// in both colours
+    split: bool,
 }
 
 impl Keyboard {
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            layout: Layout::default(),
+            alphabet: None,
+            letters: vec![Hint::default(); 26],
+            impossible: vec![false; 26],
+            palette,
+            buttons: false,
+            hints: true,
+            split: false,
+        }
+    }
+
+    /// Adds enter and delete buttons below the letters, for playing with the mouse
+    pub fn with_buttons(self, buttons: bool) -> Self {
+        Self { buttons, ..self }
+    }
+
+    pub fn with_layout(self, layout: Layout) -> Self {
+        Self { layout, ..self }
+    }
+
+    /// Makes a key for each letter of the word lists. Letters from a to z keep the
+    /// layout, but any others get a keyboard of their own, in alphabetical order
+    pub fn with_alphabet(self, alphabet: impl IntoIterator<Item = char>) -> Self {
+        let mut alphabet = alphabet.into_iter().collect::<Vec<_>>();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+        let alphabet = (!alphabet.iter().all(char::is_ascii_lowercase)).then_some(alphabet);
+        let len = alphabet.as_ref().map_or(26, Vec::len);
+        Self {
+            alphabet,
+            letters: vec![Hint::default(); len],
+            impossible: vec![false; len],
+            ..self
+        }
+    }
+
+    /// The letters and indent of each row of keys: the layout's for a to z, or else
+    /// the alphabet split evenly over as few rows as fit
+    fn key_rows(&self) -> Vec<(Vec<char>, usize)> {
+        let alphabet = match &self.alphabet {
+            Some(alphabet) => alphabet,
+            None => {
+                let rows = self.layout.rows().iter();
+                return rows
+                    .map(|&(keys, indent)| (keys.to_lowercase().chars().collect(), indent))
+                    .collect();
+            }
+        };
+        let rows = alphabet.len().div_ceil(GENERATED_WIDTH).max(1);
+        let width = alphabet.len().div_ceil(rows);
+        alphabet
+            .chunks(width.max(1))
+            .map(|keys| (keys.to_vec(), (width - keys.len()) / 2))
+            .collect()
+    }
+
+    /// Where the letter's hints are kept, if it has a key
+    fn index(&self, c: char) -> Option<usize> {
+        match &self.alphabet {
+            Some(alphabet) => alphabet.binary_search(&c).ok(),
+            None if c.is_ascii_lowercase() => Some((c as u8 - b'a') as usize),
+            None => None,
+        }
+    }
+
+    /// Without hints, the keys stay uncoloured whatever's guessed
+    pub fn with_hints(self, hints: bool) -> Self {
+        Self { hints, ..self }
+    }
+
+    /// Draws a letter in both colours once it's been found somewhere, if there's
+    /// also more of it to place elsewhere
+    pub fn with_split(self, split: bool) -> Self {
+        Self { split, ..self }
+    }
+
+    /// Finds what's drawn at the given cell, relative to the top-left key
+    pub fn press_at(&self, column: u16, row: u16) -> Option<Press> {
+        let column = column as usize;
+        let rows = self.key_rows();
+        match rows.get(row as usize) {
+            Some((keys, indent)) => {
+                let key = keys.get(column.checked_sub(*indent)?)?;
+                Some(Press::Letter(*key))
+            }
+            None if self.buttons && row as usize == rows.len() => button_at(column),
+            None => None,
+        }
+    }
+
+    /// Positions the keyboard with its top-left key at the given cell
+    pub fn at(&self, column: u16, row: u16) -> KeyboardAt<'_> {
+        KeyboardAt {
+            keyboard: self,
+            column,
+            row,
+        }
+    }
+
+    /// Gets a single row of keys, without any positioning
+    pub fn row(&self, row: usize) -> KeyboardRow<'_> {
+        KeyboardRow {
+            keyboard: self,
+            row,
+        }
+    }
+
+    /// Positions only the keys that look different from how they did on `before`,
+    /// so they can be redrawn without the rest of the keyboard
+    pub fn changes_at<'a>(
+        &'a self,
+        before: &'a Keyboard,
+        column: u16,
+        row: u16,
+    ) -> KeyboardChanges<'a> {
+        KeyboardChanges {
+            keyboard: self,
+            before,
+            column,
+            row,
+        }
+    }
+
+    /// Draws the key for a letter of the alphabet, which is lowercase
+    fn key(&self, key: char) -> impl Display {
+        let c = letters::uppercase(key);
+        let i = match self.index(key) {
+            Some(i) => i,
+            None => return Key::Plain(c),
+        };
+        let hint = self.letters[i];
+        match hint.best() {
+            Some(Match::Exact) if self.split && hint.split() => Key::Split(c, self.palette),
+            // a plain key can't be marked, so only ruled out letters are shown
+            Some(m) if self.palette == Palette::Plain && m != Match::Wrong => Key::Plain(c),
+            Some(m) => Key::Match(LetterMatch(c, m, self.palette)),
+            None if self.impossible[i] => Key::Match(LetterMatch(c, Match::Wrong, self.palette)),
+            None => Key::Plain(c),
+        }
+    }
+
+    /// Number of rows of keys
+    pub fn rows(&self) -> usize {
+        self.key_rows().len() + self.buttons as usize
+    }
+
+    /// Number of columns the widest row takes
+    pub fn width(&self) -> u16 {
+        let rows = self.key_rows();
+        let letters = rows.iter().map(|(keys, indent)| keys.len() + indent);
+        let buttons = self.buttons.then(|| BUTTONS.len());
+        letters.chain(buttons).max().unwrap_or_default() as u16
+    }
+
+    /// Marks every letter that isn't in any of the candidates as impossible,
+    /// so they're shown as wrong even if they haven't been guessed. An accented
+    /// letter keeps its plain key possible too
+    pub fn deduce(&mut self, candidates: &[&str]) {
+        if !self.hints {
+            return;
+        }
+        let mut possible = vec![false; self.letters.len()];
+        let folded = candidates.iter().map(|word| cl_wordle::fold_accents(word));
+        for c in folded.collect::<String>().chars() {
+            if let Some(i) = self.index(c) {
+                possible[i] = true;
+            }
+        }
+        for (impossible, possible) in self.impossible.iter_mut().zip(possible) {
+            *impossible = !possible;
+        }
+    }
+
+    /// Adds what the guess says about each of its letters. Nothing that's known is
+    /// lost: a letter found in place stays found, whatever later guesses say
     pub fn push(&mut self, word: &str, matches: Matches) {
-        for (b, m) in word.bytes().zip(matches.0) {
-            let b = (b - b'a') as usize;
-            let m2 = &mut self.letters[b];
-            *m2 = Some(match (m, *m2) {
-                (_, Some(Match::Exact)) | (Match::Exact, _) => Match::Exact,
-                (_, Some(Match::Close)) | (Match::Close, _) => Match::Close,
-                (_, Some(Match::Wrong)) | (Match::Wrong, _) => Match::Wrong,
-            });
+        if !self.hints {
+            return;
+        }
+        let mut copies = vec![0; self.letters.len()];
+        // only letters of the alphabet have a key
+        for (i, (c, m)) in word.chars().zip(matches.0).enumerate() {
+            let b = match self.index(c) {
+                Some(b) => b,
+                None => continue,
+            };
+            let hint = &mut self.letters[b];
+            hint.guessed = true;
+            match m {
+                Match::Exact => hint.exact |= 1 << i,
+                Match::Close => {}
+                Match::Wrong => continue,
+            }
+            // the solution has at least as many as this guess coloured
+            copies[b] += 1;
+        }
+        for (hint, copies) in self.letters.iter_mut().zip(copies) {
+            hint.copies = hint.copies.max(copies);
         }
     }
 }
 
 impl Default for Keyboard {
     fn default() -> Self {
-        Self {
-            arangement: ('A'..='Z').collect(),
-            letters: [None; 26],
+        Self::new(Palette::default())
+    }
+}
+
+pub struct KeyboardAt<'a> {
+    keyboard: &'a Keyboard,
+    column: u16,
+    row: u16,
+}
+
+impl<'a> Display for KeyboardAt<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{save}", save = cursor::SavePosition)?;
+        for i in 0..self.keyboard.rows() {
+            write!(
+                f,
+                "{start}{row}",
+                start = cursor::MoveTo(self.column, self.row + i as u16),
+                row = self.keyboard.row(i),
+            )?;
+        }
+        write!(f, "{restore}", restore = cursor::RestorePosition)?;
+
+        Ok(())
+    }
+}
+
+pub struct KeyboardRow<'a> {
+    keyboard: &'a Keyboard,
+    row: usize,
+}
+
+impl<'a> Display for KeyboardRow<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = self.keyboard.key_rows();
+        let (keys, indent) = match rows.get(self.row) {
+            Some(row) => row,
+            None => return write!(f, "{}", BUTTONS),
+        };
+        write!(f, "{:1$}", "", indent)?;
+
+        for &key in keys {
+            write!(f, "{}", self.keyboard.key(key))?;
         }
+
+        Ok(())
     }
 }
 
-impl Display for Keyboard {
+pub struct KeyboardChanges<'a> {
+    keyboard: &'a Keyboard,
+    before: &'a Keyboard,
+    column: u16,
+    row: u16,
+}
+
+impl<'a> Display for KeyboardChanges<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{save}{start}",
-            save = cursor::SavePosition,
-            start = cursor::MoveTo(14, 2)
-        )?;
-
-        for (i, b) in self.arangement.bytes().enumerate() {
-            if i == 7 || i == 14 || i == 21 {
-                write!(f, "{}{}", cursor::MoveDown(1), cursor::MoveLeft(7))?;
-            }
-            if i == 21 {
-                write!(f, " ")?;
+        let (keyboard, before) = (self.keyboard, self.before);
+        write!(f, "{save}", save = cursor::SavePosition)?;
+        for (row, (keys, indent)) in keyboard.key_rows().into_iter().enumerate() {
+            for (column, &key) in keys.iter().enumerate() {
+                let changed = keyboard.key(key).to_string() != before.key(key).to_string();
+                if changed {
+                    write!(
+                        f,
+                        "{start}{key}",
+                        start = cursor::MoveTo(
+                            self.column + (indent + column) as u16,
+                            self.row + row as u16
+                        ),
+                        key = keyboard.key(key),
+                    )?;
+                }
             }
-            match self.letters[i] {
-                Some(m) => write!(f, "{}", LetterMatch(b as char, m))?,
-                None => write!(f, "{}", b as char)?,
+        }
+        write!(f, "{restore}", restore = cursor::RestorePosition)?;
+
+        Ok(())
+    }
+}
+
+/// Several boards' keyboards drawn as one, like the split keys of Quordle. Each key
+/// has its letter once for each board, in that board's colours, then a gap.
+///
+/// A board that's solved is `None`, and its letters are left blank, as its colours
+/// don't matter any more
+pub struct SplitKeyboard<'a> {
+    pub keyboards: &'a [Option<Keyboard>],
+    pub layout: Layout,
+    /// Whether to add a row of buttons for enter and backspace below the letters
+    pub buttons: bool,
+}
+
+impl<'a> SplitKeyboard<'a> {
+    /// Number of rows of keys
+    pub fn rows(&self) -> usize {
+        self.layout.rows().len() + self.buttons as usize
+    }
+
+    /// Number of columns the widest row takes
+    pub fn width(&self) -> u16 {
+        let key = self.keyboards.len() + 1;
+        let rows = self.layout.rows().iter();
+        let letters = rows.map(|(keys, indent)| ((keys.len() + indent) * key).saturating_sub(1));
+        let buttons = self.buttons.then(|| BUTTONS.len());
+        letters.chain(buttons).max().unwrap_or_default() as u16
+    }
+
+    /// Finds the key drawn at the given cell, relative to the top-left key. The gap
+    /// after each key's letters isn't part of the key
+    pub fn press_at(&self, column: u16, row: u16) -> Option<Press> {
+        let key = self.keyboards.len() + 1;
+        let rows = self.layout.rows();
+        match rows.get(row as usize) {
+            Some(&(keys, indent)) => {
+                let column = (column as usize).checked_sub(indent * key)?;
+                if column % key == key - 1 {
+                    return None;
+                }
+                let letter = keys.as_bytes().get(column / key)?;
+                Some(Press::Letter(letter.to_ascii_lowercase() as char))
             }
+            None if self.buttons && row as usize == rows.len() => button_at(column),
+            None => None,
         }
+    }
 
-        write!(f, "{restore}", restore = cursor::RestorePosition)?;
+    /// Gets a single row of keys, without any positioning
+    pub fn row(&self, row: usize) -> SplitKeyboardRow<'_> {
+        SplitKeyboardRow {
+            keyboard: self,
+            row,
+        }
+    }
+}
+
+pub struct SplitKeyboardRow<'a> {
+    keyboard: &'a SplitKeyboard<'a>,
+    row: usize,
+}
 
+impl<'a> Display for SplitKeyboardRow<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let SplitKeyboard {
+            keyboards,
+            layout,
+            buttons,
+        } = self.keyboard;
+        let (keys, indent) = match layout.rows().get(self.row) {
+            Some(&row) => row,
+            None if *buttons && self.row == layout.rows().len() => return write!(f, "{}", BUTTONS),
+            None => return Ok(()),
+        };
+        write!(f, "{:1$}", "", indent * (keyboards.len() + 1))?;
+        for (i, key) in keys.bytes().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            for keyboard in keyboards.iter() {
+                match keyboard {
+                    Some(keyboard) => {
+                        write!(f, "{}", keyboard.key(key.to_ascii_lowercase() as char))?
+                    }
+                    None => write!(f, " ")?,
+                }
+            }
+        }
         Ok(())
     }
 }
+
+enum Key {
+    Match(LetterMatch),
+    /// Found in place, with more of it somewhere else
+    Split(char, Palette),
+    Plain(char),
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Key::Match(m) => write!(f, "{}", m),
+            Key::Split(c, palette) => palette.split(c, f),
+            Key::Plain(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::diff;
+
+    use cl_wordle::Match;
+
+    use super::{Keyboard, Layout, Palette, Press, SplitKeyboard};
+    use test_case::test_case;
+
+    /// The colour of the letter's key, and if it's drawn split
+    fn hint(keyboard: &Keyboard, c: char) -> (Option<Match>, bool) {
+        let hint = keyboard.letters[keyboard.index(c).unwrap()];
+        (hint.best(), hint.split())
+    }
+
+    #[test]
+    fn keys_never_lose_what_was_found() {
+        let mut keyboard = Keyboard::default();
+        keyboard.push("termo", diff("termo", "terra"));
+        keyboard.push("rasgo", diff("rasgo", "terra"));
+        // r was found, so being close in the next guess doesn't make it amber
+        assert_eq!(hint(&keyboard, 'r'), (Some(Match::Exact), false));
+        // o was wrong twice, and a close once is all it takes
+        assert_eq!(hint(&keyboard, 'o'), (Some(Match::Wrong), false));
+        assert_eq!(hint(&keyboard, 'a'), (Some(Match::Close), false));
+        assert_eq!(hint(&keyboard, 'z'), (None, false));
+    }
+
+    #[test]
+    fn repeated_letters_are_only_wrong_if_never_matched() {
+        let mut keyboard = Keyboard::default();
+        // the second r is wrong, as termo has only the one
+        keyboard.push("terra", diff("terra", "termo"));
+        assert_eq!(hint(&keyboard, 'r'), (Some(Match::Exact), false));
+        keyboard.push("arara", diff("arara", "termo"));
+        assert_eq!(hint(&keyboard, 'r'), (Some(Match::Exact), false));
+        assert_eq!(hint(&keyboard, 'a'), (Some(Match::Wrong), false));
+    }
+
+    #[test]
+    fn keys_split_while_more_of_the_letter_is_left() {
+        let mut keyboard = Keyboard::default().with_split(true);
+        // both rs are close, so terra has two of them
+        keyboard.push("arder", diff("arder", "terra"));
+        assert_eq!(hint(&keyboard, 'r'), (Some(Match::Close), false));
+        keyboard.push("termo", diff("termo", "terra"));
+        assert_eq!(hint(&keyboard, 'r'), (Some(Match::Exact), true));
+        let unsplit = |keyboard: &Keyboard| keyboard.clone().with_split(false).row(2).to_string();
+        assert_ne!(keyboard.row(2).to_string(), unsplit(&keyboard));
+        // with both found, there's none left to place
+        keyboard.push("terra", diff("terra", "terra"));
+        assert_eq!(hint(&keyboard, 'r'), (Some(Match::Exact), false));
+        assert_eq!(keyboard.row(2).to_string(), unsplit(&keyboard));
+    }
+
+    #[test]
+    fn changes_only_redraw_new_keys() {
+        let mut keyboard = Keyboard::default();
+        keyboard.push("terra", diff("terra", "termo"));
+        let before = keyboard.clone();
+        assert_eq!(keyboard.changes_at(&before, 0, 0).to_string(), "\x1b7\x1b8");
+
+        keyboard.push("suite", diff("suite", "termo"));
+        let changes = keyboard.changes_at(&before, 0, 0).to_string();
+        // t and e were already coloured, so only s, u and i move the cursor
+        assert_eq!(changes.matches('H').count(), 3);
+    }
+
+    #[test]
+    fn press_at() {
+        let keyboard = Keyboard::default();
+        assert_eq!(keyboard.press_at(0, 0), Some(Press::Letter('a')));
+        assert_eq!(keyboard.press_at(6, 1), Some(Press::Letter('n')));
+        // the last row is indented by one
+        assert_eq!(keyboard.press_at(0, 3), None);
+        assert_eq!(keyboard.press_at(1, 3), Some(Press::Letter('v')));
+        assert_eq!(keyboard.press_at(7, 0), None);
+        assert_eq!(keyboard.press_at(0, 4), None);
+
+        let keyboard = keyboard.with_buttons(true);
+        assert_eq!(keyboard.rows(), 5);
+        assert_eq!(keyboard.row(4).to_string(), "ENT DEL");
+        assert_eq!(keyboard.press_at(1, 4), Some(Press::Enter));
+        assert_eq!(keyboard.press_at(3, 4), None);
+        assert_eq!(keyboard.press_at(6, 4), Some(Press::Delete));
+    }
+
+    #[test]
+    fn other_alphabets_get_a_keyboard_of_their_own() {
+        let russian = "абвгдеёжзийклмнопрстуфхцчшщъыьэюя";
+        let mut keyboard = Keyboard::default().with_alphabet(russian.chars());
+        assert_eq!(keyboard.rows(), 4);
+        assert_eq!(keyboard.width(), 9);
+        assert_eq!(keyboard.row(0).to_string(), "АБВГДЕЖЗИ");
+        // ё sorts after the rest, and the shorter last row is centred
+        assert_eq!(keyboard.row(3).to_string(), " ЫЬЭЮЯЁ");
+        assert_eq!(keyboard.press_at(0, 0), Some(Press::Letter('а')));
+        assert_eq!(keyboard.press_at(0, 3), None);
+        assert_eq!(keyboard.press_at(1, 3), Some(Press::Letter('ы')));
+
+        keyboard.push("слово", diff("слово", "право"));
+        assert_eq!(hint(&keyboard, 'о'), (Some(Match::Exact), false));
+        assert_eq!(hint(&keyboard, 'с'), (Some(Match::Wrong), false));
+        assert_eq!(hint(&keyboard, 'п'), (None, false));
+    }
+
+    #[test]
+    fn latin_alphabets_keep_the_layout() {
+        let keyboard = Keyboard::default()
+            .with_layout(Layout::Qwerty)
+            .with_alphabet("termosagz".chars());
+        assert_eq!(keyboard.rows(), 3);
+        assert_eq!(keyboard.press_at(0, 0), Some(Press::Letter('q')));
+    }
+
+    #[test_case(Layout::Alphabetical)]
+    #[test_case(Layout::Qwerty)]
+    #[test_case(Layout::Qwertz)]
+    #[test_case(Layout::Azerty)]
+    #[test_case(Layout::Dvorak)]
+    #[test_case(Layout::Colemak)]
+    fn layout_has_every_letter_once(layout: Layout) {
+        let mut letters = layout
+            .rows()
+            .iter()
+            .flat_map(|(keys, _)| keys.chars())
+            .collect::<Vec<_>>();
+        letters.sort_unstable();
+        assert_eq!(letters, ('A'..='Z').collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn layout_colours_the_same_letters() {
+        let plain = Keyboard::default().with_layout(Layout::Qwertz);
+        let mut keyboard = plain.clone();
+        keyboard.push("terra", diff("terra", "termo"));
+        assert_eq!(keyboard.press_at(5, 0), Some(Press::Letter('z')));
+        assert_eq!(keyboard.press_at(1, 2), Some(Press::Letter('y')));
+        assert_eq!(keyboard.width(), 10);
+        // t, e and r are on the top row and a is on the middle one
+        assert_ne!(keyboard.row(0).to_string(), plain.row(0).to_string());
+        assert_ne!(keyboard.row(1).to_string(), plain.row(1).to_string());
+        assert_eq!(keyboard.row(2).to_string(), plain.row(2).to_string());
+    }
+
+    #[test]
+    fn plain_keys_have_no_escapes() {
+        let mut keyboard = Keyboard::new(Palette::Plain).with_layout(Layout::Alphabetical);
+        keyboard.push("suite", diff("suite", "termo"));
+        // only the letters that were ruled out are marked
+        assert_eq!(keyboard.row(0).to_string(), "ABCDEFG");
+        assert_eq!(keyboard.row(1).to_string(), "H·JKLMN");
+        assert_eq!(keyboard.row(2).to_string(), "OPQR·T·");
+    }
+
+    #[test]
+    fn keys_without_hints_stay_plain() {
+        let mut keyboard = Keyboard::new(Palette::Plain).with_hints(false);
+        keyboard.push("suite", diff("suite", "termo"));
+        keyboard.deduce(&["termo"]);
+        assert_eq!(keyboard.row(1).to_string(), "HIJKLMN");
+        assert_eq!(keyboard.row(2).to_string(), "OPQRSTU");
+    }
+
+    #[test]
+    fn split_keys_have_a_letter_per_board() {
+        let mut keyboard = Keyboard::new(Palette::Plain);
+        keyboard.push("suite", diff("suite", "termo"));
+        let keyboards = [Some(keyboard), None, Some(Keyboard::new(Palette::Plain))];
+        let split = SplitKeyboard {
+            keyboards: &keyboards,
+            layout: Layout::Alphabetical,
+            buttons: false,
+        };
+        assert_eq!(split.row(1).to_string(), "H H · I J J K K L L M M N N");
+        assert_eq!(split.row(3).to_string(), "    V V W W X X Y Y Z Z");
+        assert_eq!(split.width(), 27);
+    }
+
+    #[test]
+    fn split_press_at() {
+        let keyboards = [None, None];
+        let split = SplitKeyboard {
+            keyboards: &keyboards,
+            layout: Layout::Alphabetical,
+            buttons: true,
+        };
+        // each key is a letter for both boards then a gap
+        assert_eq!(split.press_at(0, 0), Some(Press::Letter('a')));
+        assert_eq!(split.press_at(1, 0), Some(Press::Letter('a')));
+        assert_eq!(split.press_at(2, 0), None);
+        assert_eq!(split.press_at(3, 0), Some(Press::Letter('b')));
+        assert_eq!(split.press_at(2, 3), None);
+        assert_eq!(split.press_at(3, 3), Some(Press::Letter('v')));
+        assert_eq!(split.row(4).to_string(), "ENT DEL");
+        assert_eq!(split.press_at(5, 4), Some(Press::Delete));
+        assert_eq!(split.rows(), 5);
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use unicode_width::UnicodeWidthChar;
+
+use super::letters::LetterMatch;
+
+/// How the letters of a word are spaced out on the board
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cells {
+    /// Blank columns between two letters, or on either side of each letter when
+    /// separators are drawn
+    pub spacing: u16,
+    /// Whether to draw a `│` between and around the letters
+    pub separators: bool,
+    /// Columns taken up by each letter. Narrower letters are padded to fit
+    pub letter_width: u16,
+}
+
+impl Default for Cells {
+    fn default() -> Self {
+        Self {
+            spacing: 0,
+            separators: false,
+            letter_width: 1,
+        }
+    }
+}
+
+impl Cells {
+    /// Widens the letters to fit the widest letter of the word, such as those of
+    /// CJK scripts which take up two columns
+    pub fn fit(self, word: &str) -> Self {
+        let widest = word.chars().map(|c| c.width().unwrap_or(0) as u16).max();
+        Self {
+            letter_width: self.letter_width.max(widest.unwrap_or(1)),
+            ..self
+        }
+    }
+
+    /// Columns from one letter to the next
+    fn pitch(self) -> u16 {
+        if self.separators {
+            self.letter_width + 1 + 2 * self.spacing
+        } else {
+            self.letter_width + self.spacing
+        }
+    }
+
+    /// Column of the `i`th letter, relative to the start of the row
+    pub fn offset(self, i: usize) -> u16 {
+        let lead = if self.separators { 1 + self.spacing } else { 0 };
+        lead + i as u16 * self.pitch()
+    }
+
+    /// Total width of a row of `n` letters
+    pub fn width(self, n: u16) -> u16 {
+        if self.separators {
+            n * self.pitch() + 1
+        } else {
+            (n * self.pitch()).saturating_sub(self.spacing)
+        }
+    }
+
+    /// Lays out the letters of a row according to these cells
+    pub fn row<I>(self, letters: I) -> Row<I> {
+        Row(self, letters)
+    }
+
+    /// An empty row of `n` letters
+    pub fn blank(self, n: usize) -> Row<std::iter::RepeatN<char>> {
+        self.row(std::iter::repeat_n(' ', n))
+    }
+}
+
+/// Something drawn in a single cell of the board
+pub trait Letter: fmt::Display {
+    /// Columns taken up when drawn
+    fn width(&self) -> u16;
+}
+
+impl Letter for char {
+    fn width(&self) -> u16 {
+        UnicodeWidthChar::width(*self).unwrap_or(0) as u16
+    }
+}
+
+impl Letter for LetterMatch {
+    fn width(&self) -> u16 {
+        Letter::width(&self.shown())
+    }
+}
+
+pub struct Row<I>(Cells, I);
+
+impl<I> Row<I> {
+    /// Draws the letter, padded out to the width of a cell
+    fn letter(&self, f: &mut fmt::Formatter<'_>, letter: impl Letter) -> fmt::Result {
+        let fill = self.0.letter_width.saturating_sub(letter.width()) as usize;
+        write!(f, "{}{:fill$}", letter, "")
+    }
+}
+
+impl<I> fmt::Display for Row<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Letter,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Row(cells, letters) = self;
+        let pad = cells.spacing as usize;
+        if cells.separators {
+            f.write_str("│")?;
+            for letter in letters.clone() {
+                write!(f, "{:pad$}", "")?;
+                self.letter(f, letter)?;
+                write!(f, "{:pad$}│", "")?;
+            }
+        } else {
+            for (i, letter) in letters.clone().into_iter().enumerate() {
+                if i > 0 {
+                    write!(f, "{:pad$}", "")?;
+                }
+                self.letter(f, letter)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cells;
+    use test_case::test_case;
+    use unicode_width::UnicodeWidthStr;
+
+    #[test_case(0, false, "ABCDE"; "tight")]
+    #[test_case(1, false, "A B C D E"; "spaced")]
+    #[test_case(0, true, "│A│B│C│D│E│"; "separated")]
+    #[test_case(1, true, "│ A │ B │ C │ D │ E │"; "spaced and separated")]
+    fn row(spacing: u16, separators: bool, expected: &str) {
+        let cells = Cells {
+            spacing,
+            separators,
+            ..Cells::default()
+        };
+        let row = cells.row("ABCDE".chars()).to_string();
+        assert_eq!(row, expected);
+        assert_eq!(row.chars().count(), cells.width(5) as usize);
+        for (i, c) in "ABCDE".chars().enumerate() {
+            assert_eq!(row.chars().nth(cells.offset(i) as usize), Some(c));
+        }
+    }
+
+    #[test_case(false, "漢字A 漢字"; "tight")]
+    #[test_case(true, "│漢│字│A │漢│字│"; "separated")]
+    fn wide_letters(separators: bool, expected: &str) {
+        let word = "漢字a漢字";
+        let cells = Cells {
+            separators,
+            ..Cells::default()
+        }
+        .fit(word);
+        assert_eq!(cells.letter_width, 2);
+
+        let row = cells.row("漢字A漢字".chars()).to_string();
+        assert_eq!(row, expected);
+        assert_eq!(row.width(), cells.width(5) as usize);
+    }
+}
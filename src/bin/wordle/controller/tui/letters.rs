@@ -1,29 +1,146 @@
 use std::{fmt};
 
 use cl_wordle::Match;
+use super::layout::Cells;
 use owo_colors::{
-    colors::{Black, Green, Yellow, xterm::Gray},
+    colors::{Black, Green, Red, White, Yellow, xterm::{DarkMalibuBlue, FlushOrange, Gray}},
     OwoColorize,
 };
 
-pub struct LetterMatch(pub char, pub Match);
+/// The colours matches are drawn in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    /// Green for exact matches and yellow for close ones
+    #[default]
+    Standard,
+    /// Orange for exact matches and blue for close ones, which are easier to tell
+    /// apart with colour blindness
+    HighContrast,
+    /// No colours, for when `NO_COLOR` is set. Exact matches are reversed, close
+    /// ones underlined and wrong ones dimmed
+    Mono,
+    /// No escape codes at all, for output that isn't a terminal. Exact matches are
+    /// uppercase, close ones lowercase and wrong ones a dot
+    Plain,
+}
+
+impl Palette {
+    fn render(self, m: Match, d: impl fmt::Display, mut f: impl fmt::Write) -> fmt::Result {
+        match (m, self) {
+            (Match::Exact, Palette::Standard) => write!(f, "{}", d.fg::<Black>().bg::<Green>()),
+            (Match::Close, Palette::Standard) => write!(f, "{}", d.fg::<Black>().bg::<Yellow>()),
+            (Match::Exact, Palette::HighContrast) => write!(f, "{}", d.fg::<Black>().bg::<FlushOrange>()),
+            (Match::Close, Palette::HighContrast) => write!(f, "{}", d.fg::<Black>().bg::<DarkMalibuBlue>()),
+            (Match::Exact, Palette::Mono) => write!(f, "{}", d.reversed().bold()),
+            (Match::Close, Palette::Mono) => write!(f, "{}", d.underline()),
+            (Match::Wrong, Palette::Mono) => write!(f, "{}", d.dimmed()),
+            (_, Palette::Plain) => write!(f, "{}", d),
+            (Match::Wrong, _) => write!(f, "{}", d.fg::<Gray>()),
+        }
+    }
+
+    /// Draws a letter that's exact in one place and close in another, in the close
+    /// colour on the exact one. Without colours it's drawn as exact
+    pub fn split(self, d: impl fmt::Display, mut f: impl fmt::Write) -> fmt::Result {
+        match self {
+            Palette::Standard => write!(f, "{}", d.fg::<Yellow>().bg::<Green>()),
+            Palette::HighContrast => write!(f, "{}", d.fg::<DarkMalibuBlue>().bg::<FlushOrange>()),
+            Palette::Mono => write!(f, "{}", d.reversed().bold().underline()),
+            Palette::Plain => write!(f, "{}", d),
+        }
+    }
+
+    /// The palette for output to a terminal, once `NO_COLOR` and the colour blind
+    /// option are taken into account
+    pub fn detect(high_contrast: bool) -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            Palette::Mono
+        } else if high_contrast {
+            Palette::HighContrast
+        } else {
+            Palette::Standard
+        }
+    }
+
+    /// Draws a bar of a chart, in the exact match colour if it's highlighted
+    pub fn bar(self, d: impl fmt::Display, highlight: bool, f: impl fmt::Write) -> fmt::Result {
+        let mut f = f;
+        match (self, highlight) {
+            (Palette::Standard | Palette::HighContrast, true) => self.render(Match::Exact, d, f),
+            (Palette::Standard | Palette::HighContrast, false) => {
+                write!(f, "{}", d.fg::<White>().bg::<Gray>())
+            }
+            (Palette::Mono, true) => write!(f, "{}", d.reversed().bold()),
+            (Palette::Mono, false) => write!(f, "{}", d.reversed()),
+            (Palette::Plain, _) => write!(f, "{}", d),
+        }
+    }
+
+    /// Draws a match as a square of its colour, with no letter in it
+    pub fn square(self, m: Match, mut f: impl fmt::Write) -> fmt::Result {
+        match (m, self) {
+            (Match::Exact, Palette::Plain) => write!(f, "G"),
+            (Match::Close, Palette::Plain) => write!(f, "Y"),
+            (Match::Wrong, Palette::Plain) => write!(f, "{}", PLAIN_WRONG),
+            (Match::Exact, Palette::Mono) => write!(f, "█"),
+            (Match::Close, Palette::Mono) => write!(f, "▒"),
+            (Match::Wrong, Palette::Mono) => write!(f, "·"),
+            (Match::Exact, Palette::Standard) => write!(f, "{}", "█".fg::<Green>()),
+            (Match::Close, Palette::Standard) => write!(f, "{}", "█".fg::<Yellow>()),
+            (Match::Exact, Palette::HighContrast) => write!(f, "{}", "█".fg::<FlushOrange>()),
+            (Match::Close, Palette::HighContrast) => write!(f, "{}", "█".fg::<DarkMalibuBlue>()),
+            (Match::Wrong, _) => write!(f, "{}", "█".fg::<Gray>()),
+        }
+    }
+
+    /// Highlights a word that was rejected
+    pub fn invalid(self, d: impl fmt::Display, mut f: impl fmt::Write) -> fmt::Result {
+        match self {
+            Palette::Standard | Palette::HighContrast => write!(f, "{}", d.bg::<Red>()),
+            Palette::Mono => write!(f, "{}", d.strikethrough()),
+            Palette::Plain => write!(f, "{}", d),
+        }
+    }
+}
+
+/// The letter in uppercase, in any alphabet, unless it has no uppercase letter of
+/// its own: `ß` has none, and `ς` shares `σ`'s, so they'd look like other letters
+pub fn uppercase(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) if upper.to_lowercase().eq([c]) => upper,
+        _ => c,
+    }
+}
+
+/// What a wrong letter is drawn as without any escape codes
+const PLAIN_WRONG: char = '·';
+
+pub struct LetterMatch(pub char, pub Match, pub Palette);
 impl fmt::Display for LetterMatch {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        render(self.1, self.0.to_ascii_uppercase(), f)
+        match (self.2, self.1) {
+            (Palette::Plain, Match::Close) => write!(f, "{}", self.0.to_lowercase()),
+            (Palette::Plain, Match::Wrong) => write!(f, "{}", PLAIN_WRONG),
+            _ => self.2.render(self.1, uppercase(self.0), f),
+        }
     }
 }
 
-pub struct WordMatch<'a>(pub &'a str, pub Match);
-impl<'a> fmt::Display for WordMatch<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        render(self.1, self.0.to_ascii_uppercase(), f)
+impl LetterMatch {
+    /// The character drawn for the letter, before any colour
+    pub fn shown(&self) -> char {
+        match (self.2, self.1) {
+            (Palette::Plain, Match::Wrong) => PLAIN_WRONG,
+            _ => self.0,
+        }
     }
 }
 
-fn render(m: Match, d: impl fmt::Display, mut f: impl fmt::Write) -> fmt::Result {
-    match m {
-        Match::Exact => write!(f, "{}", d.fg::<Black>().bg::<Green>()),
-        Match::Close => write!(f, "{}", d.fg::<Black>().bg::<Yellow>()),
-        Match::Wrong => write!(f, "{}", d.fg::<Gray>()),
+pub struct WordMatch<'a>(pub &'a str, pub Match, pub Cells, pub Palette);
+impl<'a> fmt::Display for WordMatch<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (m, palette) = (self.1, self.3);
+        write!(f, "{}", self.2.row(self.0.chars().map(move |c| LetterMatch(c, m, palette))))
     }
 }
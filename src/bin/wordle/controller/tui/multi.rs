@@ -0,0 +1,420 @@
+use std::{io, iter};
+
+use cl_wordle::{
+    multi::{Multi, MultiShare},
+    Match,
+};
+use crossterm::{
+    cursor,
+    event::{self, KeyCode, KeyModifiers, MouseButton, MouseEventKind},
+    execute,
+    terminal::{Clear, ClearType},
+};
+use eyre::Result;
+
+use super::{
+    guess::Guesses,
+    keyboard::{Keyboard, Layout, Press, SplitKeyboard},
+    layout::Cells,
+    letters::{Palette, WordMatch},
+    terminal::{Backend, Terminal},
+};
+
+/// Columns between neighbouring boards
+const BOARD_GAP: u16 = 3;
+
+/// Plays several boards side by side, each guess going to every unsolved one.
+///
+/// As many boards go on a row as fit the terminal, and the rest wrap onto rows
+/// below. A keyboard below them splits each key between the boards
+pub struct Controller<B = Terminal> {
+    multi: Multi,
+    cells: Cells,
+    palette: Palette,
+    layout: Layout,
+    terminal: B,
+    /// The word being typed
+    word: String,
+    /// Boards on each row, as of the last redraw
+    per_row: usize,
+    /// Whether the keys can be clicked
+    mouse: bool,
+    /// Row the keyboard was last drawn at, or `None` if it didn't fit
+    keyboard_row: Option<u16>,
+}
+
+impl Controller {
+    pub fn new(multi: Multi, cells: Cells, palette: Palette, mouse: bool) -> Result<Self> {
+        let terminal = Terminal::new(mouse)?;
+        Ok(Self::with_backend(multi, cells, palette, terminal).with_mouse(mouse))
+    }
+}
+
+impl<B: Backend> Controller<B> {
+    pub fn with_backend(multi: Multi, cells: Cells, palette: Palette, terminal: B) -> Self {
+        let cells = multi
+            .boards()
+            .iter()
+            .fold(cells, |cells, board| cells.fit(board.state().solution()));
+        let per_row = multi.boards().len();
+        Self {
+            multi,
+            cells,
+            palette,
+            layout: Layout::default(),
+            terminal,
+            word: String::new(),
+            per_row,
+            mouse: false,
+            keyboard_row: None,
+        }
+    }
+
+    /// Whether clicking a key types it, with buttons for enter and backspace
+    pub fn with_mouse(self, mouse: bool) -> Self {
+        Self { mouse, ..self }
+    }
+
+    /// The order of the keys on the keyboard
+    pub fn with_layout(self, layout: Layout) -> Self {
+        Self { layout, ..self }
+    }
+
+    /// Plays until every board is solved or the guesses run out, or returns `None`
+    /// if it was quit
+    pub fn run(mut self) -> Result<Option<MultiShare>> {
+        let (width, height) = self.terminal.size()?;
+        let needed = self.needed_height(width);
+        if height < needed {
+            eyre::bail!(
+                "{} boards of {} guesses need a terminal {} rows tall, this one is {}",
+                self.multi.boards().len(),
+                self.multi.max_guesses(),
+                needed,
+                height
+            );
+        }
+
+        self.display_window()?;
+        let len = self.multi.word_len();
+        loop {
+            self.terminal.flush()?;
+            let key = match self.terminal.read()? {
+                event::Event::Key(key) => key,
+                // clicks on the keyboard are played as if the key was typed
+                event::Event::Mouse(mouse) => match self.click(mouse) {
+                    Some(code) => code.into(),
+                    None => continue,
+                },
+                event::Event::Resize(..) => {
+                    if !self.wait_for_room()? {
+                        return Ok(None);
+                    }
+                    self.display_window()?;
+                    continue;
+                }
+            };
+            self.show_status("")?;
+            match key.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(None)
+                }
+                KeyCode::Char(c) if c.is_alphabetic() && self.word.chars().count() < len => {
+                    self.word.extend(c.to_lowercase());
+                    self.display_word()?;
+                }
+                KeyCode::Backspace => {
+                    self.word.pop();
+                    self.display_word()?;
+                }
+                KeyCode::Enter if self.word.chars().count() == len => {
+                    match self.multi.guess(&self.word) {
+                        Ok(_) => {
+                            self.word.clear();
+                            self.display_window()?;
+                        }
+                        Err(err) => self.show_status(&err.to_string())?,
+                    }
+                    if self.multi.game_over().is_some() {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.display_solutions()?;
+        execute!(self.terminal, cursor::Hide)?;
+        loop {
+            if let event::Event::Key(key) = self.terminal.read()? {
+                if let KeyCode::Enter | KeyCode::Esc = key.code {
+                    return Ok(Some(self.multi.share()));
+                }
+            }
+        }
+    }
+
+    fn display_window(&mut self) -> io::Result<()> {
+        let (width, height) = self.terminal.size()?;
+        self.per_row = self.fit(width);
+        write!(
+            self.terminal,
+            "{clear}{bottom}Press ESC to exit.{top}Termo {game_type}",
+            clear = Clear(ClearType::All),
+            bottom = cursor::MoveTo(0, height - 1),
+            top = cursor::MoveTo(0, 0),
+            game_type = self.multi.game_type(),
+        )?;
+        for (i, board) in self.multi.boards().iter().enumerate() {
+            let guesses = Guesses {
+                state: board.state(),
+                cells: self.cells,
+                palette: self.palette,
+                frequencies: None,
+            };
+            write!(
+                self.terminal,
+                "{}{}",
+                cursor::MoveTo(self.board_x(i), self.board_y(i, width)),
+                guesses
+            )?;
+        }
+        self.display_keyboard(width, height)?;
+        self.display_word()
+    }
+
+    /// Draws the split keyboard below the boards, if there's room for it
+    fn display_keyboard(&mut self, width: u16, height: u16) -> io::Result<()> {
+        let keyboards = self.keyboards();
+        let keyboard = SplitKeyboard {
+            keyboards: &keyboards,
+            layout: self.layout,
+            buttons: self.mouse,
+        };
+        let rows = self.multi.boards().len().div_ceil(self.per_row);
+        let y = 2 + rows as u16 * self.block_height();
+        // keep clear of the exit message on the last line
+        if y + keyboard.rows() as u16 >= height || keyboard.width() > width {
+            self.keyboard_row = None;
+            return Ok(());
+        }
+        for i in 0..keyboard.rows() {
+            let row = keyboard.row(i);
+            write!(self.terminal, "{}{}", cursor::MoveTo(0, y + i as u16), row)?;
+        }
+        self.keyboard_row = Some(y);
+        Ok(())
+    }
+
+    /// Each board's keyboard, or `None` for a solved board
+    fn keyboards(&self) -> Vec<Option<Keyboard>> {
+        self.multi
+            .boards()
+            .iter()
+            .map(|board| {
+                if board.game_over() == Some(true) {
+                    return None;
+                }
+                let mut keyboard = Keyboard::new(self.palette).with_layout(self.layout);
+                for cl_wordle::state::Guess(word, matches) in board.state().guesses() {
+                    keyboard.push(word, matches);
+                }
+                Some(keyboard)
+            })
+            .collect()
+    }
+
+    /// Finds the key clicked on, if the keyboard is drawn and a key was clicked
+    fn click(&self, mouse: event::MouseEvent) -> Option<KeyCode> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) || !self.mouse {
+            return None;
+        }
+        let keyboards = self.keyboards();
+        let keyboard = SplitKeyboard {
+            keyboards: &keyboards,
+            layout: self.layout,
+            buttons: true,
+        };
+        let row = mouse.row.checked_sub(self.keyboard_row?)?;
+        Some(match keyboard.press_at(mouse.column, row)? {
+            Press::Letter(c) => KeyCode::Char(c),
+            Press::Enter => KeyCode::Enter,
+            Press::Delete => KeyCode::Backspace,
+        })
+    }
+
+    /// Rows the boards take packed into the width, with the exit message below them
+    fn needed_height(&self, width: u16) -> u16 {
+        self.board_y(self.multi.boards().len() - 1, width) + self.block_height()
+    }
+
+    /// Once the terminal is resized too small for a board, or for the rows of boards,
+    /// says so instead of drawing them garbled, and waits until it's made big enough.
+    ///
+    /// Returns false if ESC is pressed before then
+    fn wait_for_room(&mut self) -> io::Result<bool> {
+        loop {
+            let (width, height) = self.terminal.size()?;
+            let board = self.cells.width(self.multi.word_len() as u16);
+            if width >= board && height >= self.needed_height(width) {
+                return Ok(true);
+            }
+            write!(
+                self.terminal,
+                "{}{}the terminal is too small for {} boards, it's {}x{}. ESC to quit",
+                Clear(ClearType::All),
+                cursor::MoveTo(0, 0),
+                self.multi.boards().len(),
+                width,
+                height
+            )?;
+            self.terminal.flush()?;
+            if let event::Event::Key(key) = self.terminal.read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.code == KeyCode::Esc || ctrl_c {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    /// How many boards fit side by side in the width, at least 1
+    fn fit(&self, width: u16) -> usize {
+        let board = self.cells.width(self.multi.word_len() as u16) + BOARD_GAP;
+        let fit = (width + BOARD_GAP) / board;
+        (fit as usize).clamp(1, self.multi.boards().len())
+    }
+
+    /// Rows for a row of boards: the guesses, a gap, the solutions and another gap
+    fn block_height(&self) -> u16 {
+        self.multi.max_guesses() as u16 + 3
+    }
+
+    /// Column the given board starts at
+    fn board_x(&self, board: usize) -> u16 {
+        let (cells, len) = (self.cells, self.multi.word_len());
+        (board % self.per_row) as u16 * (cells.width(len as u16) + BOARD_GAP)
+    }
+
+    /// Row the given board starts at, with the boards packed into the width
+    fn board_y(&self, board: usize, width: u16) -> u16 {
+        2 + (board / self.fit(width)) as u16 * self.block_height()
+    }
+
+    /// Writes the word being typed on the next row of every unsolved board
+    fn display_word(&mut self) -> io::Result<()> {
+        let (width, _) = self.terminal.size()?;
+        let (cells, len) = (self.cells, self.multi.word_len());
+        let word = self.word.to_uppercase();
+        let row = cells.row(word.chars().chain(iter::repeat(' ')).take(len));
+        for (i, board) in self.multi.boards().iter().enumerate() {
+            if board.game_over().is_none() {
+                let y = self.board_y(i, width) + board.state().guesses().len() as u16;
+                write!(
+                    self.terminal,
+                    "{}{}",
+                    cursor::MoveTo(self.board_x(i), y),
+                    row
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows the solution below each board that wasn't solved
+    fn display_solutions(&mut self) -> io::Result<()> {
+        let (width, _) = self.terminal.size()?;
+        let message = if self.multi.game_over() == Some(true) {
+            "solved them all!"
+        } else {
+            "out of guesses"
+        };
+        self.show_status(message)?;
+        for (i, board) in self.multi.boards().iter().enumerate() {
+            if board.game_over() == Some(false) {
+                let solution = board.state().solution();
+                let word = WordMatch(solution, Match::Exact, self.cells, self.palette);
+                let y = self.board_y(i, width) + self.multi.max_guesses() as u16 + 1;
+                write!(
+                    self.terminal,
+                    "{}{}",
+                    cursor::MoveTo(self.board_x(i), y),
+                    word
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the message on the line between the title and the boards,
+    /// leaving the cursor where it was
+    fn show_status(&mut self, message: &str) -> io::Result<()> {
+        write!(
+            self.terminal,
+            "{save}{status}{message}{clear}{restore}",
+            save = cursor::SavePosition,
+            status = cursor::MoveTo(0, 1),
+            clear = Clear(ClearType::UntilNewLine),
+            restore = cursor::RestorePosition,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::multi::Multi;
+    use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use super::Controller;
+    use crate::controller::tui::tests::Scripted;
+
+    fn play(keys: &str) -> Option<String> {
+        let multi = Multi::custom(vec!["termo".to_owned(), "suite".to_owned()]).unwrap();
+        let controller = Controller::with_backend(
+            multi,
+            Default::default(),
+            Default::default(),
+            Scripted::new(keys),
+        );
+        controller.run().unwrap().map(|share| share.to_string())
+    }
+
+    #[test]
+    fn solves_both_boards() {
+        let share = play("xxxxx\n\x08\x08\x08\x08\x08suite\ntermo\n\n").unwrap();
+        assert!(share.starts_with("joguei term.ooo #custom 2/7 1/7\n"));
+    }
+
+    #[test]
+    fn clicking_the_keyboard_types() {
+        let click = |column, row| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        let multi = Multi::custom(vec!["termo".to_owned(), "suite".to_owned()]).unwrap();
+        // the keyboard starts below the boards of 7 guesses, at row 12. s is the 5th
+        // key of the 3rd row, and each key is 3 columns wide with its gap
+        let mut backend = Scripted::new("");
+        backend.0.extend([click(12, 14), click(14, 14)]);
+        backend.0.extend(Scripted::new("uite").0);
+        // then enter, on the row of buttons below the letters
+        backend.0.extend([click(1, 16)]);
+        backend.0.extend(Scripted::new("termo\n\n").0);
+        let controller =
+            Controller::with_backend(multi, Default::default(), Default::default(), backend)
+                .with_mouse(true);
+        let share = controller.run().unwrap().unwrap().to_string();
+        assert!(share.starts_with("joguei term.ooo #custom 2/7 1/7\n"));
+    }
+
+    #[test]
+    fn quit() {
+        assert!(play("suite\nter\x1b").is_none());
+    }
+}
@@ -0,0 +1,145 @@
+use std::{fmt, sync::mpsc};
+
+use cl_wordle::{game::Score, Matches};
+use crossterm::{
+    cursor,
+    terminal::{Clear, ClearType},
+};
+
+use super::letters::Palette;
+
+/// Something the other player in a race did
+#[derive(Debug, PartialEq, Eq)]
+pub enum Update {
+    /// Made a guess, which matched like this
+    Row(Matches),
+    /// Finished their game
+    Done(Score),
+    /// Quit, or lost the connection
+    Left,
+}
+
+/// The other player in a race, as far as they've got
+pub struct Opponent {
+    name: String,
+    updates: mpsc::Receiver<Update>,
+    grid: Vec<Matches>,
+    result: Option<Score>,
+    left: bool,
+}
+
+impl Opponent {
+    pub fn new(name: String, updates: mpsc::Receiver<Update>) -> Self {
+        Self {
+            name,
+            updates,
+            grid: Vec::new(),
+            result: None,
+            left: false,
+        }
+    }
+
+    /// Takes in every update that's arrived, returning whether there were any
+    pub(super) fn receive(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(update) = self.updates.try_recv() {
+            match update {
+                Update::Row(matches) => self.grid.push(matches),
+                Update::Done(score) => self.result = Some(score),
+                // once they've finished, leaving doesn't change anything
+                Update::Left => self.left = self.result.is_none(),
+            }
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// The other player's board, with just the colours of their guesses, and how their
+/// game ended once it has. The top-left corner is at the given cell
+pub struct OpponentPanel<'a> {
+    pub opponent: &'a Opponent,
+    pub max_guesses: usize,
+    pub word_len: usize,
+    pub palette: Palette,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl<'a> OpponentPanel<'a> {
+    fn status(&self) -> String {
+        match (self.opponent.result, self.opponent.left) {
+            (Some(Score::Guesses(n)), _) => format!("solved in {}", n),
+            (Some(Score::Failed), _) => "didn't solve it".to_owned(),
+            (None, true) => "left".to_owned(),
+            (None, false) => "playing".to_owned(),
+        }
+    }
+}
+
+impl<'a> fmt::Display for OpponentPanel<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", cursor::SavePosition)?;
+        let start = |i: usize| cursor::MoveTo(self.column, self.row + i as u16);
+        let (name, status) = (&self.opponent.name, self.status());
+        write!(
+            f,
+            "{}{}{}, {}",
+            start(0),
+            Clear(ClearType::UntilNewLine),
+            name,
+            status
+        )?;
+        // rows past the limit aren't drawn, as in a game with unlimited guesses
+        let rows = self.max_guesses.min(self.opponent.grid.len().max(6));
+        for i in 0..rows {
+            write!(f, "{}{}", start(i + 1), Clear(ClearType::UntilNewLine))?;
+            match self.opponent.grid.get(i) {
+                Some(Matches(matches)) => {
+                    for &m in matches {
+                        self.palette.square(m, &mut *f)?;
+                    }
+                }
+                None => write!(f, "{}", "·".repeat(self.word_len))?,
+            }
+        }
+        write!(f, "{}", cursor::RestorePosition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use cl_wordle::{diff, game::Score};
+
+    use super::{Opponent, OpponentPanel, Palette, Update};
+
+    #[test]
+    fn shows_colours_but_not_letters() {
+        let (sender, updates) = mpsc::channel();
+        let mut opponent = Opponent::new("bia".to_owned(), updates);
+        assert!(!opponent.receive());
+        sender.send(Update::Row(diff("sorte", "termo"))).unwrap();
+        sender.send(Update::Row(diff("termo", "termo"))).unwrap();
+        sender.send(Update::Done(Score::Guesses(2))).unwrap();
+        sender.send(Update::Left).unwrap();
+        assert!(opponent.receive());
+
+        let panel = OpponentPanel {
+            opponent: &opponent,
+            max_guesses: 6,
+            word_len: 5,
+            palette: Palette::Plain,
+            column: 0,
+            row: 0,
+        };
+        assert_eq!(panel.status(), "solved in 2");
+        let drawn = panel.to_string();
+        assert!(drawn.contains("bia, solved in 2"));
+        assert!(drawn.contains("·YGYY"));
+        assert!(drawn.contains("GGGGG"));
+        assert!(!drawn.contains("SORTE"));
+        assert_eq!(drawn.matches("·····").count(), 4);
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::{self, Write};
+
+use crossterm::{
+    event::{self, KeyCode},
+    terminal,
+    tty::IsTty,
+};
+
+/// Prints words in as many columns as fit the terminal.
+///
+/// When writing to a terminal and the words don't fit on one screen, waits for the
+/// player to press space before showing the next screen
+pub struct Pager {
+    /// Maximum number of words to show
+    pub limit: Option<usize>,
+}
+
+impl Pager {
+    pub fn show(&self, words: &[&str]) -> io::Result<()> {
+        let shown = &words[..self.limit.unwrap_or(usize::MAX).min(words.len())];
+
+        let mut stdout = io::stdout();
+        let interactive = stdout.is_tty();
+        let (width, height) = if interactive {
+            terminal::size()?
+        } else {
+            (80, u16::MAX)
+        };
+
+        let column = shown.iter().map(|w| w.chars().count()).max().unwrap_or(0) + 2;
+        let per_line = (width as usize / column).max(1);
+        // leave a line for the prompt
+        let per_page = (height as usize).saturating_sub(1).max(1);
+
+        for (i, line) in shown.chunks(per_line).enumerate() {
+            if interactive && i > 0 && i % per_page == 0 && !more(&mut stdout)? {
+                return Ok(());
+            }
+            for word in line {
+                write!(stdout, "{:column$}", word, column = column)?;
+            }
+            writeln!(stdout)?;
+        }
+
+        if shown.len() < words.len() {
+            writeln!(stdout, "showing {} of {} words", shown.len(), words.len())?;
+        } else if words.len() == 1 {
+            writeln!(stdout, "1 word")?;
+        } else {
+            writeln!(stdout, "{} words", words.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Asks whether to show the next page
+fn more(stdout: &mut io::Stdout) -> io::Result<bool> {
+    write!(stdout, "-- press space for more, q to stop --")?;
+    stdout.flush()?;
+
+    terminal::enable_raw_mode()?;
+    let more = loop {
+        if let event::Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Char(' ') | KeyCode::Enter => break true,
+                KeyCode::Char('q') | KeyCode::Esc => break false,
+                _ => {}
+            }
+        }
+    };
+    terminal::disable_raw_mode()?;
+
+    write!(
+        stdout,
+        "\r{}",
+        terminal::Clear(terminal::ClearType::CurrentLine)
+    )?;
+    Ok(more)
+}
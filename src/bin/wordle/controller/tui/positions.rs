@@ -0,0 +1,59 @@
+use std::fmt;
+
+use crossterm::{
+    cursor,
+    terminal::{Clear, ClearType},
+};
+
+/// The letters each position could still hold, under the count of solutions left,
+/// with its top-left corner at the given cell
+pub struct PositionsPanel<'a> {
+    pub remaining: usize,
+    /// The letters for each position, from [`possible_letters`](cl_wordle::solver::possible_letters)
+    pub letters: &'a [Vec<char>],
+    pub column: u16,
+    pub row: u16,
+}
+
+impl<'a> PositionsPanel<'a> {
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![match self.remaining {
+            1 => "1 word left".to_owned(),
+            n => format!("{} words left", n),
+        }];
+        let positions = self.letters.iter().enumerate().map(|(i, letters)| {
+            let letters = letters.iter().collect::<String>().to_uppercase();
+            format!("{} {}", i + 1, letters)
+        });
+        lines.extend(positions);
+        lines
+    }
+}
+
+impl<'a> fmt::Display for PositionsPanel<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", cursor::SavePosition)?;
+        for (i, line) in self.lines().iter().enumerate() {
+            let start = cursor::MoveTo(self.column, self.row + i as u16);
+            write!(f, "{}{}{}", start, Clear(ClearType::UntilNewLine), line)?;
+        }
+        write!(f, "{}", cursor::RestorePosition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PositionsPanel;
+
+    #[test]
+    fn lists_each_positions_letters_under_the_count() {
+        let letters = [vec!['t'], vec!['e', 'o'], vec![]];
+        let panel = PositionsPanel {
+            remaining: 2,
+            letters: &letters,
+            column: 0,
+            row: 0,
+        };
+        assert_eq!(panel.lines(), ["2 words left", "1 T", "2 EO", "3 "]);
+    }
+}
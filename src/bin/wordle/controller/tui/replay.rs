@@ -0,0 +1,373 @@
+//! Recording the keys pressed in a game, and playing them back with the same timing.
+//!
+//! A replay file starts with the game, the rules it was played by and any guesses
+//! made before it was recorded, then has a line for each key, after the
+//! milliseconds since recording started:
+//!
+//! ```text
+//! game practice 3
+//! solution termo
+//! hard
+//! max-guesses 6
+//! 812 t
+//! 1040 e
+//! 2210 enter
+//! ```
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, LineWriter, Write},
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use cl_wordle::prelude::*;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use eyre::{bail, Result};
+
+use super::terminal::{Backend, Terminal};
+
+/// A recorded game, and every key pressed in it with when it was pressed
+#[derive(Debug, PartialEq, Eq)]
+pub struct Replay {
+    pub game_type: GameType,
+    pub solution: String,
+    pub rules: Rules,
+    /// Guesses already made when recording started, as in a resumed daily
+    pub guesses: Vec<String>,
+    pub keys: Vec<(Duration, KeyEvent)>,
+}
+
+/// The options a recorded game was played with, so it's played back by the same
+/// rules whatever the flags are then. A rule that isn't in the file is off, and the
+/// guesses are the usual number
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub hard: bool,
+    pub ultra_hard: bool,
+    pub no_repeats: bool,
+    pub ignore_accents: bool,
+    /// `None` for a game that goes on until it's solved, like Absurdle, or a
+    /// recording that came before the guesses were kept
+    pub max_guesses: Option<usize>,
+}
+
+impl Rules {
+    fn of(game: &Game) -> Self {
+        let state = game.state();
+        Self {
+            hard: state.hard_mode(),
+            ultra_hard: state.ultra_hard(),
+            no_repeats: !state.allows_repeats(),
+            ignore_accents: state.ignore_accents(),
+            max_guesses: (!state.unlimited()).then(|| game.max_guesses()),
+        }
+    }
+
+    /// The game with these rules
+    pub fn apply(&self, game: Game) -> Result<Game, GameInitError> {
+        let game = game
+            .allow_repeated_guesses(!self.no_repeats)
+            .with_hard_mode(self.hard)
+            .with_ultra_hard_mode(self.ultra_hard)
+            .with_ignore_accents(self.ignore_accents);
+        match self.max_guesses {
+            Some(max_guesses) if !game.state().unlimited() => game.with_max_guesses(max_guesses),
+            _ => Ok(game),
+        }
+    }
+}
+
+impl Replay {
+    pub fn parse(input: &str) -> Result<Self> {
+        let (mut game_type, mut solution) = (None, None);
+        let mut rules = Rules {
+            hard: false,
+            ultra_hard: false,
+            no_repeats: false,
+            ignore_accents: false,
+            max_guesses: None,
+        };
+        let mut guesses = Vec::new();
+        let mut keys = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let (first, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match first {
+                "game" => game_type = Some(rest.parse::<GameType>()?),
+                "solution" => solution = Some(rest.to_owned()),
+                "guess" => guesses.push(rest.to_owned()),
+                "hard" => rules.hard = true,
+                "ultra-hard" => rules.ultra_hard = true,
+                "no-repeats" => rules.no_repeats = true,
+                "ignore-accents" => rules.ignore_accents = true,
+                "max-guesses" => match rest.parse() {
+                    Ok(n) if n > 0 => rules.max_guesses = Some(n),
+                    _ => bail!("line {}: expected a number of guesses", i + 1),
+                },
+                "" => {}
+                millis => {
+                    let at = match millis.parse() {
+                        Ok(millis) => Duration::from_millis(millis),
+                        Err(_) => bail!("line {}: expected a game, guess or key", i + 1),
+                    };
+                    match decode(rest) {
+                        Some(key) => keys.push((at, key)),
+                        None => bail!("line {}: unknown key {:?}", i + 1, rest),
+                    }
+                }
+            }
+        }
+        match (game_type, solution) {
+            (Some(game_type), Some(solution)) => Ok(Self {
+                game_type,
+                solution,
+                rules,
+                guesses,
+                keys,
+            }),
+            _ => bail!("not a replay, there's no game and solution at the start"),
+        }
+    }
+}
+
+/// Writes each key to the replay file as it's read, so a game that's quit part way
+/// is still recorded up to there
+pub struct Recording {
+    file: LineWriter<File>,
+    started: Instant,
+}
+
+impl Recording {
+    /// Starts the file with the game, its rules and the guesses it already has
+    pub fn create(path: &Path, game: &Game) -> io::Result<Self> {
+        let mut file = LineWriter::new(File::create(path)?);
+        writeln!(file, "game {}", game.game_type())?;
+        writeln!(file, "solution {}", game.state().solution())?;
+        let rules = Rules::of(game);
+        for (on, rule) in [
+            (rules.hard, "hard"),
+            (rules.ultra_hard, "ultra-hard"),
+            (rules.no_repeats, "no-repeats"),
+            (rules.ignore_accents, "ignore-accents"),
+        ] {
+            if on {
+                writeln!(file, "{}", rule)?;
+            }
+        }
+        if let Some(max_guesses) = rules.max_guesses {
+            writeln!(file, "max-guesses {}", max_guesses)?;
+        }
+        for Guess(word, _) in game.state().guesses() {
+            writeln!(file, "guess {}", word)?;
+        }
+        Ok(Self {
+            file,
+            started: Instant::now(),
+        })
+    }
+
+    /// Keys that can't be played back, like function keys, are left out
+    pub(super) fn push(&mut self, key: KeyEvent) -> io::Result<()> {
+        match encode(key) {
+            Some(key) => writeln!(self.file, "{} {}", self.started.elapsed().as_millis(), key),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Types the recorded keys as if they were pressed, at `speed` times the pace they
+/// were recorded at. Once they run out, keys are read from the terminal, so the end
+/// screen can be left as usual
+pub struct Playback {
+    terminal: Terminal,
+    keys: VecDeque<(Duration, KeyEvent)>,
+    speed: f64,
+    started: Instant,
+}
+
+impl Playback {
+    /// Callers must keep the speed from 0.01 to 100, as --speed is, so the pauses
+    /// can be divided by it
+    pub fn new(keys: Vec<(Duration, KeyEvent)>, speed: f64) -> io::Result<Self> {
+        Ok(Self {
+            terminal: Terminal::new(false)?,
+            keys: keys.into(),
+            speed,
+            started: Instant::now(),
+        })
+    }
+
+    /// How long until the next key is due, if there's one left
+    fn next_due(&self) -> Option<Duration> {
+        let (at, _) = self.keys.front()?;
+        Some(
+            at.div_f64(self.speed)
+                .saturating_sub(self.started.elapsed()),
+        )
+    }
+}
+
+impl Write for Playback {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.terminal.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.terminal.flush()
+    }
+}
+
+impl Backend for Playback {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        self.terminal.size()
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        match self.next_due() {
+            Some(due) => {
+                thread::sleep(due);
+                let (_, key) = self.keys.pop_front().expect("a key is due");
+                Ok(Event::Key(key))
+            }
+            None => self.terminal.read(),
+        }
+    }
+
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        match self.next_due() {
+            Some(due) if due <= timeout => {
+                thread::sleep(due);
+                Ok(true)
+            }
+            Some(_) => {
+                thread::sleep(timeout);
+                Ok(false)
+            }
+            None => self.terminal.poll(timeout),
+        }
+    }
+}
+
+/// The key as a word, like `t`, `enter` or `ctrl-u`
+fn encode(key: KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(' ') => "space".to_owned(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "enter".to_owned(),
+        KeyCode::Backspace => "backspace".to_owned(),
+        KeyCode::Esc => "esc".to_owned(),
+        KeyCode::Tab => "tab".to_owned(),
+        KeyCode::Up => "up".to_owned(),
+        KeyCode::Down => "down".to_owned(),
+        _ => return None,
+    };
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        Some(format!("ctrl-{}", name))
+    } else {
+        Some(name)
+    }
+}
+
+fn decode(name: &str) -> Option<KeyEvent> {
+    let (name, modifiers) = match name.strip_prefix("ctrl-") {
+        Some(name) => (name, KeyModifiers::CONTROL),
+        None => (name, KeyModifiers::NONE),
+    };
+    let code = match name {
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "backspace" => KeyCode::Backspace,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        name => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use cl_wordle::prelude::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use test_case::test_case;
+
+    use super::{decode, encode, Recording, Replay, Rules};
+
+    #[test_case(KeyCode::Char('t'), KeyModifiers::NONE, "t")]
+    #[test_case(KeyCode::Enter, KeyModifiers::NONE, "enter")]
+    #[test_case(KeyCode::Char('u'), KeyModifiers::CONTROL, "ctrl-u")]
+    #[test_case(KeyCode::Char('-'), KeyModifiers::CONTROL, "ctrl--")]
+    fn keys_round_trip(code: KeyCode, modifiers: KeyModifiers, name: &str) {
+        let key = KeyEvent::new(code, modifiers);
+        assert_eq!(encode(key).as_deref(), Some(name));
+        assert_eq!(decode(name), Some(key));
+    }
+
+    #[test]
+    fn parses_a_replay() {
+        let replay =
+            Replay::parse("game practice 3\nsolution termo\nguess sagaz\n812 t\n2210 enter\n")
+                .unwrap();
+        assert_eq!(replay.game_type, GameType::Practice { seed: 3 });
+        assert_eq!(replay.solution, "termo");
+        assert_eq!(replay.guesses, ["sagaz"]);
+        assert_eq!(
+            replay.keys,
+            [
+                (
+                    Duration::from_millis(812),
+                    KeyEvent::from(KeyCode::Char('t'))
+                ),
+                (Duration::from_millis(2210), KeyEvent::from(KeyCode::Enter)),
+            ]
+        );
+        assert!(!replay.rules.hard && replay.rules.max_guesses.is_none());
+        assert!(Replay::parse("812 t").is_err());
+        assert!(Replay::parse("game custom\nsolution termo\n812 f1").is_err());
+    }
+
+    #[test]
+    fn replays_keep_the_rules() {
+        let game = Game::custom("termo".to_owned())
+            .unwrap()
+            .with_hard_mode(true)
+            .allow_repeated_guesses(false)
+            .with_max_guesses(8)
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("wordle-replay-{}", std::process::id()));
+        Recording::create(&path, &game).unwrap();
+        let replay = Replay::parse(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            replay.rules,
+            Rules {
+                hard: true,
+                ultra_hard: false,
+                no_repeats: true,
+                ignore_accents: false,
+                max_guesses: Some(8),
+            }
+        );
+
+        // played back without the flags, the guesses fail the same way
+        let mut game = replay
+            .rules
+            .apply(Game::custom("termo".to_owned()).unwrap())
+            .unwrap();
+        assert_eq!(game.max_guesses(), 8);
+        game.guess("tempo").unwrap();
+        assert!(game.guess("sagaz").is_err());
+        assert!(Replay::parse("game custom\nsolution termo\nmax-guesses 0").is_err());
+    }
+}
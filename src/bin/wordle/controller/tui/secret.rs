@@ -0,0 +1,107 @@
+//! Typing in a word for someone else to guess, without it showing on screen.
+
+use std::io;
+
+use cl_wordle::game::GameInitError;
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, KeyModifiers},
+    execute,
+    terminal::{Clear, ClearType},
+};
+
+use super::terminal::{Backend, Terminal};
+
+/// Reads a word on the terminal, as in [`read`], then puts the terminal back
+pub fn ask<T>(prompt: &str, check: impl FnMut(&str) -> Result<T, String>) -> io::Result<Option<T>> {
+    read(&mut Terminal::new(false)?, prompt, check)
+}
+
+/// Reads a word with a dot shown for each letter, until `check` accepts it, saying
+/// why each word it turned down was. Returns `None` if Esc is pressed instead
+pub fn read<B: Backend, T>(
+    terminal: &mut B,
+    prompt: &str,
+    mut check: impl FnMut(&str) -> Result<T, String>,
+) -> io::Result<Option<T>> {
+    let mut problem = String::new();
+    let mut word = String::new();
+    loop {
+        let masked = "•".repeat(word.chars().count());
+        show(terminal, &[prompt, "", &masked, "", &problem])?;
+        let key = match terminal.read()? {
+            Event::Key(key) => key,
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(None),
+            KeyCode::Enter => match check(&word) {
+                Ok(checked) => return Ok(Some(checked)),
+                Err(err) => {
+                    problem = err;
+                    word.clear();
+                }
+            },
+            KeyCode::Backspace => {
+                word.pop();
+            }
+            KeyCode::Char(c) if c.is_alphabetic() => word.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Why a word typed in can't be played, without repeating the word itself
+pub fn rejection(err: &GameInitError) -> String {
+    match err {
+        GameInitError::InvalidSolution(_) => "That word isn't in the word lists".to_owned(),
+        GameInitError::MalformedSolution(_) => "Only letters can be used".to_owned(),
+        GameInitError::NoWordList(len) => format!("There are no {} letter words to play", len),
+        err => format!("That word can't be played: {}", err),
+    }
+}
+
+/// Clears the screen for the lines, one below the other from the top
+pub(super) fn show<B: Backend>(terminal: &mut B, lines: &[&str]) -> io::Result<()> {
+    execute!(
+        terminal,
+        cursor::Hide,
+        cursor::MoveTo(0, 0),
+        Clear(ClearType::All),
+    )?;
+    for (row, line) in lines.iter().enumerate() {
+        write!(terminal, "{}{}", cursor::MoveTo(0, row as u16), line)?;
+    }
+    terminal.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::game::Game;
+
+    use super::{read, rejection};
+    use crate::controller::tui::tests::Scripted;
+
+    fn custom(keys: &str) -> Option<Game> {
+        let check = |word: &str| Game::custom(word.to_owned()).map_err(|err| rejection(&err));
+        read(&mut Scripted::new(keys), "word", check).unwrap()
+    }
+
+    #[test]
+    fn words_are_asked_for_until_one_can_be_played() {
+        let game = custom("xyzzy\ntermos\nterr\x08mo\n").unwrap();
+        assert_eq!(game.state().solution(), "termo");
+        assert!(custom("ter\x1b").is_none());
+    }
+
+    #[test]
+    fn rejections_don_t_give_the_word_away() {
+        for word in ["xyzzy", "termos", "t3rmo"] {
+            match Game::custom(word.to_owned()) {
+                Err(err) => assert!(!rejection(&err).contains(word)),
+                Ok(_) => panic!("{} can't be played", word),
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::io::{self, BufRead, Write};
+
+use cl_wordle::prelude::*;
+use eyre::{bail, Result};
+
+use super::{
+    keyboard::{Keyboard, Layout},
+    layout::Cells,
+    letters::{LetterMatch, Palette, WordMatch},
+    KEYBOARD_GAP,
+};
+
+/// Plays a list of guesses and prints the final board, without any interaction
+pub struct Controller {
+    game: Game,
+    keyboard: Keyboard,
+    skip_invalid: bool,
+    cells: Cells,
+    palette: Palette,
+}
+
+impl Controller {
+    pub fn new(
+        game: Game,
+        skip_invalid: bool,
+        cells: Cells,
+        palette: Palette,
+        layout: Layout,
+    ) -> Self {
+        Self {
+            cells: cells.fit(game.state().solution()),
+            game,
+            keyboard: Keyboard::new(palette).with_layout(layout),
+            skip_invalid,
+            palette,
+        }
+    }
+
+    /// Reads one guess per line from the input, then prints the board to stdout
+    pub fn run(mut self, input: impl BufRead) -> Result<()> {
+        let mut over = None;
+        for line in input.lines() {
+            let line = line?;
+            let word = line.trim().to_ascii_lowercase();
+            if word.is_empty() {
+                continue;
+            }
+            if over.is_some() {
+                bail!("game was already over before guess '{}'", word);
+            }
+
+            match self.game.guess(&word) {
+                Ok(matches) => self.keyboard.push(&word, matches),
+                Err(_) if self.skip_invalid => continue,
+                Err(_) => bail!("invalid guess '{}'", word),
+            }
+            over = self.game.game_over();
+        }
+
+        self.write_board(io::stdout().lock())?;
+        Ok(())
+    }
+
+    fn write_board(&self, mut w: impl Write) -> io::Result<()> {
+        let state = self.game.state();
+        writeln!(w, "Termo {}", self.game.game_type())?;
+        writeln!(w)?;
+
+        let mut rows = state.guesses();
+        let mut solution = matches!(state.game_over(), Some(false)).then(|| state.solution());
+        let lines = (state.guesses().len() + solution.is_some() as usize).max(self.keyboard.rows());
+        for i in 0..lines {
+            if let Some(Guess(word, Matches(m))) = rows.next() {
+                let letters = word
+                    .chars()
+                    .zip(m)
+                    .map(|(c, m)| LetterMatch(c, m, self.palette));
+                write!(w, "{}", self.cells.row(letters))?;
+            } else if let Some(solution) = solution.take() {
+                write!(
+                    w,
+                    "{}",
+                    WordMatch(solution, Match::Exact, self.cells, self.palette)
+                )?;
+            } else {
+                write!(
+                    w,
+                    "{:1$}",
+                    "",
+                    self.cells.width(self.game.word_len() as u16) as usize
+                )?;
+            }
+
+            if i < self.keyboard.rows() {
+                let gap = KEYBOARD_GAP as usize;
+                write!(w, "{:gap$}{}", "", self.keyboard.row(i))?;
+            }
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,286 @@
+//! A terminal on the other end of any reader and writer, such as a telnet client
+//! connected to `wordle serve`.
+//!
+//! The bytes read are turned into key presses the way a terminal in raw mode sends
+//! them. Telnet commands in among them are skipped, apart from the window size,
+//! which is taken as a resize.
+
+use std::{
+    collections::VecDeque,
+    io::{self, Read, Write},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{Event, KeyCode, KeyEvent, KeyModifiers},
+    execute, style, terminal,
+};
+
+use super::terminal::Backend;
+
+/// Telnet's "interpret as command", which starts every command
+const IAC: u8 = 255;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const WILL: u8 = 251;
+const DO: u8 = 253;
+const DONT: u8 = 254;
+const ECHO: u8 = 1;
+const SUPPRESS_GO_AHEAD: u8 = 3;
+/// The option for the client to send its window size
+const NAWS: u8 = 31;
+
+/// Asks a telnet client to send each key as it's pressed, leaving the echo to the
+/// server, and to say how big its window is
+pub const TELNET_SETUP: [u8; 9] = [IAC, WILL, ECHO, IAC, WILL, SUPPRESS_GO_AHEAD, IAC, DO, NAWS];
+
+/// The size assumed until the other end says otherwise
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Drawn on by writing to `output`, with the keys read from `input` on a thread of
+/// their own so they can be waited on with a timeout
+pub struct Stream<W: Write> {
+    output: W,
+    chunks: mpsc::Receiver<Vec<u8>>,
+    /// Bytes read that don't make up a whole key yet
+    pending: Vec<u8>,
+    events: VecDeque<Event>,
+    size: (u16, u16),
+}
+
+impl<W: Write> Stream<W> {
+    pub fn new(mut input: impl Read + Send + 'static, mut output: W) -> io::Result<Self> {
+        let (sender, chunks) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buf = [0; 256];
+            // a closed connection ends the thread, which the stream reads as the end
+            while let Ok(n @ 1..) = input.read(&mut buf) {
+                if sender.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+        execute!(output, terminal::EnterAlternateScreen)?;
+        Ok(Self {
+            output,
+            chunks,
+            pending: Vec::new(),
+            events: VecDeque::new(),
+            size: DEFAULT_SIZE,
+        })
+    }
+
+    /// Takes in a chunk of bytes, decoding every key they finish
+    fn push(&mut self, chunk: &[u8]) {
+        self.pending.extend_from_slice(chunk);
+        let mut start = 0;
+        while let Some((used, event)) = decode(&self.pending[start..]) {
+            start += used;
+            if let Some(Event::Resize(columns, rows)) = event {
+                self.size = (columns, rows);
+            }
+            self.events.extend(event);
+        }
+        self.pending.drain(..start);
+    }
+}
+
+impl<W: Write> Write for Stream<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Backend for Stream<W> {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.size)
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(event);
+            }
+            match self.chunks.recv() {
+                Ok(chunk) => self.push(&chunk),
+                Err(_) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            }
+        }
+    }
+
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        let deadline = Instant::now() + timeout;
+        while self.events.is_empty() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            match self.chunks.recv_timeout(left) {
+                Ok(chunk) => self.push(&chunk),
+                Err(mpsc::RecvTimeoutError::Timeout) => return Ok(false),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(io::ErrorKind::UnexpectedEof.into())
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Puts the other end's terminal back as it was, if it's still there to see it
+impl<W: Write> Drop for Stream<W> {
+    fn drop(&mut self) {
+        let _ = execute!(
+            self.output,
+            style::ResetColor,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+    }
+}
+
+/// Decodes the first key at the start of the bytes, returning how many bytes it took
+/// and the event, if it was one the game uses. Returns `None` if the bytes stop
+/// part way through
+fn decode(bytes: &[u8]) -> Option<(usize, Option<Event>)> {
+    let key = |code| Some(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+    let (&first, rest) = bytes.split_first()?;
+    Some(match first {
+        IAC => return telnet(rest).map(|(used, event)| (used + 1, event)),
+        // a telnet client sends enter as \r\n or \r\0
+        b'\r' => match rest.first() {
+            Some(b'\n' | b'\0') => (2, key(KeyCode::Enter)),
+            _ => (1, key(KeyCode::Enter)),
+        },
+        b'\n' => (1, key(KeyCode::Enter)),
+        b'\t' => (1, key(KeyCode::Tab)),
+        0x7f | 0x08 => (1, key(KeyCode::Backspace)),
+        0x1b => match rest.first() {
+            Some(b'[' | b'O') => {
+                // a control sequence runs up to a byte from @ to ~
+                let end = rest[1..].iter().position(|b| (0x40..=0x7e).contains(b))?;
+                let code = match &rest[1..end + 2] {
+                    b"A" => Some(KeyCode::Up),
+                    b"B" => Some(KeyCode::Down),
+                    b"C" => Some(KeyCode::Right),
+                    b"D" => Some(KeyCode::Left),
+                    b"H" | b"1~" => Some(KeyCode::Home),
+                    b"F" | b"4~" => Some(KeyCode::End),
+                    b"3~" => Some(KeyCode::Delete),
+                    _ => None,
+                };
+                (end + 3, code.and_then(key))
+            }
+            // on its own, it's the key itself
+            _ => (1, key(KeyCode::Esc)),
+        },
+        ctrl @ 0x01..=0x1a => {
+            let letter = char::from(b'a' + ctrl - 1);
+            let key = KeyEvent::new(KeyCode::Char(letter), KeyModifiers::CONTROL);
+            (1, Some(Event::Key(key)))
+        }
+        _ => {
+            let len = match first {
+                0xf0.. => 4,
+                0xe0.. => 3,
+                0xc0.. => 2,
+                _ => 1,
+            };
+            let c = std::str::from_utf8(bytes.get(..len)?)
+                .ok()
+                .and_then(|s| s.chars().next());
+            (
+                len,
+                c.filter(|c| !c.is_control())
+                    .and_then(|c| key(KeyCode::Char(c))),
+            )
+        }
+    })
+}
+
+/// Decodes a telnet command, after the [`IAC`] that started it. The window size is a
+/// resize, and the rest are skipped
+fn telnet(bytes: &[u8]) -> Option<(usize, Option<Event>)> {
+    let (&command, rest) = bytes.split_first()?;
+    Some(match command {
+        SB => {
+            let end = rest.windows(2).position(|pair| pair == [IAC, SE])?;
+            let event = match rest[..end] {
+                [NAWS, w1, w0, h1, h0] => Some(Event::Resize(
+                    u16::from_be_bytes([w1, w0]),
+                    u16::from_be_bytes([h1, h0]),
+                )),
+                _ => None,
+            };
+            (end + 3, event)
+        }
+        // agreeing to an option, or not, is followed by the option
+        WILL..=DONT => {
+            rest.first()?;
+            (2, None)
+        }
+        _ => (1, None),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{decode, IAC, NAWS, SB, SE, WILL};
+
+    fn decode_all(mut bytes: &[u8]) -> Vec<Event> {
+        let mut events = Vec::new();
+        while let Some((used, event)) = decode(bytes) {
+            events.extend(event);
+            bytes = &bytes[used..];
+        }
+        assert!(bytes.is_empty(), "left over: {:?}", bytes);
+        events
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn keys_are_decoded() {
+        assert_eq!(
+            decode_all("té\r\0\x7f\x1b[D\x1b\x03".as_bytes()),
+            [
+                key(KeyCode::Char('t')),
+                key(KeyCode::Char('é')),
+                key(KeyCode::Enter),
+                key(KeyCode::Backspace),
+                key(KeyCode::Left),
+                key(KeyCode::Esc),
+                Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            ]
+        );
+    }
+
+    #[test]
+    fn telnet_commands_are_skipped_but_the_size_is_kept() {
+        let bytes = [
+            &[IAC, WILL, NAWS][..],
+            &[IAC, SB, NAWS, 0, 100, 0, 30, IAC, SE],
+            b"a",
+        ]
+        .concat();
+        assert_eq!(
+            decode_all(&bytes),
+            [Event::Resize(100, 30), key(KeyCode::Char('a'))]
+        );
+    }
+
+    #[test]
+    fn unfinished_keys_wait_for_the_rest() {
+        assert_eq!(decode(&"é".as_bytes()[..1]), None);
+        assert_eq!(decode(b"\x1b[3"), None);
+        assert_eq!(decode(&[IAC, SB, NAWS, 0]), None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use cl_wordle::game::Score;
+use crossterm::{
+    cursor,
+    terminal::{Clear, ClearType},
+};
+
+/// How the game went, and the keys for what can be done next, with its top-left
+/// corner at the given cell. It's drawn where the keyboard was once the game is over.
+///
+/// Every one of `height` lines is cleared, so whatever was there before doesn't
+/// show past the last line
+pub struct SummaryPanel<'a> {
+    pub score: Score,
+    /// Guesses the game allowed, or `None` if they were unlimited
+    pub max_guesses: Option<usize>,
+    pub solution: &'a str,
+    /// The current and best streaks of daily wins, counting this game
+    pub streak: Option<(usize, usize)>,
+    /// Seconds until the next daily, for a daily game
+    pub next_daily: Option<i64>,
+    /// Each key that does something, and what it does
+    pub keys: &'a [(&'a str, &'a str)],
+    pub height: u16,
+    pub column: u16,
+    pub row: u16,
+}
+
+impl<'a> SummaryPanel<'a> {
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            match (self.score, self.max_guesses) {
+                (Score::Guesses(n), Some(max)) => format!("Solved in {}/{}", n, max),
+                (Score::Guesses(n), None) => format!("Solved in {}", n),
+                (Score::Failed, _) => "Not solved".to_owned(),
+            },
+            format!("The word was {}", self.solution.to_uppercase()),
+        ];
+        if let Some((current, best)) = self.streak {
+            lines.push(format!("Streak {}, best {}", current, best));
+        }
+        if let Some(left) = self.next_daily {
+            lines.push(format!(
+                "Next Termo in {}:{:02}:{:02}",
+                left / 3600,
+                left / 60 % 60,
+                left % 60
+            ));
+        }
+        lines.push(String::new());
+        let keys = self
+            .keys
+            .iter()
+            .map(|(key, action)| format!("{:<6}{}", key, action));
+        lines.extend(keys);
+        lines
+    }
+}
+
+impl<'a> fmt::Display for SummaryPanel<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", cursor::SavePosition)?;
+        let lines = self.lines();
+        for i in 0..self.height.max(lines.len() as u16) {
+            let start = cursor::MoveTo(self.column, self.row + i);
+            let line = lines.get(i as usize).map_or("", String::as_str);
+            write!(f, "{}{}{}", start, Clear(ClearType::UntilNewLine), line)?;
+        }
+        write!(f, "{}", cursor::RestorePosition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::game::Score;
+
+    use super::SummaryPanel;
+
+    #[test]
+    fn lists_the_result_then_the_keys() {
+        let panel = SummaryPanel {
+            score: Score::Guesses(3),
+            max_guesses: Some(6),
+            solution: "termo",
+            streak: Some((4, 7)),
+            next_daily: Some(3 * 3600 + 5 * 60 + 9),
+            keys: &[("c", "copy the result"), ("enter", "exit")],
+            height: 0,
+            column: 0,
+            row: 0,
+        };
+        assert_eq!(
+            panel.lines(),
+            [
+                "Solved in 3/6",
+                "The word was TERMO",
+                "Streak 4, best 7",
+                "Next Termo in 3:05:09",
+                "",
+                "c     copy the result",
+                "enter exit",
+            ]
+        );
+    }
+}
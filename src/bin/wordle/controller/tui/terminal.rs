@@ -1,37 +1,144 @@
 use std::{
-    io::{self, stdout, Stdout},
-    ops::{Deref, DerefMut},
+    io::{self, stderr, stdout, Write},
+    time::Duration,
 };
 
-use crossterm::{cursor, execute, style, terminal};
+use crossterm::{
+    cursor,
+    event::{self, Event},
+    execute, style, terminal,
+    tty::IsTty,
+};
 
-pub struct Terminal(Stdout);
-impl Deref for Terminal {
-    type Target = Stdout;
+use self::screen::Screen;
+use super::replay::Recording;
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
+pub(super) mod screen;
+
+/// Everything the controller needs from the terminal.
+///
+/// Drawing is done by writing crossterm commands, and any setup, such as raw mode,
+/// is done when the backend is created and undone when it's dropped
+pub trait Backend: Write {
+    /// Number of columns and rows
+    fn size(&self) -> io::Result<(u16, u16)>;
+    /// Waits for the next input event
+    fn read(&mut self) -> io::Result<Event>;
+    /// Waits up to `timeout` for an input event, returning whether one is ready
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
 }
-impl DerefMut for Terminal {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+
+/// Borrowed, so one terminal can be played on by one controller after another
+impl<B: Backend + ?Sized> Backend for &mut B {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        (**self).size()
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        (**self).read()
+    }
+
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        (**self).poll(timeout)
     }
 }
 
+/// The real terminal, in raw mode on the alternate screen.
+///
+/// What's written is kept until it's flushed, then only the cells that look
+/// different from what's on screen are sent, so clearing the screen to draw it all
+/// again doesn't flicker, even over a slow connection
+pub struct Terminal {
+    /// Stdout, or stderr if stdout is piped, so only the result printed after the
+    /// game ends up in the pipe
+    output: Box<dyn Write>,
+    screen: Screen,
+    /// Whether mouse events are captured, which stops the terminal from selecting text
+    mouse: bool,
+    /// Where each key read is written, to play the game back later
+    recording: Option<Recording>,
+}
+
 impl Terminal {
-    pub fn new() -> io::Result<Self> {
-        let mut stdout = stdout();
-        execute!(stdout, terminal::EnterAlternateScreen)?;
+    pub fn new(mouse: bool) -> io::Result<Self> {
+        let mut output: Box<dyn Write> = if stdout().is_tty() {
+            Box::new(stdout())
+        } else {
+            Box::new(stderr())
+        };
+        execute!(output, terminal::EnterAlternateScreen)?;
+        if mouse {
+            execute!(output, event::EnableMouseCapture)?;
+        }
         terminal::enable_raw_mode()?;
-        Ok(Self(stdout))
+        Ok(Self {
+            output,
+            screen: Screen::default(),
+            mouse,
+            recording: None,
+        })
+    }
+
+    pub fn record_to(&mut self, recording: Recording) {
+        self.recording = Some(recording);
+    }
+}
+
+/// Why the board can't be drawn here, if it can't: the terminal is too dumb for
+/// escape codes, there's no terminal to draw on, or it can't be put in raw mode
+pub fn unusable() -> Option<String> {
+    if std::env::var_os("TERM").is_some_and(|term| term == "dumb") {
+        return Some("TERM is dumb".to_owned());
+    }
+    if !(stdout().is_tty() || stderr().is_tty()) {
+        return Some("there's no terminal to draw on".to_owned());
+    }
+    match terminal::enable_raw_mode().and_then(|()| terminal::disable_raw_mode()) {
+        Ok(()) => None,
+        Err(err) => Some(format!("the terminal can't be put in raw mode: {}", err)),
+    }
+}
+
+impl Write for Terminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.screen.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let changes = self.screen.render(terminal::size()?);
+        self.output.write_all(changes.as_bytes())?;
+        self.output.flush()
+    }
+}
+
+impl Backend for Terminal {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        let event = event::read()?;
+        if let (Some(recording), Event::Key(key)) = (&mut self.recording, &event) {
+            recording.push(*key)?;
+        }
+        Ok(event)
+    }
+
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
     }
 }
 
+/// Undone before [`Controller::run`](super::Controller::run) returns, so the
+/// terminal is back to normal by the time the result is printed
 impl Drop for Terminal {
     fn drop(&mut self) {
+        if self.mouse {
+            execute!(self.output, event::DisableMouseCapture).unwrap();
+        }
         execute!(
-            self.0,
+            self.output,
             style::ResetColor,
             cursor::Show,
             terminal::LeaveAlternateScreen
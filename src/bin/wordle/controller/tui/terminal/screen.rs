@@ -0,0 +1,535 @@
+//! A copy of the screen kept as cells, so redrawing it only sends the cells that
+//! changed.
+//!
+//! Everything written is read back as the escape codes crossterm and owo-colors
+//! draw with: cursor moves, clears and colours land on the cells they'd have drawn,
+//! and on a flush only the cells that differ from what the terminal shows are
+//! written out. A full redraw that clears the screen first then costs no more than
+//! the few cells it actually changed, and doesn't flicker.
+
+use std::{fmt::Write, mem};
+
+use crossterm::cursor;
+use unicode_width::UnicodeWidthChar;
+
+/// Stands in for the right half of a character two columns wide
+const WIDE: char = '\0';
+
+/// Most unchanged cells written out again to get from one changed cell to the next,
+/// rather than moving the cursor, which takes at least as many bytes
+const SKIP: u16 = 4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Color {
+    #[default]
+    Default,
+    /// One of the 16 named colours, with the bright ones from 8 up
+    Ansi(u8),
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Writes the colour as the parameters of a foreground colour, or of a background
+    /// colour with `base` 40
+    fn write(self, base: u8, out: &mut String) {
+        // the bright colours are 60 on from their plain ones
+        match self {
+            Color::Default => write!(out, ";{}", base + 9),
+            Color::Ansi(n @ 0..=7) => write!(out, ";{}", base + n),
+            Color::Ansi(n) => write!(out, ";{}", base + 60 + n - 8),
+            Color::Indexed(n) => write!(out, ";{};5;{}", base + 8, n),
+            Color::Rgb(r, g, b) => write!(out, ";{};2;{};{};{}", base + 8, r, g, b),
+        }
+        .unwrap();
+    }
+}
+
+/// How a cell is drawn, as set by SGR codes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    fg: Color,
+    bg: Color,
+    /// Bold, dim, italic and the rest, each as the bit of its SGR code from 1 to 9
+    attributes: u16,
+}
+
+impl Style {
+    /// Applies an SGR sequence, given by its parameters
+    fn apply(&mut self, params: &str) {
+        let mut params = params.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Style::default(),
+                1..=9 => self.attributes |= 1 << param,
+                // 22 turns off both bold and dim
+                22 => self.attributes &= !(1 << 1 | 1 << 2),
+                23..=29 => self.attributes &= !(1 << (param - 20)),
+                30..=37 => self.fg = Color::Ansi(param - 30),
+                38 => self.fg = extended(&mut params),
+                39 => self.fg = Color::Default,
+                40..=47 => self.bg = Color::Ansi(param - 40),
+                48 => self.bg = extended(&mut params),
+                49 => self.bg = Color::Default,
+                90..=97 => self.fg = Color::Ansi(param - 90 + 8),
+                100..=107 => self.bg = Color::Ansi(param - 100 + 8),
+                _ => {}
+            }
+        }
+    }
+
+    /// The SGR sequence that draws in this style, whatever was drawn in before
+    fn write(self, out: &mut String) {
+        out.push_str("\x1b[0");
+        for bit in (1..=9).filter(|bit| self.attributes & 1 << bit != 0) {
+            write!(out, ";{}", bit).unwrap();
+        }
+        if self.fg != Color::Default {
+            self.fg.write(30, out);
+        }
+        if self.bg != Color::Default {
+            self.bg.write(40, out);
+        }
+        out.push('m');
+    }
+}
+
+/// The colour of a `38` or `48` code, from the parameters after it
+fn extended(params: &mut impl Iterator<Item = u8>) -> Color {
+    match params.next() {
+        Some(5) => Color::Indexed(params.next().unwrap_or(0)),
+        Some(2) => {
+            let mut next = || params.next().unwrap_or(0);
+            Color::Rgb(next(), next(), next())
+        }
+        _ => Color::Default,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cell {
+    c: char,
+    style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            c: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Screen {
+    /// Columns and rows
+    size: (u16, u16),
+    /// The screen as it'll be after the next flush, row by row
+    cells: Vec<Cell>,
+    /// The screen as the terminal shows it. `None` before it's first drawn and after
+    /// it's resized, which draws it all again from a cleared screen
+    shown: Option<Vec<Cell>>,
+    cursor: (u16, u16),
+    /// Where the cursor was saved, to be restored to
+    saved: (u16, u16),
+    /// The style characters are written in
+    style: Style,
+    /// Whether the cursor is shown
+    hidden: bool,
+    /// The style, cursor position and visibility the terminal was left with by the
+    /// last flush. The position isn't known until the first
+    pen: Style,
+    pen_at: Option<(u16, u16)>,
+    pen_hidden: bool,
+    /// Written since the last flush, and not yet drawn on the cells
+    pending: Vec<u8>,
+    /// Escape codes that don't draw anything, such as setting the title, which are
+    /// passed on as they are
+    passthrough: String,
+}
+
+impl Screen {
+    pub fn write(&mut self, buf: &[u8]) {
+        self.pending.extend_from_slice(buf);
+    }
+
+    /// The characters drawn on a row, for tests to check what ended up where
+    #[cfg(test)]
+    pub fn line(&self, y: u16) -> String {
+        let width = self.size.0 as usize;
+        let row = self.cells.iter().skip(y as usize * width).take(width);
+        row.map(|cell| cell.c).filter(|&c| c != WIDE).collect()
+    }
+
+    #[cfg(test)]
+    pub fn cursor(&self) -> (u16, u16) {
+        self.cursor
+    }
+
+    /// Draws what's been written since the last call, and returns what to send the
+    /// terminal to show it: only the cells that changed, and where the cursor is
+    pub fn render(&mut self, size: (u16, u16)) -> String {
+        if size != self.size {
+            self.resize(size);
+        }
+        self.parse();
+
+        let mut out = mem::take(&mut self.passthrough);
+        let shown = match &mut self.shown {
+            Some(shown) => shown,
+            None => {
+                out.push_str("\x1b[0m\x1b[2J");
+                self.pen = Style::default();
+                self.shown.insert(vec![Cell::default(); self.cells.len()])
+            }
+        };
+        let width = self.size.0 as usize;
+        for (i, (&cell, was)) in self.cells.iter().zip(shown.iter_mut()).enumerate() {
+            // the right half of a wide character is drawn with its left half
+            if cell == *was || cell.c == WIDE {
+                *was = cell;
+                continue;
+            }
+            *was = cell;
+            let at = ((i % width) as u16, (i / width) as u16);
+            match self.pen_at {
+                Some(pen) if pen == at => {}
+                // a short gap of the same style is cheaper to write out again than to
+                // move over
+                Some((x, y)) if y == at.1 && x < at.0 && at.0 - x <= SKIP => {
+                    let gap = &self.cells[i - (at.0 - x) as usize..i];
+                    let pen = self.pen;
+                    if gap
+                        .iter()
+                        .all(|gap| gap.style == pen && gap.c.width() == Some(1))
+                    {
+                        out.extend(gap.iter().map(|gap| gap.c));
+                    } else {
+                        write!(out, "{}", cursor::MoveTo(at.0, at.1)).unwrap();
+                    }
+                }
+                _ => write!(out, "{}", cursor::MoveTo(at.0, at.1)).unwrap(),
+            }
+            if cell.style != self.pen {
+                cell.style.write(&mut out);
+                self.pen = cell.style;
+            }
+            out.push(cell.c);
+            let columns = cell.c.width().unwrap_or(1) as u16;
+            self.pen_at = Some((at.0 + columns, at.1));
+        }
+
+        let cursor = (
+            self.cursor.0.min(self.size.0.saturating_sub(1)),
+            self.cursor.1.min(self.size.1.saturating_sub(1)),
+        );
+        if self.pen_at != Some(cursor) {
+            write!(out, "{}", cursor::MoveTo(cursor.0, cursor.1)).unwrap();
+            self.pen_at = Some(cursor);
+        }
+        if self.hidden != self.pen_hidden {
+            match self.hidden {
+                true => write!(out, "{}", cursor::Hide),
+                false => write!(out, "{}", cursor::Show),
+            }
+            .unwrap();
+            self.pen_hidden = self.hidden;
+        }
+        out
+    }
+
+    /// Keeps what fits of the screen at its new size, to be drawn again from scratch
+    fn resize(&mut self, size: (u16, u16)) {
+        let (width, height) = (size.0 as usize, size.1 as usize);
+        let mut cells = vec![Cell::default(); width * height];
+        let old = self.size.0 as usize;
+        if old > 0 {
+            for (row, old) in cells.chunks_mut(width.max(1)).zip(self.cells.chunks(old)) {
+                let kept = width.min(old.len());
+                row[..kept].copy_from_slice(&old[..kept]);
+                // a wide character cut in half by the new edge goes altogether
+                if kept > 0 && kept < old.len() && old[kept].c == WIDE {
+                    row[kept - 1] = Cell::default();
+                }
+            }
+        }
+        self.cells = cells;
+        self.size = size;
+        self.shown = None;
+        self.pen_at = None;
+    }
+
+    /// Draws every complete character and escape code written so far, leaving any
+    /// that was cut off to be finished by the next write
+    fn parse(&mut self) {
+        let pending = mem::take(&mut self.pending);
+        let (text, rest) = match std::str::from_utf8(&pending) {
+            Ok(text) => (text, &[][..]),
+            Err(err) => {
+                let (valid, rest) = pending.split_at(err.valid_up_to());
+                // bytes that can never be valid are dropped, not waited on forever
+                let rest = match err.error_len() {
+                    Some(len) => &rest[len..],
+                    None => rest,
+                };
+                (std::str::from_utf8(valid).unwrap(), rest)
+            }
+        };
+        let done = self.draw(text);
+        let mut left = text.as_bytes()[done..].to_vec();
+        left.extend_from_slice(rest);
+        self.pending = left;
+    }
+
+    /// Draws the text, returning how much of it was drawn. Only an escape code cut
+    /// off at the end is left
+    fn draw(&mut self, text: &str) -> usize {
+        let mut chars = text.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            match c {
+                '\x1b' => match chars.next() {
+                    Some((_, '[')) => {
+                        let mut params = String::new();
+                        let end = loop {
+                            match chars.next() {
+                                Some((_, c @ '\x40'..='\x7e')) => break c,
+                                Some((_, c)) => params.push(c),
+                                None => return start,
+                            }
+                        };
+                        if !self.csi(&params, end) {
+                            let (i, _) = chars.peek().copied().unwrap_or((text.len(), ' '));
+                            self.passthrough.push_str(&text[start..i]);
+                        }
+                    }
+                    Some((_, '7')) => self.saved = self.cursor,
+                    Some((_, '8')) => self.cursor = self.saved,
+                    // an OSC, such as a title, runs until a bell or ESC \
+                    Some((_, ']')) => loop {
+                        match chars.next() {
+                            Some((i, '\x07')) => {
+                                self.passthrough.push_str(&text[start..=i]);
+                                break;
+                            }
+                            Some((_, '\x1b')) => {
+                                if let Some((i, _)) = chars.next() {
+                                    self.passthrough.push_str(&text[start..=i]);
+                                    break;
+                                }
+                                return start;
+                            }
+                            Some(_) => {}
+                            None => return start,
+                        }
+                    },
+                    Some((i, c)) => self.passthrough.push_str(&text[start..i + c.len_utf8()]),
+                    None => return start,
+                },
+                '\n' => self.cursor.1 = (self.cursor.1 + 1).min(self.size.1.saturating_sub(1)),
+                '\r' => self.cursor.0 = 0,
+                '\x08' => self.cursor.0 = self.cursor.0.saturating_sub(1),
+                '\t' => self.cursor.0 = (self.cursor.0 / 8 + 1).saturating_mul(8),
+                c if c.is_control() => {}
+                c => self.put(c),
+            }
+        }
+        text.len()
+    }
+
+    /// Handles a CSI code, returning false if it's one that doesn't draw anything
+    fn csi(&mut self, params: &str, end: char) -> bool {
+        if let Some(mode) = params.strip_prefix('?') {
+            return match (mode, end) {
+                ("25", 'l') => {
+                    self.hidden = true;
+                    true
+                }
+                ("25", 'h') => {
+                    self.hidden = false;
+                    true
+                }
+                _ => false,
+            };
+        }
+        let numbers = params.split(';').map(|p| p.parse::<u16>().unwrap_or(0));
+        let mut numbers = numbers.collect::<Vec<_>>();
+        numbers.resize(2, 0);
+        // most codes take 0 to mean the same as 1
+        let n = numbers[0].max(1);
+        let (x, y) = &mut self.cursor;
+        match end {
+            'H' | 'f' => (*x, *y) = (numbers[1].max(1) - 1, n - 1),
+            'A' => *y = y.saturating_sub(n),
+            'B' => *y = y.saturating_add(n),
+            'C' => *x = x.saturating_add(n),
+            'D' => *x = x.saturating_sub(n),
+            'E' => (*x, *y) = (0, y.saturating_add(n)),
+            'F' => (*x, *y) = (0, y.saturating_sub(n)),
+            'G' => *x = n - 1,
+            'd' => *y = n - 1,
+            'J' => {
+                let at = self.index(self.cursor);
+                let len = self.cells.len();
+                match numbers[0] {
+                    0 => self.clear(at, len),
+                    1 => self.clear(0, (at + 1).min(len)),
+                    _ => self.clear(0, len),
+                }
+            }
+            'K' => {
+                let width = self.size.0 as usize;
+                let line = self.index((0, self.cursor.1));
+                let at = self.index(self.cursor);
+                match numbers[0] {
+                    0 => self.clear(at, line + width),
+                    1 => self.clear(line, at + 1),
+                    _ => self.clear(line, line + width),
+                }
+            }
+            's' => self.saved = self.cursor,
+            'u' => self.cursor = self.saved,
+            'm' => self.style.apply(params),
+            _ => return false,
+        }
+        let (width, height) = self.size;
+        self.cursor.0 = self.cursor.0.min(width.saturating_sub(1));
+        self.cursor.1 = self.cursor.1.min(height.saturating_sub(1));
+        true
+    }
+
+    /// Index of the cell, keeping it on the screen
+    fn index(&self, (x, y): (u16, u16)) -> usize {
+        let (width, height) = (self.size.0 as usize, self.size.1 as usize);
+        (y as usize * width + x as usize).min(width * height)
+    }
+
+    fn clear(&mut self, from: usize, to: usize) {
+        let to = to.min(self.cells.len());
+        if from < to {
+            self.cells[from..to].fill(Cell::default());
+            self.fix_wide(from);
+            self.fix_wide(to);
+        }
+    }
+
+    /// Writes a character at the cursor, and moves past it. Characters that go past
+    /// the edge of the screen are cut off, rather than wrapping onto the next line
+    fn put(&mut self, c: char) {
+        let columns = match c.width() {
+            Some(0) | None => return,
+            Some(columns) => columns as u16,
+        };
+        let (x, y) = self.cursor;
+        self.cursor.0 = x.saturating_add(columns);
+        if y >= self.size.1 || x.saturating_add(columns) > self.size.0 {
+            return;
+        }
+        let i = self.index((x, y));
+        let cell = Cell {
+            c,
+            style: self.style,
+        };
+        self.cells[i] = cell;
+        if columns == 2 {
+            self.cells[i + 1] = Cell { c: WIDE, ..cell };
+        }
+        self.fix_wide(i);
+        self.fix_wide(i + columns as usize);
+    }
+
+    /// Blanks what's left of a wide character split at the cell, after the cell up
+    /// to it or from it was drawn over
+    fn fix_wide(&mut self, i: usize) {
+        match (i.checked_sub(1).map(|i| self.cells[i]), self.cells.get(i)) {
+            (Some(left), Some(right)) if right.c == WIDE && left.c.width() != Some(2) => {
+                self.cells[i] = Cell::default();
+            }
+            (Some(left), right)
+                if left.c.width() == Some(2) && right.map(|r| r.c) != Some(WIDE) =>
+            {
+                self.cells[i - 1] = Cell::default();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Screen;
+
+    fn render(screen: &mut Screen, text: &str) -> String {
+        screen.write(text.as_bytes());
+        screen.render((10, 3))
+    }
+
+    #[test]
+    fn only_changed_cells_are_sent() {
+        let mut screen = Screen::default();
+        let first = render(&mut screen, "\x1b[2J\x1b[1;1HTermo\x1b[3;1H");
+        assert!(first.starts_with("\x1b[0m\x1b[2J"));
+        assert!(first.contains("Termo"));
+
+        // clearing and drawing it all again changes nothing
+        assert_eq!(render(&mut screen, "\x1b[2J\x1b[1;1HTermo\x1b[3;1H"), "");
+        assert_eq!(
+            render(&mut screen, "\x1b[2J\x1b[1;1HTerma\x1b[3;1H"),
+            "\x1b[1;5Ha\x1b[3;1H"
+        );
+    }
+
+    #[test]
+    fn colours_are_compared_too() {
+        let mut screen = Screen::default();
+        render(&mut screen, "\x1b[42mT\x1b[49m");
+        assert_eq!(render(&mut screen, "\x1b[1;1H\x1b[42mT\x1b[49m"), "");
+        let changed = render(&mut screen, "\x1b[1;1H\x1b[43mT\x1b[0m");
+        assert_eq!(changed, "\x1b[1;1H\x1b[0;43mT");
+    }
+
+    #[test]
+    fn clears_and_cursor_moves_land_where_the_terminal_would_put_them() {
+        let mut screen = Screen::default();
+        render(
+            &mut screen,
+            "abcdef\x1b[1;4H\x1b[K\x1b7\x1b[2;2Hxy\x1b8\x1b[?25l",
+        );
+        let redrawn = render(
+            &mut screen,
+            "\x1b[2J\x1b[1;1Habc\x1b[2;2Hxy\x1b[1;4H\x1b[?25l",
+        );
+        assert_eq!(redrawn, "");
+    }
+
+    #[test]
+    fn wide_characters_take_two_cells() {
+        let mut screen = Screen::default();
+        render(&mut screen, "🟩a");
+        assert_eq!(screen.cursor, (3, 0));
+        // drawing over half of it blanks the other half
+        render(&mut screen, "\x1b[1;2Hb");
+        assert_eq!(screen.cells[0].c, ' ');
+        assert_eq!(screen.cells[1].c, 'b');
+    }
+
+    #[test]
+    fn text_past_the_edge_is_cut_off() {
+        let mut screen = Screen::default();
+        render(&mut screen, &"\t".repeat(10_000));
+        render(&mut screen, &"a".repeat(70_000));
+        assert_eq!(screen.cursor.0, u16::MAX);
+        let redrawn = render(&mut screen, "\x1b[1;1Hb");
+        assert_eq!(redrawn, "\x1b[1;1Hb");
+    }
+
+    #[test]
+    fn resizing_draws_everything_again() {
+        let mut screen = Screen::default();
+        render(&mut screen, "Termo");
+        screen.write(b"");
+        let resized = screen.render((20, 5));
+        assert!(resized.starts_with("\x1b[0m\x1b[2J"));
+        assert!(resized.contains("Termo"));
+    }
+}
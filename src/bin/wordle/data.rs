@@ -0,0 +1,105 @@
+//! Files kept between runs, such as training progress and stats
+//!
+//! Each profile keeps its own files in a directory of its own under `profiles`, and
+//! playing without a profile keeps them at the top, as before profiles existed.
+
+use std::{fs, io, path::PathBuf, sync::OnceLock};
+
+/// Set from --data-dir, in place of the usual data directory
+static DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Set from --profile
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Keeps the files in this directory instead. Only the first call has any effect
+pub fn set_dir(dir: PathBuf) {
+    let _ = DIR.set(dir);
+}
+
+/// Keeps the files apart from everyone else's, under the given name. Only the first
+/// call has any effect
+pub fn set_profile(name: String) -> eyre::Result<()> {
+    let valid = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    if name.is_empty() || !name.chars().all(valid) {
+        eyre::bail!(
+            "profile names can only have letters, numbers, - and _, not {:?}",
+            name
+        );
+    }
+    let _ = PROFILE.set(name);
+    Ok(())
+}
+
+/// Reads the saved file, or an empty string if nothing has been saved yet
+pub fn read(name: &str) -> String {
+    read_from(PROFILE.get().map(String::as_str), name)
+}
+
+/// Reads a file saved by the given profile, or without one if it's `None`
+pub fn read_from(profile: Option<&str>, name: &str) -> String {
+    path(profile, name)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default()
+}
+
+pub fn write(name: &str, contents: &str) -> io::Result<()> {
+    write_to(PROFILE.get().map(String::as_str), name, contents)
+}
+
+/// Reads a file shared by every profile, like the downloaded word lists
+pub fn read_shared(name: &str) -> String {
+    read_from(None, name)
+}
+
+/// Saves a file shared by every profile
+#[cfg(feature = "net")]
+pub fn write_shared(name: &str, contents: &str) -> io::Result<()> {
+    write_to(None, name, contents)
+}
+
+/// Deletes a file shared by every profile, if there is one
+#[cfg(feature = "net")]
+pub fn remove_shared(name: &str) -> io::Result<()> {
+    let path = path(None, name).ok_or_else(|| io::Error::other("no data directory"))?;
+    match fs::remove_file(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn write_to(profile: Option<&str>, name: &str, contents: &str) -> io::Result<()> {
+    let path = path(profile, name).ok_or_else(|| io::Error::other("no data directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Names of every profile that's saved something, sorted
+pub fn profiles() -> Vec<String> {
+    let entries = base()
+        .and_then(|base| fs::read_dir(base.join("profiles")).ok())
+        .into_iter()
+        .flatten()
+        .flatten();
+    let mut profiles = entries
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect::<Vec<_>>();
+    profiles.sort();
+    profiles
+}
+
+fn base() -> Option<PathBuf> {
+    match DIR.get() {
+        Some(dir) => Some(dir.clone()),
+        None => Some(dirs::data_dir()?.join("cl-wordle")),
+    }
+}
+
+fn path(profile: Option<&str>, name: &str) -> Option<PathBuf> {
+    let base = base()?;
+    Some(match profile {
+        Some(profile) => base.join("profiles").join(profile).join(name),
+        None => base.join(name),
+    })
+}
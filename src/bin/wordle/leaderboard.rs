@@ -0,0 +1,92 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use cl_wordle::stats::Stats;
+
+/// Someone's daily stats, to rank against everyone else's
+pub struct Player {
+    pub name: String,
+    pub stats: Stats,
+}
+
+/// Ranks the players by their current streak, then by the share of their games they've won
+/// and then by how few guesses their wins took, and lays them out in a table
+pub fn leaderboard(mut players: Vec<Player>) -> String {
+    players.sort_by(|a, b| {
+        let (a_stats, b_stats) = (&a.stats, &b.stats);
+        b_stats
+            .current_streak()
+            .cmp(&a_stats.current_streak())
+            .then(b_stats.win_percentage().cmp(&a_stats.win_percentage()))
+            .then_with(|| {
+                let infinite = |stats: &Stats| stats.average_guesses().unwrap_or(f64::INFINITY);
+                infinite(a_stats)
+                    .partial_cmp(&infinite(b_stats))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let width = players
+        .iter()
+        .map(|player| player.name.chars().count())
+        .chain(["name".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "    {:width$}  streak  best   won  guesses  played",
+        "name"
+    );
+    for (i, Player { name, stats }) in players.iter().enumerate() {
+        let average = stats
+            .average_guesses()
+            .map_or_else(|| "-".to_owned(), |average| format!("{:.2}", average));
+        let _ = writeln!(
+            out,
+            "{:>2}  {:width$}  {:>6}  {:>4}  {:>4}  {:>7}  {:>6}",
+            i + 1,
+            name,
+            stats.current_streak(),
+            stats.max_streak(),
+            format!("{}%", stats.win_percentage()),
+            average,
+            stats.played(),
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::stats::Stats;
+
+    use super::{leaderboard, Player};
+
+    #[test]
+    fn ranks_by_streak_then_wins_then_guesses() {
+        let player = |name: &str, games: &[Option<u8>]| {
+            let mut stats = Stats::default();
+            for (day, &guesses) in games.iter().enumerate() {
+                stats.record(day, guesses);
+            }
+            Player {
+                name: name.to_owned(),
+                stats,
+            }
+        };
+        let players = vec![
+            player("ana", &[Some(4), Some(4)]),
+            player("bia", &[None, Some(3), Some(3)]),
+            player("caio", &[Some(3), Some(3)]),
+            player("duda", &[]),
+        ];
+        let expected = "    name  streak  best   won  guesses  played
+ 1  caio       2     2  100%     3.00       2
+ 2  ana        2     2  100%     4.00       2
+ 3  bia        2     2   66%     3.00       3
+ 4  duda       0     0    0%        -       0
+";
+        assert_eq!(leaderboard(players), expected);
+    }
+}
@@ -1,38 +1,1472 @@
-use cl_wordle::game::Game;
-use clap::Parser;
+#[cfg(feature = "tui")]
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    time::Duration,
+};
+use std::{iter, process::ExitCode};
+
+use cl_wordle::{
+    challenge::{Challenge, Seed},
+    prelude::*,
+};
+use clap::{FromArgMatches, IntoApp};
 
 mod args;
+mod compare;
+mod config;
 mod controller;
+mod data;
+mod leaderboard;
+#[cfg(feature = "tui")]
+mod race;
+#[cfg(feature = "tui")]
+mod serve;
+mod wordlists;
+mod zone;
 
 use args::{App, GameMode};
-use controller::cli;
+use controller::{accessible, cli};
 
 #[cfg(feature = "rand")]
 use rand::Rng;
 
-fn main() -> eyre::Result<()> {
-    let app = App::parse();
-    let game = match app.game_mode {
-        None => Game::new()?,
-        Some(GameMode::Custom(custom)) => Game::custom(custom.word)?,
-        Some(GameMode::Day(day)) => Game::from_day(day.day)?,
+/// Exit code when the game was lost
+const EXIT_LOST: u8 = 2;
+/// Exit code when the game was quit before it was over
+const EXIT_QUIT: u8 = 3;
+
+fn main() -> eyre::Result<ExitCode> {
+    let mut app = parse_args();
+    if let Some(boards) = app.boards {
+        if app.game_mode.is_some() {
+            eyre::bail!("--boards starts a game of its own, leave out the subcommand");
+        }
+        app.game_mode = Some(GameMode::Multi(args::Multi {
+            words: Vec::new(),
+            boards,
+            seed: None,
+        }));
+    }
+    if let Some(dir) = &app.data_dir {
+        data::set_dir(dir.clone());
+    }
+    if let Some(profile) = &app.profile {
+        data::set_profile(profile.clone())?;
+    }
+    app.offset = Some(daily_offset(&app)?);
+    // there's no terminal to draw the tui in if both ends are piped. Guesses given
+    // with --guess are played as a batch either way
+    #[cfg(feature = "tui")]
+    let pipe = app.guesses.is_empty()
+        && !app.accessible
+        && (app.pipe || {
+            use crossterm::tty::IsTty;
+            !(app.no_tui || io::stdin().is_tty() || io::stdout().is_tty())
+        });
+    #[cfg(not(feature = "tui"))]
+    let pipe = app.pipe && app.guesses.is_empty();
+    #[cfg(feature = "tui")]
+    let cells = app.cells();
+    #[cfg(feature = "tui")]
+    let palette = app.palette();
+    let high_contrast = app.high_contrast();
+    // the schedule to record the result in, when training
+    let mut training = None;
+    // the number of rounds and the seed they're picked from, in a speedrun
+    let mut speedrun = None;
+    // the seed the words are picked from, in a marathon
+    let mut marathon = None;
+    // the keys to play back and how fast, when watching a replay
+    #[cfg(feature = "tui")]
+    let mut playback = None;
+    // the connection to the other player, when racing them
+    #[cfg(feature = "tui")]
+    let mut race = None;
+    // the stats to record the result in, when playing today's daily by hand. A game of
+    // --dict words isn't the daily, even on today's day number
+    let standard = app.length == cl_wordle::WORD_LEN
+        && app.max_guesses == cl_wordle::MAX_GUESSES
+        && app.dict.is_none();
+    // guesses from --guess or a program on the other end of --pipe aren't
+    let by_hand = app.guesses.is_empty() && !pipe;
+    let mut daily = (app.game_mode.is_none() && standard && by_hand)
+        .then(|| cl_wordle::stats::Stats::parse(&data::read("stats")));
+    // past days played by hand are kept apart, so they don't count towards the streak
+    let archived = matches!(app.game_mode, Some(GameMode::Day(_) | GameMode::Date(_)));
+    let mut archive = (archived && standard && by_hand)
+        .then(|| cl_wordle::stats::Stats::parse(&data::read("archive")));
+    let dict = match &app.dict {
+        Some(path) => {
+            let mut words =
+                cl_wordle::source::WordList::parse(&std::fs::read_to_string(path)?, app.length)?;
+            if let Some(path) = &app.accept_list {
+                words = words.with_accepted(&std::fs::read_to_string(path)?)?;
+            }
+            Some(std::sync::Arc::new(words) as cl_wordle::source::SharedSource)
+        }
+        None => None,
+    };
+    let source = match &app.daily {
+        Some(source) => Some(daily_source(source, app.length)?),
+        None => None,
+    };
+    // the daily games, which come from the dictionary if one was given
+    let from_day = |day| {
+        // the lists from `wordle update-words` still count as the daily, unlike --dict
+        let downloaded = (dict.is_none() && source.is_none() && app.length == cl_wordle::WORD_LEN)
+            .then(wordlists::cached)
+            .flatten();
+        from_day_in(dict.clone(), source.clone().or(downloaded), day, app.length)
+    };
+    let game = match app.game_mode.take() {
+        #[cfg(feature = "net")]
+        None if app.solution_url.is_some() => {
+            let url = app.solution_url.as_deref().unwrap_or_default();
+            Game::from_url(url, app.offset).or_else(|err| {
+                eprintln!("warning: {}, playing the bundled daily instead", err);
+                Game::from_offset(app.offset)
+            })?
+        }
+        None => from_day(cl_wordle::get_day(today(app.offset)?))?,
+        Some(GameMode::Custom(custom)) => {
+            let unlisted = custom.unlisted;
+            let start = move |word: String| match unlisted {
+                true => Game::custom_unlisted(word),
+                false => Game::custom(word),
+            };
+            let game = match custom.word {
+                Some(word) => start(word)?,
+                #[cfg(feature = "tui")]
+                None if pipe => {
+                    eyre::bail!("give the word to guess, there's no terminal to type it in")
+                }
+                #[cfg(feature = "tui")]
+                None => {
+                    let prompt = "Type the word to guess, it won't be shown";
+                    let check = |word: &str| {
+                        start(word.to_owned()).map_err(|err| match err {
+                            GameInitError::InvalidSolution(_) => {
+                                "That word isn't in the word lists, \
+                                 run with --unlisted to play it anyway"
+                                    .to_owned()
+                            }
+                            err => controller::tui::secret::rejection(&err),
+                        })
+                    };
+                    match controller::tui::secret::ask(prompt, check)? {
+                        Some(game) => game,
+                        None => return Ok(ExitCode::from(EXIT_QUIT)),
+                    }
+                }
+                #[cfg(not(feature = "tui"))]
+                None => {
+                    eyre::bail!("give the word to guess, it can only be typed in hidden in the tui")
+                }
+            };
+            game.with_aliases(custom.aliases)?
+        }
+        Some(GameMode::Day(day)) => {
+            let day = match day.day {
+                Some(day) => past_day(day, app.offset)?,
+                None => match pick_day(app.offset)? {
+                    Some(day) => day,
+                    None => return Ok(ExitCode::from(EXIT_QUIT)),
+                },
+            };
+            from_day(day)?
+        }
         #[cfg(feature = "rand")]
-        Some(GameMode::Random) => Game::from_day(rand::thread_rng().gen())?,
-        Some(GameMode::Date(date)) => Game::from_date(date.date)?,
+        Some(GameMode::Random) => from_day(rand::thread_rng().gen())?,
+        Some(GameMode::Date(date)) => {
+            let day = match date.date {
+                Some(date) if date < cl_wordle::words::FIRST_DAY => {
+                    eyre::bail!("the first game was on {}", cl_wordle::words::FIRST_DAY);
+                }
+                Some(date) => past_day(cl_wordle::get_day(date), app.offset)?,
+                None => match pick_day(app.offset)? {
+                    Some(day) => day,
+                    None => return Ok(ExitCode::from(EXIT_QUIT)),
+                },
+            };
+            from_day(day)?
+        }
+        Some(GameMode::Tournament(t)) => Game::tournament(t.seed, t.round)?,
+        Some(GameMode::Practice(practice)) => {
+            // the rounds are picked from the bundled solutions
+            if app.dict.is_some() {
+                eyre::bail!("--dict can't be used in practice, try `wordle --dict <FILE>`");
+            }
+            Game::random(practice.seed)
+        }
+        Some(GameMode::Speedrun(run)) => {
+            if app.dict.is_some() {
+                eyre::bail!("--dict can't be used in a speedrun");
+            }
+            if run.rounds == 0 {
+                eyre::bail!("a speedrun needs at least 1 round");
+            }
+            if !app.guesses.is_empty() {
+                eyre::bail!("--guess can't be used in a speedrun");
+            }
+            speedrun = Some((run.rounds, run.seed));
+            Game::random(run.seed)
+        }
+        Some(GameMode::Marathon(run)) => {
+            if app.dict.is_some() {
+                eyre::bail!("--dict can't be used in a marathon");
+            }
+            if !app.guesses.is_empty() {
+                eyre::bail!("--guess can't be used in a marathon");
+            }
+            marathon = Some(run.seed);
+            Game::random(run.seed)
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Replay(replay)) => {
+            let file = std::fs::read_to_string(&replay.file)?;
+            let recorded = controller::tui::replay::Replay::parse(&file)?;
+            let game = replay_game(&recorded)?;
+            playback = Some((recorded, replay.speed));
+            game
+        }
+        Some(GameMode::Absurdle) => Game::absurdle(),
+        Some(GameMode::Multi(multi)) => {
+            let multi = if multi.words.is_empty() {
+                cl_wordle::multi::Multi::random(multi.seed, multi.boards)?
+            } else {
+                cl_wordle::multi::Multi::custom(multi.words)?
+            };
+            let multi = multi.with_max_guesses(app.max_guesses)?;
+            #[cfg(feature = "tui")]
+            let output = if app.accessible {
+                accessible::play_multi(multi)?
+            } else if app.no_tui {
+                cli::play_multi(multi)?
+            } else {
+                controller::tui::multi::Controller::new(multi, cells, palette, app.mouse)?
+                    .with_layout(app.layout)
+                    .run()?
+            };
+            #[cfg(not(feature = "tui"))]
+            let output = if app.accessible {
+                accessible::play_multi(multi)?
+            } else {
+                cli::play_multi(multi)?
+            };
+            return Ok(match output {
+                Some(share) => {
+                    println!("{}", share);
+                    if share.won() {
+                        ExitCode::SUCCESS
+                    } else {
+                        ExitCode::from(EXIT_LOST)
+                    }
+                }
+                None => ExitCode::from(EXIT_QUIT),
+            });
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Crosswordle(args::Crosswordle { day })) => {
+            let day = match day {
+                Some(day) => past_day(day, app.offset)?,
+                None => cl_wordle::get_day(today(app.offset)?),
+            };
+            let board = cl_wordle::crosswordle::Crosswordle::from_day(day);
+            let output =
+                controller::tui::crosswordle::Controller::new(board, cells, palette)?.run()?;
+            return Ok(match output {
+                Some(share) => {
+                    println!("{}", share);
+                    ExitCode::SUCCESS
+                }
+                None => ExitCode::from(EXIT_QUIT),
+            });
+        }
+        Some(GameMode::Train) => {
+            let schedule =
+                cl_wordle::train::Schedule::parse(cl_wordle::train::DECK, &data::read("training"));
+            let word = schedule.next().unwrap_or_default().to_owned();
+            training = Some((schedule, word.clone()));
+            Game::custom(word)?
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Render(render)) => {
+            render_board(render, cells, palette, app.layout)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Compare(compare)) => {
+            compare_shares(compare)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        #[cfg(feature = "image")]
+        Some(GameMode::Share(args::Share { file, image })) => {
+            let share = GameShare::parse(&read_input(file)?)?;
+            // a share pasted with ⬜ stays on a light background
+            let white_squares = app.white_squares || share.white_squares();
+            let share = share
+                .with_high_contrast(app.high_contrast())
+                .with_white_squares(white_squares);
+            save_image(&share, &image)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Challenge(create)) => {
+            create_challenge(create)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Seed(create)) => {
+            println!("{}", create_seed(create)?);
+            return Ok(ExitCode::SUCCESS);
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Duel(duel)) => {
+            let options = controller::tui::Options {
+                // the share of a word typed in by a friend isn't worth copying
+                no_clipboard: true,
+                ..tui_options(&app, cells, palette, app.timer)?
+            };
+            let players = cl_wordle::duel::Duel::new(duel.first, duel.second);
+            let duel = controller::tui::duel::Controller::new(players, options)?
+                .with_rounds(duel.rounds)
+                .run()?;
+            println!("{}", duel);
+            return Ok(ExitCode::SUCCESS);
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Host(host)) => {
+            check_race(&app, pipe)?;
+            let hosted = race::Race::host(host.port, &player_name(host.name))?;
+            let game = Game::seeded(hosted.seed);
+            race = Some(hosted);
+            game
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Join(join)) => {
+            check_race(&app, pipe)?;
+            let joined = race::Race::join(&join.address, &player_name(join.name))?;
+            let game = Game::seeded(joined.seed);
+            race = Some(joined);
+            game
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Serve(args::Serve { port })) => {
+            let options = controller::tui::Options {
+                // clicks aren't read from the connection, and the clipboard would be
+                // the server's
+                mouse: false,
+                no_clipboard: true,
+                ..tui_options(&app, cells, palette, app.timer)?
+            };
+            serve::serve(port, options, app.offset)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Search(search)) => {
+            search_words(search)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        #[cfg(feature = "tui")]
+        Some(GameMode::Find(find)) => {
+            find_words(find)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Solve(solve)) => {
+            solve_words(solve)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Openers(openers)) => {
+            print_openers(openers);
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Verify(verify)) => {
+            verify_day(verify, app.offset)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Config(args::Config::Init { force })) => {
+            let path = config::init(force)?;
+            println!("wrote {}", path.display());
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Leaderboard) => {
+            // anyone who's never played a daily has nothing to rank
+            let players = players().into_iter().filter(|p| p.stats.played() > 0);
+            print!("{}", leaderboard::leaderboard(players.collect()));
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Profiles) => {
+            list_profiles();
+            return Ok(ExitCode::SUCCESS);
+        }
+        #[cfg(feature = "net")]
+        Some(GameMode::UpdateWords(update)) => {
+            if update.bundled {
+                wordlists::reset()?;
+                println!("playing the bundled words again");
+                return Ok(ExitCode::SUCCESS);
+            }
+            let url = update.solutions.as_deref().unwrap_or_default();
+            let words = wordlists::update(url, update.accepted.as_deref())?;
+            // without giving it away, say whether today's word is any different
+            let day = cl_wordle::get_day(today(app.offset)?);
+            let same = cl_wordle::source::WordSource::solution_for_day(&words, day)
+                == cl_wordle::get_solution(day);
+            println!(
+                "downloaded {} solutions, today's is {} the bundled one",
+                words.solutions().len(),
+                if same {
+                    "the same as"
+                } else {
+                    "different from"
+                }
+            );
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Next) => {
+            let now = cl_wordle::now(app.offset)?;
+            let left = cl_wordle::time_until_next_day(now).whole_seconds();
+            println!(
+                "Termo {} in {}:{:02}:{:02}",
+                cl_wordle::get_day(now.date()) + 1,
+                left / 3600,
+                left / 60 % 60,
+                left % 60
+            );
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Status) => {
+            let stats = cl_wordle::stats::Stats::parse(&data::read("stats"));
+            let day = cl_wordle::get_day(today(app.offset)?);
+            return Ok(match stats.daily_result(day) {
+                Some(guesses) => {
+                    let score = guesses.map_or("X".to_owned(), |n| n.to_string());
+                    println!("played, {}/{}", score, cl_wordle::MAX_GUESSES);
+                    ExitCode::SUCCESS
+                }
+                None => {
+                    println!("not played yet");
+                    ExitCode::FAILURE
+                }
+            });
+        }
+        Some(GameMode::Stats(args::Stats { command: None })) => {
+            let stats = cl_wordle::stats::Stats::parse(&data::read("stats"));
+            println!("{}", stats.render());
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Stats(args::Stats {
+            command: Some(command),
+        })) => {
+            manage_stats(command)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Some(GameMode::Play(play)) => match (play.code, play.challenge) {
+            (Some(code), _) => Game::seeded(code.parse()?),
+            (None, Some(path)) => {
+                let challenge: Challenge = std::fs::read_to_string(path)?.parse()?;
+                challenge.into_game()?
+            }
+            (None, None) => unreachable!("clap requires a code or a challenge"),
+        },
     };
-
+    if let (Some(stats), GameType::Daily(day)) = (&daily, game.game_type()) {
+        // a later day counts too, in case it was played in a time zone that's ahead
+        if let Some(last) = stats.last_day().filter(|last| last >= day) {
+            let guesses = stats.result(last).unwrap_or_default();
+            let saved = GameShare::parse(&data::read("share")).ok();
+            match saved.filter(|share| share.game_type() == &GameType::Daily(last)) {
+                Some(share) => println!("already played day {}:\n\n{}\n", last, share),
+                None => {
+                    let score = guesses.map_or("X".to_owned(), |n| n.to_string());
+                    println!("already played day {}: {}/6\n", last, score);
+                }
+            }
+            println!("{}", stats.render());
+            // on stderr, like the stats after a game, so only the result is piped
+            eprintln!("\nrun `wordle practice` for a game that doesn't count");
+            return Ok(if guesses.is_some() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(EXIT_LOST)
+            });
+        }
+    }
+    let (no_repeats, hard, par, max_guesses) = (app.no_repeats, app.hard, app.par, app.max_guesses);
+    let ultra_hard = app.ultra_hard;
+    let ignore_accents = app.ignore_accents;
+    let configure = move |game: Game| {
+        let game = game
+            .allow_repeated_guesses(!no_repeats)
+            .with_hard_mode(hard)
+            .with_ultra_hard_mode(ultra_hard)
+            .with_ignore_accents(ignore_accents);
+        // absurdle goes on until it's solved
+        let game = if game.state().unlimited() {
+            game
+        } else {
+            game.with_max_guesses(max_guesses)?
+        };
+        Ok::<_, GameInitError>(match par {
+            Some(par) => game.with_par(par),
+            None => game,
+        })
+    };
+    // a replay is played by the rules it was recorded with, not the flags
     #[cfg(feature = "tui")]
-    let output = if app.no_tui {
-        Some(cli::Controller::new(game).run()?)
-    } else {
-        controller::tui::Controller::new(game)?.run()?
+    let replaying = playback.is_some();
+    #[cfg(not(feature = "tui"))]
+    let replaying = false;
+    let game = if replaying { game } else { configure(game)? };
+    // the guesses made before it was recorded
+    #[cfg(feature = "tui")]
+    let game = match &playback {
+        Some((recorded, _)) => {
+            let mut game = game;
+            for guess in &recorded.guesses {
+                game.guess(guess)?;
+            }
+            game
+        }
+        None => game,
+    };
+    // the word for each round after the first, the same for everyone with the seed
+    let speedrun_round = move |seed: Option<u64>, round: u64| {
+        configure(Game::random(seed.map(|seed| seed.wrapping_add(round)))).unwrap()
     };
+    // a terminal the tui can't be drawn in gets the board printed after each guess.
+    // --guess batches never draw one
+    #[cfg(feature = "tui")]
+    let board = !(app.no_tui || app.accessible || pipe || playback.is_some())
+        && app.guesses.is_empty()
+        && match controller::tui::unusable() {
+            Some(reason) => {
+                eprintln!(
+                    "{}, so the board is printed after each guess instead",
+                    reason
+                );
+                true
+            }
+            None => false,
+        };
+    #[cfg(not(feature = "tui"))]
+    let board = false;
+    #[cfg(feature = "tui")]
+    let cli_only = app.no_tui || app.accessible || pipe || board;
     #[cfg(not(feature = "tui"))]
-    let output = Some(cli::Controller::new(game).run()?);
+    let cli_only = true;
+    if let (Some((rounds, seed)), true) = (speedrun, cli_only) {
+        let mut shares = Vec::with_capacity(rounds);
+        let mut game = game;
+        for round in 1..=rounds as u64 {
+            let started = std::time::Instant::now();
+            let share = if app.accessible {
+                accessible::Controller::new(game).run()?
+            } else {
+                cli::Controller::new(game)
+                    .with_pipe(pipe)
+                    .with_board(board)
+                    .run()?
+            };
+            match share {
+                Some(share) => shares.push(share.with_time(started.elapsed())),
+                None => break,
+            }
+            game = speedrun_round(seed, round);
+        }
+        return Ok(report_speedrun(&shares, rounds));
+    }
+    if let (Some(seed), true) = (marathon, cli_only) {
+        let mut shares = Vec::new();
+        let mut game = game;
+        for round in 1.. {
+            let share = if app.accessible {
+                accessible::Controller::new(game).run()?
+            } else {
+                cli::Controller::new(game)
+                    .with_pipe(pipe)
+                    .with_board(board)
+                    .run()?
+            };
+            match share {
+                Some(share) if share.won() => shares.push(share),
+                Some(share) => {
+                    shares.push(share);
+                    break;
+                }
+                None => break,
+            }
+            game = speedrun_round(seed, round);
+        }
+        return Ok(report_marathon(&shares));
+    }
+    #[cfg(feature = "tui")]
+    if app.preview && matches!(game.game_type(), GameType::Daily(_)) {
+        eyre::bail!("--preview can't be used in daily games, try `wordle practice`");
+    }
 
+    // a game that isn't today's daily could be played while the streak runs out
+    let reminder = (daily.is_none() && !pipe && app.guesses.is_empty())
+        .then(|| streak_reminder(app.offset))
+        .flatten();
+    let output = if !app.guesses.is_empty() {
+        controller::batch::Controller::new(game, app.guesses).run()?
+    } else if app.accessible {
+        if let Some(reminder) = &reminder {
+            eprintln!("{}\n", reminder);
+        }
+        play_accessible(game, daily.is_some())?
+    } else {
+        #[cfg(feature = "tui")]
+        let output = if app.no_tui || pipe || board {
+            if let Some(reminder) = &reminder {
+                eprintln!("{}\n", reminder);
+            }
+            play_cli(game, daily.is_some(), pipe, board)?
+        } else {
+            // pick up today's game where it was left, if it was quit part way through
+            let mut game = game;
+            if daily.is_some() {
+                game.resume(&data::read("progress"));
+            }
+            let options = tui_options(&app, cells, palette, app.timer || speedrun.is_some())?;
+            let options = match race {
+                Some(_) => controller::tui::Options {
+                    // the solver would do the racing for whoever asked it
+                    hint: false,
+                    letter_hints: 0,
+                    candidates: false,
+                    positions: false,
+                    assist: None,
+                    ..options
+                },
+                None => options,
+            };
+            if let Some((recorded, speed)) = playback {
+                let options = controller::tui::Options {
+                    // the keys come as fast as they were typed, so held keys still bounce
+                    debounce: options.debounce.div_f64(speed),
+                    ..options
+                };
+                let terminal = controller::tui::replay::Playback::new(recorded.keys, speed)?;
+                let output =
+                    controller::tui::Controller::with_backend(game, options, terminal).run()?;
+                return Ok(match output {
+                    Some(_) => ExitCode::SUCCESS,
+                    None => ExitCode::from(EXIT_QUIT),
+                });
+            }
+            let recording = match &app.record {
+                Some(path) => Some(controller::tui::replay::Recording::create(path, &game)?),
+                None => None,
+            };
+            let practice = matches!(game.game_type(), GameType::Practice { .. });
+            let mut controller = controller::tui::Controller::new(game, options)?;
+            if let Some(reminder) = reminder {
+                controller = controller.with_notice(reminder);
+            }
+            if let Some(recording) = recording {
+                controller = controller.with_recording(recording);
+            }
+            controller = controller.with_on_keyboard(|shown| {
+                // there's nowhere to warn while the board is shown, and it's only the
+                // next game that starts the old way
+                let _ = config::set("hide_keyboard", &(!shown).to_string());
+            });
+            if let Some(race) = race {
+                let opponent = race.opponent.clone();
+                let (updates, on_guess) = race.split();
+                controller = controller
+                    .with_opponent(controller::tui::opponent::Opponent::new(opponent, updates))
+                    .with_on_guess(on_guess);
+            }
+            if let Some(stats) = &daily {
+                controller = controller.with_stats(stats.clone()).with_on_guess(|game| {
+                    // there's nowhere to warn while the board is shown, and losing the
+                    // save only matters if the game is quit
+                    let _ = data::write("progress", &game.save());
+                });
+            }
+            if let Some((rounds, seed)) = speedrun {
+                let mut round = 0;
+                let shares = controller
+                    .with_next_round(move || {
+                        round += 1;
+                        speedrun_round(seed, round)
+                    })
+                    .run_rounds(rounds)?;
+                return Ok(report_speedrun(&shares, rounds));
+            }
+            if let Some(seed) = marathon {
+                let mut round = 0;
+                let shares = controller
+                    .with_next_round(move || {
+                        round += 1;
+                        speedrun_round(seed, round)
+                    })
+                    .run_marathon()?;
+                return Ok(report_marathon(&shares));
+            }
+            if practice {
+                controller
+                    // the first round was configured the same way, so the rest can't fail
+                    .with_next_round(move || configure(Game::random(None)).unwrap())
+                    .run()?
+            } else {
+                controller.run()?
+            }
+        };
+        #[cfg(not(feature = "tui"))]
+        if let Some(reminder) = &reminder {
+            eprintln!("{}\n", reminder);
+        }
+        #[cfg(not(feature = "tui"))]
+        let output = play_cli(game, daily.is_some(), pipe, board)?;
+        output
+    };
+
+    let code = match &output {
+        Some(share) if share.won() => ExitCode::SUCCESS,
+        Some(_) => ExitCode::from(EXIT_LOST),
+        None => ExitCode::from(EXIT_QUIT),
+    };
+    // kept in the stats even when it's left out of the share
+    let time = output.as_ref().and_then(GameShare::time);
+    let output = match output {
+        Some(share) if app.no_share_time => Some(share.without_time()),
+        output => output,
+    };
     if let Some(share) = output {
-        println!("{}", share);
+        if let (Some((mut schedule, word)), Some(score)) = (training, share.score()) {
+            schedule.review(&word, score);
+            if let Err(err) = data::write("training", &schedule.to_string()) {
+                eprintln!("warning: could not save training progress: {}", err);
+            }
+        }
+        if let (Some(stats), GameType::Daily(day)) = (&mut daily, share.game_type()) {
+            let guesses = share.score().and_then(|score| score.guesses());
+            stats.record(*day, guesses.map(|n| n as u8));
+            if let Some(time) = time {
+                stats.record_time(*day, time);
+            }
+            if let Some(opener) = share.opener() {
+                stats.record_opener(*day, opener);
+            }
+            if let Err(err) = data::write("stats", &stats.to_string()) {
+                eprintln!("warning: could not save stats: {}", err);
+            }
+            // the game is over, so there's nothing left to resume
+            let _ = data::write("progress", "");
+            // kept to show again if today's game is started once more
+            let _ = data::write("share", &share.to_string());
+        }
+        if let (Some(archive), GameType::Daily(day)) = (&mut archive, share.game_type()) {
+            let guesses = share.score().and_then(|score| score.guesses());
+            archive.record(*day, guesses.map(|n| n as u8));
+            if let Err(err) = data::write("archive", &archive.to_string()) {
+                eprintln!("warning: could not save the past days played: {}", err);
+            }
+        }
+        // bots only get the answers to their guesses
+        if pipe {
+            return Ok(code);
+        }
+        let share = share
+            .with_style(app.share_style)
+            .with_high_contrast(high_contrast)
+            .with_white_squares(app.white_squares)
+            .with_template(app.share_template.clone());
+        #[cfg(feature = "image")]
+        if let Some(path) = &app.png {
+            share.to_png(path)?;
+        }
+        #[cfg(feature = "serde")]
+        if app.format == args::Format::Json {
+            println!("{}", share.with_analysis(app.analysis).to_json());
+        } else {
+            println!("{}", share);
+            print_analysis(app.analysis, &share);
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            println!("{}", share);
+            print_analysis(app.analysis, &share);
+        }
+        if let Some(stats) = daily {
+            // on stderr, so the share can still be piped on its own
+            eprintln!("\n{}", stats.render());
+        }
+    }
+
+    Ok(code)
+}
+
+#[cfg(feature = "tui")]
+fn render_board(
+    render: args::Render,
+    cells: controller::tui::Cells,
+    palette: controller::tui::Palette,
+    layout: controller::tui::Layout,
+) -> eyre::Result<()> {
+    let game = Game::custom(render.solution)?;
+    // written to a file or another program, the board is plain text
+    let palette = if crossterm::tty::IsTty::is_tty(&io::stdout()) {
+        palette
+    } else {
+        controller::tui::Palette::Plain
+    };
+    let controller = controller::tui::snapshot::Controller::new(
+        game,
+        render.skip_invalid,
+        cells,
+        palette,
+        layout,
+    );
+    match render.guesses {
+        Some(path) => controller.run(BufReader::new(File::open(path)?)),
+        None => controller.run(io::stdin().lock()),
+    }
+}
+
+#[cfg(feature = "tui")]
+fn search_words(search: args::Search) -> eyre::Result<()> {
+    let pattern = search.pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let words = cl_wordle::words::FINAL
+        .iter()
+        .copied()
+        .filter(|word| {
+            word.chars().count() == pattern.len()
+                && word.chars().zip(&pattern).all(|(c, &p)| p == '.' || c == p)
+        })
+        .collect::<Vec<_>>();
+
+    let pager = controller::tui::pager::Pager {
+        limit: search.limit,
+    };
+    pager.show(&words)?;
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+fn find_words(find: args::Find) -> eyre::Result<()> {
+    let mut constraints = cl_wordle::solver::Constraints::default();
+    for green in find.green {
+        constraints = constraints.green(green)?;
+    }
+    for amber in find.amber {
+        constraints = constraints.amber(amber)?;
+    }
+    let constraints = constraints.absent(&find.absent)?;
+    let words = constraints.candidates(cl_wordle::words::FINAL);
+
+    let pager = controller::tui::pager::Pager { limit: find.limit };
+    pager.show(&words)?;
+    Ok(())
+}
+
+/// The command line, after the flags from the config file. A flag given again on
+/// the command line replaces the config file's
+fn parse_args() -> App {
+    let mut args = std::env::args_os();
+    let argv0 = args.next().unwrap_or_default();
+    let config = config::args().unwrap_or_else(|err| {
+        eprintln!("warning: {}, ignoring the config file", err);
+        Vec::new()
+    });
+    let config = config.into_iter().map(Into::into);
+    let matches = App::into_app()
+        .setting(clap::AppSettings::AllArgsOverrideSelf)
+        .get_matches_from(std::iter::once(argv0).chain(config).chain(args));
+    App::from_arg_matches(&matches).unwrap_or_else(|err| err.exit())
+}
+
+/// The words of `--daily`, read from the file if it's one
+fn daily_source(
+    source: &args::DailySource,
+    length: usize,
+) -> eyre::Result<cl_wordle::source::SharedSource> {
+    use std::sync::Arc;
+
+    let bundled = |source: cl_wordle::source::SharedSource| {
+        if length != cl_wordle::WORD_LEN {
+            eyre::bail!(
+                "only {} letter words are bundled, play {} letter words with --daily file:<path>",
+                cl_wordle::WORD_LEN,
+                length
+            );
+        }
+        Ok(source)
+    };
+    match source {
+        args::DailySource::Bundled => bundled(Arc::new(cl_wordle::source::Bundled)),
+        args::DailySource::Shuffled(seed) => {
+            bundled(Arc::new(cl_wordle::source::Shuffled::new(seed.as_str())))
+        }
+        #[cfg(feature = "serde")]
+        args::DailySource::File(path) => {
+            let input = std::fs::read_to_string(path)?;
+            let words = cl_wordle::source::WordList::from_json(&input, length)
+                .map_err(|err| eyre::eyre!("{}: {}", path.display(), err))?;
+            Ok(Arc::new(words))
+        }
+        #[cfg(not(feature = "serde"))]
+        args::DailySource::File(_) => {
+            eyre::bail!("reading the dailies from a file needs wordle built with the serde feature")
+        }
+    }
+}
+
+/// The given day's game, from the dictionary, or the daily source or lists
+/// downloaded if there are any
+fn from_day_in(
+    dict: Option<cl_wordle::source::SharedSource>,
+    daily: Option<cl_wordle::source::SharedSource>,
+    day: usize,
+    length: usize,
+) -> eyre::Result<Game> {
+    match (dict, daily) {
+        (Some(dict), _) => Ok(Game::from_dictionary(dict, day)?),
+        (None, Some(daily)) => Ok(Game::from_source(daily, day)?),
+        (None, None) => Game::from_day_with_length(day, length).map_err(|err| match err {
+            GameInitError::NoWordList(len) => eyre::eyre!(
+                "only {} letter words are bundled, play {} letter words with --dict <FILE>",
+                cl_wordle::WORD_LEN,
+                len
+            ),
+            err => err.into(),
+        }),
+    }
+}
+
+/// The game a replay was recorded in, or a custom game of its solution if that
+/// game has a different one, as a daily from another word list would. Either way
+/// it has the rules it was recorded with
+#[cfg(feature = "tui")]
+fn replay_game(replay: &controller::tui::replay::Replay) -> eyre::Result<Game> {
+    let game = match &replay.game_type {
+        GameType::Daily(day) => Game::from_day(*day).ok(),
+        GameType::Practice { seed } => Some(Game::random(Some(*seed))),
+        GameType::Tournament { seed, round } => Game::tournament(seed.clone(), *round).ok(),
+        // the solution only stood in until the guesses forced one
+        GameType::Absurdle => return Ok(replay.rules.apply(Game::absurdle())?),
+        GameType::Seeded { code } => code.parse().ok().map(Game::seeded),
+        GameType::Custom => None,
+    };
+    let game = match game.filter(|game| game.state().solution() == replay.solution) {
+        Some(game) => game,
+        None => Game::custom_unlisted(replay.solution.clone())?,
+    };
+    Ok(replay.rules.apply(game)?)
+}
+
+/// The options the tui is played with, from the flags that aren't about which game
+#[cfg(feature = "tui")]
+fn tui_options(
+    app: &App,
+    cells: controller::tui::Cells,
+    palette: controller::tui::Palette,
+    timer: bool,
+) -> eyre::Result<controller::tui::Options> {
+    let frequencies = match &app.frequencies {
+        Some(path) => Some(cl_wordle::frequency::Frequencies::parse(
+            &std::fs::read_to_string(path)?,
+        )),
+        None => None,
+    };
+    let mut definitions = cl_wordle::definitions::Definitions::bundled();
+    if let Some(path) = &app.definitions {
+        definitions.extend(&std::fs::read_to_string(path)?);
     }
+    let celebrations = if app.no_celebrations {
+        vec![]
+    } else if app.celebrations.is_empty() {
+        controller::tui::CELEBRATIONS.map(String::from).to_vec()
+    } else {
+        app.celebrations.clone()
+    };
+    let reveal_delay = if app.no_animation {
+        Duration::ZERO
+    } else {
+        Duration::from_millis(app.reveal_delay)
+    };
+    Ok(controller::tui::Options {
+        reveal_delay,
+        shake: !app.no_animation,
+        debounce: Duration::from_millis(app.debounce),
+        align: app.align,
+        reveal_style: app.reveal_style,
+        short_word: app.short_word,
+        interrupt: app.interrupt,
+        hide_keyboard: app.hide_keyboard,
+        no_keyboard_hints: app.no_keyboard_hints,
+        split_keys: app.split_keys,
+        deduce_keyboard: app.deduce_keyboard,
+        hint: app.hint,
+        letter_hints: app.letter_hints,
+        candidates: app.candidates,
+        positions: app.positions,
+        preview: app.preview,
+        cells,
+        alphabet: app.alphabet,
+        frequencies,
+        celebrations,
+        share_style: app.share_style,
+        share_template: app.share_template.clone(),
+        white_squares: app.white_squares,
+        palette,
+        mouse: app.mouse,
+        layout: app.layout,
+        text: app.text,
+        timed: app.timed,
+        timer,
+        no_share_time: app.no_share_time,
+        offset: app.offset,
+        no_clipboard: app.no_clipboard,
+        assist: app.assist.then_some(app.assist_by),
+        definitions,
+        #[cfg(feature = "net")]
+        online_definitions: app.online_definitions,
+    })
+}
 
+/// Races are only drawn in the tui, where the other player's board can be kept up
+/// to date
+#[cfg(feature = "tui")]
+fn check_race(app: &App, pipe: bool) -> eyre::Result<()> {
+    if app.no_tui || app.accessible || pipe || !app.guesses.is_empty() {
+        eyre::bail!("races can only be played in the tui");
+    }
+    Ok(())
+}
+
+/// The name shown to the other player in a race, which is the user's own unless
+/// another was given
+#[cfg(feature = "tui")]
+fn player_name(name: Option<String>) -> String {
+    name.or_else(|| std::env::var("USER").ok())
+        .unwrap_or_else(|| "player".to_owned())
+}
+
+/// Plays on the command line. Today's game is picked up where it was left, and
+/// saved after each guess, as in the tui
+fn play_cli(
+    mut game: Game,
+    daily: bool,
+    pipe: bool,
+    board: bool,
+) -> eyre::Result<Option<GameShare>> {
+    let controller = |game| cli::Controller::new(game).with_pipe(pipe).with_board(board);
+    if !daily {
+        return controller(game).run();
+    }
+    game.resume(&data::read("progress"));
+    controller(game)
+        .with_on_guess(|game| {
+            // losing the save only matters if the game is quit
+            let _ = data::write("progress", &game.save());
+        })
+        .run()
+}
+
+/// Exports the stats, or merges an export into them
+fn manage_stats(command: args::StatsCommand) -> eyre::Result<()> {
+    use args::{StatsCommand, StatsFormat};
+    use cl_wordle::stats::Stats;
+
+    let stats = Stats::parse(&data::read("stats"));
+    match command {
+        StatsCommand::Export { format } => match format {
+            StatsFormat::Csv => print!("{}", stats.to_csv()),
+            #[cfg(feature = "serde")]
+            StatsFormat::Json => println!("{}", stats.to_json()),
+            #[cfg(not(feature = "serde"))]
+            StatsFormat::Json => eyre::bail!("JSON needs wordle built with the serde feature"),
+        },
+        StatsCommand::Import { file, format } => {
+            let input = read_input(file)?;
+            let format = format.unwrap_or(if input.trim_start().starts_with('[') {
+                StatsFormat::Json
+            } else {
+                StatsFormat::Csv
+            });
+            let imported = match format {
+                StatsFormat::Csv => Stats::parse_csv(&input)?,
+                #[cfg(feature = "serde")]
+                StatsFormat::Json => Stats::from_json(&input)?,
+                #[cfg(not(feature = "serde"))]
+                StatsFormat::Json => eyre::bail!("JSON needs wordle built with the serde feature"),
+            };
+            import_stats(stats, &imported)?;
+        }
+        StatsCommand::ImportShare { file } => {
+            let imported = Stats::from_shares(&read_input(file)?)?;
+            import_stats(stats, &imported)?;
+        }
+    }
+    Ok(())
+}
+
+/// Saves the days imported that weren't played yet, and says how many there were
+fn import_stats(
+    mut stats: cl_wordle::stats::Stats,
+    imported: &cl_wordle::stats::Stats,
+) -> eyre::Result<()> {
+    let added = stats.merge(imported);
+    data::write("stats", &stats.to_string())?;
+    let days = |n| if n == 1 { "day" } else { "days" };
+    let already = imported.played() - added;
+    println!(
+        "imported {} {}, {} {} already played\n\n{}",
+        added,
+        days(added),
+        already,
+        if already == 1 { "was" } else { "were" },
+        stats.summary()
+    );
+    Ok(())
+}
+
+/// The contents of the file, or everything on stdin without one
+fn read_input(file: Option<std::path::PathBuf>) -> eyre::Result<String> {
+    Ok(match file {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => std::io::read_to_string(std::io::stdin())?,
+    })
+}
+
+/// Plays a line at a time for screen readers, saving today's game after each guess
+/// as [`play_cli`] does
+fn play_accessible(mut game: Game, daily: bool) -> eyre::Result<Option<GameShare>> {
+    if !daily {
+        return accessible::Controller::new(game).run();
+    }
+    game.resume(&data::read("progress"));
+    accessible::Controller::new(game)
+        .with_on_guess(|game| {
+            let _ = data::write("progress", &game.save());
+        })
+        .run()
+}
+
+/// Prints each round of a speedrun with its time, then the total and average.
+/// It's only a success if every round was played and won
+fn report_speedrun(shares: &[GameShare], rounds: usize) -> ExitCode {
+    use cl_wordle::game::Elapsed;
+
+    let times = shares.iter().map(|share| share.time().unwrap_or_default());
+    for (i, (share, time)) in shares.iter().zip(times.clone()).enumerate() {
+        let score = share
+            .score()
+            .map_or("?".to_owned(), |score| score.to_string());
+        println!(
+            "round {}  {}/{}  {}",
+            i + 1,
+            score,
+            share.max_guesses(),
+            Elapsed(time)
+        );
+    }
+    let total = times.sum::<std::time::Duration>();
+    let solved = shares.iter().filter(|share| share.won()).count();
+    println!("\n{} of {} solved in {}", solved, rounds, Elapsed(total));
+    if !shares.is_empty() {
+        println!("{} on average", Elapsed(total / shares.len() as u32));
+    }
+    if shares.len() < rounds {
+        ExitCode::from(EXIT_QUIT)
+    } else if solved < rounds {
+        ExitCode::from(EXIT_LOST)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Prints how each guess was rated with --analysis, on stderr so the result can still
+/// be piped on its own
+fn print_analysis(enabled: bool, share: &GameShare) {
+    if !enabled {
+        return;
+    }
+    match share.analysis() {
+        Some(analysis) if !analysis.guesses.is_empty() => eprintln!("\n{}", analysis),
+        _ => eprintln!("\nonly games of the bundled words can be rated"),
+    }
+}
+
+/// Prints how many words of a marathon were solved, with the guesses they took.
+/// It's a success if it was played until a word wasn't solved, rather than quit
+fn report_marathon(shares: &[GameShare]) -> ExitCode {
+    let mut marathon = cl_wordle::marathon::Marathon::new();
+    for share in shares {
+        marathon.record(share.score().unwrap_or(Score::Failed));
+    }
+    println!("{}", marathon);
+    if marathon.over() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(EXIT_QUIT)
+    }
+}
+
+/// The offset the day is worked out at: UTC with --utc, then --offset, then the
+/// --tz zone's offset right now, and the local timezone's without any of them.
+/// Where the local timezone can't be found, the day changes at midnight UTC
+fn daily_offset(app: &App) -> eyre::Result<time::UtcOffset> {
+    if app.utc {
+        return Ok(time::UtcOffset::UTC);
+    }
+    if let Some(offset) = app.offset {
+        return Ok(offset);
+    }
+    if let Some(zone) = &app.tz {
+        return zone::offset(zone, time::OffsetDateTime::now_utc());
+    }
+    match time::UtcOffset::current_local_offset() {
+        Ok(offset) => Ok(offset),
+        Err(_) => {
+            eprintln!("warning: the local timezone is unknown, the daily changes at midnight UTC");
+            Ok(time::UtcOffset::UTC)
+        }
+    }
+}
+
+/// Today's date, at the given offset from UTC or in the local timezone
+fn today(offset: Option<time::UtcOffset>) -> Result<time::Date, GameInitError> {
+    Ok(cl_wordle::now(offset)?.date())
+}
+
+/// A reminder that today's daily is still to play, if the streak ends without it
+fn streak_reminder(offset: Option<time::UtcOffset>) -> Option<String> {
+    let now = cl_wordle::now(offset).ok()?;
+    let stats = cl_wordle::stats::Stats::parse(&data::read("stats"));
+    let streak = stats.streak_at_risk(cl_wordle::get_day(now.date()))?;
+    let left = cl_wordle::time_until_next_day(now).whole_seconds();
+    Some(format!(
+        "play today's Termo to keep your {} day streak, {}:{:02} left",
+        streak,
+        left / 3600,
+        left / 60 % 60
+    ))
+}
+
+/// Lists the days before today to pick one to play, marking those already played
+/// as a daily or from the list
+#[cfg(feature = "tui")]
+fn pick_day(offset: Option<time::UtcOffset>) -> eyre::Result<Option<usize>> {
+    let today = cl_wordle::get_day(today(offset)?);
+    let daily = cl_wordle::stats::Stats::parse(&data::read("stats"));
+    let archive = cl_wordle::stats::Stats::parse(&data::read("archive"));
+    let entries = (0..today)
+        .rev()
+        .map(|day| controller::tui::archive::Entry {
+            day,
+            date: cl_wordle::words::FIRST_DAY + time::Duration::days(day as i64),
+            result: daily.result(day).or_else(|| archive.result(day)),
+        })
+        .collect();
+    controller::tui::archive::Controller::new(entries)?.run()
+}
+
+#[cfg(not(feature = "tui"))]
+fn pick_day(_: Option<time::UtcOffset>) -> eyre::Result<Option<usize>> {
+    eyre::bail!("listing past days needs the tui feature, pick one with `wordle day <DAY>`")
+}
+
+/// Checks the day's game is out already, so catching up on missed days can't spoil
+/// the ones to come
+fn past_day(day: usize, offset: Option<time::UtcOffset>) -> eyre::Result<usize> {
+    let today = cl_wordle::get_day(today(offset)?);
+    if day > today {
+        eyre::bail!("day {} isn't out yet, today is day {}", day, today);
+    }
+    Ok(day)
+}
+
+/// The daily stats of the games played without a profile, then of each profile
+fn players() -> Vec<leaderboard::Player> {
+    let profiles = data::profiles().into_iter().map(Some);
+    iter::once(None)
+        .chain(profiles)
+        .map(|name| leaderboard::Player {
+            stats: cl_wordle::stats::Stats::parse(&data::read_from(name.as_deref(), "stats")),
+            name: name.unwrap_or_else(|| "(no profile)".to_owned()),
+        })
+        .collect()
+}
+
+/// Prints each profile with its streaks, after the games played without a profile
+fn list_profiles() {
+    let players = players();
+    let width = players
+        .iter()
+        .map(|player| player.name.chars().count())
+        .max();
+    for leaderboard::Player { name, stats } in &players {
+        println!(
+            "{:width$}  streak {}, best {}",
+            name,
+            stats.current_streak(),
+            stats.max_streak(),
+            width = width.unwrap_or_default()
+        );
+    }
+    if players.len() == 1 {
+        eprintln!("\nno profiles yet, play with --profile <NAME> to make one");
+    }
+}
+
+fn verify_day(verify: args::Verify, offset: Option<time::UtcOffset>) -> eyre::Result<()> {
+    let date = match verify.date {
+        Some(date) => date,
+        None => today(offset)?,
+    };
+    let day = cl_wordle::get_day(date);
+    println!("day {}", day);
+    println!("solution hash {:016x}", cl_wordle::get_solution_hash(day));
+    Ok(())
+}
+
+fn solve_words(solve: args::Solve) -> eyre::Result<()> {
+    use std::io::IsTerminal;
+
+    let dict = cl_wordle::words::FINAL;
+    let word = match solve.word {
+        Some(word) => word.to_lowercase(),
+        None => {
+            let tty = std::io::stderr().is_terminal();
+            let benchmark = cl_wordle::solver::benchmark(solve.strategy, dict, |done, total| {
+                if tty && (done % 50 == 0 || done == total) {
+                    eprint!("\rsolved {}/{}", done, total);
+                }
+            });
+            if tty {
+                eprintln!();
+            }
+            let opener = benchmark.opener.as_deref().unwrap_or_default();
+            println!("opening with {}", opener.to_uppercase());
+            println!(
+                "{} words solved in {:.3} guesses on average",
+                benchmark.scores.len(),
+                benchmark.average()
+            );
+            let failures = benchmark.failures(cl_wordle::MAX_GUESSES);
+            println!(
+                "{} took more than {} guesses",
+                failures,
+                cl_wordle::MAX_GUESSES
+            );
+            if solve.worst > 0 {
+                println!("\nhardest:");
+            }
+            for (word, guesses) in benchmark.scores.iter().take(solve.worst) {
+                println!("{} {}", word.to_uppercase(), guesses);
+            }
+            return Ok(());
+        }
+    };
+    if !dict.contains(&word.as_str()) {
+        eyre::bail!("{} isn't one of the solutions", word);
+    }
+    let guesses = cl_wordle::solver::play(solve.strategy, &word, dict);
+    for guess in &guesses {
+        println!("{}", guess.to_uppercase());
+    }
+    println!("solved in {}", guesses.len());
+    Ok(())
+}
+
+/// The solver's best first guesses, or how each of yours has done with --mine
+fn print_openers(args: args::Openers) {
+    if !args.mine {
+        let ranked = cl_wordle::solver::openers().iter().take(args.top);
+        for (i, opener) in ranked.enumerate() {
+            println!(
+                "{:>2}. {}  {:.2} bits, {:.1} left on average",
+                i + 1,
+                opener.word.to_uppercase(),
+                opener.entropy,
+                opener.expected_remaining
+            );
+        }
+        return;
+    }
+    let openers = cl_wordle::stats::Stats::parse(&data::read("stats")).openers();
+    if openers.is_empty() {
+        println!("no openers recorded yet, they're kept from the next daily played");
+        return;
+    }
+    let width = openers.iter().map(|opener| opener.word.chars().count());
+    let width = width.max().unwrap_or(0).max("Opener".len());
+    println!("{:width$}  Played  Won  Average", "Opener");
+    for opener in openers.iter().take(args.top) {
+        let average = opener.average_guesses;
+        let average = average.map_or("-".to_owned(), |average| format!("{:.2}", average));
+        println!(
+            "{:width$}  {:>6}  {:>3}  {:>7}",
+            opener.word.to_uppercase(),
+            opener.played,
+            opener.won,
+            average
+        );
+    }
+}
+
+fn compare_shares(args: args::Compare) -> eyre::Result<()> {
+    let entries = if args.shares.is_empty() {
+        compare::Entry::split(&std::io::read_to_string(std::io::stdin())?)
+    } else {
+        let entries = args
+            .shares
+            .iter()
+            .map(|path| compare::Entry::from_file(path));
+        entries.collect::<Result<_, _>>()?
+    };
+    print!("{}", compare::compare(entries));
+    Ok(())
+}
+
+/// Draws the share as an SVG or PNG, going by the file's extension
+#[cfg(feature = "image")]
+fn save_image(share: &GameShare, path: &std::path::Path) -> eyre::Result<()> {
+    match path.extension() {
+        Some(extension) if extension.eq_ignore_ascii_case("svg") => {
+            std::fs::write(path, share.to_svg())?
+        }
+        _ => share.to_png(path)?,
+    }
+    Ok(())
+}
+
+fn create_seed(create: args::CreateSeed) -> eyre::Result<Seed> {
+    let word = match create.word {
+        Some(word) => word,
+        None => return Ok(Seed::random()),
+    };
+    let salt = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .subsec_nanos() as u16;
+    match Seed::new(&word, salt) {
+        Some(seed) => Ok(seed),
+        None => eyre::bail!(
+            "{} isn't in the bundled words, send it with `wordle challenge` instead",
+            word
+        ),
+    }
+}
+
+fn create_challenge(create: args::CreateChallenge) -> eyre::Result<()> {
+    // validate the word up front so the file is always playable
+    Game::custom(create.word.clone())?;
+
+    let salt = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .subsec_nanos();
+    let mut challenge = Challenge::new(create.word, salt);
+    if let Some(dictionary) = create.dictionary {
+        challenge = challenge.with_dictionary(dictionary);
+    }
+
+    match create.output {
+        Some(path) => std::fs::write(path, challenge.to_string())?,
+        None => print!("{}", challenge),
+    }
     Ok(())
 }
@@ -0,0 +1,275 @@
+//! Racing a friend over the network to solve the same word, with `wordle host` and
+//! `wordle join`.
+//!
+//! The two ends swap a line of text for each thing that happens. Both start with
+//! their name, and the host adds the seed both of them play:
+//!
+//! ```text
+//! hello 1 T6GVZ5TF ana
+//! row BYBBB
+//! row GGGGG
+//! done 2
+//! ```
+//!
+//! Only how each guess matched is sent, never its letters, so the opponent's board
+//! can show the race without giving the word away. A connection that closes means
+//! the other player left, as does one that sends something it shouldn't: a line
+//! longer than [`MAX_LINE`], or a row that isn't as long as the word. Names are
+//! drawn on the board, so they lose any control characters and are cut short.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    thread,
+};
+
+use cl_wordle::{challenge::Seed, prelude::*};
+use eyre::{bail, Result};
+
+use crate::controller::tui::opponent::Update;
+
+/// Changes whenever a message changes, so different versions don't misread each other
+const VERSION: u32 = 1;
+
+/// The port hosted on and joined when none is given
+pub const PORT: u16 = 7878;
+
+/// Longest line read from the other player, in bytes, with room for a long name
+const MAX_LINE: u64 = 256;
+
+/// Most characters of the other player's name that are kept
+const MAX_NAME: usize = 20;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Message {
+    /// Who's playing, and from the host, the seed to play
+    Hello { seed: Option<Seed>, name: String },
+    /// How the latest guess matched
+    Row(Matches),
+    /// The game is over
+    Done(Score),
+}
+
+impl Message {
+    fn parse(line: &str) -> Option<Self> {
+        let (kind, rest) = line.trim_end().split_once(' ')?;
+        match kind {
+            "hello" => {
+                let (version, rest) = rest.split_once(' ')?;
+                if version.parse() != Ok(VERSION) {
+                    return None;
+                }
+                let (seed, name) = match rest.split_once(' ') {
+                    Some((seed, name)) if seed != "-" => (Some(seed.parse().ok()?), name),
+                    Some((_, name)) => (None, name),
+                    None => return None,
+                };
+                // the name is drawn as it is, so it mustn't move the cursor or
+                // change the terminal
+                let name = name.chars().filter(|c| !c.is_control());
+                Some(Message::Hello {
+                    seed,
+                    name: name.take(MAX_NAME).collect(),
+                })
+            }
+            "row" => Some(Message::Row(rest.parse().ok()?)),
+            "done" => Some(Message::Done(rest.parse().ok()?)),
+            _ => None,
+        }
+    }
+
+    fn encode(&self) -> String {
+        match self {
+            Message::Hello { seed, name } => {
+                let seed = seed.map_or("-".to_owned(), |seed| seed.to_string());
+                format!("hello {} {} {}\n", VERSION, seed, name)
+            }
+            Message::Row(matches) => format!("row {:#}\n", matches),
+            Message::Done(score) => format!("done {}\n", score),
+        }
+    }
+}
+
+/// The connection to the other player, once both have said hello
+pub struct Race {
+    /// The seed both players solve
+    pub seed: Seed,
+    /// The other player's name
+    pub opponent: String,
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Race {
+    /// Waits on the port for someone to join, then sends them a random seed to play
+    pub fn host(port: u16, name: &str) -> Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        eprintln!(
+            "waiting for someone to run `wordle join <this machine>:{}`",
+            port
+        );
+        let (stream, address) = listener.accept()?;
+        eprintln!("{} joined", address);
+        let seed = Seed::random();
+        let mut race = Self::start(stream, seed)?;
+        race.send(&Message::Hello {
+            seed: Some(seed),
+            name: name.to_owned(),
+        })?;
+        match race.receive()? {
+            Message::Hello { name, .. } => race.opponent = name,
+            _ => bail!("the other player didn't say hello"),
+        }
+        Ok(race)
+    }
+
+    /// Connects to a host, given as `host` or `host:port`, and takes the seed it sends
+    pub fn join(address: &str, name: &str) -> Result<Self> {
+        let stream = match address.contains(':') {
+            true => TcpStream::connect(address)?,
+            false => TcpStream::connect((address, PORT))?,
+        };
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let (seed, opponent) = match receive(&mut reader)? {
+            Message::Hello {
+                seed: Some(seed),
+                name,
+            } => (seed, name),
+            _ => bail!("the host didn't send a word to play"),
+        };
+        let mut race = Self {
+            seed,
+            opponent,
+            stream,
+            reader,
+        };
+        race.send(&Message::Hello {
+            seed: None,
+            name: name.to_owned(),
+        })?;
+        Ok(race)
+    }
+
+    fn start(stream: TcpStream, seed: Seed) -> Result<Self> {
+        Ok(Self {
+            seed,
+            opponent: String::new(),
+            reader: BufReader::new(stream.try_clone()?),
+            stream,
+        })
+    }
+
+    fn send(&mut self, message: &Message) -> Result<()> {
+        self.stream.write_all(message.encode().as_bytes())?;
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Message> {
+        receive(&mut self.reader)
+    }
+
+    /// Splits the connection into what the other player does, read on a thread of its
+    /// own, and a callback sending each of this player's guesses to them
+    pub fn split(self) -> (mpsc::Receiver<Update>, impl FnMut(&Game)) {
+        let (sender, updates) = mpsc::channel();
+        let mut reader = self.reader;
+        let word_len = self.seed.solution().chars().count();
+        thread::spawn(move || {
+            let update = loop {
+                let update = match receive(&mut reader) {
+                    Ok(Message::Row(matches)) if matches.0.len() == word_len => {
+                        Update::Row(matches)
+                    }
+                    // a row of another length can't be drawn, so it's taken as leaving
+                    Ok(Message::Row(_)) => break Update::Left,
+                    Ok(Message::Done(score)) => Update::Done(score),
+                    Ok(Message::Hello { .. }) => continue,
+                    Err(_) => break Update::Left,
+                };
+                if sender.send(update).is_err() {
+                    return;
+                }
+            };
+            let _ = sender.send(update);
+        });
+
+        let mut stream = self.stream;
+        let on_guess = move |game: &Game| {
+            let mut send = |message: Message| {
+                // the other player sees this one leave if the connection's gone
+                let _ = stream.write_all(message.encode().as_bytes());
+            };
+            if let Some(Guess(_, matches)) = game.state().guesses().next_back() {
+                send(Message::Row(matches));
+            }
+            if game.game_over().is_some() {
+                send(Message::Done(game.state().score()));
+            }
+        };
+        (updates, on_guess)
+    }
+}
+
+fn receive(reader: &mut impl BufRead) -> Result<Message> {
+    let mut line = String::new();
+    match reader.take(MAX_LINE).read_line(&mut line)? {
+        0 => bail!("the other player left"),
+        n if n as u64 == MAX_LINE && !line.ends_with('\n') => {
+            bail!("the other player sent a line that's too long")
+        }
+        _ => {}
+    }
+    match Message::parse(&line) {
+        Some(message) => Ok(message),
+        None => bail!(
+            "the other player sent {:?}, are they on another version?",
+            line.trim_end()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::{challenge::Seed, diff, prelude::*};
+    use test_case::test_case;
+
+    use super::{receive, Message, MAX_LINE};
+
+    #[test_case(Message::Hello { seed: Some(Seed::new("termo", 7).unwrap()), name: "ana bia".to_owned() })]
+    #[test_case(Message::Hello { seed: None, name: "bia".to_owned() })]
+    #[test_case(Message::Row(diff("sorte", "termo")))]
+    #[test_case(Message::Done(Score::Guesses(3)))]
+    #[test_case(Message::Done(Score::Failed))]
+    fn messages_round_trip(message: Message) {
+        let line = message.encode();
+        assert!(line.ends_with('\n'));
+        assert_eq!(Message::parse(&line), Some(message));
+    }
+
+    #[test]
+    fn letters_aren_t_sent() {
+        assert_eq!(Message::Row(diff("sorte", "termo")).encode(), "row BYGYY\n");
+        assert_eq!(Message::parse("hello 2 - bia"), None);
+        assert_eq!(Message::parse("row GGXGG"), None);
+    }
+
+    #[test]
+    fn names_can_t_change_the_terminal() {
+        let line = format!("hello 1 - \x1b]0;title\x07bia\x1b[2J{}\n", "a".repeat(50));
+        let name = match Message::parse(&line) {
+            Some(Message::Hello { name, .. }) => name,
+            message => panic!("expected a hello, got {:?}", message),
+        };
+        assert_eq!(name, format!("]0;titlebia[2J{}", "a".repeat(6)));
+    }
+
+    #[test]
+    fn long_lines_aren_t_read() {
+        let line = format!("row {}\n", "G".repeat(MAX_LINE as usize));
+        assert!(receive(&mut line.as_bytes()).is_err());
+        let mut lines = "row GGGGG\nrow BBBBB\n".as_bytes();
+        assert!(matches!(receive(&mut lines), Ok(Message::Row(_))));
+        assert!(matches!(receive(&mut lines), Ok(Message::Row(_))));
+    }
+}
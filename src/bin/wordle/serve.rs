@@ -0,0 +1,89 @@
+//! Offering the daily to anyone with telnet, with `wordle serve`.
+//!
+//! Each connection gets a game of its own in the tui, drawn on the other end just as
+//! it would be in a terminal here. Nothing is saved on the server, so the games
+//! don't count towards anyone's stats.
+
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use cl_wordle::prelude::*;
+use eyre::Result;
+
+use crate::controller::tui::{
+    stream::{Stream, TELNET_SETUP},
+    Controller, Options,
+};
+
+/// The port served on when none is given
+pub const PORT: u16 = 2323;
+
+/// Takes connections on the port until it's stopped, playing each on a thread of
+/// its own
+pub fn serve(port: u16, options: Options, offset: Option<time::UtcOffset>) -> Result<()> {
+    // the local timezone can't be looked up once there are other threads running
+    let offset = match offset {
+        Some(offset) => offset,
+        None => time::UtcOffset::current_local_offset()?,
+    };
+    // and the tui counts down to the next daily in it too
+    let options = Options {
+        offset: Some(offset),
+        ..options
+    };
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    eprintln!(
+        "serving the daily, play with `telnet <this machine> {}`",
+        port
+    );
+    for connection in listener.incoming() {
+        let connection = match connection {
+            Ok(connection) => connection,
+            Err(err) => {
+                eprintln!("warning: {}", err);
+                continue;
+            }
+        };
+        let options = options.clone();
+        thread::spawn(move || {
+            let address = match connection.peer_addr() {
+                Ok(address) => address.to_string(),
+                Err(_) => "someone".to_owned(),
+            };
+            eprintln!("{} connected", address);
+            match play(connection, options, Some(offset)) {
+                Ok(Some(share)) => match share.score() {
+                    Some(score) => {
+                        eprintln!("{} scored {}/{}", address, score, share.max_guesses())
+                    }
+                    None => eprintln!("{} left", address),
+                },
+                Ok(None) => eprintln!("{} quit", address),
+                Err(err) => eprintln!("{} left: {}", address, err),
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Plays today's daily on the connection, then sends the result once the board is
+/// cleared away
+fn play(
+    mut connection: TcpStream,
+    options: Options,
+    offset: Option<time::UtcOffset>,
+) -> Result<Option<GameShare>> {
+    connection.write_all(&TELNET_SETUP)?;
+    let game = Game::from_offset(offset)?;
+    let terminal = Stream::new(connection.try_clone()?, connection.try_clone()?)?;
+    let share = Controller::with_backend(game, options, terminal).run()?;
+    if let Some(share) = &share {
+        // the other end is out of raw mode again, and wants its lines ended in full
+        let share = share.to_string().replace('\n', "\r\n");
+        write!(connection, "{}\r\n", share)?;
+    }
+    Ok(share)
+}
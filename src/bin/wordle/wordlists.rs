@@ -0,0 +1,106 @@
+//! Word lists downloaded with `wordle update-words`, kept in the data directory and
+//! played in place of the bundled ones, so the daily follows edits to the lists
+//! without a new release
+
+use std::sync::Arc;
+
+use cl_wordle::{
+    source::{SharedSource, WordList},
+    words,
+};
+use eyre::{eyre, Result};
+
+use crate::data;
+
+const SOLUTIONS: &str = "solutions";
+const ACCEPTED: &str = "accepted";
+
+/// The downloaded lists, or `None` if nothing's been downloaded or the lists no
+/// longer check out
+pub fn cached() -> Option<SharedSource> {
+    let solutions = data::read_shared(SOLUTIONS);
+    if solutions.is_empty() {
+        return None;
+    }
+    match verify(&solutions, &data::read_shared(ACCEPTED)) {
+        Ok(words) => Some(Arc::new(words)),
+        Err(err) => {
+            eprintln!(
+                "warning: the downloaded {}, playing the bundled words instead",
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Reads the lists, checking there are solutions and every word is a 5 letter word,
+/// so an error page or the lists for another game aren't kept. Without a list of
+/// accepted words, the bundled ones can still be guessed
+pub fn verify(solutions: &str, accepted: &str) -> Result<WordList> {
+    let words = WordList::parse(solutions, cl_wordle::WORD_LEN)
+        .map_err(|err| eyre!("solutions: {}", err))?;
+    if accepted.trim().is_empty() {
+        let bundled = words::FINAL.iter().chain(words::ACCEPT);
+        let words = WordList::new(words.solutions().to_vec(), bundled.map(|&w| w.to_owned()));
+        return Ok(words.expect("the solutions were checked"));
+    }
+    words
+        .with_accepted(accepted)
+        .map_err(|err| eyre!("accepted words: {}", err))
+}
+
+/// Downloads the lists and keeps them, once they've been verified
+#[cfg(feature = "net")]
+pub fn update(solutions_url: &str, accepted_url: Option<&str>) -> Result<WordList> {
+    let solutions = download(solutions_url)?;
+    let accepted = match accepted_url {
+        Some(url) => download(url)?,
+        None => String::new(),
+    };
+    let words = verify(&solutions, &accepted)?;
+    data::write_shared(SOLUTIONS, &solutions)?;
+    data::write_shared(ACCEPTED, &accepted)?;
+    Ok(words)
+}
+
+/// Deletes the downloaded lists, going back to the bundled ones
+#[cfg(feature = "net")]
+pub fn reset() -> Result<()> {
+    data::remove_shared(SOLUTIONS)?;
+    data::remove_shared(ACCEPTED)?;
+    Ok(())
+}
+
+#[cfg(feature = "net")]
+fn download(url: &str) -> Result<String> {
+    let response = ureq::get(url)
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+        .map_err(|err| eyre!("couldn't download {}", err))?;
+    Ok(response.into_string()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use cl_wordle::source::WordSource;
+
+    use super::verify;
+
+    #[test]
+    fn lists_are_checked_before_theyre_kept() {
+        let words = verify("termo\nsagaz\n", "suite\n").unwrap();
+        assert_eq!(words.solutions(), ["termo", "sagaz"]);
+
+        // only solutions still leave the bundled words to guess
+        let words = verify("termo\nsagaz\n", "").unwrap();
+        assert_eq!(words.solutions(), ["termo", "sagaz"]);
+        assert!(["aanas", "suite"]
+            .iter()
+            .all(|word| words.is_acceptable(word)));
+
+        assert!(verify("termo\ntem\n", "").is_err());
+        assert!(verify("termo\n", "<html>\n").is_err());
+        assert!(verify("", "").is_err());
+    }
+}
@@ -0,0 +1,447 @@
+//! Offsets of the timezones named with `--tz`, like `Europe/Lisbon`, read from the
+//! system's timezone database, so the daily can change at midnight somewhere else.
+//!
+//! The database is a directory of TZif files, `/usr/share/zoneinfo` or `$TZDIR`.
+//! Each file lists when the zone's offset changed, and ends with a POSIX TZ rule,
+//! like `WET0WEST,M3.5.0/1,M10.5.0`, for times after the last change it lists.
+
+use std::{fs, path::PathBuf};
+
+use eyre::{bail, eyre, Result};
+use time::{Date, Month, OffsetDateTime, UtcOffset, Weekday};
+
+/// The zone's offset from UTC at the time
+pub fn offset(name: &str, at: OffsetDateTime) -> Result<UtcOffset> {
+    if name.eq_ignore_ascii_case("utc") {
+        return Ok(UtcOffset::UTC);
+    }
+    // a name is a path under the database, which mustn't lead out of it
+    let valid = name
+        .split('/')
+        .all(|part| !part.is_empty() && part != ".." && part != "." && !part.contains('\\'));
+    if !valid {
+        bail!("{} isn't a timezone name, like Europe/Lisbon", name);
+    }
+    let dir = std::env::var_os("TZDIR")
+        .map_or_else(|| PathBuf::from("/usr/share/zoneinfo"), PathBuf::from);
+    let data =
+        fs::read(dir.join(name)).map_err(|err| eyre!("unknown timezone {}: {}", name, err))?;
+    let seconds = Zone::parse(&data)
+        .ok_or_else(|| eyre!("{} isn't a timezone file", dir.join(name).display()))?
+        .offset(at.unix_timestamp());
+    Ok(UtcOffset::from_whole_seconds(seconds)?)
+}
+
+/// A zone's offsets, in seconds east of UTC
+#[derive(Debug, PartialEq, Eq)]
+struct Zone {
+    /// The offset before the first change
+    initial: i32,
+    /// When the offset changed, in seconds since 1970, and what it changed to
+    changes: Vec<(i64, i32)>,
+    /// How the offset changes after the last one listed
+    rule: Option<Rule>,
+}
+
+impl Zone {
+    /// Reads a TZif file, using the 64 bit times of version 2 and up where they're
+    /// there
+    fn parse(data: &[u8]) -> Option<Self> {
+        let (header, rest) = Header::parse(data)?;
+        let (header, rest, time_len) = match header.version {
+            0 => (header, rest, 4),
+            // the 32 bit data is skipped for the 64 bit data after it
+            _ => {
+                let (header, rest) = Header::parse(rest.get(header.len(4)..)?)?;
+                (header, rest, 8)
+            }
+        };
+        let times = rest.get(..header.changes * time_len)?;
+        let rest = &rest[times.len()..];
+        let types = rest.get(..header.changes)?;
+        let infos = rest.get(header.changes..header.changes + header.types * 6)?;
+        let offsets = infos
+            .chunks(6)
+            .map(|info| i32::from_be_bytes(info[..4].try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        let changes = times.chunks(time_len).zip(types).map(|(time, &ty)| {
+            let time = match time_len {
+                4 => i32::from_be_bytes(time.try_into().unwrap()) as i64,
+                _ => i64::from_be_bytes(time.try_into().unwrap()),
+            };
+            Some((time, *offsets.get(ty as usize)?))
+        });
+        let changes = changes.collect::<Option<Vec<_>>>()?;
+
+        // the rule is on a line of its own after the 64 bit data
+        let rule = match header.version {
+            0 => None,
+            _ => {
+                let footer = rest.get(header.len(8) - times.len()..)?;
+                let footer = std::str::from_utf8(footer).ok()?;
+                let rule = footer.strip_prefix('\n')?.split('\n').next()?;
+                (!rule.is_empty()).then(|| Rule::parse(rule)).flatten()
+            }
+        };
+        Some(Self {
+            initial: *offsets.first()?,
+            changes,
+            rule,
+        })
+    }
+
+    fn offset(&self, at: i64) -> i32 {
+        match self.changes.iter().rposition(|&(time, _)| time <= at) {
+            Some(last) if last + 1 == self.changes.len() => match &self.rule {
+                Some(rule) => rule.offset(at),
+                None => self.changes[last].1,
+            },
+            Some(last) => self.changes[last].1,
+            None if self.changes.is_empty() => match &self.rule {
+                Some(rule) => rule.offset(at),
+                None => self.initial,
+            },
+            None => self.initial,
+        }
+    }
+}
+
+/// The counts at the start of a TZif file, saying how long each part is
+struct Header {
+    version: u8,
+    is_ut: usize,
+    is_std: usize,
+    leaps: usize,
+    changes: usize,
+    types: usize,
+    chars: usize,
+}
+
+impl Header {
+    fn parse(data: &[u8]) -> Option<(Self, &[u8])> {
+        if data.get(..4)? != b"TZif" {
+            return None;
+        }
+        let version = match *data.get(4)? {
+            0 => 0,
+            version => version.checked_sub(b'0')?,
+        };
+        let count = |i: usize| {
+            let bytes = data.get(20 + i * 4..24 + i * 4)?;
+            Some(u32::from_be_bytes(bytes.try_into().unwrap()) as usize)
+        };
+        let header = Self {
+            version,
+            is_ut: count(0)?,
+            is_std: count(1)?,
+            leaps: count(2)?,
+            changes: count(3)?,
+            types: count(4)?,
+            chars: count(5)?,
+        };
+        Some((header, &data[44..]))
+    }
+
+    /// Length of the data after the header, with times this many bytes long
+    fn len(&self, time_len: usize) -> usize {
+        self.changes * (time_len + 1)
+            + self.types * 6
+            + self.chars
+            + self.leaps * (time_len + 4)
+            + self.is_std
+            + self.is_ut
+    }
+}
+
+/// A POSIX TZ rule: a standard offset, and maybe a summer time and when it starts
+/// and ends each year
+#[derive(Debug, PartialEq, Eq)]
+struct Rule {
+    /// Seconds east of UTC
+    standard: i32,
+    summer: Option<Summer>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Summer {
+    offset: i32,
+    /// When summer time starts, in standard time, and ends, in summer time
+    start: (Day, i32),
+    end: (Day, i32),
+}
+
+/// The day of the year a change happens on
+#[derive(Debug, PartialEq, Eq)]
+enum Day {
+    /// `Jn`: from 1 to 365, never counting February 29th
+    Julian(u16),
+    /// `n`: from 0 to 365, counting February 29th
+    Ordinal(u16),
+    /// `Mm.w.d`: weekday `d` of week `w` of month `m`, where week 5 is the last
+    Weekday(u8, u8, u8),
+}
+
+impl Rule {
+    /// Parses a rule like `<-03>3` or `WET0WEST,M3.5.0/1,M10.5.0`
+    fn parse(input: &str) -> Option<Self> {
+        let mut rest = skip_name(input)?;
+        // POSIX offsets are hours west of UTC, the other way round from ISO ones
+        let (standard, after) = parse_time(rest)?;
+        let standard = -standard;
+        rest = after;
+        if rest.is_empty() {
+            return Some(Self {
+                standard,
+                summer: None,
+            });
+        }
+        rest = skip_name(rest)?;
+        let offset = match rest.starts_with(',') {
+            true => standard + 3600,
+            false => {
+                let (offset, after) = parse_time(rest)?;
+                rest = after;
+                -offset
+            }
+        };
+        let mut changes = rest.strip_prefix(',')?.split(',');
+        let mut change = || {
+            let change = changes.next()?;
+            let (day, time) = change.split_once('/').unwrap_or((change, "2"));
+            let (time, rest) = parse_time(time)?;
+            rest.is_empty().then_some(())?;
+            Some((Day::parse(day)?, time))
+        };
+        let (start, end) = (change()?, change()?);
+        Some(Self {
+            standard,
+            summer: Some(Summer { offset, start, end }),
+        })
+    }
+
+    fn offset(&self, at: i64) -> i32 {
+        let summer = match &self.summer {
+            Some(summer) => summer,
+            None => return self.standard,
+        };
+        let year = OffsetDateTime::from_unix_timestamp(at + self.standard as i64)
+            .map_or(1970, |local| local.year());
+        let when = |(day, time): &(Day, i32), offset: i32| {
+            day.date(year).map(|date| {
+                let midnight = date.midnight().assume_utc().unix_timestamp();
+                midnight + *time as i64 - offset as i64
+            })
+        };
+        let (start, end) = match (
+            when(&summer.start, self.standard),
+            when(&summer.end, summer.offset),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return self.standard,
+        };
+        // in the southern hemisphere, summer time goes over the new year
+        let in_summer = match start < end {
+            true => (start..end).contains(&at),
+            false => !(end..start).contains(&at),
+        };
+        match in_summer {
+            true => summer.offset,
+            false => self.standard,
+        }
+    }
+}
+
+impl Day {
+    fn parse(input: &str) -> Option<Self> {
+        if let Some(julian) = input.strip_prefix('J') {
+            return Some(Day::Julian(julian.parse().ok()?));
+        }
+        if let Some(date) = input.strip_prefix('M') {
+            let mut parts = date.split('.').map(|part| part.parse::<u8>().ok());
+            let (month, week, weekday) = (parts.next()??, parts.next()??, parts.next()??);
+            return Some(Day::Weekday(month, week, weekday));
+        }
+        Some(Day::Ordinal(input.parse().ok()?))
+    }
+
+    fn date(&self, year: i32) -> Option<Date> {
+        match *self {
+            Day::Julian(day) => {
+                let leap = time::util::is_leap_year(year);
+                let ordinal = if leap && day >= 60 {
+                    day.checked_add(1)?
+                } else {
+                    day
+                };
+                Date::from_ordinal_date(year, ordinal).ok()
+            }
+            Day::Ordinal(day) => Date::from_ordinal_date(year, day.checked_add(1)?).ok(),
+            Day::Weekday(month, week, weekday) => {
+                let month = Month::try_from(month).ok()?;
+                let first = Date::from_calendar_date(year, month, 1).ok()?;
+                let from_sunday = |day: Weekday| day.number_days_from_sunday();
+                let first_day = (weekday.checked_add(7)? - from_sunday(first.weekday())) % 7 + 1;
+                let days = time::util::days_in_year_month(year, month);
+                // week 5 is the last, which may be the fourth
+                let mut day = first_day + (week.clamp(1, 5) - 1) * 7;
+                while day > days {
+                    day -= 7;
+                }
+                Date::from_calendar_date(year, month, day).ok()
+            }
+        }
+    }
+}
+
+/// Skips a zone abbreviation, like `WET` or `<-03>`
+fn skip_name(input: &str) -> Option<&str> {
+    if let Some(quoted) = input.strip_prefix('<') {
+        return Some(quoted.split_once('>')?.1);
+    }
+    let end = input
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(input.len());
+    (end >= 3).then(|| &input[end..])
+}
+
+/// Parses `[+-]hh[:mm[:ss]]` as seconds, returning what's left after it
+fn parse_time(input: &str) -> Option<(i32, &str)> {
+    let (sign, rest) = match input.as_bytes().first()? {
+        b'-' => (-1, &input[1..]),
+        b'+' => (1, &input[1..]),
+        _ => (1, input),
+    };
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ':'))
+        .unwrap_or(rest.len());
+    let mut seconds = 0;
+    let mut parts = 0;
+    for (part, scale) in rest[..end].split(':').zip([3600, 60, 1]) {
+        seconds += part.parse::<i32>().ok()? * scale;
+        parts += 1;
+    }
+    (parts > 0).then_some((sign * seconds, &rest[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::{Day, Rule, Summer, Zone};
+
+    /// A version 2 TZif file with the changes, each to the offset given, and the rule
+    fn tzif(changes: &[(i64, i32)], rule: &str) -> Vec<u8> {
+        let header = |types: usize| {
+            let mut header = b"TZif2".to_vec();
+            header.resize(20, 0);
+            for count in [0, 0, 0, changes.len(), types, 4] {
+                header.extend((count as u32).to_be_bytes());
+            }
+            header
+        };
+        // every change has a type of its own, after the first offset
+        let types = changes.len() + 1;
+        let mut data = header(types);
+        data.extend(vec![0; changes.len() * 5 + types * 6 + 4]);
+        data.extend(header(types));
+        for &(time, _) in changes {
+            data.extend(time.to_be_bytes());
+        }
+        data.extend((1..=changes.len() as u8).collect::<Vec<_>>());
+        let offsets = [3600]
+            .into_iter()
+            .chain(changes.iter().map(|&(_, offset)| offset));
+        for offset in offsets {
+            data.extend(offset.to_be_bytes());
+            data.extend([0, 0]);
+        }
+        data.extend(b"ABC\0");
+        data.extend(format!("\n{}\n", rule).bytes());
+        data
+    }
+
+    #[test]
+    fn rules_are_parsed() {
+        assert_eq!(
+            Rule::parse("<-03>3"),
+            Some(Rule {
+                standard: -3 * 3600,
+                summer: None
+            })
+        );
+        assert_eq!(
+            Rule::parse("WET0WEST,M3.5.0/1,M10.5.0"),
+            Some(Rule {
+                standard: 0,
+                summer: Some(Summer {
+                    offset: 3600,
+                    start: (Day::Weekday(3, 5, 0), 3600),
+                    end: (Day::Weekday(10, 5, 0), 7200),
+                })
+            })
+        );
+        assert_eq!(
+            Rule::parse("NZST-12NZDT,M9.5.0,M4.1.0/3").unwrap().standard,
+            12 * 3600
+        );
+        assert_eq!(Rule::parse("W"), None);
+    }
+
+    #[test]
+    fn summer_time_follows_the_rule() {
+        let lisbon = Rule::parse("WET0WEST,M3.5.0/1,M10.5.0").unwrap();
+        // summer time started at 1am UTC on the last Sunday of March 2024, the 31st
+        let before = datetime!(2024-03-31 00:59:59 UTC).unix_timestamp();
+        assert_eq!(lisbon.offset(before), 0);
+        assert_eq!(lisbon.offset(before + 1), 3600);
+        assert_eq!(
+            lisbon.offset(datetime!(2024-10-27 00:59:59 UTC).unix_timestamp()),
+            3600
+        );
+        assert_eq!(
+            lisbon.offset(datetime!(2024-10-27 01:00:00 UTC).unix_timestamp()),
+            0
+        );
+
+        // and in the south it's summer over the new year
+        let auckland = Rule::parse("NZST-12NZDT,M9.5.0,M4.1.0/3").unwrap();
+        assert_eq!(
+            auckland.offset(datetime!(2024-01-01 00:00 UTC).unix_timestamp()),
+            13 * 3600
+        );
+        assert_eq!(
+            auckland.offset(datetime!(2024-07-01 00:00 UTC).unix_timestamp()),
+            12 * 3600
+        );
+    }
+
+    #[test]
+    fn days_out_of_range_leave_standard_time() {
+        let at = datetime!(2024-07-01 00:00 UTC).unix_timestamp();
+        for rule in [
+            "WET0WEST,65535,M10.5.0",
+            "WET0WEST,J65535,M10.5.0",
+            "WET0WEST,M3.5.255,M10.5.0",
+        ] {
+            assert_eq!(Rule::parse(rule).unwrap().offset(at), 0);
+        }
+    }
+
+    #[test]
+    fn files_list_changes_then_follow_their_rule() {
+        let zone = Zone::parse(&tzif(&[(1000, 7200), (2000, -10800)], "<-03>3")).unwrap();
+        assert_eq!(zone.offset(999), 3600);
+        assert_eq!(zone.offset(1000), 7200);
+        assert_eq!(zone.offset(1999), 7200);
+        assert_eq!(zone.offset(5000), -10800);
+
+        // slim files leave everything to the rule
+        let zone = Zone::parse(&tzif(&[], "WET0WEST,M3.5.0/1,M10.5.0")).unwrap();
+        assert_eq!(
+            zone.offset(datetime!(2024-07-01 00:00 UTC).unix_timestamp()),
+            3600
+        );
+        assert!(Zone::parse(b"not a tzif file").is_none());
+    }
+}
@@ -0,0 +1,153 @@
+//! A [`Renderer`] that keeps the board as a grid of cells, instead of drawing it
+//!
+//! Nothing is drawn here. It's a starting point for frontends without a terminal,
+//! like a browser build, which can paint [`Canvas::rows`] and [`Canvas::message`]
+//! however it likes after each key press.
+
+use std::convert::Infallible;
+
+use crate::{
+    frontend::Renderer,
+    game::Game,
+    state::{Guess, GuessError},
+    Match, Matches,
+};
+
+/// A square of the board
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cell {
+    pub letter: Option<char>,
+    /// How the letter matched, once it's been guessed
+    pub matched: Option<Match>,
+}
+
+pub struct Canvas {
+    game: Game,
+    rows: Vec<Vec<Cell>>,
+    /// Row and column of the next letter to type
+    cursor: (usize, usize),
+    message: Option<String>,
+}
+
+impl Canvas {
+    pub fn new(game: Game) -> Self {
+        let mut canvas = Self {
+            game,
+            rows: Vec::new(),
+            cursor: (0, 0),
+            message: None,
+        };
+        canvas.draw_window().unwrap_or_else(|never| match never {});
+        canvas
+    }
+
+    /// Every row of the board, from the first guess down
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// Why the last word was rejected, until the board is next redrawn
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    fn fill_row(&mut self, row: usize, word: &str, matches: Option<Matches>) {
+        let mut matches = matches.map(|Matches(m)| m.into_iter());
+        let mut letters = word.chars();
+        for cell in &mut self.rows[row] {
+            *cell = Cell {
+                letter: letters.next(),
+                matched: matches.as_mut().and_then(Iterator::next),
+            };
+        }
+    }
+}
+
+impl Renderer for Canvas {
+    type Error = Infallible;
+
+    fn game(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    fn draw_window(&mut self) -> Result<(), Infallible> {
+        let rows = self.game.state().rows();
+        self.rows = vec![vec![Cell::default(); self.game.word_len()]; rows];
+        self.message = None;
+        let guesses = self
+            .game
+            .state()
+            .guesses()
+            .map(|Guess(word, matches)| (word.to_owned(), matches))
+            .collect::<Vec<_>>();
+        for (row, (word, matches)) in guesses.into_iter().enumerate() {
+            self.fill_row(row, &word, Some(matches));
+        }
+        self.cursor = (self.game.state().guesses().len(), 0);
+        Ok(())
+    }
+
+    fn draw_word(&mut self, word: &str) -> Result<(), Infallible> {
+        let row = self.game.state().guesses().len();
+        if row < self.rows.len() {
+            self.fill_row(row, word, None);
+        }
+        self.cursor = (row, word.chars().count());
+        Ok(())
+    }
+
+    fn draw_guess(&mut self, word: &str, matches: Matches) -> Result<(), Infallible> {
+        let row = self.game.state().guesses().len() - 1;
+        self.fill_row(row, word, Some(matches));
+        self.cursor = (row + 1, 0);
+        self.message = None;
+        Ok(())
+    }
+
+    fn show_invalid(&mut self, _: &str, err: GuessError) -> Result<(), Infallible> {
+        self.message = Some(err.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Canvas, Cell};
+    use crate::{
+        frontend::{Key, Typing},
+        game::Game,
+        Match,
+    };
+
+    #[test]
+    fn fills_the_board() {
+        let mut canvas = Canvas::new(Game::from_day(0).unwrap());
+        let mut typing = Typing::new();
+        for c in "suite".chars() {
+            typing.press(Key::Letter(c), &mut canvas).unwrap();
+        }
+        assert_eq!(canvas.rows()[0][4].letter, Some('e'));
+        assert_eq!(canvas.cursor(), (0, 5));
+        typing.press(Key::Submit, &mut canvas).unwrap();
+        assert_eq!(
+            canvas.rows()[0][3],
+            Cell {
+                letter: Some('t'),
+                matched: Some(Match::Close)
+            }
+        );
+        assert_eq!(canvas.cursor(), (1, 0));
+
+        for c in "xxxxx".chars() {
+            typing.press(Key::Letter(c), &mut canvas).unwrap();
+        }
+        typing.press(Key::Submit, &mut canvas).unwrap();
+        assert_eq!(canvas.message(), Some("not in the word list"));
+        typing.press(Key::Clear, &mut canvas).unwrap();
+        assert_eq!(canvas.rows()[1], vec![Cell::default(); 5]);
+    }
+}
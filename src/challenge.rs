@@ -0,0 +1,361 @@
+//! Puzzle files, and shorter codes, that can be sent to a friend without revealing
+//! the solution
+//!
+//! The solution is obfuscated rather than encrypted. It keeps the word from being
+//! read at a glance, but anyone determined can still decode it.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::game::{Game, GameInitError};
+
+const MAGIC: &str = "cl-wordle challenge v1";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A puzzle with an obfuscated solution
+pub struct Challenge {
+    solution: String,
+    dictionary: Option<String>,
+    salt: u32,
+}
+
+impl Challenge {
+    /// Creates a challenge for the given solution.
+    ///
+    /// The salt changes how the solution is obfuscated, so sharing the same word twice
+    /// doesn't produce the same file.
+    pub fn new(solution: String, salt: u32) -> Self {
+        Self {
+            solution,
+            dictionary: None,
+            salt,
+        }
+    }
+
+    /// Records which dictionary the solution was picked from
+    pub fn with_dictionary(mut self, dictionary: String) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    pub fn word_len(&self) -> usize {
+        self.solution.len()
+    }
+
+    pub fn dictionary(&self) -> Option<&str> {
+        self.dictionary.as_deref()
+    }
+
+    /// Starts a custom game with the hidden solution.
+    ///
+    /// Any error is reported without the solution so it isn't leaked.
+    pub fn into_game(self) -> Result<Game, ChallengeError> {
+        Game::custom(self.solution).map_err(|err| match err {
+            GameInitError::InvalidSolution(_)
+            | GameInitError::MalformedSolution(_)
+            | GameInitError::NoWordList(_) => ChallengeError::InvalidSolution,
+            _ => ChallengeError::Format,
+        })
+    }
+
+    fn checksum(&self) -> u32 {
+        let mut hash = fnv1a(FNV_OFFSET, self.solution.as_bytes());
+        hash = fnv1a(hash, self.dictionary.as_deref().unwrap_or("").as_bytes());
+        fnv1a(hash, &self.salt.to_le_bytes())
+    }
+}
+
+const FNV_OFFSET: u32 = 0x811c9dc5;
+
+fn fnv1a(mut hash: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        hash ^= b as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash
+}
+
+/// Xors the bytes with a simple keystream derived from the salt.
+/// Applying it twice gives back the original bytes
+fn obfuscate(salt: u32, bytes: &[u8]) -> Vec<u8> {
+    let mut state = salt ^ 0x5bd1e995;
+    bytes
+        .iter()
+        .map(|&b| {
+            // xorshift32
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            b ^ state as u8
+        })
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", MAGIC)?;
+        writeln!(f, "length {}", self.word_len())?;
+        if let Some(dictionary) = &self.dictionary {
+            writeln!(f, "dictionary {}", dictionary)?;
+        }
+        writeln!(f, "salt {:08x}", self.salt)?;
+        writeln!(
+            f,
+            "solution {}",
+            to_hex(&obfuscate(self.salt, self.solution.as_bytes()))
+        )?;
+        writeln!(f, "checksum {:08x}", self.checksum())
+    }
+}
+
+impl FromStr for Challenge {
+    type Err = ChallengeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty());
+        if lines.next() != Some(MAGIC) {
+            return Err(ChallengeError::Format);
+        }
+
+        let (mut length, mut dictionary, mut salt, mut solution, mut checksum) =
+            (None, None, None, None, None);
+        for line in lines {
+            let (key, value) = line.split_once(' ').ok_or(ChallengeError::Format)?;
+            match key {
+                "length" => length = value.parse::<usize>().ok(),
+                "dictionary" => dictionary = Some(value.to_owned()),
+                "salt" => salt = u32::from_str_radix(value, 16).ok(),
+                "solution" => solution = from_hex(value),
+                "checksum" => checksum = u32::from_str_radix(value, 16).ok(),
+                _ => return Err(ChallengeError::Format),
+            }
+        }
+
+        let salt = salt.ok_or(ChallengeError::Format)?;
+        let solution = solution.ok_or(ChallengeError::Format)?;
+        let solution =
+            String::from_utf8(obfuscate(salt, &solution)).map_err(|_| ChallengeError::Checksum)?;
+
+        let challenge = Self {
+            solution,
+            dictionary,
+            salt,
+        };
+        if checksum != Some(challenge.checksum()) || length != Some(challenge.word_len()) {
+            return Err(ChallengeError::Checksum);
+        }
+        Ok(challenge)
+    }
+}
+
+/// Letters for the codes, Crockford's base32, which leaves out I, L, O and U so
+/// they're hard to misread
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A bundled word hidden in a short code, like `4D2X9KQM`, to send instead of a
+/// challenge file. The code is the salt, the word's place in the bundled lists
+/// obfuscated by the salt, and a checksum, in base32.
+///
+/// Like a challenge, the solution isn't read at a glance, but isn't hidden from
+/// anyone determined to decode it.
+pub struct Seed {
+    /// Where the solution is in the bundled solutions, then the accepted words
+    index: u16,
+    salt: u16,
+}
+
+impl Seed {
+    /// The seed for the given word, or `None` if it isn't a bundled 5 letter word
+    pub fn new(solution: &str, salt: u16) -> Option<Self> {
+        let solution = solution.to_lowercase();
+        let index = crate::words::FINAL
+            .iter()
+            .chain(crate::words::ACCEPT)
+            .position(|&word| word == solution)?;
+        Some(Self {
+            index: index as u16,
+            salt,
+        })
+    }
+
+    /// A seed for a random solution
+    pub fn random() -> Self {
+        let seed = crate::game::random_seed();
+        let index = crate::tournament::SplitMix64(seed).below(crate::words::FINAL.len() as u64);
+        Self {
+            index: index as u16,
+            salt: (seed >> 48) as u16,
+        }
+    }
+
+    pub fn solution(&self) -> &'static str {
+        let final_len = crate::words::FINAL.len();
+        match self.index as usize {
+            i if i < final_len => crate::words::FINAL[i],
+            i => crate::words::ACCEPT[i - final_len],
+        }
+    }
+
+    fn key(&self) -> u16 {
+        obfuscate(self.salt as u32, &[0, 0])
+            .iter()
+            .fold(0, |key, &b| key << 8 | b as u16)
+    }
+
+    fn checksum(&self) -> u8 {
+        let hash = fnv1a(FNV_OFFSET, &self.index.to_le_bytes());
+        fnv1a(hash, &self.salt.to_le_bytes()) as u8
+    }
+}
+
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hidden = self.index ^ self.key();
+        let bits = (self.salt as u64) << 24 | (hidden as u64) << 8 | self.checksum() as u64;
+        for i in (0..8).rev() {
+            let c = CODE_ALPHABET[(bits >> (i * 5) & 31) as usize];
+            write!(f, "{}", c as char)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Seed {
+    type Err = ChallengeError;
+
+    /// Codes are read in any case, with 0 for O and 1 for I or L as they're easy to
+    /// mix up
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().count() != 8 {
+            return Err(ChallengeError::Code);
+        }
+        let mut bits = 0u64;
+        for c in s.chars() {
+            let c = match c.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                c => c,
+            };
+            let value = CODE_ALPHABET
+                .iter()
+                .position(|&a| a as char == c)
+                .ok_or(ChallengeError::Code)?;
+            bits = bits << 5 | value as u64;
+        }
+
+        let salt = (bits >> 24) as u16;
+        let mut seed = Self { index: 0, salt };
+        seed.index = (bits >> 8) as u16 ^ seed.key();
+        let words = crate::words::FINAL.len() + crate::words::ACCEPT.len();
+        if seed.checksum() != bits as u8 || seed.index as usize >= words {
+            return Err(ChallengeError::Code);
+        }
+        Ok(seed)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a challenge could not be loaded
+pub enum ChallengeError {
+    /// The file isn't a challenge file
+    Format,
+    /// The file was corrupted
+    Checksum,
+    /// The hidden solution isn't a valid solution
+    InvalidSolution,
+    /// The code isn't one made for a [`Seed`], or was mistyped
+    Code,
+}
+
+impl fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChallengeError::Format => write!(f, "not a valid challenge file"),
+            ChallengeError::Checksum => write!(f, "challenge file is corrupted"),
+            ChallengeError::InvalidSolution => write!(f, "challenge solution is not valid"),
+            ChallengeError::Code => write!(f, "not a valid seed code"),
+        }
+    }
+}
+
+impl Error for ChallengeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Challenge, ChallengeError, Seed};
+
+    #[test]
+    fn test_challenge_roundtrip() {
+        let challenge = Challenge::new("termo".to_owned(), 42).with_dictionary("pt".to_owned());
+        let file = challenge.to_string();
+        assert!(!file.contains("termo"));
+
+        let loaded: Challenge = file.parse().unwrap();
+        assert_eq!(loaded, challenge);
+        assert_eq!(loaded.into_game().unwrap().state().solution(), "termo");
+    }
+
+    #[test]
+    fn test_challenge_corrupted() {
+        let file = Challenge::new("termo".to_owned(), 42).to_string();
+        let solution = file.lines().find(|l| l.starts_with("solution ")).unwrap();
+        let mut corrupted = solution.to_owned();
+        let last = corrupted.pop().unwrap();
+        corrupted.push(if last == '0' { '1' } else { '0' });
+
+        let file = file.replace(solution, &corrupted);
+        assert_eq!(file.parse::<Challenge>(), Err(ChallengeError::Checksum));
+    }
+
+    #[test]
+    fn test_challenge_salted() {
+        let a = Challenge::new("termo".to_owned(), 1).to_string();
+        let b = Challenge::new("termo".to_owned(), 2).to_string();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_seed_roundtrip() {
+        for word in ["termo", "suite", "uvula"] {
+            let seed = Seed::new(word, 1234).unwrap();
+            let code = seed.to_string();
+            assert_eq!(code.len(), 8);
+            assert_eq!(code.parse::<Seed>(), Ok(seed));
+            assert_eq!(code.to_lowercase().parse::<Seed>(), Ok(seed));
+            assert_eq!(seed.solution(), word);
+        }
+        assert_eq!(Seed::new("xxxxx", 1), None);
+    }
+
+    #[test]
+    fn test_seed_salted() {
+        let a = Seed::new("termo", 1).unwrap().to_string();
+        let b = Seed::new("termo", 2).unwrap().to_string();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_seed_mistyped() {
+        let code = Seed::new("termo", 42).unwrap().to_string();
+        let mut mistyped = code.clone();
+        let last = mistyped.pop().unwrap();
+        mistyped.push(if last == '0' { '1' } else { '0' });
+        assert_eq!(mistyped.parse::<Seed>(), Err(ChallengeError::Code));
+        assert_eq!(code[..7].parse::<Seed>(), Err(ChallengeError::Code));
+        assert_eq!("UUUUUUUU".parse::<Seed>(), Err(ChallengeError::Code));
+    }
+}
@@ -0,0 +1,46 @@
+//! Where the current time comes from, to work out which day's puzzle is today.
+//!
+//! Games started with [`Game::new`](crate::game::Game::new) read the system clock
+//! through [`System`]. There's no system clock on `wasm32-unknown-unknown`, so
+//! [`System`] isn't there, and a web frontend passes in the browser's time with
+//! [`Fixed`] and [`Game::from_clock`](crate::game::Game::from_clock) instead.
+
+use time::{OffsetDateTime, UtcOffset};
+
+/// Tells the time, and the timezone it's in
+pub trait Clock {
+    fn now_utc(&self) -> OffsetDateTime;
+
+    /// The local timezone's offset from UTC, or `None` if it can't be found
+    fn local_offset(&self) -> Option<UtcOffset>;
+}
+
+/// The system clock and timezone
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct System;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Clock for System {
+    fn now_utc(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    fn local_offset(&self) -> Option<UtcOffset> {
+        UtcOffset::current_local_offset().ok()
+    }
+}
+
+/// Always the same time, in its own offset, such as one taken from a browser
+#[derive(Clone, Copy, Debug)]
+pub struct Fixed(pub OffsetDateTime);
+
+impl Clock for Fixed {
+    fn now_utc(&self) -> OffsetDateTime {
+        self.0.to_offset(UtcOffset::UTC)
+    }
+
+    fn local_offset(&self) -> Option<UtcOffset> {
+        Some(self.0.offset())
+    }
+}
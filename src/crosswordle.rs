@@ -0,0 +1,291 @@
+//! Crosswordle, which plays a finished board backwards: the solution and the colours
+//! of every guess are shown, and the guesses that would have been coloured like that
+//! have to be filled back in.
+//!
+//! Any word in the word lists that [fits](fits) a row's colours will do, not just the
+//! one the board was made from, and the rows can be filled in any order.
+
+use std::fmt;
+
+use crate::{
+    game::{GameType, HEADER},
+    solver,
+    state::Guess,
+    tournament::SplitMix64,
+    Match, Matches,
+};
+
+pub struct Crosswordle {
+    solution: String,
+    /// The colours each row has to come out as, from the top. The solution goes on
+    /// the row below them
+    target: Vec<Matches>,
+    /// The word filled in on each row, if it has one yet
+    filled: Vec<Option<String>>,
+    /// Words tried that didn't fit their row
+    misses: usize,
+    game_type: GameType,
+}
+
+impl Crosswordle {
+    /// The board of the day's solution. It's made by a player guessing at random, but
+    /// only ever words that fit what they'd seen, so it's the same every time
+    pub fn from_day(day: usize) -> Self {
+        let solution = crate::get_solution(day);
+        let mut rng = SplitMix64(day as u64);
+        let target = loop {
+            let board = random_board(solution, &mut rng);
+            // a lucky first guess leaves nothing to fill in
+            if (1..crate::MAX_GUESSES).contains(&board.len()) {
+                break board;
+            }
+        };
+        Self::new(solution.to_owned(), target, GameType::Daily(day))
+    }
+
+    /// A board of the colours given for the solution, which the rows between them
+    /// have to fit
+    pub fn custom(solution: String, target: Vec<Matches>) -> Self {
+        Self::new(solution, target, GameType::Custom)
+    }
+
+    fn new(solution: String, target: Vec<Matches>, game_type: GameType) -> Self {
+        Self {
+            solution,
+            filled: vec![None; target.len()],
+            target,
+            misses: 0,
+            game_type,
+        }
+    }
+
+    pub fn solution(&self) -> &str {
+        &self.solution
+    }
+
+    /// The colours each row has to come out as, from the top, not counting the
+    /// solution's own row
+    pub fn target(&self) -> &[Matches] {
+        &self.target
+    }
+
+    /// The word filled in on each row, if it has one yet
+    pub fn filled(&self) -> &[Option<String>] {
+        &self.filled
+    }
+
+    /// Number of words tried that didn't fit their row
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn game_type(&self) -> &GameType {
+        &self.game_type
+    }
+
+    /// Number of letters in the solution, and so in every row
+    pub fn word_len(&self) -> usize {
+        self.solution.chars().count()
+    }
+
+    /// Whether every row has a word that fits it
+    pub fn solved(&self) -> bool {
+        self.filled.iter().all(Option::is_some)
+    }
+
+    /// Fills in the row with the word, if it's one that would have been coloured
+    /// like the row. A word that's been filled in can be replaced.
+    ///
+    /// A word in the word lists that doesn't fit counts as a miss
+    pub fn fill(&mut self, row: usize, word: &str) -> Result<(), FillError> {
+        if self.solved() {
+            return Err(FillError::Solved);
+        }
+        let target = match self.target.get(row) {
+            Some(target) => target,
+            None => return Err(FillError::NoSuchRow(row)),
+        };
+        let (expected, got) = (self.word_len(), word.chars().count());
+        if got != expected {
+            return Err(FillError::WrongLength { expected, got });
+        }
+        if !crate::valid(word) {
+            return Err(FillError::NotInWordList);
+        }
+        let matches = crate::diff(word, &self.solution);
+        if matches != *target {
+            self.misses += 1;
+            return Err(FillError::DoesntFit(matches));
+        }
+        self.filled[row] = Some(word.to_owned());
+        Ok(())
+    }
+
+    pub fn share(&self) -> CrosswordleShare {
+        CrosswordleShare {
+            game_type: self.game_type.clone(),
+            target: self.target.clone(),
+            misses: self.misses,
+            solved: self.solved(),
+        }
+    }
+}
+
+/// Whether the word, guessed against the solution, would be coloured like the pattern
+pub fn fits(word: &str, solution: &str, pattern: &Matches) -> bool {
+    crate::diff(word, solution) == *pattern
+}
+
+/// The colours of a game of the solution, played by guessing any solution that fits
+/// the guesses before it. The solution's own row is left off
+fn random_board(solution: &str, rng: &mut SplitMix64) -> Vec<Matches> {
+    let mut remaining = crate::words::FINAL.to_vec();
+    let mut board = Vec::new();
+    loop {
+        let guess = remaining[rng.below(remaining.len() as u64) as usize];
+        if guess == solution {
+            return board;
+        }
+        let matches = crate::diff(guess, solution);
+        remaining = solver::candidates(&[Guess(guess, matches.clone())], &remaining);
+        board.push(matches);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a word couldn't be filled in by [`Crosswordle::fill`]
+pub enum FillError {
+    /// The word isn't in the word lists
+    NotInWordList,
+    /// The word isn't as long as the solution. Lengths are in letters, not bytes
+    WrongLength { expected: usize, got: usize },
+    /// The word would have been coloured like this instead
+    DoesntFit(Matches),
+    /// There's no row with this number
+    NoSuchRow(usize),
+    /// Every row was already filled in
+    Solved,
+}
+
+impl fmt::Display for FillError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FillError::NotInWordList => f.write_str("not in the word list"),
+            FillError::WrongLength { expected, got } => {
+                write!(f, "need {} letters, got {}", expected, got)
+            }
+            FillError::DoesntFit(matches) => write!(f, "that would be {}", matches),
+            FillError::NoSuchRow(row) => write!(f, "there's no row {}", row + 1),
+            FillError::Solved => f.write_str("every row is filled in"),
+        }
+    }
+}
+
+impl std::error::Error for FillError {}
+
+/// The board's colours, with how many words were tried that didn't fit
+pub struct CrosswordleShare {
+    game_type: GameType,
+    target: Vec<Matches>,
+    misses: usize,
+    solved: bool,
+}
+
+impl CrosswordleShare {
+    pub fn game_type(&self) -> &GameType {
+        &self.game_type
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Whether every row was filled in
+    pub fn solved(&self) -> bool {
+        self.solved
+    }
+}
+
+impl fmt::Display for CrosswordleShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{HEADER}{} crosswordle", self.game_type)?;
+        match (self.solved, self.misses) {
+            (false, _) => write!(f, " X")?,
+            (true, 1) => write!(f, " 1 miss")?,
+            (true, misses) => write!(f, " {} misses", misses)?,
+        }
+        writeln!(f)?;
+        let len = self.target.first().map_or(0, |row| row.0.len());
+        for row in &self.target {
+            write!(f, "\n{}", row)?;
+        }
+        write!(f, "\n{}", Matches(vec![Match::Exact; len]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fits, Crosswordle, FillError};
+    use crate::{diff, game::GameType};
+
+    fn board() -> Crosswordle {
+        Crosswordle::custom(
+            "termo".to_owned(),
+            vec![diff("sagaz", "termo"), diff("sorte", "termo")],
+        )
+    }
+
+    #[test]
+    fn any_word_with_the_colours_fits() {
+        let mut board = board();
+        assert!(fits("sorte", "termo", &board.target()[1]));
+        assert!(!fits("termo", "termo", &board.target()[1]));
+        assert_eq!(board.fill(1, "sorte"), Ok(()));
+        assert!(!board.solved());
+
+        // sagaz has none of termo's letters, so any other word without them fits too
+        assert_eq!(board.fill(0, "xxxxx"), Err(FillError::NotInWordList));
+        assert_eq!(
+            board.fill(0, "sorte"),
+            Err(FillError::DoesntFit(diff("sorte", "termo")))
+        );
+        assert_eq!(
+            board.fill(0, "nuvem").unwrap_err().to_string(),
+            "that would be ⬛⬛⬛🟨🟨"
+        );
+        assert_eq!(board.fill(0, "chuva"), Ok(()));
+        assert!(board.solved());
+        assert_eq!(board.misses(), 2);
+        assert_eq!(board.fill(0, "sagaz"), Err(FillError::Solved));
+        assert_eq!(
+            board.share().to_string(),
+            "joguei term.ooo #custom crosswordle 2 misses\n\n⬛⬛⬛⬛⬛\n⬛🟨🟩🟨🟨\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[test]
+    fn rows_must_exist() {
+        let mut board = board();
+        assert_eq!(board.fill(2, "termo"), Err(FillError::NoSuchRow(2)));
+        assert_eq!(
+            board.fill(0, "sol"),
+            Err(FillError::WrongLength {
+                expected: 5,
+                got: 3
+            })
+        );
+        assert_eq!(board.misses(), 0);
+    }
+
+    #[test]
+    fn the_daily_board_can_be_filled() {
+        let board = Crosswordle::from_day(0);
+        assert_eq!(board.solution(), "termo");
+        assert_eq!(board.game_type(), &GameType::Daily(0));
+        assert!((1..crate::MAX_GUESSES).contains(&board.target().len()));
+        for row in board.target() {
+            assert!(row.0.iter().any(|&m| m != crate::Match::Exact));
+        }
+        assert_eq!(Crosswordle::from_day(0).target(), board.target());
+    }
+}
@@ -0,0 +1,87 @@
+//! Short definitions of the solutions, to show once a game is over.
+//!
+//! A definition is bundled for some of the solutions. Others can be added from a
+//! file in the same format, or with the `net` feature, looked up in Wiktionary.
+
+use std::collections::HashMap;
+
+/// A word, a tab and its definition on each line. Lines starting with `#` are comments
+const BUNDLED: &str = include_str!("definitions.tsv");
+
+/// A definition for each word that has one
+#[derive(Clone, Debug, Default)]
+pub struct Definitions {
+    words: HashMap<String, String>,
+}
+
+impl Definitions {
+    /// The definitions bundled with the game
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED)
+    }
+
+    /// Reads a list with a word, a tab and its definition on each line. Lines without
+    /// a tab are skipped, and a word defined twice keeps the last definition
+    pub fn parse(list: &str) -> Self {
+        let mut definitions = Self::default();
+        definitions.extend(list);
+        definitions
+    }
+
+    /// Adds the definitions in the list, replacing any already there for the same words
+    pub fn extend(&mut self, list: &str) {
+        let lines = list.lines().filter(|line| !line.starts_with('#'));
+        for (word, definition) in lines.filter_map(|line| line.split_once('\t')) {
+            let (word, definition) = (word.trim().to_lowercase(), definition.trim());
+            if !word.is_empty() && !definition.is_empty() {
+                self.words.insert(word, definition.to_owned());
+            }
+        }
+    }
+
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.words.get(word).map(String::as_str)
+    }
+
+    /// Number of words with a definition
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Looks the word up in Wiktionary, for the first of its Portuguese definitions.
+///
+/// `Ok(None)` if there's no Portuguese entry for it
+#[cfg(feature = "net")]
+pub fn lookup(word: &str) -> Result<Option<String>, String> {
+    crate::remote::define(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Definitions;
+
+    #[test]
+    fn parse() {
+        let mut definitions =
+            Definitions::parse("# comment\ttoo\nTermo\tpalavra\nsuite\n\ntermo\t fim \n");
+        assert_eq!(definitions.get("termo"), Some("fim"));
+        assert_eq!(definitions.get("suite"), None);
+        assert_eq!(definitions.len(), 1);
+        definitions.extend("suite\tquarto");
+        assert_eq!(definitions.get("suite"), Some("quarto"));
+    }
+
+    #[test]
+    fn bundled_words_are_solutions() {
+        let definitions = Definitions::bundled();
+        assert!(definitions.len() > 50);
+        for word in definitions.words.keys() {
+            assert!(crate::words::FINAL.contains(&word.as_str()), "{}", word);
+        }
+    }
+}
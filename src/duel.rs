@@ -0,0 +1,190 @@
+//! Two players taking turns on the same device: one picks a word, the other guesses it.
+//!
+//! [`Duel`] keeps the turns and the score, and leaves reading the word without
+//! showing it to the frontend. Each round starts with [`Duel::start`] and is scored
+//! with [`Duel::record`] once its game is over.
+
+use std::fmt;
+
+use crate::game::{Game, GameInitError, Score};
+
+/// A round that was played, and the points the solver got for it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Round {
+    /// Index into [`Duel::players`] of whoever picked the word
+    pub setter: usize,
+    pub solution: String,
+    pub score: Score,
+    pub max_guesses: usize,
+}
+
+impl Round {
+    /// Index into [`Duel::players`] of whoever guessed the word
+    pub fn solver(&self) -> usize {
+        1 - self.setter
+    }
+
+    /// A point for each guess left over, counting the one that solved it, so a win
+    /// on the last guess is 1 and a loss is 0
+    pub fn points(&self) -> usize {
+        match self.score {
+            Score::Guesses(n) => (self.max_guesses + 1).saturating_sub(n as usize),
+            Score::Failed => 0,
+        }
+    }
+}
+
+/// The players, whose turn it is, and the rounds played so far
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Duel {
+    players: [String; 2],
+    rounds: Vec<Round>,
+}
+
+impl Duel {
+    /// The first player picks the first word
+    pub fn new(first: String, second: String) -> Self {
+        Self {
+            players: [first, second],
+            rounds: Vec::new(),
+        }
+    }
+
+    pub fn players(&self) -> &[String; 2] {
+        &self.players
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// Index into [`Duel::players`] of whoever picks the next word. The players
+    /// swap every round
+    pub fn setter(&self) -> usize {
+        self.rounds.len() % 2
+    }
+
+    /// Index into [`Duel::players`] of whoever guesses the next word
+    pub fn solver(&self) -> usize {
+        1 - self.setter()
+    }
+
+    /// Starts the next round's game, with the word the setter picked. It has to be
+    /// in the word lists, as for a custom game
+    pub fn start(&self, word: &str) -> Result<Game, GameInitError> {
+        Game::custom(word.trim().to_lowercase())
+    }
+
+    /// Scores the round once its game is over, and passes the turn on
+    pub fn record(&mut self, solution: String, score: Score, max_guesses: usize) {
+        self.rounds.push(Round {
+            setter: self.setter(),
+            solution,
+            score,
+            max_guesses,
+        });
+    }
+
+    /// Each player's points, from every round they guessed in
+    pub fn points(&self) -> [usize; 2] {
+        let mut points = [0; 2];
+        for round in &self.rounds {
+            points[round.solver()] += round.points();
+        }
+        points
+    }
+
+    /// Index into [`Duel::players`] of whoever has the most points, or `None` for a tie
+    pub fn leader(&self) -> Option<usize> {
+        let [first, second] = self.points();
+        match first.cmp(&second) {
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// A line for each round, then the points, like
+///
+/// ```text
+/// round 1  bia solved ana's TERMO in 3/6, 4 points
+/// round 2  ana didn't solve bia's SAGAZ, 0 points
+///
+/// ana 0, bia 4: bia leads
+/// ```
+impl fmt::Display for Duel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, round) in self.rounds.iter().enumerate() {
+            let solver = &self.players[round.solver()];
+            let setter = &self.players[round.setter];
+            let solution = round.solution.to_uppercase();
+            write!(f, "round {}  ", i + 1)?;
+            match round.score {
+                Score::Guesses(n) => write!(
+                    f,
+                    "{} solved {}'s {} in {}/{}",
+                    solver, setter, solution, n, round.max_guesses
+                )?,
+                Score::Failed => write!(f, "{} didn't solve {}'s {}", solver, setter, solution)?,
+            }
+            match round.points() {
+                1 => writeln!(f, ", 1 point")?,
+                n => writeln!(f, ", {} points", n)?,
+            }
+        }
+        if !self.rounds.is_empty() {
+            writeln!(f)?;
+        }
+        let [first, second] = self.points();
+        write!(
+            f,
+            "{} {}, {} {}: ",
+            self.players[0], first, self.players[1], second
+        )?;
+        match self.leader() {
+            Some(leader) => write!(f, "{} leads", self.players[leader]),
+            None => write!(f, "tied"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Duel;
+    use crate::game::Score;
+
+    #[test]
+    fn players_take_turns() {
+        let mut duel = Duel::new("ana".to_owned(), "bia".to_owned());
+        assert_eq!((duel.setter(), duel.solver()), (0, 1));
+        let game = duel.start(" TERMO\n").unwrap();
+        assert_eq!(game.state().solution(), "termo");
+        assert!(duel.start("xxxxx").is_err());
+
+        duel.record("termo".to_owned(), Score::Guesses(3), 6);
+        assert_eq!((duel.setter(), duel.solver()), (1, 0));
+        assert_eq!(duel.points(), [0, 4]);
+        assert_eq!(duel.leader(), Some(1));
+
+        duel.record("sagaz".to_owned(), Score::Failed, 6);
+        assert_eq!(duel.points(), [0, 4]);
+        duel.record("campo".to_owned(), Score::Guesses(6), 6);
+        assert_eq!(duel.points(), [0, 5]);
+    }
+
+    #[test]
+    fn scoreboard() {
+        let mut duel = Duel::new("ana".to_owned(), "bia".to_owned());
+        assert_eq!(duel.to_string(), "ana 0, bia 0: tied");
+        duel.record("termo".to_owned(), Score::Guesses(6), 6);
+        duel.record("sagaz".to_owned(), Score::Failed, 6);
+        assert_eq!(
+            duel.to_string(),
+            "round 1  bia solved ana's TERMO in 6/6, 1 point\n\
+             round 2  ana didn't solve bia's SAGAZ, 0 points\n\
+             \n\
+             ana 0, bia 1: bia leads"
+        );
+    }
+}
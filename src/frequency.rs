@@ -0,0 +1,59 @@
+//! How common words are, from a word list sorted by frequency.
+//!
+//! No frequency list is bundled, so one has to be supplied, such as one built from a
+//! corpus of subtitles or news articles.
+
+use std::collections::HashMap;
+
+/// The rank of each word in a frequency list, where 0 is the most common
+#[derive(Clone, Debug, Default)]
+pub struct Frequencies {
+    ranks: HashMap<String, usize>,
+}
+
+impl Frequencies {
+    /// Reads a list of words, one per line, most common first.
+    ///
+    /// Anything after the first whitespace on a line is ignored, so lists with counts
+    /// such as `termo 1234` can be used as is
+    pub fn parse(list: &str) -> Self {
+        let mut ranks = HashMap::new();
+        let words = list
+            .lines()
+            .filter_map(|line| line.split_whitespace().next());
+        for word in words {
+            let rank = ranks.len();
+            ranks.entry(word.to_lowercase()).or_insert(rank);
+        }
+        Self { ranks }
+    }
+
+    /// Gets how common the word is, or `None` if it's not in the list
+    pub fn rank(&self, word: &str) -> Option<usize> {
+        self.ranks.get(word).copied()
+    }
+
+    /// Number of words in the list
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Frequencies;
+
+    #[test]
+    fn parse() {
+        let frequencies = Frequencies::parse("Termo 900\nsuite 12\n\ntermo 3\nsagaz\n");
+        assert_eq!(frequencies.rank("termo"), Some(0));
+        assert_eq!(frequencies.rank("suite"), Some(1));
+        assert_eq!(frequencies.rank("sagaz"), Some(2));
+        assert_eq!(frequencies.rank("fungo"), None);
+        assert_eq!(frequencies.len(), 3);
+    }
+}
@@ -0,0 +1,204 @@
+//! Turning key presses into guesses, for any frontend that can draw a board
+//!
+//! [`Typing`] keeps the word being typed, makes the guess once it's complete and
+//! checks whether the game is over. All a frontend has to do is feed it [`Key`]s and
+//! implement [`Renderer`] to draw what changed, so the same rules apply whether the
+//! board is in a terminal or on a canvas.
+
+use crate::{game::Game, state::GuessError, Matches};
+
+/// The presses that change the word being typed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    /// Types a letter, if the word isn't complete yet
+    Letter(char),
+    /// Deletes the last letter
+    Delete,
+    /// Deletes the whole word
+    Clear,
+    /// Guesses the word, if it's complete
+    Submit,
+}
+
+/// Draws the game, as told to by [`Typing`]
+pub trait Renderer {
+    type Error;
+
+    /// The game being played
+    fn game(&mut self) -> &mut Game;
+
+    /// Redraws everything, including every guess made so far
+    fn draw_window(&mut self) -> Result<(), Self::Error>;
+
+    /// Draws the word being typed on the current row, blanking the cells after it,
+    /// and leaves the cursor on the next cell
+    fn draw_word(&mut self, word: &str) -> Result<(), Self::Error>;
+
+    /// Draws the guess that was just made, with how each letter matched
+    fn draw_guess(&mut self, word: &str, matches: Matches) -> Result<(), Self::Error>;
+
+    /// Shows that the word was rejected, and why
+    fn show_invalid(&mut self, word: &str, err: GuessError) -> Result<(), Self::Error>;
+}
+
+/// The word being typed
+#[derive(Clone, Debug, Default)]
+pub struct Typing {
+    word: String,
+}
+
+impl Typing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Number of letters typed so far
+    pub fn len(&self) -> usize {
+        self.word.chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.word.is_empty()
+    }
+
+    /// Replaces the word, such as with an earlier guess, and draws it
+    pub fn replace<R: Renderer>(&mut self, word: &str, renderer: &mut R) -> Result<(), R::Error> {
+        self.word.clear();
+        self.word.push_str(word);
+        renderer.draw_word(&self.word)
+    }
+
+    /// Redraws everything, then the word being typed on top
+    pub fn redraw<R: Renderer>(&self, renderer: &mut R) -> Result<(), R::Error> {
+        renderer.draw_window()?;
+        renderer.draw_word(&self.word)
+    }
+
+    /// Handles a key press, returning whether the game was won once it's over.
+    ///
+    /// Letters are lowercased, and presses that don't apply, like a letter when the
+    /// word is already complete, are ignored. The word is cleared after each
+    /// accepted guess, and kept after a rejected one so it can be fixed
+    pub fn press<R: Renderer>(
+        &mut self,
+        key: Key,
+        renderer: &mut R,
+    ) -> Result<Option<bool>, R::Error> {
+        let len = renderer.game().word_len();
+        match key {
+            Key::Letter(c) if c.is_alphabetic() && self.len() < len => {
+                self.word.extend(c.to_lowercase());
+                renderer.draw_word(&self.word)?;
+            }
+            Key::Delete if !self.word.is_empty() => {
+                self.word.pop();
+                renderer.draw_word(&self.word)?;
+            }
+            Key::Clear => {
+                self.word.clear();
+                renderer.draw_word(&self.word)?;
+            }
+            Key::Submit if self.len() == len => match renderer.game().guess(&self.word) {
+                Ok(matches) => {
+                    renderer.draw_guess(&self.word, matches)?;
+                    if let Some(win) = renderer.game().game_over() {
+                        return Ok(Some(win));
+                    }
+                    self.word.clear();
+                }
+                Err(err) => renderer.show_invalid(&self.word, err)?,
+            },
+            _ => {}
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::{Key, Renderer, Typing};
+    use crate::{game::Game, state::GuessError, Matches};
+
+    /// Writes down what it was asked to draw
+    struct Recorder(Game, Vec<String>);
+
+    impl Renderer for Recorder {
+        type Error = Infallible;
+
+        fn game(&mut self) -> &mut Game {
+            &mut self.0
+        }
+
+        fn draw_window(&mut self) -> Result<(), Infallible> {
+            self.1.push("window".to_owned());
+            Ok(())
+        }
+
+        fn draw_word(&mut self, word: &str) -> Result<(), Infallible> {
+            self.1.push(format!("word {}", word));
+            Ok(())
+        }
+
+        fn draw_guess(&mut self, word: &str, matches: Matches) -> Result<(), Infallible> {
+            self.1.push(format!("guess {} {}", word, matches));
+            Ok(())
+        }
+
+        fn show_invalid(&mut self, word: &str, err: GuessError) -> Result<(), Infallible> {
+            self.1.push(format!("invalid {} {}", word, err));
+            Ok(())
+        }
+    }
+
+    fn press(typing: &mut Typing, keys: &str, renderer: &mut Recorder) -> Option<bool> {
+        let mut over = None;
+        for c in keys.chars() {
+            let key = match c {
+                '\n' => Key::Submit,
+                '\x08' => Key::Delete,
+                '\x15' => Key::Clear,
+                c => Key::Letter(c),
+            };
+            over = typing.press(key, renderer).unwrap();
+        }
+        over
+    }
+
+    #[test]
+    fn typing_a_game() {
+        let mut renderer = Recorder(Game::from_day(0).unwrap(), Vec::new());
+        let mut typing = Typing::new();
+        // too short to submit, and too long to take the last letter
+        assert_eq!(press(&mut typing, "SUIT\nex", &mut renderer), None);
+        assert_eq!(typing.word(), "suite");
+        assert_eq!(press(&mut typing, "\n", &mut renderer), None);
+        assert!(typing.is_empty());
+
+        assert_eq!(press(&mut typing, "xxxxx\n", &mut renderer), None);
+        assert_eq!(
+            renderer.1.last().unwrap(),
+            "invalid xxxxx not in the word list"
+        );
+        assert_eq!(
+            press(&mut typing, "\x15ter\x08rmo\n", &mut renderer),
+            Some(true)
+        );
+        assert_eq!(typing.word(), "termo");
+
+        let drawn = renderer.1.iter().filter(|d| !d.starts_with("word"));
+        assert_eq!(
+            drawn.collect::<Vec<_>>(),
+            [
+                "guess suite ⬛⬛⬛🟨🟨",
+                "invalid xxxxx not in the word list",
+                "guess termo 🟩🟩🟩🟩🟩",
+            ]
+        );
+    }
+}
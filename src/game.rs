@@ -1,51 +1,354 @@
-use std::fmt;
+use std::{error::Error, fmt, str::FromStr, time::Duration};
 
-use crate::state::State;
-use eyre::{ensure, Result};
+use crate::{
+    analysis::Analysis,
+    source::SharedSource,
+    state::{Guess, GuessError, Reveal, State},
+    Match, Matches,
+};
 
 pub struct Game {
     state: State,
     game_type: GameType,
+    par: Option<usize>,
 }
 
 impl Game {
+    /// Starts today's daily game, according to the local timezone.
+    ///
+    /// Fails with [`GameInitError::TimezoneUnknown`] if the local offset can't be
+    /// determined, in which case callers may want to fall back to
+    /// [`Game::from_date`] with a UTC date. There's no local timezone or clock on
+    /// `wasm32-unknown-unknown`, where [`Game::from_clock`] is used instead.
+    #[cfg(all(
+        feature = "time",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub fn new() -> Result<Self, GameInitError> {
+        Self::from_offset(None)
+    }
+
+    /// Starts today's daily game in hard mode, according to the local timezone. The same
+    /// as [`Game::new`] followed by [`Game::with_hard_mode`]
+    #[cfg(all(
+        feature = "time",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub fn hard() -> Result<Self, GameInitError> {
+        Ok(Self::new()?.with_hard_mode(true))
+    }
+
+    /// Starts today's daily game, according to the given offset from UTC rather than
+    /// the local timezone, or the local timezone if it's `None`
+    #[cfg(all(
+        feature = "time",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub fn from_offset(offset: Option<time::UtcOffset>) -> Result<Self, GameInitError> {
+        Self::from_clock(&crate::clock::System, offset)
+    }
+
+    /// Starts today's daily game by the clock, at the given offset from UTC, or in
+    /// the clock's timezone if it's `None`
     #[cfg(feature = "time")]
-    pub fn new() -> Result<Self> {
-        use eyre::WrapErr;
-        let now =
-            time::OffsetDateTime::now_local().wrap_err("could not determine local timezone")?;
-        Self::from_date(now.date())
+    pub fn from_clock(
+        clock: &dyn crate::clock::Clock,
+        offset: Option<time::UtcOffset>,
+    ) -> Result<Self, GameInitError> {
+        Self::from_date(crate::now_from(clock, offset)?.date())
     }
 
-    pub fn custom(solution: String) -> Result<Self> {
+    /// Starts a game with the given solution, which must be in the word lists.
+    ///
+    /// The solution is lowercased first, so `"HELLO"` plays the same as `"hello"`.
+    /// Fails with [`GameInitError::MalformedSolution`] if it isn't all letters, and
+    /// [`GameInitError::NoWordList`] if there's no list for its length.
+    pub fn custom(solution: String) -> Result<Self, GameInitError> {
         Self::new_raw(solution, GameType::Custom)
     }
 
+    /// Starts a game with a solution that doesn't have to be in the word lists, such
+    /// as a name. It still has to be letters, which are lowercased, but can be any length
+    pub fn custom_unlisted(solution: String) -> Result<Self, GameInitError> {
+        Self::new_unlisted(solution.to_lowercase(), GameType::Custom)
+    }
+
     #[cfg(feature = "time")]
-    pub fn from_date(date: time::Date) -> Result<Self> {
+    pub fn from_date(date: time::Date) -> Result<Self, GameInitError> {
         let day = crate::get_day(date);
         Self::from_day(day)
     }
 
-    pub fn from_day(day: usize) -> Result<Self> {
+    pub fn from_day(day: usize) -> Result<Self, GameInitError> {
         let solution = crate::get_solution(day).to_owned();
         Self::new_raw(solution, GameType::Daily(day))
     }
 
-    fn new_raw(solution: String, game_type: GameType) -> Result<Self> {
-        ensure!(
-            crate::words::FINAL.contains(&&*solution),
-            "{} is not a valid solution",
-            solution
-        );
+    /// Starts the given day's game with words of a different length than the
+    /// default 5, from the bundled lists for that length.
+    ///
+    /// Fails with [`GameInitError::NoWordList`] if there's no list for the length.
+    pub fn from_day_with_length(day: usize, len: usize) -> Result<Self, GameInitError> {
+        let source = crate::source::bundled(len).ok_or(GameInitError::NoWordList(len))?;
+        Self::from_source(source, day)
+    }
+
+    /// Starts the given day's game, with the solution and accepted guesses decided by
+    /// `source` instead of the bundled lists
+    pub fn from_source(source: SharedSource, day: usize) -> Result<Self, GameInitError> {
+        let solution = source.solution_for_day(day).to_owned();
+        let mut game = Self::new_unlisted(solution, GameType::Daily(day))?;
+        game.state.set_source(source);
+        Ok(game)
+    }
+
+    /// Starts a game with the solution for the day and the accepted guesses taken from
+    /// a dictionary of the player's own, such as one read by
+    /// [`WordList::parse`](crate::source::WordList::parse).
+    ///
+    /// The game counts as custom, so the share doesn't give a day number that only
+    /// means something with that dictionary.
+    pub fn from_dictionary(source: SharedSource, day: usize) -> Result<Self, GameInitError> {
+        let mut game = Self::from_source(source, day)?;
+        game.game_type = GameType::Custom;
+        Ok(game)
+    }
+
+    /// Starts today's daily game with the solution served at `url`, for dailies
+    /// that don't follow the bundled list.
+    ///
+    /// The response body should be just the solution word. Each fetched solution is
+    /// cached, so if the server can't be reached the day can still be played as long
+    /// as it was fetched once before. Otherwise this fails with
+    /// [`GameInitError::Fetch`], and callers may want to fall back to [`Game::new`].
+    ///
+    /// Today is decided as by [`Game::from_offset`].
+    #[cfg(feature = "net")]
+    pub fn from_url(url: &str, offset: Option<time::UtcOffset>) -> Result<Self, GameInitError> {
+        let day = crate::get_day(crate::now(offset)?.date());
+
+        match crate::remote::fetch(url) {
+            Ok(solution) => {
+                let game = Self::new_raw(solution, GameType::Daily(day))?;
+                // the cache is only a fallback, so failing to write it isn't an error
+                let _ = crate::remote::store(day, game.state.solution());
+                Ok(game)
+            }
+            Err(err) => match crate::remote::cached(day) {
+                Some(solution) => Self::new_raw(solution, GameType::Daily(day)),
+                None => Err(GameInitError::Fetch(err)),
+            },
+        }
+    }
+
+    /// Starts a practice game with a random solution, to play as many as wanted
+    /// on top of the daily.
+    ///
+    /// The same seed always gives the same word, so two players can race each
+    /// other. Without one, a seed is picked at random. Either way it's shown as part
+    /// of the [`GameType::Practice`] in the header and share.
+    pub fn random(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(random_seed);
+        // mixed, so neighbouring seeds don't just walk through the dailies in order
+        let i = crate::tournament::SplitMix64(seed).below(crate::words::FINAL.len() as u64);
+        let solution = crate::words::FINAL[i as usize].to_owned();
+        Self {
+            state: State::new(solution),
+            game_type: GameType::Practice { seed },
+            par: None,
+        }
+    }
+
+    /// Starts an adversarial game, like Absurdle, where there's no solution up front.
+    ///
+    /// Each guess gets the matches that leave the most of the bundled solutions
+    /// possible, and the game is only won once a single word is left and it's guessed.
+    /// There's no limit on the guesses, and the share gives the score without one.
+    /// See [`Adversarial`](crate::state::Adversarial)
+    pub fn absurdle() -> Self {
+        Self {
+            state: State::adversarial(crate::words::FINAL),
+            game_type: GameType::Absurdle,
+            par: None,
+        }
+    }
+
+    /// Starts the given round of a tournament. See [`tournament`](crate::tournament)
+    pub fn tournament(seed: String, round: usize) -> Result<Self, GameInitError> {
+        let solution = crate::tournament::solution(&seed, round).to_owned();
+        Self::new_raw(solution, GameType::Tournament { seed, round })
+    }
+
+    /// Starts a game of the word hidden in a seed's code, which the header and share
+    /// give in place of a day. See [`Seed`](crate::challenge::Seed)
+    pub fn seeded(seed: crate::challenge::Seed) -> Self {
+        Self {
+            state: State::new(seed.solution().to_owned()),
+            game_type: GameType::Seeded {
+                code: seed.to_string(),
+            },
+            par: None,
+        }
+    }
+
+    fn new_raw(solution: String, game_type: GameType) -> Result<Self, GameInitError> {
+        let solution = solution.to_lowercase();
+        if !well_formed(&solution) {
+            return Err(GameInitError::MalformedSolution(solution));
+        }
+        let len = solution.chars().count();
+        if crate::source::bundled(len).is_none() {
+            return Err(GameInitError::NoWordList(len));
+        }
+        if !crate::valid(&solution) {
+            return Err(GameInitError::InvalidSolution(solution));
+        }
+        Self::new_unlisted(solution, game_type)
+    }
+
+    fn new_unlisted(solution: String, game_type: GameType) -> Result<Self, GameInitError> {
+        if !well_formed(&solution) {
+            return Err(GameInitError::MalformedSolution(solution));
+        }
         Ok(Self {
             state: State::new(solution),
             game_type,
+            par: None,
         })
     }
 
-    pub fn game_type(&self) -> GameType {
-        self.game_type
+    /// Accepts alternative spellings of the solution, such as "favour" for "favor",
+    /// as a correct guess.
+    ///
+    /// Aliases must be the same length as the solution, as the game is only won with a
+    /// full row of exact matches.
+    pub fn with_aliases(
+        mut self,
+        aliases: impl IntoIterator<Item = String>,
+    ) -> Result<Self, GameInitError> {
+        let aliases = aliases.into_iter().collect::<Vec<_>>();
+        let len = self.state.solution().chars().count();
+        if let Some(alias) = aliases.iter().find(|alias| alias.chars().count() != len) {
+            return Err(GameInitError::InvalidAlias(alias.clone()));
+        }
+        self.state.add_aliases(aliases);
+        Ok(self)
+    }
+
+    /// Sets whether the same word can be guessed more than once.
+    ///
+    /// Repeats are allowed by default, matching the official rules. When disallowed,
+    /// repeated guesses fail with [`GuessError::AlreadyGuessed`].
+    ///
+    /// [`GuessError::AlreadyGuessed`]: crate::state::GuessError::AlreadyGuessed
+    pub fn allow_repeated_guesses(mut self, allowed: bool) -> Self {
+        self.state.set_allow_repeats(allowed);
+        self
+    }
+
+    /// Sets whether guesses must use every hint revealed so far.
+    ///
+    /// Off by default. In hard mode green letters must stay in place and amber letters
+    /// must be reused, or the guess fails with [`GuessError::HardModeViolation`].
+    /// The share marks the score with a `*`.
+    ///
+    /// [`GuessError::HardModeViolation`]: crate::state::GuessError::HardModeViolation
+    pub fn with_hard_mode(mut self, enabled: bool) -> Self {
+        self.state.set_hard_mode(enabled);
+        self
+    }
+
+    /// Sets whether guesses must fit everything the earlier ones showed, on top of
+    /// hard mode's rules.
+    ///
+    /// Off by default. In ultra hard mode a grey letter can't be played again, or
+    /// not more times than it was found, and an amber letter can't go back where it
+    /// was, or the guess fails with [`GuessError::MisplacedLetter`] or
+    /// [`GuessError::ExcessLetter`]. Every guess could still be the solution. The
+    /// share marks the score with `**`.
+    ///
+    /// [`GuessError::MisplacedLetter`]: crate::state::GuessError::MisplacedLetter
+    /// [`GuessError::ExcessLetter`]: crate::state::GuessError::ExcessLetter
+    pub fn with_ultra_hard_mode(mut self, enabled: bool) -> Self {
+        self.state.set_ultra_hard(enabled);
+        self
+    }
+
+    /// Off by default. With accents ignored, letters match whatever their accents, so
+    /// `o` matches the `õ` in `leões`, and words can be typed without them, as with
+    /// `leoes`. A word typed that way is guessed with its listed spelling.
+    pub fn with_ignore_accents(mut self, enabled: bool) -> Self {
+        self.state.set_ignore_accents(enabled);
+        self
+    }
+
+    /// Changes how many guesses are allowed before the game is lost, from the usual
+    /// [`MAX_GUESSES`](crate::MAX_GUESSES). The share's score is out of this many.
+    ///
+    /// Fails with [`GameInitError::InvalidMaxGuesses`] if it's 0.
+    pub fn with_max_guesses(mut self, max_guesses: usize) -> Result<Self, GameInitError> {
+        if max_guesses == 0 {
+            return Err(GameInitError::InvalidMaxGuesses(max_guesses));
+        }
+        self.state.set_max_guesses(max_guesses);
+        Ok(self)
+    }
+
+    /// Sets a target number of guesses, which the share compares the score against
+    pub fn with_par(mut self, par: usize) -> Self {
+        self.par = Some(par);
+        self
+    }
+
+    pub fn game_type(&self) -> &GameType {
+        &self.game_type
+    }
+
+    /// Number of letters in the solution, and so in every guess
+    pub fn word_len(&self) -> usize {
+        self.state.word_len()
+    }
+
+    /// Number of guesses allowed before the game is lost
+    pub fn max_guesses(&self) -> usize {
+        self.state.max_guesses()
+    }
+
+    pub fn par(&self) -> Option<usize> {
+        self.par
+    }
+
+    /// Makes a guess, returning how each of its letters matched the solution.
+    ///
+    /// This, [`Game::game_over`] and [`Game::share`] are enough to play a whole game
+    /// from any frontend, as nothing here touches the terminal.
+    pub fn guess(&mut self, word: &str) -> Result<Matches, GuessError> {
+        self.state.guess(word)
+    }
+
+    /// Takes back the last guess, returning it, or `None` if there were none yet.
+    ///
+    /// Anything can be undone, so it's up to the frontend to only offer it in games
+    /// that don't count, like practice. A game that was given up stays given up
+    pub fn undo(&mut self) -> Option<String> {
+        self.state.undo()
+    }
+
+    /// Ends the game as a loss, as if every guess had missed, so the solution can be
+    /// shown. The share keeps the guesses made so far, scored `X`
+    pub fn give_up(&mut self) {
+        self.state.give_up();
+    }
+
+    /// `Some(true)` once won, `Some(false)` once lost, or `None` while still playing
+    pub fn game_over(&self) -> Option<bool> {
+        self.state.game_over()
+    }
+
+    /// Gives away a letter of the solution, as [`State::reveal`] does. The share
+    /// says how many were
+    pub fn reveal(&mut self) -> Option<Reveal> {
+        self.state.reveal()
     }
 
     pub fn state(&self) -> &State {
@@ -56,15 +359,158 @@ impl Game {
         &mut self.state
     }
 
-    pub fn share(self) -> GameShare {
-        GameShare(self)
+    /// The game type and guesses so far, one per line, so an unfinished game can be
+    /// picked up again with [`Game::resume`]. Each letter [revealed](Game::reveal) is
+    /// a `?` line, between the guesses it came between
+    pub fn save(&self) -> String {
+        let mut saved = self.game_type.to_string();
+        let mut revealed = self.state.revealed_after().peekable();
+        for (i, Guess(word, _)) in self.state.guesses().enumerate() {
+            while revealed.next_if(|&after| after == i).is_some() {
+                saved.push_str("\n?");
+            }
+            saved.push('\n');
+            saved.push_str(word);
+        }
+        for _ in revealed {
+            saved.push_str("\n?");
+        }
+        saved
+    }
+
+    /// Replays the guesses from [`Game::save`], returning whether they were resumed.
+    ///
+    /// Nothing changes if the save is for another game, or any of its guesses is no
+    /// longer accepted, so matches are never shown against the wrong solution
+    pub fn resume(&mut self, saved: &str) -> bool {
+        let mut lines = saved.lines();
+        let same_game = lines.next().and_then(|line| line.parse::<GameType>().ok())
+            == Some(self.game_type.clone());
+        if !same_game || self.state.guesses().len() > 0 {
+            return false;
+        }
+        for word in lines {
+            if word == "?" {
+                self.state.reveal();
+            } else if self.state.guess(word).is_err() {
+                self.state.clear_guesses();
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn share(&self) -> GameShare {
+        GameShare {
+            game_type: self.game_type.clone(),
+            score: Some(self.state.score()),
+            max_guesses: self.state.max_guesses(),
+            hard_mode: self.state.hard_mode(),
+            ultra_hard: self.state.ultra_hard(),
+            no_keyboard_hints: false,
+            par: self.par,
+            grid: self.state.guesses().map(|Guess(_, m)| m).collect(),
+            hints: self.state.revealed().count(),
+            style: ShareStyle::Full,
+            high_contrast: false,
+            white_squares: false,
+            template: None,
+            time: None,
+            guesses: Vec::new(),
+            solution_hash: None,
+            analysis: false,
+        }
+    }
+}
+
+/// A seed for when none was given, different every time
+pub(crate) fn random_seed() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+/// Words have to be lowercase letters, as guesses always are
+pub(crate) fn well_formed(solution: &str) -> bool {
+    !solution.is_empty()
+        && solution
+            .chars()
+            .all(|c| c.is_alphabetic() && !c.is_uppercase())
+}
+
+#[derive(Debug)]
+/// Reasons a [`Game`] could not be created
+pub enum GameInitError {
+    /// The local timezone offset could not be determined
+    TimezoneUnknown,
+    /// The given word is not in the word lists
+    InvalidSolution(String),
+    /// The given word is not made of lowercase letters
+    MalformedSolution(String),
+    /// The given alias is not the same length as the solution
+    InvalidAlias(String),
+    /// The solution could not be fetched, and none was cached for today
+    Fetch(String),
+    /// There's no word list for words of this length
+    NoWordList(usize),
+    /// A game has to allow at least one guess
+    InvalidMaxGuesses(usize),
+    /// A game has to have at least one board
+    NoBoards,
+    /// A game can have at most [`MAX_BOARDS`](crate::multi::MAX_BOARDS) boards
+    TooManyBoards(usize),
+}
+
+impl fmt::Display for GameInitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameInitError::TimezoneUnknown => write!(f, "could not determine local timezone"),
+            GameInitError::InvalidSolution(solution) => {
+                write!(f, "{} is not a valid solution", solution)
+            }
+            GameInitError::MalformedSolution(solution) => {
+                write!(f, "{} is not lowercase letters", solution)
+            }
+            GameInitError::InvalidAlias(alias) => {
+                write!(f, "{} is not the same length as the solution", alias)
+            }
+            GameInitError::Fetch(err) => write!(f, "could not fetch the solution: {}", err),
+            GameInitError::NoWordList(len) => write!(f, "no word list for {} letter words", len),
+            GameInitError::InvalidMaxGuesses(n) => write!(f, "can't play with {} guesses", n),
+            GameInitError::NoBoards => write!(f, "can't play without any boards"),
+            GameInitError::TooManyBoards(n) => write!(
+                f,
+                "can't play {} boards, at most {} fit",
+                n,
+                crate::multi::MAX_BOARDS
+            ),
+        }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl Error for GameInitError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum GameType {
     Daily(usize),
     Custom,
+    Tournament {
+        seed: String,
+        round: usize,
+    },
+    /// A random solution picked from the seed, with [`Game::random`]
+    Practice {
+        seed: u64,
+    },
+    /// No solution until the guesses force one, with [`Game::absurdle`]
+    Absurdle,
+    /// The word hidden in a code, with [`Game::seeded`]
+    Seeded {
+        code: String,
+    },
 }
 
 impl fmt::Display for GameType {
@@ -72,16 +518,1687 @@ impl fmt::Display for GameType {
         match self {
             GameType::Daily(day) => write!(f, "{}", day),
             GameType::Custom => write!(f, "custom"),
+            GameType::Tournament { seed, round } => write!(f, "Tournament {} #{}", seed, round),
+            GameType::Practice { seed } => write!(f, "practice {}", seed),
+            GameType::Absurdle => write!(f, "absurdle"),
+            GameType::Seeded { code } => write!(f, "seed {}", code),
+        }
+    }
+}
+
+impl FromStr for GameType {
+    type Err = ParseShareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "custom" {
+            return Ok(GameType::Custom);
+        }
+        if s == "absurdle" {
+            return Ok(GameType::Absurdle);
+        }
+        if let Some(code) = s.strip_prefix("seed ") {
+            return Ok(GameType::Seeded {
+                code: code.to_owned(),
+            });
+        }
+        if let Some(seed) = s.strip_prefix("practice ") {
+            let seed = seed.parse().map_err(|_| ParseShareError::InvalidGameType)?;
+            return Ok(GameType::Practice { seed });
         }
+        if let Some(tournament) = s.strip_prefix("Tournament ") {
+            let (seed, round) = tournament
+                .rsplit_once(" #")
+                .ok_or(ParseShareError::InvalidGameType)?;
+            return Ok(GameType::Tournament {
+                seed: seed.to_owned(),
+                round: round
+                    .parse()
+                    .map_err(|_| ParseShareError::InvalidGameType)?,
+            });
+        }
+        s.parse()
+            .map(GameType::Daily)
+            .map_err(|_| ParseShareError::InvalidGameType)
     }
 }
 
-pub struct GameShare(Game);
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ArgEnum))]
+/// Which parts of the result to include when sharing
+pub enum ShareStyle {
+    /// The header, score and grid
+    #[default]
+    Full,
+    /// The header and grid, without revealing the score
+    NoScore,
+    /// The header and score, without the grid
+    NoGrid,
+    /// Everything, with each row of the grid in Discord spoiler tags so it's hidden
+    /// until clicked
+    DiscordSpoiler,
+}
+
+pub(crate) const HEADER: &str = "joguei term.ooo #";
+/// Starts the line with the time taken, after the grid
+const TIMER: &str = "⏱ ";
+/// Starts the line with the number of letters revealed, between the grid and the time
+const HINTS: &str = "💡 ";
+
+#[derive(Debug, PartialEq, Eq)]
+/// The shareable result of a game
+pub struct GameShare {
+    game_type: GameType,
+    score: Option<Score>,
+    /// Number of guesses the game allowed, which the score is out of
+    max_guesses: usize,
+    /// Whether the game was played in hard mode, marked with a `*` after the score
+    hard_mode: bool,
+    /// Whether the game was played in ultra hard mode, marked with `**` instead
+    ultra_hard: bool,
+    /// Whether the keyboard was played without the letters coloured, marked with a
+    /// `!` after the score
+    no_keyboard_hints: bool,
+    /// Target number of guesses, shown after the score as eg `(1 under par 3)`
+    par: Option<usize>,
+    grid: Vec<Matches>,
+    /// Number of letters [revealed](Game::reveal) during the game, shown on a line
+    /// after the grid like `💡 2 hints`
+    hints: usize,
+    style: ShareStyle,
+    /// Whether the grid uses 🟧 and 🟦 in place of 🟩 and 🟨
+    high_contrast: bool,
+    /// Whether absent letters are ⬜ in place of ⬛, for light backgrounds
+    white_squares: bool,
+    /// Lays the share out in place of the usual header and grid
+    template: Option<ShareTemplate>,
+    /// How long the game took, in whole seconds, shown on a line after the grid
+    time: Option<Duration>,
+    /// The words guessed, only for the JSON record, as they'd spoil the game
+    guesses: Vec<String>,
+    /// Tells apart the solution in the JSON record without spelling it out
+    solution_hash: Option<u64>,
+    /// Whether the JSON record rates each guess
+    analysis: bool,
+}
+
+impl GameShare {
+    /// Parses the output of [`GameShare`]'s `Display` impl.
+    ///
+    /// The score and the grid are both optional, so any [`ShareStyle`] can be read back.
+    ///
+    /// Shares pasted from other apps are accepted too. Variation selectors and
+    /// zero-width characters are ignored, and white or high contrast squares are read
+    /// as their usual colours. A grid with ⬜ in it keeps its
+    /// [white squares](GameShare::white_squares) when it's drawn again.
+    pub fn parse(input: &str) -> Result<Self, ParseShareError> {
+        let input = normalize(input);
+        let spoiler = input.contains("||");
+        // spoiler tags and code blocks are added by chat apps, so they're dropped
+        let mut lines = input.trim().lines().map(|line| {
+            let line = line.trim().trim_matches('`').trim();
+            let line = line.strip_prefix("||").unwrap_or(line);
+            line.strip_suffix("||").unwrap_or(line)
+        });
+        let header = lines
+            .find(|line| !line.is_empty())
+            .and_then(|line| line.trim().strip_prefix(HEADER))
+            .ok_or(ParseShareError::MissingHeader)?;
+        let (header, par) = match header.strip_suffix(')').and_then(|h| h.rsplit_once(" (")) {
+            Some((header, par)) => {
+                let par = par.rsplit_once("par ").map(|(_, par)| par.parse());
+                match par {
+                    Some(Ok(par)) => (header, Some(par)),
+                    _ => return Err(ParseShareError::InvalidPar),
+                }
+            }
+            None => (header, None),
+        };
+        let (header, no_keyboard_hints) = match header.strip_suffix('!') {
+            Some(header) => (header, true),
+            None => (header, false),
+        };
+        let (header, hard_mode) = match header.strip_suffix('*') {
+            Some(header) => (header, true),
+            None => (header, false),
+        };
+        let (header, ultra_hard) = match header.strip_suffix('*') {
+            Some(header) if hard_mode => (header, true),
+            _ => (header, false),
+        };
+
+        // the game type can contain spaces, so the score is found from the end
+        let score = header
+            .rsplit_once(' ')
+            .and_then(|(game_type, score)| Some((game_type, score.split_once('/')?)))
+            .filter(|(_, (_, max))| !max.is_empty() && max.bytes().all(|b| b.is_ascii_digit()));
+        let (game_type, score, max_guesses) = match score {
+            Some((game_type, (score, max))) => {
+                let max = max.parse().ok().filter(|&n| n > 0);
+                let max = max.ok_or(ParseShareError::InvalidScore)?;
+                (game_type, Some(score.parse()?), max)
+            }
+            // only games without a limit on the guesses have a score with nothing to
+            // be out of, and they're always absurdle
+            None => match header.rsplit_once(' ') {
+                Some((game_type @ "absurdle", score)) => {
+                    (game_type, Some(score.parse()?), usize::MAX)
+                }
+                _ => (header, None, crate::MAX_GUESSES),
+            },
+        };
+        let game_type = game_type.parse()?;
+
+        let mut time = None;
+        let mut hints = 0;
+        let mut grid = Vec::new();
+        let mut white_squares = false;
+        for line in lines.map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(elapsed) = line.strip_prefix(TIMER) {
+                let Elapsed(elapsed) = elapsed.parse()?;
+                time = Some(elapsed);
+            } else if let Some(used) = line.strip_prefix(HINTS) {
+                let used = used.split_once(' ').map_or(used, |(used, _)| used);
+                hints = used.parse().map_err(|_| ParseShareError::InvalidHints)?;
+            } else {
+                grid.push(parse_row(line).ok_or(ParseShareError::InvalidRow)?);
+                white_squares |= line.contains('⬜');
+            }
+        }
+
+        let style = match (score, grid.is_empty()) {
+            (None, _) => ShareStyle::NoScore,
+            (Some(_), false) if spoiler => ShareStyle::DiscordSpoiler,
+            (Some(_), true) => ShareStyle::NoGrid,
+            (Some(_), false) => ShareStyle::Full,
+        };
+
+        Ok(Self {
+            game_type,
+            score,
+            max_guesses,
+            hard_mode,
+            ultra_hard,
+            no_keyboard_hints,
+            par,
+            grid,
+            hints,
+            style,
+            high_contrast: false,
+            white_squares,
+            template: None,
+            time,
+            guesses: Vec::new(),
+            solution_hash: None,
+            analysis: false,
+        })
+    }
+
+    /// Changes which parts of the result are displayed
+    pub fn with_style(mut self, style: ShareStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Switches the grid to the 🟧 and 🟦 of the colour blind friendly palette
+    pub fn with_high_contrast(mut self, enabled: bool) -> Self {
+        self.high_contrast = enabled;
+        self
+    }
+
+    /// Draws absent letters as ⬜ instead of ⬛, to stand out on a light background
+    pub fn with_white_squares(mut self, enabled: bool) -> Self {
+        self.white_squares = enabled;
+        self
+    }
+
+    /// Lays the share out with the template, or the usual way with `None`. A share
+    /// laid out by a template can't be parsed back
+    pub fn with_template(mut self, template: Option<ShareTemplate>) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Adds how long the game took, as a `⏱ 00:47` line after the grid.
+    /// Only whole seconds are kept
+    pub fn with_time(mut self, time: Duration) -> Self {
+        self.time = Some(Duration::from_secs(time.as_secs()));
+        self
+    }
+
+    /// Keeps the words guessed in the game and a hash of its solution, for the JSON
+    /// record. Neither is ever in the text, so a parsed share has none
+    pub fn with_guesses(mut self, game: &Game) -> Self {
+        self.guesses = game
+            .state()
+            .guesses()
+            .map(|Guess(word, _)| word.to_owned())
+            .collect();
+        self.solution_hash = Some(crate::tournament::fnv1a(game.state().solution().as_bytes()));
+        self
+    }
+
+    /// Adds the [analysis](GameShare::analysis) of the guesses to the JSON record
+    pub fn with_analysis(mut self, enabled: bool) -> Self {
+        self.analysis = enabled;
+        self
+    }
+
+    /// Rates each guess, from a share [with the guesses](GameShare::with_guesses).
+    /// `None` without them
+    pub fn analysis(&self) -> Option<Analysis> {
+        if self.guesses.is_empty() {
+            return None;
+        }
+        let history = self.guesses.iter().zip(&self.grid);
+        let history = history.map(|(word, matches)| Guess(word, matches.clone()));
+        Some(Analysis::new(&history.collect::<Vec<_>>()))
+    }
+
+    /// The first guess, from a share [with the guesses](GameShare::with_guesses)
+    pub fn opener(&self) -> Option<&str> {
+        self.guesses.first().map(String::as_str)
+    }
+
+    /// Marks the game as played without the keyboard showing what's known of each
+    /// letter, with a `!` after the score
+    pub fn with_no_keyboard_hints(mut self, enabled: bool) -> Self {
+        self.no_keyboard_hints = enabled;
+        self
+    }
+
+    /// Leaves the time out, for sharing a timed game without it
+    pub fn without_time(mut self) -> Self {
+        self.time = None;
+        self
+    }
+
+    pub fn game_type(&self) -> &GameType {
+        &self.game_type
+    }
+
+    /// The number of guesses taken, or [`Score::Failed`] for a loss.
+    /// `None` if parsed from a share that omitted the score.
+    pub fn score(&self) -> Option<Score> {
+        self.score
+    }
+
+    /// Whether the game was played in hard mode, which ultra hard mode counts as
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    pub fn ultra_hard(&self) -> bool {
+        self.ultra_hard
+    }
+
+    pub fn no_keyboard_hints(&self) -> bool {
+        self.no_keyboard_hints
+    }
+
+    pub fn high_contrast(&self) -> bool {
+        self.high_contrast
+    }
+
+    pub fn white_squares(&self) -> bool {
+        self.white_squares
+    }
+
+    pub fn grid(&self) -> &[Matches] {
+        &self.grid
+    }
+
+    pub fn style(&self) -> ShareStyle {
+        self.style
+    }
+
+    /// Number of letters revealed during the game
+    pub fn hints(&self) -> usize {
+        self.hints
+    }
+
+    pub fn par(&self) -> Option<usize> {
+        self.par
+    }
+
+    /// Number of guesses the game allowed, which the score is out of
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+
+    pub fn time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    /// How the score compares to par, if there was one and the score is known
+    pub fn par_result(&self) -> Option<ParResult> {
+        let par = self.par?;
+        let guesses = self.score?.guesses();
+        Some(match guesses {
+            None => ParResult::Missed,
+            Some(n) if n < par => ParResult::Under(par - n),
+            Some(n) if n == par => ParResult::Met,
+            Some(n) => ParResult::Over(n - par),
+        })
+    }
+
+    /// Writes the result as JSON, for tools that would rather not parse the emoji.
+    ///
+    /// The solution isn't given a field of its own. `solution_withheld` says whether
+    /// it's kept out of the record altogether, which it is unless the guesses of a
+    /// won game are included, as the last of them is the solution. Each row of the grid
+    /// is a list of `"green"`, `"amber"` or `"black"`, one per letter, and `seconds` is
+    /// the time taken if the game was timed. `day` repeats the day of a daily game.
+    ///
+    /// From a share [with the guesses](GameShare::with_guesses), `guesses` has the words
+    /// and `solution_hash` the 64-bit FNV-1a hash of the solution, in hex, to tell
+    /// whether two records had the same one. There are few enough words to try them
+    /// all, so it doesn't keep the solution secret from anyone determined. Otherwise
+    /// both are null, and so is `analysis` unless it's
+    /// [added](GameShare::with_analysis), with each guess rated
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        #[derive(serde::Serialize)]
+        struct Json<'a> {
+            game_type: &'a GameType,
+            day: Option<usize>,
+            solution_withheld: bool,
+            solution_hash: Option<String>,
+            guesses: Option<&'a [String]>,
+            score: Option<Score>,
+            /// null when there's no limit
+            max_guesses: Option<usize>,
+            hard_mode: bool,
+            par: Option<usize>,
+            hints: usize,
+            grid: &'a [Matches],
+            seconds: Option<u64>,
+            analysis: Option<Analysis>,
+        }
+
+        let guesses = self.solution_hash.is_some().then_some(&self.guesses[..]);
+        let won = self
+            .grid
+            .last()
+            .is_some_and(|Matches(row)| row.iter().all(|&m| m == Match::Exact));
+        let json = Json {
+            game_type: &self.game_type,
+            day: match self.game_type {
+                GameType::Daily(day) => Some(day),
+                _ => None,
+            },
+            // the analysis only comes with the guesses, so it can't give it away alone
+            solution_withheld: !(won && guesses.is_some_and(|guesses| !guesses.is_empty())),
+            solution_hash: self.solution_hash.map(|hash| format!("{:016x}", hash)),
+            guesses,
+            score: self.score,
+            max_guesses: (self.max_guesses != usize::MAX).then_some(self.max_guesses),
+            hard_mode: self.hard_mode,
+            par: self.par,
+            hints: self.hints,
+            grid: &self.grid,
+            seconds: self.time.map(|time| time.as_secs()),
+            analysis: self.analysis.then(|| self.analysis()).flatten(),
+        };
+        serde_json::to_string(&json).expect("shares are always serializable")
+    }
+
+    /// Copies the result, exactly as displayed, to the system clipboard
+    #[cfg(feature = "clipboard")]
+    pub fn copy_to_clipboard(&self) -> Result<(), arboard::Error> {
+        arboard::Clipboard::new()?.set_text(self.to_string())
+    }
+
+    /// Draws the share as a PNG image: the first line as a caption, over a square
+    /// for each letter of the grid in the share's colours
+    #[cfg(feature = "image")]
+    pub fn to_png(&self, path: impl AsRef<std::path::Path>) -> Result<(), png::EncodingError> {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        crate::image::encode(self, file)
+    }
+
+    /// Draws the share as an SVG image, laid out like [`to_png`](Self::to_png) but
+    /// with the caption in a real font, so it can have any letters
+    #[cfg(feature = "image")]
+    pub fn to_svg(&self) -> String {
+        crate::image::svg(self)
+    }
+
+    /// Whether the game was won, going by the score, or by the last row of the grid
+    /// if there's no score
+    pub fn won(&self) -> bool {
+        match self.score {
+            Some(score) => score.won(),
+            None => self
+                .grid
+                .last()
+                .is_some_and(|Matches(row)| row.iter().all(|&m| m == Match::Exact)),
+        }
+    }
+}
+
+impl GameShare {
+    fn row<'a>(&self, matches: &'a Matches) -> GridRow<'a> {
+        GridRow {
+            matches,
+            high_contrast: self.high_contrast,
+            white_squares: self.white_squares,
+        }
+    }
+
+    /// `*` after the score in hard mode, or `**` in ultra hard mode
+    fn hard_marker(&self) -> &'static str {
+        match (self.hard_mode, self.ultra_hard) {
+            (true, true) => "**",
+            (true, false) => "*",
+            (false, _) => "",
+        }
+    }
+
+    /// The score out of the guesses allowed, unless the style leaves it out
+    fn write_score(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.style, self.score) {
+            (ShareStyle::NoScore, _) | (_, None) => Ok(()),
+            (_, Some(score)) if self.max_guesses == usize::MAX => write!(f, "{score}"),
+            (_, Some(score)) => write!(f, "{score}/{max}", max = self.max_guesses),
+        }
+    }
+
+    /// A line for each row of the grid, unless the style leaves it out
+    fn write_grid(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, m) in self.grid.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match self.style {
+                ShareStyle::NoGrid => return Ok(()),
+                ShareStyle::DiscordSpoiler => write!(f, "||{}||", self.row(m))?,
+                ShareStyle::Full | ShareStyle::NoScore => write!(f, "{}", self.row(m))?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_template(&self, template: &ShareTemplate, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &template.0 {
+            match part {
+                TemplatePart::Text(text) => f.write_str(text)?,
+                TemplatePart::Day => write!(f, "{}", self.game_type)?,
+                TemplatePart::Score => self.write_score(f)?,
+                TemplatePart::Hard => {
+                    f.write_str(self.hard_marker())?;
+                    if self.no_keyboard_hints {
+                        write!(f, "!")?;
+                    }
+                }
+                TemplatePart::Grid => self.write_grid(f)?,
+                TemplatePart::Time => {
+                    if let Some(time) = self.time {
+                        write!(f, "{}", Elapsed(time))?;
+                    }
+                }
+                TemplatePart::Hints => {
+                    if self.hints > 0 {
+                        write!(f, "{}", Hints(self.hints))?;
+                    }
+                }
+                TemplatePart::Url => f.write_str(URL)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A row of the share grid, in any of the palettes
+struct GridRow<'a> {
+    matches: &'a Matches,
+    high_contrast: bool,
+    white_squares: bool,
+}
+
+impl fmt::Display for GridRow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for m in &self.matches.0 {
+            match m {
+                Match::Exact if self.high_contrast => f.write_str("🟧")?,
+                Match::Close if self.high_contrast => f.write_str("🟦")?,
+                Match::Wrong if self.white_squares => f.write_str("⬜")?,
+                m => write!(f, "{}", m)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The number of letters revealed, like `1 hint` or `2 hints`
+struct Hints(usize);
+
+impl fmt::Display for Hints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1 => write!(f, "1 hint"),
+            n => write!(f, "{} hints", n),
+        }
+    }
+}
+
+/// Where the daily game can be played, for the `{url}` of a [`ShareTemplate`]
+pub const URL: &str = "https://term.ooo";
+
+/// A layout for the share in place of the usual one, for chat apps that want it
+/// some other way. Each part of the result has a placeholder:
+///
+/// - `{day}` is the day number, or the kind of game for anything but a daily
+/// - `{score}` is like `3/6` or `X/6`
+/// - `{hard}` is a `*` in hard mode, and a `!` without keyboard hints
+/// - `{grid}` is the squares, a line for each guess
+/// - `{time}` is how long the game took, if it was timed
+/// - `{hints}` is like `2 hints`, if any letters were revealed
+/// - `{url}` is [`URL`]
+///
+/// `\n` starts a new line, and `{{` and `}}` stand for braces. The share's style
+/// still leaves out the score or the grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShareTemplate(Vec<TemplatePart>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Day,
+    Score,
+    Hard,
+    Grid,
+    Time,
+    Hints,
+    Url,
+}
+
+impl FromStr for ShareTemplate {
+    type Err = ParseTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('\\', Some('n')) => text.push('\n'),
+                ('{', Some('{')) => text.push('{'),
+                ('}', Some('}')) => text.push('}'),
+                ('{', _) => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(ParseTemplateError::Unclosed),
+                        }
+                    }
+                    let part = match name.as_str() {
+                        "day" => TemplatePart::Day,
+                        "score" => TemplatePart::Score,
+                        "hard" => TemplatePart::Hard,
+                        "grid" => TemplatePart::Grid,
+                        "time" => TemplatePart::Time,
+                        "hints" => TemplatePart::Hints,
+                        "url" => TemplatePart::Url,
+                        _ => return Err(ParseTemplateError::UnknownPlaceholder(name)),
+                    };
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(part);
+                    continue;
+                }
+                (c, _) => {
+                    text.push(c);
+                    continue;
+                }
+            }
+            // the second character of the pair is used up too
+            chars.next();
+        }
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+        Ok(Self(parts))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a share template could not be parsed
+pub enum ParseTemplateError {
+    /// A `{` is never closed with a `}`
+    Unclosed,
+    /// The name between the braces isn't one of the parts of the share
+    UnknownPlaceholder(String),
+}
+
+impl fmt::Display for ParseTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseTemplateError::Unclosed => {
+                write!(f, "a {{ is never closed, write {{{{ for a brace")
+            }
+            ParseTemplateError::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder {{{}}}, expected day, score, hard, grid, time, hints or url",
+                name
+            ),
+        }
+    }
+}
+
+impl Error for ParseTemplateError {}
+
+/// Removes the invisible characters that copy-pasting tends to add
+pub(crate) fn normalize(input: &str) -> String {
+    input
+        .chars()
+        .filter_map(|c| match c {
+            // variation selectors, joiners and byte order marks
+            '\u{fe00}'..='\u{fe0f}' | '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' => None,
+            '\u{a0}' => Some(' '),
+            c => Some(c),
+        })
+        .collect()
+}
+
+pub(crate) fn parse_row(line: &str) -> Option<Matches> {
+    let matches = line
+        .chars()
+        .map(Match::from_square)
+        .collect::<Option<Vec<_>>>()?;
+    (!matches.is_empty()).then_some(Matches(matches))
+}
 
 impl fmt::Display for GameShare {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "joguei term.ooo #{game_type} ", game_type = self.0.game_type())?;
-        self.0.state().display_score_card(f)?;
+        if let Some(template) = &self.template {
+            return self.write_template(template, f);
+        }
+        write!(f, "{HEADER}{game_type}", game_type = self.game_type)?;
+        match (self.style, self.score) {
+            (ShareStyle::NoScore, _) | (_, None) => {}
+            _ => {
+                write!(f, " ")?;
+                self.write_score(f)?;
+            }
+        }
+        f.write_str(self.hard_marker())?;
+        if self.no_keyboard_hints {
+            write!(f, "!")?;
+        }
+        if let (Some(par), Some(result)) = (self.par, self.par_result()) {
+            // the par result gives the score away
+            if self.style != ShareStyle::NoScore {
+                write!(f, " ({} par {})", result, par)?;
+            }
+        }
+        match self.style {
+            ShareStyle::NoGrid => {}
+            ShareStyle::DiscordSpoiler => {
+                writeln!(f)?;
+                for m in &self.grid {
+                    write!(f, "\n||{}||", self.row(m))?;
+                }
+            }
+            ShareStyle::Full | ShareStyle::NoScore => {
+                writeln!(f)?;
+                for m in &self.grid {
+                    write!(f, "\n{}", self.row(m))?;
+                }
+            }
+        }
+        if self.hints > 0 {
+            write!(f, "\n{HINTS}{}", Hints(self.hints))?;
+        }
+        if let Some(time) = self.time {
+            write!(f, "\n{TIMER}{}", Elapsed(time))?;
+        }
         Ok(())
     }
 }
+
+/// A length of time as minutes and seconds, like `00:47`, with hours in front if
+/// there are any, like `1:02:05`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed(pub Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.0.as_secs();
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            write!(f, "{}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            write!(f, "{:02}:{:02}", minutes, seconds)
+        }
+    }
+}
+
+impl FromStr for Elapsed {
+    type Err = ParseShareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut seconds: u64 = 0;
+        for (i, part) in s.split(':').enumerate() {
+            let part: u64 = part.parse().map_err(|_| ParseShareError::InvalidTime)?;
+            // only the hours (or minutes, if there are none) can go past 59
+            if i > 0 && part >= 60 {
+                return Err(ParseShareError::InvalidTime);
+            }
+            seconds = seconds
+                .checked_mul(60)
+                .and_then(|seconds| seconds.checked_add(part))
+                .ok_or(ParseShareError::InvalidTime)?;
+        }
+        Ok(Elapsed(Duration::from_secs(seconds)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+/// How many guesses a game took, as shown in the share header
+pub enum Score {
+    /// Won in this many guesses
+    Guesses(u8),
+    /// Ran out of guesses, shown as `X`
+    Failed,
+}
+
+impl Score {
+    /// The number of guesses taken to win, or `None` for a loss
+    pub fn guesses(self) -> Option<usize> {
+        match self {
+            Score::Guesses(n) => Some(n as usize),
+            Score::Failed => None,
+        }
+    }
+
+    pub fn won(self) -> bool {
+        matches!(self, Score::Guesses(_))
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Guesses(n) => write!(f, "{}", n),
+            Score::Failed => write!(f, "X"),
+        }
+    }
+}
+
+impl FromStr for Score {
+    type Err = ParseShareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Score::Failed),
+            // only plain digits, so eg `+3` isn't taken as a score
+            _ if s.bytes().all(|b| b.is_ascii_digit()) => s
+                .parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .map(Score::Guesses)
+                .ok_or(ParseShareError::InvalidScore),
+            _ => Err(ParseShareError::InvalidScore),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// How a score compares to par
+pub enum ParResult {
+    /// Won in this many fewer guesses than par
+    Under(usize),
+    Met,
+    /// Won in this many more guesses than par
+    Over(usize),
+    /// Lost, which always misses par
+    Missed,
+}
+
+impl fmt::Display for ParResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParResult::Under(n) => write!(f, "{} under", n),
+            ParResult::Met => write!(f, "met"),
+            ParResult::Over(n) => write!(f, "{} over", n),
+            ParResult::Missed => write!(f, "missed"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a share could not be parsed
+pub enum ParseShareError {
+    /// The first line isn't a share header
+    MissingHeader,
+    /// The game type in the header isn't a day number or "custom"
+    InvalidGameType,
+    /// The score in the header isn't a number or 'X' out of a number
+    InvalidScore,
+    /// A line of the grid isn't 5 squares
+    InvalidRow,
+    /// The par after the score isn't a number
+    InvalidPar,
+    /// The time after the grid isn't minutes and seconds
+    InvalidTime,
+    /// The number of hints after the grid isn't a number
+    InvalidHints,
+}
+
+impl fmt::Display for ParseShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseShareError::MissingHeader => write!(f, "missing share header"),
+            ParseShareError::InvalidGameType => write!(f, "invalid game number"),
+            ParseShareError::InvalidScore => write!(f, "invalid score"),
+            ParseShareError::InvalidRow => write!(f, "invalid row in grid"),
+            ParseShareError::InvalidPar => write!(f, "invalid par"),
+            ParseShareError::InvalidTime => write!(f, "invalid time"),
+            ParseShareError::InvalidHints => write!(f, "invalid number of hints"),
+        }
+    }
+}
+
+impl Error for ParseShareError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Elapsed, Game, GameInitError, GameShare, GameType, ParResult, ParseShareError,
+        ParseTemplateError, Score, ShareStyle, ShareTemplate,
+    };
+    use crate::{
+        source::WordList,
+        state::{GuessError, Reveal},
+        Match, Matches,
+    };
+    use std::{sync::Arc, time::Duration};
+    use test_case::test_case;
+
+    fn played() -> GameShare {
+        let mut game = Game::from_day(0).unwrap();
+        game.state_mut().guess("suite").unwrap();
+        game.state_mut().guess("termo").unwrap();
+        game.share()
+    }
+
+    #[test]
+    fn test_absurdle() {
+        let mut game = Game::absurdle();
+        // a word that's possible can't win while others are too
+        let first = game.state().solution().to_owned();
+        assert_ne!(game.guess(&first).unwrap(), Matches(vec![Match::Exact; 5]));
+        assert_eq!(game.game_over(), None);
+        // guessing the word standing in as the solution narrows it down until it wins
+        let mut left = game.state().adversary().unwrap().remaining();
+        while game.game_over().is_none() {
+            let word = game.state().solution().to_owned();
+            game.guess(&word).unwrap();
+            let remaining = game.state().adversary().unwrap().remaining();
+            assert!(remaining <= left);
+            left = remaining;
+        }
+        assert_eq!(game.game_over(), Some(true));
+        assert_eq!(left, 1);
+        assert_eq!(game.state().rows(), game.state().guesses().len());
+
+        let share = game.share();
+        let n = game.state().guesses().len();
+        assert!(share
+            .to_string()
+            .starts_with(&format!("joguei term.ooo #absurdle {}\n", n)));
+        assert_eq!(GameShare::parse(&share.to_string()).unwrap(), share);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut game = Game::custom("termo".to_owned()).unwrap();
+        assert_eq!(game.undo(), None);
+        game.guess("suite").unwrap();
+        game.guess("termo").unwrap();
+        assert_eq!(game.undo().as_deref(), Some("termo"));
+        assert_eq!(game.game_over(), None);
+        assert_eq!(game.state().guesses().len(), 1);
+
+        // the adversary picks the same way again after an undo
+        let mut game = Game::absurdle();
+        game.guess("suite").unwrap();
+        let (solution, remaining) = (
+            game.state().solution().to_owned(),
+            game.state().adversary().unwrap().remaining(),
+        );
+        game.guess("termo").unwrap();
+        game.undo();
+        assert_eq!(game.state().solution(), solution);
+        assert_eq!(game.state().adversary().unwrap().remaining(), remaining);
+    }
+
+    #[test]
+    fn test_give_up() {
+        let mut game = Game::custom("termo".to_owned()).unwrap();
+        game.guess("suite").unwrap();
+        game.give_up();
+        assert_eq!(game.game_over(), Some(false));
+        assert_eq!(game.guess("termo"), Err(GuessError::GameOver));
+        let share = game.share();
+        assert_eq!(share.score(), Some(Score::Failed));
+        assert_eq!(
+            share.to_string(),
+            "joguei term.ooo #custom X/6\n\n⬛⬛⬛🟨🟨"
+        );
+
+        // a won game stays won
+        let mut game = Game::custom("termo".to_owned()).unwrap();
+        game.guess("termo").unwrap();
+        game.give_up();
+        assert_eq!(game.game_over(), Some(true));
+    }
+
+    #[test]
+    fn test_max_guesses() {
+        let mut game = Game::custom("termo".to_owned())
+            .unwrap()
+            .with_max_guesses(3)
+            .unwrap();
+        for word in ["suite", "terra", "sexto"] {
+            assert_eq!(game.game_over(), None);
+            game.guess(word).unwrap();
+        }
+        assert_eq!(game.game_over(), Some(false));
+        assert_eq!(game.guess("termo"), Err(GuessError::GameOver));
+        let share = game.share();
+        assert_eq!(share.score(), Some(Score::Failed));
+        assert!(share
+            .to_string()
+            .starts_with("joguei term.ooo #custom X/3\n"));
+        assert_eq!(
+            GameShare::parse(&share.to_string()).unwrap().max_guesses(),
+            3
+        );
+
+        let game = Game::custom("termo".to_owned())
+            .unwrap()
+            .with_max_guesses(0);
+        assert!(matches!(game, Err(GameInitError::InvalidMaxGuesses(0))));
+    }
+
+    #[test]
+    fn test_alias_wins() {
+        let mut game = Game::custom("termo".to_owned())
+            .unwrap()
+            .with_aliases(["suite".to_owned()])
+            .unwrap();
+        let matches = game.state_mut().guess("suite").unwrap();
+        assert_eq!(matches, Matches(vec![Match::Exact; 5]));
+        assert_eq!(game.state().game_over(), Some(true));
+        assert_eq!(game.share().score(), Some(Score::Guesses(1)));
+    }
+
+    #[test]
+    fn test_repeated_guesses() {
+        let mut game = Game::from_day(0).unwrap();
+        game.state_mut().guess("suite").unwrap();
+        assert!(game.state_mut().guess("suite").is_ok());
+
+        let mut game = Game::from_day(0).unwrap().allow_repeated_guesses(false);
+        game.state_mut().guess("suite").unwrap();
+        assert_eq!(
+            game.state_mut().guess("suite"),
+            Err(GuessError::AlreadyGuessed)
+        );
+        assert_eq!(game.state().guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        let mut game = Game::from_day(0).unwrap().with_hard_mode(true);
+        game.state_mut().guess("suite").unwrap();
+        // suite reveals an amber t and e
+        assert_eq!(
+            game.state_mut().guess("sexto"),
+            Ok(Matches(vec![
+                Match::Wrong,
+                Match::Exact,
+                Match::Wrong,
+                Match::Close,
+                Match::Exact
+            ]))
+        );
+        assert_eq!(
+            game.state_mut().guess("turma"),
+            Err(GuessError::HardModeViolation {
+                letter: 'e',
+                position: None
+            })
+        );
+        // the amber t can be reused as a green
+        game.state_mut().guess("tempo").unwrap();
+        assert_eq!(
+            game.state_mut().guess("metro"),
+            Err(GuessError::HardModeViolation {
+                letter: 't',
+                position: Some(0)
+            })
+        );
+        assert_eq!(
+            game.state_mut().guess("terno"),
+            Err(GuessError::HardModeViolation {
+                letter: 'm',
+                position: None
+            })
+        );
+        assert!(game.share().hard_mode());
+    }
+
+    #[test]
+    fn test_ultra_hard_mode() {
+        let mut game = Game::from_day(0).unwrap().with_ultra_hard_mode(true);
+        // suite reveals an amber t and e, and that there's no s, u or i
+        game.state_mut().guess("suite").unwrap();
+        assert_eq!(
+            game.state_mut().guess("porte"),
+            Err(GuessError::MisplacedLetter {
+                letter: 't',
+                position: 3
+            })
+        );
+        // hard mode would take it, as it has the t and the e
+        assert_eq!(
+            game.state_mut().guess("tesem"),
+            Err(GuessError::ExcessLetter {
+                letter: 's',
+                most: 0
+            })
+        );
+        // the rules of hard mode still hold too
+        assert_eq!(
+            game.state_mut().guess("porta"),
+            Err(GuessError::HardModeViolation {
+                letter: 'e',
+                position: None
+            })
+        );
+        game.state_mut().guess("metro").unwrap();
+        let share = game.share();
+        assert!(share.hard_mode() && share.ultra_hard());
+        assert!(share.to_string().starts_with("joguei term.ooo #0 2/6**\n"));
+    }
+
+    #[test_case(GuessError::NotInWordList, "not in the word list")]
+    #[test_case(GuessError::WrongLength { expected: 5, got: 4 }, "need 5 letters, got 4")]
+    #[test_case(GuessError::HardModeViolation { letter: 'e', position: Some(1) }, "E must be letter 2")]
+    #[test_case(GuessError::HardModeViolation { letter: 'é', position: None }, "must contain É")]
+    #[test_case(GuessError::MisplacedLetter { letter: 't', position: 3 }, "T can't be letter 4")]
+    #[test_case(GuessError::ExcessLetter { letter: 's', most: 0 }, "S is not in the word")]
+    #[test_case(GuessError::ExcessLetter { letter: 'e', most: 1 }, "only 1 E in the word")]
+    fn test_guess_error_message(err: GuessError, expected: &str) {
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_ignore_accents() {
+        let mut game = Game::custom("leões".to_owned()).unwrap();
+        assert_eq!(game.guess("leoes"), Err(GuessError::NotInWordList));
+        let matches = game.guess("melao").unwrap();
+        let (exact, close, wrong) = (Match::Exact, Match::Close, Match::Wrong);
+        assert_eq!(matches.0, [wrong, exact, close, wrong, wrong]);
+
+        let mut game = Game::custom("leões".to_owned())
+            .unwrap()
+            .with_ignore_accents(true);
+        let matches = game.guess("melao").unwrap();
+        assert_eq!(matches.0, [wrong, exact, close, wrong, close]);
+        assert_eq!(game.guess("leoes").unwrap().0, [exact; 5]);
+        let guesses = game
+            .state()
+            .guesses()
+            .map(|crate::state::Guess(word, _)| word);
+        assert_eq!(guesses.collect::<Vec<_>>(), ["melao", "leões"]);
+        assert_eq!(game.game_over(), Some(true));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let mut game = Game::from_day(0).unwrap();
+        assert_eq!(game.guess("temro"), Err(GuessError::NotInWordList));
+        assert!(game
+            .state()
+            .suggestions("temro", 3)
+            .contains(&"termo".to_owned()));
+        assert!(game.state().suggestions("termo", 3).len() <= 3);
+
+        // words that couldn't be guessed aren't suggested
+        game.guess("termo").unwrap();
+        assert!(game.state().suggestions("temro", 3).is_empty());
+    }
+
+    #[test]
+    fn test_hard_mode_duplicates() {
+        let mut game = Game::from_day(0).unwrap().with_hard_mode(true);
+        // only one of the e's is in termo
+        game.state_mut().guess("tesem").unwrap();
+        assert!(game.state_mut().guess("temor").is_ok());
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("ter-o"; "not alphabetic")]
+    #[test_case("12345"; "digits")]
+    fn test_malformed_solution(solution: &str) {
+        let game = Game::custom(solution.to_owned());
+        assert!(matches!(game, Err(GameInitError::MalformedSolution(s)) if s == solution));
+        let game = Game::custom_unlisted(solution.to_owned());
+        assert!(matches!(game, Err(GameInitError::MalformedSolution(s)) if s == solution));
+    }
+
+    #[test]
+    fn test_uppercase_solution() {
+        let game = Game::custom("TERMO".to_owned()).unwrap();
+        assert_eq!(game.state().solution(), "termo");
+        let game = Game::custom_unlisted("AÇÕES".to_owned()).unwrap();
+        assert_eq!(game.state().solution(), "ações");
+    }
+
+    #[test]
+    fn test_solution_length() {
+        let game = Game::custom("hi".to_owned());
+        assert!(matches!(game, Err(GameInitError::NoWordList(2))));
+        assert_eq!(
+            game.err().unwrap().to_string(),
+            "no word list for 2 letter words"
+        );
+    }
+
+    #[test]
+    fn test_unlisted_solution() {
+        let game = Game::custom("xyzzy".to_owned());
+        assert!(matches!(game, Err(GameInitError::InvalidSolution(_))));
+
+        let mut game = Game::custom_unlisted("xyzzy".to_owned()).unwrap();
+        let matches = game.state_mut().guess("xyzzy").unwrap();
+        assert_eq!(matches, Matches(vec![Match::Exact; 5]));
+    }
+
+    #[test]
+    fn test_word_length() {
+        let game = Game::custom("termos".to_owned());
+        assert!(matches!(game, Err(GameInitError::NoWordList(6))));
+
+        let mut game = Game::custom_unlisted("termos".to_owned()).unwrap();
+        assert_eq!(game.word_len(), 6);
+        let matches = game.state_mut().guess("termos").unwrap();
+        assert_eq!(matches, Matches(vec![Match::Exact; 6]));
+        assert_eq!(
+            Game::custom_unlisted("termos".to_owned())
+                .unwrap()
+                .state_mut()
+                .guess("termo"),
+            Err(GuessError::WrongLength {
+                expected: 6,
+                got: 5
+            })
+        );
+        let share = game.share().to_string();
+        assert_eq!(share, "joguei term.ooo #custom 1/6\n\n🟩🟩🟩🟩🟩🟩");
+        assert_eq!(GameShare::parse(&share).unwrap().grid()[0].0.len(), 6);
+
+        let game = Game::from_day_with_length(0, 5).unwrap();
+        assert_eq!(game.state().solution(), "termo");
+        let game = Game::from_day_with_length(0, 6);
+        assert!(matches!(game, Err(GameInitError::NoWordList(6))));
+    }
+
+    #[test]
+    fn test_non_ascii_solution() {
+        let mut game = Game::custom_unlisted("ações".to_owned()).unwrap();
+        let matches = game.state_mut().guess("ações").unwrap();
+        assert_eq!(matches, Matches(vec![Match::Exact; 5]));
+
+        let game = Game::custom_unlisted("漢字漢字漢".to_owned());
+        assert!(game.is_ok(), "letters without case are lowercase enough");
+    }
+
+    #[test]
+    fn test_from_source() {
+        let source = WordList::new(["xyzzy".to_owned()], ["plugh".to_owned()]).unwrap();
+        let mut game = Game::from_source(Arc::new(source), 7).unwrap();
+        assert_eq!(game.game_type(), &GameType::Daily(7));
+        assert_eq!(game.state().solution(), "xyzzy");
+        assert!(game.state_mut().guess("plugh").is_ok());
+        assert_eq!(
+            game.state_mut().guess("termo"),
+            Err(GuessError::NotInWordList)
+        );
+    }
+
+    #[test]
+    fn test_from_dictionary() {
+        let source = WordList::parse("gato\npato\n", 4).unwrap();
+        let mut game = Game::from_dictionary(Arc::new(source), 1).unwrap();
+        assert_eq!(game.state().solution(), "pato");
+        assert!(game.guess("gato").is_ok());
+        assert_eq!(game.share().game_type(), &GameType::Custom);
+    }
+
+    #[test]
+    fn test_cyrillic_dictionary() {
+        let source = WordList::parse(
+            "СЛОВО
+книга
+",
+            5,
+        )
+        .unwrap();
+        let mut game = Game::from_dictionary(Arc::new(source), 0).unwrap();
+        assert_eq!(game.state().solution(), "слово");
+        assert_eq!(
+            game.guess("книга").unwrap(),
+            "BBBBB".parse::<Matches>().unwrap()
+        );
+        assert_eq!(
+            game.state().alphabet(),
+            "авгиклнос".chars().collect::<Vec<_>>()
+        );
+        assert!(game.guess("слово").is_ok());
+        assert_eq!(game.game_over(), Some(true));
+    }
+
+    #[test]
+    fn test_alias_wrong_length() {
+        let game = Game::custom("termo".to_owned())
+            .unwrap()
+            .with_aliases(["termos".to_owned()]);
+        assert!(matches!(game, Err(GameInitError::InvalidAlias(alias)) if alias == "termos"));
+    }
+
+    #[test_case(ShareStyle::Full, "joguei term.ooo #0 2/6\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩"; "full")]
+    #[test_case(ShareStyle::NoScore, "joguei term.ooo #0\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩"; "no score")]
+    #[test_case(ShareStyle::NoGrid, "joguei term.ooo #0 2/6"; "no grid")]
+    #[test_case(ShareStyle::DiscordSpoiler, "joguei term.ooo #0 2/6\n\n||⬛⬛⬛🟨🟨||\n||🟩🟩🟩🟩🟩||"; "discord spoiler")]
+    fn test_share_style(style: ShareStyle, expected: &str) {
+        let share = played().with_style(style);
+        assert_eq!(share.to_string(), expected);
+    }
+
+    #[test_case("joguei term.ooo #0 2/6", true; "won")]
+    #[test_case("joguei term.ooo #0 X/6", false; "lost")]
+    #[test_case("joguei term.ooo #0\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩", true; "won without score")]
+    #[test_case("joguei term.ooo #0\n\n⬛⬛⬛🟨🟨", false; "lost without score")]
+    fn test_share_won(input: &str, won: bool) {
+        assert_eq!(GameShare::parse(input).unwrap().won(), won);
+    }
+
+    #[test_case("joguei term.ooo #3 2/6\n\n⬛️⬛️⬛️🟨️🟨️\n🟩️🟩️🟩️🟩️🟩️"; "variation selectors")]
+    #[test_case("\u{feff}joguei term.ooo #3 2/6\n\n⬛⬛\u{200b}⬛🟨🟨\u{200d}\n🟩🟩🟩🟩🟩"; "zero width")]
+    #[test_case("joguei term.ooo #3\u{a0}2/6\r\n\r\n⬜⬜⬜🟨🟨 \r\n🟩🟩🟩🟩🟩\r\n"; "light mode")]
+    #[test_case("  joguei term.ooo #3 2/6\n\n⬛⬛⬛🟦🟦\n🟧🟧🟧🟧🟧\n\n"; "high contrast")]
+    #[test_case("```\njoguei term.ooo #3 2/6\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩\n```"; "code block")]
+    #[test_case("||joguei term.ooo #3 2/6||\n\n|| ⬛⬛⬛🟨🟨 ||\n||🟩🟩🟩🟩🟩||"; "spoiler")]
+    fn test_share_parse_pasted(input: &str) {
+        let parsed = GameShare::parse(input)
+            .unwrap()
+            .with_style(ShareStyle::Full)
+            .with_white_squares(false);
+        assert_eq!(
+            parsed.to_string(),
+            "joguei term.ooo #3 2/6\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_share_json() {
+        let json = played().to_json();
+        assert_eq!(
+            json,
+            r#"{"game_type":{"daily":0},"day":0,"solution_withheld":true,"solution_hash":null,"guesses":null,"score":{"guesses":2},"max_guesses":6,"hard_mode":false,"par":null,"hints":0,"grid":[["black","black","black","amber","amber"],["green","green","green","green","green"]],"seconds":null,"analysis":null}"#
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let grid: Vec<Matches> = serde_json::from_value(value["grid"].clone()).unwrap();
+        assert_eq!(grid, played().grid());
+
+        let mut game = Game::from_day(0).unwrap();
+        game.state_mut().guess("suite").unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&game.share().with_guesses(&game).to_json()).unwrap();
+        assert_eq!(value["guesses"], serde_json::json!(["suite"]));
+        assert_eq!(value["solution_withheld"], true);
+        assert_eq!(value["solution_hash"].as_str().map(str::len), Some(16));
+        assert_eq!(value["analysis"], serde_json::Value::Null);
+
+        let share = game.share().with_guesses(&game).with_analysis(true);
+        let value: serde_json::Value = serde_json::from_str(&share.to_json()).unwrap();
+        let rated = &value["analysis"]["guesses"][0];
+        assert_eq!(rated["word"], "suite");
+        assert_eq!(rated["before"], crate::words::FINAL.len());
+        assert!(GameShare::parse(&share.to_string())
+            .unwrap()
+            .analysis()
+            .is_none());
+
+        // the last guess of a win is the solution
+        game.state_mut().guess("termo").unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&game.share().with_guesses(&game).to_json()).unwrap();
+        assert_eq!(value["solution_withheld"], false);
+        let value: serde_json::Value = serde_json::from_str(&game.share().to_json()).unwrap();
+        assert_eq!(value["solution_withheld"], true);
+    }
+
+    #[test]
+    fn test_share_high_contrast() {
+        let share = played().with_high_contrast(true);
+        assert_eq!(
+            share.to_string(),
+            "joguei term.ooo #0 2/6\n\n⬛⬛⬛🟦🟦\n🟧🟧🟧🟧🟧"
+        );
+        assert_eq!(
+            GameShare::parse(&share.to_string()).unwrap().grid(),
+            played().grid()
+        );
+    }
+
+    #[test]
+    fn test_share_white_squares() {
+        let share = played().with_white_squares(true);
+        assert_eq!(
+            share.to_string(),
+            "joguei term.ooo #0 2/6\n\n⬜⬜⬜🟨🟨\n🟩🟩🟩🟩🟩"
+        );
+        let parsed = GameShare::parse(&share.to_string()).unwrap();
+        assert_eq!(parsed.grid(), played().grid());
+        // pasted back, it's still drawn in white
+        assert!(parsed.white_squares());
+        assert!(!GameShare::parse(&played().to_string())
+            .unwrap()
+            .white_squares());
+    }
+
+    #[test_case("Termo {day} {score}{hard}\\n{grid}\\n{url}", ShareStyle::Full,
+        "Termo 0 2/6*\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩\nhttps://term.ooo"; "full")]
+    #[test_case("**{day}** {{{score}}} {time}", ShareStyle::NoScore, "**0** {} 00:47"; "no score")]
+    #[test_case("{score}{hints}", ShareStyle::Full, "2/6"; "no hints")]
+    #[test_case("{grid}", ShareStyle::DiscordSpoiler, "||⬛⬛⬛🟨🟨||\n||🟩🟩🟩🟩🟩||"; "spoiler")]
+    fn test_share_template(template: &str, style: ShareStyle, expected: &str) {
+        let template = template.parse::<ShareTemplate>().unwrap();
+        let mut game = Game::from_day(0).unwrap().with_hard_mode(true);
+        game.state_mut().guess("suite").unwrap();
+        game.state_mut().guess("termo").unwrap();
+        let share = game
+            .share()
+            .with_style(style)
+            .with_time(Duration::from_secs(47))
+            .with_template(Some(template));
+        assert_eq!(share.to_string(), expected);
+    }
+
+    #[test]
+    fn test_share_template_errors() {
+        assert_eq!(
+            "{day".parse::<ShareTemplate>(),
+            Err(ParseTemplateError::Unclosed)
+        );
+        assert_eq!(
+            "{date}".parse::<ShareTemplate>(),
+            Err(ParseTemplateError::UnknownPlaceholder("date".to_owned()))
+        );
+    }
+
+    #[test_case(2, "joguei term.ooo #0 2/6 (met par 2)"; "met")]
+    #[test_case(4, "joguei term.ooo #0 2/6 (2 under par 4)"; "under")]
+    #[test_case(1, "joguei term.ooo #0 2/6 (1 over par 1)"; "over")]
+    fn test_share_par(par: usize, header: &str) {
+        let mut game = Game::from_day(0).unwrap().with_par(par);
+        game.state_mut().guess("sagaz").unwrap();
+        game.state_mut().guess("termo").unwrap();
+        let share = game.share().with_style(ShareStyle::NoGrid);
+        assert_eq!(share.to_string(), header);
+        let parsed = GameShare::parse(header).unwrap();
+        assert_eq!(parsed.par_result(), share.par_result());
+    }
+
+    #[test]
+    fn test_share_par_lost() {
+        let share = GameShare::parse("joguei term.ooo #0 X/6* (missed par 3)").unwrap();
+        assert_eq!(share.par(), Some(3));
+        assert_eq!(share.par_result(), Some(ParResult::Missed));
+        assert!(share.hard_mode());
+    }
+
+    #[test]
+    fn test_share_time() {
+        let share = played().with_time(Duration::from_millis(47_900));
+        let text = share.to_string();
+        assert_eq!(
+            text,
+            "joguei term.ooo #0 2/6\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩\n⏱ 00:47"
+        );
+        assert_eq!(GameShare::parse(&text).unwrap(), share);
+        assert_eq!(share.without_time().to_string(), played().to_string());
+        // pasted with the emoji's variation selector
+        let pasted = GameShare::parse("joguei term.ooo #0 1/6\n\n🟩🟩🟩🟩🟩\n⏱️ 1:02:05").unwrap();
+        assert_eq!(pasted.time(), Some(Duration::from_secs(3725)));
+        assert_eq!(
+            GameShare::parse("joguei term.ooo #0 1/6\n⏱ soon"),
+            Err(ParseShareError::InvalidTime)
+        );
+    }
+
+    #[test_case(0, "00:00")]
+    #[test_case(47, "00:47")]
+    #[test_case(605, "10:05")]
+    #[test_case(3725, "1:02:05")]
+    fn test_elapsed(seconds: u64, expected: &str) {
+        let elapsed = Elapsed(Duration::from_secs(seconds));
+        assert_eq!(elapsed.to_string(), expected);
+        assert_eq!(expected.parse(), Ok(elapsed));
+    }
+
+    #[test_case("1:60")]
+    #[test_case("1:02:75")]
+    #[test_case("99999999999999999:00:00")]
+    #[test_case("18446744073709551615:00")]
+    fn test_elapsed_invalid(input: &str) {
+        assert_eq!(input.parse::<Elapsed>(), Err(ParseShareError::InvalidTime));
+    }
+
+    #[test_case(Score::Guesses(4), "4")]
+    #[test_case(Score::Guesses(10), "10")]
+    #[test_case(Score::Failed, "X")]
+    fn test_score_roundtrip(score: Score, expected: &str) {
+        assert_eq!(score.to_string(), expected);
+        assert_eq!(expected.parse(), Ok(score));
+    }
+
+    #[test_case("joguei term.ooo #3 10/6", Some(Score::Guesses(10)); "two digits")]
+    #[test_case("joguei term.ooo #3 X/6", Some(Score::Failed); "failed")]
+    #[test_case("joguei term.ooo #3 0/6", None; "zero")]
+    #[test_case("joguei term.ooo #3 2/8", Some(Score::Guesses(2)); "more guesses")]
+    #[test_case("joguei term.ooo #3 2/0", None; "no guesses")]
+    #[test_case("joguei term.ooo #3 +4/6", None; "sign")]
+    fn test_share_parse_score(input: &str, score: Option<Score>) {
+        match score {
+            Some(score) => {
+                let parsed = GameShare::parse(input).unwrap();
+                assert_eq!(parsed.score(), Some(score));
+                assert_eq!(parsed.to_string(), input);
+            }
+            None => assert_eq!(
+                GameShare::parse(input).map(|_| ()),
+                Err(ParseShareError::InvalidScore)
+            ),
+        }
+    }
+
+    #[test_case("joguei term.ooo #3 4/6*", Some(Score::Guesses(4)); "with score")]
+    #[test_case("joguei term.ooo #3*\n\n🟩🟩🟩🟩🟩", None; "without score")]
+    fn test_share_parse_hard_mode(input: &str, score: Option<Score>) {
+        let parsed = GameShare::parse(input).unwrap();
+        assert!(parsed.hard_mode());
+        assert_eq!(parsed.score(), score);
+        assert_eq!(parsed.to_string(), input);
+    }
+
+    #[test_case("joguei term.ooo #3 4/6**!", true; "ultra hard")]
+    #[test_case("joguei term.ooo #3 4/6*!", false; "hard")]
+    fn test_share_parse_ultra_hard(input: &str, ultra_hard: bool) {
+        let parsed = GameShare::parse(input).unwrap();
+        assert!(parsed.hard_mode());
+        assert_eq!(parsed.ultra_hard(), ultra_hard);
+        assert_eq!(parsed.to_string(), input);
+    }
+
+    #[test_case("joguei term.ooo #3 4/6!", false; "without hard mode")]
+    #[test_case("joguei term.ooo #3 4/6*! (2 over par 2)", true; "with hard mode and par")]
+    fn test_share_parse_no_keyboard_hints(input: &str, hard_mode: bool) {
+        let parsed = GameShare::parse(input).unwrap();
+        assert!(parsed.no_keyboard_hints());
+        assert_eq!(parsed.hard_mode(), hard_mode);
+        assert_eq!(parsed.to_string(), input);
+    }
+
+    #[test]
+    fn test_share_parse_tournament() {
+        let mut game = Game::tournament("abc".to_owned(), 3).unwrap();
+        game.state_mut().guess("suite").unwrap();
+        let share = game.share();
+        assert!(share
+            .to_string()
+            .starts_with("joguei term.ooo #Tournament abc #3 "));
+
+        let parsed = GameShare::parse(&share.to_string()).unwrap();
+        assert_eq!(parsed, share);
+    }
+
+    #[test]
+    fn test_share_parse_seeded() {
+        let seed = crate::challenge::Seed::new("termo", 7).unwrap();
+        let mut game = Game::seeded(seed);
+        game.state_mut().guess("suite").unwrap();
+        let share = game.share();
+        let header = format!("joguei term.ooo #seed {} ", seed);
+        assert!(share.to_string().starts_with(&header));
+        assert!(!share.to_string().contains("termo"));
+
+        let parsed = GameShare::parse(&share.to_string()).unwrap();
+        assert_eq!(parsed, share);
+    }
+
+    #[test]
+    fn test_practice() {
+        let mut game = Game::random(Some(42));
+        game.guess("suite").unwrap();
+        let solution = game.state().solution().to_owned();
+        assert_eq!(Game::random(Some(42)).state().solution(), solution);
+        assert_ne!(Game::random(Some(43)).state().solution(), solution);
+
+        let share = game.share();
+        assert!(share
+            .to_string()
+            .starts_with("joguei term.ooo #practice 42 "));
+        assert_eq!(GameShare::parse(&share.to_string()).unwrap(), share);
+    }
+
+    #[test]
+    fn test_resume() {
+        let mut game = Game::from_day(0).unwrap();
+        game.guess("suite").unwrap();
+        game.guess("terra").unwrap();
+        let saved = game.save();
+        assert_eq!(saved, "0\nsuite\nterra");
+
+        let mut resumed = Game::from_day(0).unwrap();
+        assert!(resumed.resume(&saved));
+        assert_eq!(resumed.save(), saved);
+        assert_eq!(
+            resumed.state().guesses().nth(1).unwrap().1,
+            game.state().diff("terra")
+        );
+    }
+
+    #[test]
+    fn test_reveal() {
+        let mut game = Game::from_day(0).unwrap();
+        game.guess("suite").unwrap();
+        // the t and e of termo are already known to be in it, so they're placed
+        let reveals = [game.reveal(), game.reveal(), game.reveal()];
+        assert_eq!(
+            reveals,
+            [
+                Some(Reveal::Placed(0, 't')),
+                Some(Reveal::Placed(1, 'e')),
+                Some(Reveal::Present('r'))
+            ]
+        );
+        assert_eq!(reveals[0].unwrap().to_string(), "T is letter 1");
+        assert_eq!(reveals[2].unwrap().to_string(), "R is in the word");
+        assert_eq!(game.reveal(), Some(Reveal::Placed(2, 'r')));
+
+        game.guess("termo").unwrap();
+        assert_eq!(game.reveal(), None);
+        let share = game.share();
+        assert_eq!(share.hints(), 4);
+        assert_eq!(
+            share.to_string(),
+            "joguei term.ooo #0 2/6\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩\n💡 4 hints"
+        );
+        assert_eq!(GameShare::parse(&share.to_string()).unwrap(), share);
+        assert_eq!(
+            GameShare::parse("joguei term.ooo #0 2/6\n\n💡 some"),
+            Err(ParseShareError::InvalidHints)
+        );
+
+        let saved = game.save();
+        assert_eq!(saved, "0\nsuite\n?\n?\n?\n?\ntermo");
+        let mut resumed = Game::from_day(0).unwrap();
+        assert!(resumed.resume(&saved));
+        assert_eq!(resumed.share(), share);
+    }
+
+    #[test]
+    fn test_reveal_before_guessing() {
+        let mut game = Game::from_day(0).unwrap();
+        assert_eq!(game.reveal(), Some(Reveal::Present('t')));
+        assert_eq!(game.reveal(), Some(Reveal::Placed(0, 't')));
+        assert_eq!(game.save(), "0\n?\n?");
+        assert_eq!(
+            game.share().with_style(ShareStyle::NoGrid).to_string(),
+            "joguei term.ooo #0 0/6\n💡 2 hints"
+        );
+    }
+
+    #[test_case("1\nsuite" ; "another day")]
+    #[test_case("0\nsuite\nzzzzz" ; "guess no longer accepted")]
+    #[test_case("" ; "empty")]
+    fn test_resume_discards(saved: &str) {
+        let mut game = Game::from_day(0).unwrap();
+        assert!(!game.resume(saved));
+        assert_eq!(game.state().guesses().len(), 0);
+    }
+
+    #[test_case(ShareStyle::Full)]
+    #[test_case(ShareStyle::NoScore)]
+    #[test_case(ShareStyle::NoGrid)]
+    #[test_case(ShareStyle::DiscordSpoiler)]
+    fn test_share_parse_roundtrip(style: ShareStyle) {
+        let share = played().with_style(style);
+        let parsed = GameShare::parse(&share.to_string()).unwrap();
+        assert_eq!(parsed.game_type(), &GameType::Daily(0));
+        assert_eq!(parsed.style(), style);
+        assert_eq!(parsed.to_string(), share.to_string());
+
+        match style {
+            ShareStyle::NoScore => assert_eq!(parsed.score(), None),
+            _ => assert_eq!(parsed.score(), Some(Score::Guesses(2))),
+        }
+        match style {
+            ShareStyle::NoGrid => assert!(parsed.grid().is_empty()),
+            _ => assert_eq!(parsed.grid(), share.grid()),
+        }
+    }
+}
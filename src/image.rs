@@ -0,0 +1,334 @@
+//! Draws a [`GameShare`] as a picture, for places that don't line up emoji
+//!
+//! The caption is the first line of the share. In a PNG it's in a small bitmap font
+//! that only knows ascii letters, digits and the punctuation a share can have, and
+//! anything else is left blank, while an SVG leaves it to the viewer's monospace
+//! font. Below it, each row of the grid is a row of solid squares, in the same
+//! order as on screen.
+
+use std::{fmt::Write as _, io::Write};
+
+use crate::{game::GameShare, Match, Matches};
+
+/// Side of a grid cell, in pixels
+const CELL: u32 = 40;
+/// Space between cells, and between the caption and the grid
+const GAP: u32 = 6;
+/// Space around everything
+const MARGIN: u32 = 16;
+/// Each pixel of the font is drawn as a square this big
+const SCALE: u32 = 3;
+/// Glyphs are this many font pixels wide and tall, plus one of space after each
+const GLYPH: (u32, u32) = (5, 7);
+
+/// The background and text, dark like the board, or light for a share with white
+/// squares
+fn background(white_squares: bool) -> ([u8; 3], [u8; 3]) {
+    match white_squares {
+        false => ([0x12, 0x12, 0x13], [0xff, 0xff, 0xff]),
+        true => ([0xff, 0xff, 0xff], [0x1a, 0x1a, 0x1b]),
+    }
+}
+
+/// The colour of a square, matching the emoji in the share
+fn colour(m: Match, share: &GameShare) -> [u8; 3] {
+    match (m, share.high_contrast(), share.white_squares()) {
+        (Match::Exact, false, _) => [0x53, 0x8d, 0x4e],
+        (Match::Close, false, _) => [0xb5, 0x9f, 0x3b],
+        (Match::Exact, true, _) => [0xf5, 0x79, 0x3a],
+        (Match::Close, true, _) => [0x85, 0xc0, 0xf9],
+        (Match::Wrong, _, false) => [0x3a, 0x3a, 0x3c],
+        (Match::Wrong, _, true) => [0xd3, 0xd6, 0xda],
+    }
+}
+
+/// Where the caption and squares go, and the size of the picture around them
+struct Layout<'a> {
+    caption: String,
+    grid: &'a [Matches],
+    width: u32,
+    height: u32,
+}
+
+impl<'a> Layout<'a> {
+    fn new(share: &'a GameShare) -> Self {
+        let caption = share.to_string();
+        let caption = caption.lines().next().unwrap_or_default().to_owned();
+        let grid = match share.style() {
+            crate::game::ShareStyle::NoGrid => &[],
+            _ => share.grid(),
+        };
+
+        let columns = grid.iter().map(|Matches(row)| row.len()).max().unwrap_or(0) as u32;
+        let grid_width = (columns * (CELL + GAP)).saturating_sub(GAP);
+        let grid_height = grid.len() as u32 * (CELL + GAP);
+        Self {
+            width: caption_width(&caption).max(grid_width) + 2 * MARGIN,
+            height: GLYPH.1 * SCALE + grid_height + 2 * MARGIN,
+            caption,
+            grid,
+        }
+    }
+
+    /// The top left corner of each square, with its match
+    fn squares(&self) -> impl Iterator<Item = (u32, u32, Match)> + '_ {
+        let top = MARGIN + GLYPH.1 * SCALE + GAP;
+        self.grid
+            .iter()
+            .enumerate()
+            .flat_map(move |(row, Matches(matches))| {
+                matches.iter().enumerate().map(move |(column, &m)| {
+                    let (x, y) = (column as u32 * (CELL + GAP), row as u32 * (CELL + GAP));
+                    (MARGIN + x, top + y, m)
+                })
+            })
+    }
+}
+
+/// Width of the caption in the bitmap font, which the SVG's is stretched to as well
+fn caption_width(caption: &str) -> u32 {
+    let letters = caption.chars().count() as u32;
+    (letters * (GLYPH.0 + 1)).saturating_sub(1) * SCALE
+}
+
+/// An RGB image, drawn into from the top left
+struct Pixels {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Pixels {
+    fn new(width: u32, height: u32, background: [u8; 3]) -> Self {
+        let data = background.repeat((width * height) as usize);
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, rgb: [u8; 3]) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                let i = ((row * self.width + column) * 3) as usize;
+                self.data[i..i + 3].copy_from_slice(&rgb);
+            }
+        }
+    }
+
+    fn text(&mut self, x: u32, y: u32, text: &str, rgb: [u8; 3]) {
+        for (i, c) in text.chars().enumerate() {
+            let left = x + i as u32 * (GLYPH.0 + 1) * SCALE;
+            for (row, bits) in glyph(c).into_iter().enumerate() {
+                for column in 0..GLYPH.0 {
+                    if bits & (1 << (GLYPH.0 - 1 - column)) != 0 {
+                        let (px, py) = (left + column * SCALE, y + row as u32 * SCALE);
+                        self.fill(px, py, SCALE, SCALE, rgb);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Draws the caption and grid
+fn draw(share: &GameShare) -> Pixels {
+    let layout = Layout::new(share);
+    let (background, text) = background(share.white_squares());
+    let mut pixels = Pixels::new(layout.width, layout.height, background);
+    pixels.text(MARGIN, MARGIN, &layout.caption, text);
+    for (x, y, m) in layout.squares() {
+        pixels.fill(x, y, CELL, CELL, colour(m, share));
+    }
+    pixels
+}
+
+/// Writes the share as an SVG, laid out like the PNG
+pub(crate) fn svg(share: &GameShare) -> String {
+    let layout = Layout::new(share);
+    let (background, text) = background(share.white_squares());
+    let hex = |[r, g, b]: [u8; 3]| format!("#{:02x}{:02x}{:02x}", r, g, b);
+    let (width, height) = (layout.width, layout.height);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(background)
+    );
+    // the font's cap height is about three quarters of its size
+    let size = GLYPH.1 * SCALE * 4 / 3;
+    let caption = layout
+        .caption
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    writeln!(
+        svg,
+        "<text x=\"{}\" y=\"{}\" fill=\"{}\" font-family=\"monospace\" font-size=\"{}\" \
+         textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\">{}</text>",
+        MARGIN,
+        MARGIN + GLYPH.1 * SCALE,
+        hex(text),
+        size,
+        caption_width(&layout.caption),
+        caption
+    )
+    .unwrap();
+    for (x, y, m) in layout.squares() {
+        let fill = hex(colour(m, share));
+        writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"{fill}\"/>"
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Writes the share as a PNG
+pub(crate) fn encode(share: &GameShare, w: impl Write) -> Result<(), png::EncodingError> {
+    let pixels = draw(share);
+    let mut encoder = png::Encoder::new(w, pixels.width, pixels.height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&pixels.data)
+}
+
+/// The rows of a 5 by 7 glyph, with the leftmost pixel in the highest bit. Lowercase
+/// letters are drawn as uppercase
+#[rustfmt::skip]
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        '#' => [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010],
+        '/' => [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '*' => [0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000],
+        '(' => [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010],
+        ')' => [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000],
+        ':' => [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        _ => [0; 7],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{background, colour, draw, encode, svg, CELL, GAP, GLYPH, MARGIN, SCALE};
+    use crate::{game::GameShare, Match};
+
+    fn pixel(data: &[u8], width: u32, x: u32, y: u32) -> &[u8] {
+        let i = ((y * width + x) * 3) as usize;
+        &data[i..i + 3]
+    }
+
+    #[test]
+    fn squares_follow_the_grid() {
+        let share = GameShare::parse("joguei term.ooo #0 2/6\n\n🟨⬛⬛⬛🟩\n🟩🟩🟩🟩🟩").unwrap();
+        let pixels = draw(&share);
+        // the caption is wider than the grid
+        assert_eq!(
+            pixels.width,
+            22 * (GLYPH.0 + 1) * SCALE - SCALE + 2 * MARGIN
+        );
+        assert_eq!(
+            pixels.height,
+            GLYPH.1 * SCALE + 2 * (CELL + GAP) + 2 * MARGIN
+        );
+
+        let top = MARGIN + GLYPH.1 * SCALE + GAP;
+        let centre = |column: u32, row: u32| {
+            let (x, y) = (
+                column * (CELL + GAP) + CELL / 2,
+                row * (CELL + GAP) + CELL / 2,
+            );
+            pixel(&pixels.data, pixels.width, MARGIN + x, top + y).to_vec()
+        };
+        assert_eq!(centre(0, 0), colour(Match::Close, &share));
+        assert_eq!(centre(1, 0), colour(Match::Wrong, &share));
+        assert_eq!(centre(4, 0), colour(Match::Exact, &share));
+        assert_eq!(centre(2, 1), colour(Match::Exact, &share));
+
+        let share = share.with_high_contrast(true).with_white_squares(true);
+        let pixels = draw(&share);
+        let (x, y) = (MARGIN + CELL / 2, top + CELL / 2);
+        assert_eq!(pixel(&pixels.data, pixels.width, x, y), [0x85, 0xc0, 0xf9]);
+        // white squares are for a light background
+        assert_eq!(pixel(&pixels.data, pixels.width, 0, 0), background(true).0);
+        assert_eq!(
+            pixel(
+                &pixels.data,
+                pixels.width,
+                MARGIN + CELL + GAP + CELL / 2,
+                y
+            ),
+            [0xd3, 0xd6, 0xda]
+        );
+    }
+
+    #[test]
+    fn svgs_are_laid_out_like_pngs() {
+        let share = GameShare::parse("joguei term.ooo #0 2/6\n\n🟨⬛⬛⬛🟩\n🟩🟩🟩🟩🟩").unwrap();
+        let svg = svg(&share);
+        let pixels = draw(&share);
+        assert!(svg.starts_with(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\"",
+            pixels.width, pixels.height
+        )));
+        assert!(svg.contains(">joguei term.ooo #0 2/6</text>"));
+        assert_eq!(svg.matches("<rect x=").count(), 10);
+        assert_eq!(svg.matches("fill=\"#538d4e\"").count(), 6);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn encodes_a_png() {
+        let share = GameShare::parse("joguei term.ooo #0 1/6\n\n🟩🟩🟩🟩🟩").unwrap();
+        let mut png = Vec::new();
+        encode(&share, &mut png).unwrap();
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(png));
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut data).unwrap();
+        let pixels = draw(&share);
+        assert_eq!((info.width, info.height), (pixels.width, pixels.height));
+        assert_eq!(&data[..info.buffer_size()], pixels.data);
+    }
+}
@@ -1,41 +1,175 @@
-use std::fmt::Display;
+//! Wordle in your terminal
+//!
+//! The library holds the game logic used by the `wordle` binary, so it can be
+//! embedded in other frontends.
+//!
+//! None of it needs a terminal, so a server or test harness can depend on it with
+//! `default-features = false` and play a game with [`Game::guess`](game::Game::guess),
+//! [`Game::game_over`](game::Game::game_over) and [`Game::share`](game::Game::share).
+//! A frontend taking key presses can leave the typing to [`frontend::Typing`] and
+//! only implement [`frontend::Renderer`], as the terminal one does.
+//!
+//! Everything in [`prelude`] is considered stable, along with [`diff`], [`valid`],
+//! [`get_solution`], [`get_solution_hash`], [`get_day`] and the [`words`] lists.
+//! That includes how [`diff`] scores repeated letters, and the `G`, `Y` and `B` letters
+//! [`Matches`] are written and [parsed](Matches::from_str) as, for tools built on them.
+//! The [`solver`] and [`analysis`] modules are still experimental and may change between
+//! minor versions.
+
+use std::{fmt::Display, str::FromStr};
+
+use source::WordSource;
 
 pub mod words;
 pub mod state;
 pub mod game;
+pub mod multi;
+pub mod frontend;
+#[cfg(feature = "canvas")]
+pub mod canvas;
+pub mod solver;
+pub mod analysis;
+pub mod challenge;
+pub mod tournament;
+pub mod duel;
+pub mod marathon;
+pub mod crosswordle;
+pub mod train;
+pub mod stats;
+pub mod frequency;
+pub mod definitions;
+pub mod source;
+#[cfg(feature = "time")]
+pub mod clock;
+#[cfg(feature = "net")]
+mod remote;
+#[cfg(feature = "image")]
+mod image;
+
+/// Re-exports the types needed to play a game
+pub mod prelude {
+    pub use crate::game::{
+        Game, GameInitError, GameShare, GameType, ParResult, ParseShareError, Score,
+        ShareStyle,
+    };
+    pub use crate::state::{Guess, GuessError, State};
+    pub use crate::{Match, Matches, ParseMatchesError};
+}
+
+/// Number of letters in the bundled words
+pub const WORD_LEN: usize = 5;
+
+/// Number of guesses a game allows, unless it's changed with
+/// [`Game::with_max_guesses`](game::Game::with_max_guesses)
+pub const MAX_GUESSES: usize = 6;
 
 /// Gets the solution word for the given day
 pub fn get_solution(day: usize) -> &'static str {
     words::FINAL[day % words::FINAL.len()]
 }
 
+/// Gets a stable hash of the solution for the given day, so builds can be
+/// compared without revealing the word
+pub fn get_solution_hash(day: usize) -> u64 {
+    tournament::fnv1a(get_solution(day).as_bytes())
+}
+
 /// Gets the current day number from the given date
 #[cfg(feature = "time")]
 pub fn get_day(date: time::Date) -> usize {
     (date.to_julian_day() - words::FIRST_DAY.to_julian_day()) as usize
 }
 
+/// Gets the current time at the given offset from UTC, or in the local timezone if
+/// there's none, so the day can follow a chosen region instead of the machine
+#[cfg(all(feature = "time", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+pub fn now(offset: Option<time::UtcOffset>) -> Result<time::OffsetDateTime, game::GameInitError> {
+    now_from(&clock::System, offset)
+}
+
+/// Gets the time from the clock, as [`now`] does from the system's
+#[cfg(feature = "time")]
+pub fn now_from(
+    clock: &dyn clock::Clock,
+    offset: Option<time::UtcOffset>,
+) -> Result<time::OffsetDateTime, game::GameInitError> {
+    let offset = match offset.or_else(|| clock.local_offset()) {
+        Some(offset) => offset,
+        None => return Err(game::GameInitError::TimezoneUnknown),
+    };
+    Ok(clock.now_utc().to_offset(offset))
+}
+
+/// Gets how long until the next day's puzzle, at midnight in the offset of `now`
+#[cfg(feature = "time")]
+pub fn time_until_next_day(now: time::OffsetDateTime) -> time::Duration {
+    match now.date().next_day() {
+        Some(tomorrow) => tomorrow.midnight().assume_offset(now.offset()) - now,
+        None => time::Duration::ZERO,
+    }
+}
+
+/// The word with the accents taken off its letters, so `leões` becomes `leoes` and
+/// `λέξη` becomes `λεξη`, as words are compared in games that
+/// [ignore accents](crate::game::Game::with_ignore_accents). Letters without a
+/// plain form in the alphabet, like `ß`, are kept as they are
+pub fn fold_accents(word: &str) -> String {
+    word.chars().map(fold_accent).collect()
+}
+
+fn fold_accent(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ç' => 'c',
+        'ñ' => 'n',
+        'ά' => 'α',
+        'έ' => 'ε',
+        'ή' => 'η',
+        'ί' | 'ϊ' | 'ΐ' => 'ι',
+        'ό' => 'ο',
+        'ύ' | 'ϋ' | 'ΰ' => 'υ',
+        'ώ' => 'ω',
+        c => c,
+    }
+}
+
 /// Determines if the given word is valid, according to the default word lists
 pub fn valid(word: &str) -> bool {
-    words::ACCEPT.contains(&word) || words::FINAL.contains(&word)
+    source::Bundled.is_acceptable(word)
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Represents a match for a given letter against the solution
 pub enum Match {
     /// Letter is in the correct position
+    #[cfg_attr(feature = "serde", serde(rename = "green"))]
     Exact,
     /// Letter is in the solution but not in the correct positon
+    #[cfg_attr(feature = "serde", serde(rename = "amber"))]
     Close,
     /// Letter is not in the solution
+    #[cfg_attr(feature = "serde", serde(rename = "black"))]
     Wrong,
 }
 
-/// Represents the outcome for a single guess
-pub struct Matches(pub [Match; 5]);
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents the outcome for a single guess, with one match per letter
+pub struct Matches(pub Vec<Match>);
 
+/// Draws the match as its emoji square, as in a share, or with `{:#}`, as its
+/// [letter](Match::letter)
 impl Display for Match {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", self.letter());
+        }
         match self {
             Match::Exact => write!(f, "🟩"),
             Match::Close => write!(f, "🟨"),
@@ -44,33 +178,153 @@ impl Display for Match {
     }
 }
 
+/// Draws the matches as a row of emoji squares, like `⬛🟨🟩🟨🟨`, or with `{:#}`, as
+/// letters, like `BYGYY`. Both can be [parsed](Matches::from_str) back
 impl Display for Matches {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for m in self.0 {
-            write!(f, "{}", m)?;
+        for m in &self.0 {
+            match f.alternate() {
+                true => write!(f, "{:#}", m)?,
+                false => write!(f, "{}", m)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads a row of matches, one for each letter of the guess, written as letters
+/// like `BYGYY`, in either case, or as emoji squares like `⬛🟨🟩🟨🟨`.
+///
+/// The high contrast 🟧 and 🟦 and the light mode ⬜ are read as the colours they
+/// stand for
+impl FromStr for Matches {
+    type Err = ParseMatchesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let matches = s.chars().map(|c| {
+            let m = Match::from_letter(c).or_else(|| Match::from_square(c));
+            m.ok_or(ParseMatchesError::InvalidMatch(c))
+        });
+        let matches = matches.collect::<Result<Vec<_>, _>>()?;
+        match matches.is_empty() {
+            true => Err(ParseMatchesError::Empty),
+            false => Ok(Matches(matches)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a row of matches could not be [parsed](Matches::from_str)
+pub enum ParseMatchesError {
+    /// There were no matches at all
+    Empty,
+    /// This isn't a letter or square for a match
+    InvalidMatch(char),
+}
+
+impl Display for ParseMatchesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseMatchesError::Empty => write!(f, "no matches given"),
+            ParseMatchesError::InvalidMatch(c) => {
+                write!(f, "{:?} is not a match, use G, Y and B", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMatchesError {}
+
+impl Match {
+    /// The colour of the match, as named in the JSON output
+    pub fn name(self) -> &'static str {
+        match self {
+            Match::Exact => "green",
+            Match::Close => "amber",
+            Match::Wrong => "black",
         }
-        write!(f, "")
+    }
+
+    /// The letter for the match: `G` for green, `Y` for yellow and `B` for black
+    pub fn letter(self) -> char {
+        match self {
+            Match::Exact => 'G',
+            Match::Close => 'Y',
+            Match::Wrong => 'B',
+        }
+    }
+
+    /// The match for its [letter](Match::letter), in either case
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'G' => Some(Match::Exact),
+            'Y' => Some(Match::Close),
+            'B' => Some(Match::Wrong),
+            _ => None,
+        }
+    }
+
+    /// The match for an emoji square of a share, in any of its colour schemes
+    pub fn from_square(c: char) -> Option<Self> {
+        match c {
+            // orange and blue are the high contrast colours
+            '🟩' | '🟧' => Some(Match::Exact),
+            '🟨' | '🟦' => Some(Match::Close),
+            // white is used in light mode
+            '⬛' | '⬜' => Some(Match::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl Matches {
+    /// Spells out each letter of the guess and how it matched, like
+    /// `C green, R amber, A black, N black, E green`, for screen readers
+    pub fn describe(&self, word: &str) -> String {
+        let letters = word.chars().zip(&self.0);
+        let described = letters.map(|(c, m)| format!("{} {}", c.to_uppercase(), m.name()));
+        described.collect::<Vec<_>>().join(", ")
     }
 }
 
+/// Scores the guess against the solution, one letter at a time, the way the official
+/// game does when letters repeat:
+///
+/// - every letter in the same place as in the solution is [exact](Match::Exact), and
+///   these are found first
+/// - then from left to right, a letter that's elsewhere in the solution is
+///   [close](Match::Close), but only as many times as the solution has it left over
+///   once the exact ones are taken
+/// - every other letter is [wrong](Match::Wrong)
+///
+/// So `eerie` against `there` is `YBYBG`: the last e is exact, which leaves one e for
+/// the first e to be close, and none for the second. This is part of the stable API,
+/// and won't change between versions.
+///
+/// Letters are `char`s rather than bytes, so words outside of ascii can be scored too.
+/// They're compared as they are, so fold them first to match without case or accents.
+///
+/// # Panics
+///
+/// If the guess isn't as long as the solution, in letters
 pub fn diff(input: &str, solution: &str) -> Matches {
-    assert!(
-        input.is_ascii(),
-        "input guess should only be 5 ascii letters"
-    );
-    assert_eq!(input.len(), 5, "input guess should only be 5 ascii letters");
-    debug_assert!(solution.is_ascii());
-    debug_assert_eq!(solution.len(), 5);
+    if input.is_ascii() && solution.is_ascii() {
+        return diff_letters(input.as_bytes(), solution.as_bytes().to_owned());
+    }
+    let input = input.chars().collect::<Vec<_>>();
+    diff_letters(&input, solution.chars().collect())
+}
 
-    let input = input.as_bytes();
-    let mut solution = solution.as_bytes().to_owned();
+/// Scores equal sized letters, where the default value is never a letter
+fn diff_letters<T: Copy + Default + PartialEq>(input: &[T], mut solution: Vec<T>) -> Matches {
+    assert_eq!(input.len(), solution.len(), "input guess should be as long as the solution");
 
-    let mut diff = [Match::Wrong; 5];
+    let mut diff = vec![Match::Wrong; input.len()];
 
     // find exact matches first
     for (i, &b) in input.iter().enumerate() {
         if solution[i] == b {
-            solution[i] = 0; // letters only match once
+            solution[i] = T::default(); // letters only match once
             diff[i] = Match::Exact;
         }
     }
@@ -81,7 +335,7 @@ pub fn diff(input: &str, solution: &str) -> Matches {
             continue;
         }
         if let Some(j) = solution.iter().position(|&x| x == b) {
-            solution[j] = 0; // letters only match once
+            solution[j] = T::default(); // letters only match once
             diff[i] = Match::Close;
         }
     }
@@ -92,15 +346,101 @@ pub fn diff(input: &str, solution: &str) -> Matches {
 #[cfg(test)]
 mod tests {
     use super::{
-        diff,
+        diff, fold_accents,
         Match::{self, *},
+        Matches, ParseMatchesError,
     };
     use test_case::test_case;
 
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_solution_hash() {
+        let day = super::get_day(time::macros::date!(2022 - 02 - 01));
+        assert_eq!(day, 30);
+        assert_eq!(super::get_solution_hash(day), 0xe727d8afb3c3924b);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_until_next_day() {
+        use time::macros::datetime;
+        let now = datetime!(2022-01-10 20:47:53 -3);
+        assert_eq!(
+            super::time_until_next_day(now),
+            time::Duration::seconds(3 * 3600 + 12 * 60 + 7)
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_now_at_offset() {
+        let offset = time::macros::offset!(+5:30);
+        let now = super::now(Some(offset)).unwrap();
+        assert_eq!(now.offset(), offset);
+        let utc = time::OffsetDateTime::now_utc();
+        assert!((utc - now).abs() < time::Duration::minutes(1));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_now_from_clock() {
+        use time::macros::{datetime, offset};
+        let clock = crate::clock::Fixed(datetime!(2022-01-10 23:30 -3));
+        let now = super::now_from(&clock, None).unwrap();
+        assert_eq!(now.date(), time::macros::date!(2022 - 01 - 10));
+        // already the next day in UTC
+        let utc = super::now_from(&clock, Some(offset!(UTC))).unwrap();
+        assert_eq!(super::get_day(utc.date()), 9);
+    }
+
     #[test_case("class", "crest", [Exact, Wrong, Wrong, Exact, Wrong]; "double letter, one exact, one wrong")]
     #[test_case("stars", "crest", [Close, Close, Wrong, Close, Wrong]; "double letter, one close, one wrong")]
     #[test_case("kills", "skill", [Close, Close, Close, Exact, Close]; "double letter, one exact, one close")]
+    #[test_case("keeps", "abbey", [Wrong, Close, Wrong, Wrong, Wrong]; "double letter, one close, one extra")]
+    #[test_case("aabbb", "babba", [Close, Exact, Exact, Exact, Close]; "repeated letters, exact before close")]
+    #[test_case("eerie", "there", [Close, Wrong, Close, Wrong, Exact]; "triple letter, one exact, one close, one extra")]
+    #[test_case("eeeee", "there", [Wrong, Wrong, Exact, Wrong, Exact]; "same letter, only exact")]
+    #[test_case("eõlas", "leões", [Close, Close, Close, Wrong, Exact]; "accented letters")]
     fn test_diff(input: &str, solution: &str, matches: [Match; 5]) {
         assert_eq!(diff(input, solution).0, matches);
     }
+
+    #[test_case("eerie", "there", "YBYBG")]
+    #[test_case("sorte", "termo", "BYGYY")]
+    #[test_case("termo", "termo", "GGGGG")]
+    fn test_matches_letters(input: &str, solution: &str, letters: &str) {
+        let matches = diff(input, solution);
+        assert_eq!(format!("{:#}", matches), letters);
+        assert_eq!(letters.parse(), Ok(matches.clone()));
+        assert_eq!(letters.to_lowercase().parse(), Ok(matches.clone()));
+        assert_eq!(matches.to_string().parse(), Ok(matches));
+    }
+
+    #[test]
+    fn test_parse_matches() {
+        let matches = "⬜🟦🟧🟦🟦".parse::<Matches>().unwrap();
+        assert_eq!(matches, diff("sorte", "termo"));
+        assert_eq!("".parse::<Matches>(), Err(ParseMatchesError::Empty));
+        assert_eq!("GYXBB".parse::<Matches>(), Err(ParseMatchesError::InvalidMatch('X')));
+        assert_eq!(
+            ParseMatchesError::InvalidMatch('X').to_string(),
+            "'X' is not a match, use G, Y and B"
+        );
+    }
+
+    #[test_case("leões", "leoes")]
+    #[test_case("açaí", "acai")]
+    #[test_case("niño", "nino")]
+    #[test_case("straße", "straße")]
+    #[test_case("λέξη", "λεξη")]
+    #[test_case("слово", "слово")]
+    fn test_fold_accents(word: &str, folded: &str) {
+        assert_eq!(fold_accents(word), folded);
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(diff("terra", "termo").describe("terra"), "T green, E green, R green, R black, A black");
+        assert_eq!(diff("eõlas", "leões").describe("eõlas"), "E amber, Õ amber, L amber, A black, S green");
+    }
 }
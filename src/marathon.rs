@@ -0,0 +1,107 @@
+//! Marathons: random words one after another, for as long as each one is solved.
+//!
+//! [`Marathon`] keeps the score of every word played, and leaves picking them and
+//! playing them to the frontend. The more words solved the better, and between two
+//! runs that solved as many, the one that took fewer guesses.
+
+use std::fmt;
+
+use crate::game::Score;
+
+/// The words played so far in a marathon, which is over once one isn't solved
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Marathon {
+    scores: Vec<Score>,
+}
+
+impl Marathon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How each word went, in the order they were played
+    pub fn scores(&self) -> &[Score] {
+        &self.scores
+    }
+
+    /// Adds the score of the word just played. Nothing is added once the marathon
+    /// is over
+    pub fn record(&mut self, score: Score) {
+        if !self.over() {
+            self.scores.push(score);
+        }
+    }
+
+    /// Whether a word wasn't solved, which ends the marathon
+    pub fn over(&self) -> bool {
+        self.scores.last().is_some_and(|score| !score.won())
+    }
+
+    /// Number of words solved
+    pub fn words(&self) -> usize {
+        self.scores.iter().filter(|score| score.won()).count()
+    }
+
+    /// Guesses taken over every word solved. The word that ended the marathon
+    /// doesn't count towards them
+    pub fn guesses(&self) -> usize {
+        self.scores.iter().filter_map(|score| score.guesses()).sum()
+    }
+}
+
+/// The totals, then each word's score, like
+///
+/// ```text
+/// Marathon: 3 words, 11 guesses
+///
+/// 4 3 4 X
+/// ```
+impl fmt::Display for Marathon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (words, guesses) = (self.words(), self.guesses());
+        write!(
+            f,
+            "Marathon: {} word{}, {} guess{}",
+            words,
+            if words == 1 { "" } else { "s" },
+            guesses,
+            if guesses == 1 { "" } else { "es" }
+        )?;
+        if !self.scores.is_empty() {
+            let scores = self.scores.iter().map(Score::to_string);
+            write!(f, "\n\n{}", scores.collect::<Vec<_>>().join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Marathon;
+    use crate::game::Score;
+
+    #[test]
+    fn ends_on_the_first_word_not_solved() {
+        let mut marathon = Marathon::new();
+        for score in [Score::Guesses(4), Score::Guesses(3), Score::Failed] {
+            assert!(!marathon.over());
+            marathon.record(score);
+        }
+        assert!(marathon.over());
+        marathon.record(Score::Guesses(1));
+        assert_eq!(marathon.scores().len(), 3);
+        assert_eq!((marathon.words(), marathon.guesses()), (2, 7));
+        assert_eq!(
+            marathon.to_string(),
+            "Marathon: 2 words, 7 guesses\n\n4 3 X"
+        );
+    }
+
+    #[test]
+    fn counts_in_the_singular() {
+        let mut marathon = Marathon::new();
+        assert_eq!(marathon.to_string(), "Marathon: 0 words, 0 guesses");
+        marathon.record(Score::Guesses(1));
+        assert_eq!(marathon.to_string(), "Marathon: 1 word, 1 guess\n\n1");
+    }
+}
@@ -0,0 +1,294 @@
+//! Several boards played at once, like Dordle or Quordle
+//!
+//! Every guess goes to each board that isn't solved yet, and there's an extra guess
+//! for each board after the first, so there's time to solve them all. Each board is
+//! a [`Game`] of its own, so anything that can draw a game can draw a board.
+
+use std::fmt;
+
+use crate::{
+    game::{Game, GameInitError, GameShare, GameType, HEADER},
+    state::GuessError,
+    tournament::SplitMix64,
+    Matches,
+};
+
+/// Most boards a game can have. Each one takes a guess more, and past this many
+/// they don't fit on a terminal
+pub const MAX_BOARDS: usize = 8;
+
+pub struct Multi {
+    boards: Vec<Game>,
+    game_type: GameType,
+}
+
+impl Multi {
+    /// Starts a practice game with `boards` different random solutions. As with
+    /// [`Game::random`], the same seed always gives the same solutions.
+    ///
+    /// Fails with [`GameInitError::NoBoards`] or [`GameInitError::TooManyBoards`]
+    /// unless there are 1 to [`MAX_BOARDS`] boards, as there are only so many
+    /// solutions to tell apart
+    pub fn random(seed: Option<u64>, boards: usize) -> Result<Self, GameInitError> {
+        check_boards(boards)?;
+        let seed = seed.unwrap_or_else(crate::game::random_seed);
+        let mut rng = SplitMix64(seed);
+        let mut games = Vec::<Game>::with_capacity(boards);
+        while games.len() < boards {
+            let game = Game::random(Some(rng.next()));
+            let solution = game.state().solution();
+            if games.iter().all(|g| g.state().solution() != solution) {
+                games.push(game);
+            }
+        }
+        Self::new(games, GameType::Practice { seed })
+    }
+
+    /// Starts a game with a board for each solution, which must be in the word lists
+    pub fn custom(solutions: Vec<String>) -> Result<Self, GameInitError> {
+        let games = solutions.into_iter().map(Game::custom);
+        Self::new(games.collect::<Result<_, _>>()?, GameType::Custom)
+    }
+
+    fn new(boards: Vec<Game>, game_type: GameType) -> Result<Self, GameInitError> {
+        check_boards(boards.len())?;
+        let max_guesses = crate::MAX_GUESSES + boards.len() - 1;
+        let boards = boards
+            .into_iter()
+            .map(|game| game.with_max_guesses(max_guesses))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { boards, game_type })
+    }
+
+    /// Changes how many guesses a single board would get from the usual
+    /// [`MAX_GUESSES`](crate::MAX_GUESSES). There's still an extra one for each
+    /// board after the first. Fails with [`GameInitError::InvalidMaxGuesses`] if it's 0
+    pub fn with_max_guesses(self, max_guesses: usize) -> Result<Self, GameInitError> {
+        if max_guesses == 0 {
+            return Err(GameInitError::InvalidMaxGuesses(max_guesses));
+        }
+        let extra = self.boards.len() - 1;
+        let boards = self
+            .boards
+            .into_iter()
+            .map(|game| game.with_max_guesses(max_guesses.saturating_add(extra)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { boards, ..self })
+    }
+
+    pub fn boards(&self) -> &[Game] {
+        &self.boards
+    }
+
+    pub fn game_type(&self) -> &GameType {
+        &self.game_type
+    }
+
+    /// Number of letters in every solution, and so in every guess
+    pub fn word_len(&self) -> usize {
+        self.boards[0].word_len()
+    }
+
+    /// Number of guesses allowed on each board before it's lost
+    pub fn max_guesses(&self) -> usize {
+        self.boards[0].max_guesses()
+    }
+
+    /// Makes a guess on every board that isn't solved yet, returning how it matched
+    /// on each, or `None` for the boards it skipped.
+    ///
+    /// If any board would reject the guess, it isn't made on any of them.
+    pub fn guess(&mut self, word: &str) -> Result<Vec<Option<Matches>>, GuessError> {
+        if self.game_over().is_some() {
+            return Err(GuessError::GameOver);
+        }
+        let playing = || {
+            self.boards
+                .iter()
+                .filter(|board| board.game_over().is_none())
+        };
+        for board in playing() {
+            board.state().check(word)?;
+        }
+        self.boards
+            .iter_mut()
+            .map(|board| match board.game_over() {
+                Some(_) => Ok(None),
+                None => board.guess(word).map(Some),
+            })
+            .collect()
+    }
+
+    /// `Some(true)` once every board is solved, `Some(false)` once the guesses ran
+    /// out first, or `None` while still playing
+    pub fn game_over(&self) -> Option<bool> {
+        let mut over = self.boards.iter().map(Game::game_over);
+        if over.clone().all(|over| over == Some(true)) {
+            Some(true)
+        } else if over.any(|over| over == Some(false)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    pub fn share(&self) -> MultiShare {
+        MultiShare {
+            game_type: self.game_type.clone(),
+            boards: self.boards.iter().map(Game::share).collect(),
+        }
+    }
+}
+
+fn check_boards(boards: usize) -> Result<(), GameInitError> {
+    match boards {
+        0 => Err(GameInitError::NoBoards),
+        // every board needs a solution of its own
+        n if n > MAX_BOARDS.min(crate::words::FINAL.len()) => Err(GameInitError::TooManyBoards(n)),
+        _ => Ok(()),
+    }
+}
+
+/// The result of every board, with a score in the header and a grid for each
+pub struct MultiShare {
+    game_type: GameType,
+    boards: Vec<GameShare>,
+}
+
+impl MultiShare {
+    pub fn game_type(&self) -> &GameType {
+        &self.game_type
+    }
+
+    pub fn boards(&self) -> &[GameShare] {
+        &self.boards
+    }
+
+    /// Whether every board was solved
+    pub fn won(&self) -> bool {
+        self.boards.iter().all(GameShare::won)
+    }
+}
+
+impl fmt::Display for MultiShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{HEADER}{game_type}", game_type = self.game_type)?;
+        for board in &self.boards {
+            if let Some(score) = board.score() {
+                write!(f, " {}/{}", score, board.max_guesses())?;
+            }
+        }
+        for board in &self.boards {
+            writeln!(f)?;
+            for row in board.grid() {
+                write!(f, "\n{}", row)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Multi, MAX_BOARDS};
+    use crate::{
+        game::{GameInitError, GameType},
+        state::GuessError,
+    };
+
+    #[test]
+    fn guesses_go_to_unsolved_boards() {
+        let mut multi = Multi::custom(vec!["termo".to_owned(), "suite".to_owned()]).unwrap();
+        assert_eq!(multi.max_guesses(), 7);
+        assert_eq!(multi.guess("xxxxx"), Err(GuessError::NotInWordList));
+        assert!(multi
+            .boards()
+            .iter()
+            .all(|b| b.state().guesses().len() == 0));
+
+        let matches = multi.guess("termo").unwrap();
+        assert_eq!(matches[0].as_ref().unwrap().to_string(), "🟩🟩🟩🟩🟩");
+        assert_eq!(matches[1].as_ref().unwrap().to_string(), "🟨🟨⬛⬛⬛");
+        assert_eq!(multi.game_over(), None);
+
+        let matches = multi.guess("suite").unwrap();
+        assert!(matches[0].is_none());
+        assert_eq!(multi.game_over(), Some(true));
+        assert_eq!(
+            multi.share().to_string(),
+            "joguei term.ooo #custom 1/7 2/7\n\n🟩🟩🟩🟩🟩\n\n🟨🟨⬛⬛⬛\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[test]
+    fn lost_when_guesses_run_out() {
+        let mut multi = Multi::custom(vec!["termo".to_owned(), "suite".to_owned()]).unwrap();
+        multi.guess("termo").unwrap();
+        for _ in 1..7 {
+            multi.guess("sagaz").unwrap();
+        }
+        assert_eq!(multi.game_over(), Some(false));
+        assert!(!multi.share().won());
+        assert!(multi
+            .share()
+            .to_string()
+            .starts_with("joguei term.ooo #custom 1/7 X/7\n"));
+    }
+
+    #[test]
+    fn max_guesses_are_per_board() {
+        let multi = Multi::custom(vec!["termo".to_owned(), "suite".to_owned()]).unwrap();
+        let multi = multi.with_max_guesses(4).unwrap();
+        assert_eq!(multi.max_guesses(), 5);
+        let multi = Multi::random(Some(1), 2).unwrap();
+        assert_eq!(
+            multi.with_max_guesses(usize::MAX).unwrap().max_guesses(),
+            usize::MAX
+        );
+        assert!(Multi::random(Some(1), 2)
+            .unwrap()
+            .with_max_guesses(0)
+            .is_err());
+    }
+
+    #[test]
+    fn random_boards_differ() {
+        let multi = Multi::random(Some(7), 4).unwrap();
+        assert_eq!(multi.game_type(), &GameType::Practice { seed: 7 });
+        let solutions = |multi: &Multi| {
+            let boards = multi.boards().iter();
+            boards
+                .map(|b| b.state().solution().to_owned())
+                .collect::<Vec<_>>()
+        };
+        let first = solutions(&multi);
+        let mut unique = first.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 4);
+        assert_eq!(solutions(&Multi::random(Some(7), 4).unwrap()), first);
+        assert!(matches!(
+            Multi::random(None, 0),
+            Err(GameInitError::NoBoards)
+        ));
+    }
+
+    #[test]
+    fn boards_are_limited() {
+        assert_eq!(
+            Multi::random(Some(7), MAX_BOARDS).unwrap().boards().len(),
+            8
+        );
+        for boards in [MAX_BOARDS + 1, 100_000, usize::MAX] {
+            assert!(matches!(
+                Multi::random(Some(7), boards),
+                Err(GameInitError::TooManyBoards(n)) if n == boards
+            ));
+        }
+        let solutions = vec!["termo".to_owned(); MAX_BOARDS + 1];
+        assert!(matches!(
+            Multi::custom(solutions),
+            Err(GameInitError::TooManyBoards(9))
+        ));
+    }
+}
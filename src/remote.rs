@@ -0,0 +1,101 @@
+//! Fetching the daily solution from a server, for dailies that can't be derived from the date
+
+use std::{fs, io, path::PathBuf, time::Duration};
+
+/// Gets the solution served at `url`
+pub(crate) fn fetch(url: &str) -> Result<String, String> {
+    let response = ureq::get(url)
+        .timeout(Duration::from_secs(5))
+        .call()
+        .map_err(|err| err.to_string())?;
+    let body = response.into_string().map_err(|err| err.to_string())?;
+    Ok(body.trim().to_lowercase())
+}
+
+/// Gets the last fetched solution, if it was fetched for the same day
+pub(crate) fn cached(day: usize) -> Option<String> {
+    let contents = fs::read_to_string(cache_path()?).ok()?;
+    parse_cache(&contents, day)
+}
+
+/// Remembers the fetched solution so the day can still be played offline
+pub(crate) fn store(day: usize, solution: &str) -> io::Result<()> {
+    let path = cache_path().ok_or_else(|| io::Error::other("no cache directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{} {}\n", day, solution))
+}
+
+/// Gets the first Portuguese definition of the word from Wiktionary, without its
+/// markup, or `None` if the word isn't there
+pub(crate) fn define(word: &str) -> Result<Option<String>, String> {
+    let url = format!(
+        "https://en.wiktionary.org/api/rest_v1/page/definition/{}",
+        word
+    );
+    let response = match ureq::get(&url).timeout(Duration::from_secs(5)).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(404, _)) => return Ok(None),
+        Err(err) => return Err(err.to_string()),
+    };
+    let body = response.into_string().map_err(|err| err.to_string())?;
+    let entries: serde_json::Value = serde_json::from_str(&body).map_err(|err| err.to_string())?;
+    let definition = entries["pt"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|entry| entry["definitions"].as_array().into_iter().flatten())
+        .filter_map(|definition| definition["definition"].as_str())
+        .map(strip_markup)
+        .find(|definition| !definition.is_empty());
+    Ok(definition)
+}
+
+/// Drops the tags from some html, and decodes the entities Wiktionary uses
+fn strip_markup(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    text.trim().to_owned()
+}
+
+fn cache_path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("cl-wordle").join("daily"))
+}
+
+fn parse_cache(contents: &str, day: usize) -> Option<String> {
+    let (cached_day, solution) = contents.trim().split_once(' ')?;
+    (cached_day.parse::<usize>().ok()? == day).then(|| solution.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cache, strip_markup};
+
+    #[test]
+    fn cache_is_per_day() {
+        assert_eq!(parse_cache("40 termo\n", 40).as_deref(), Some("termo"));
+        assert_eq!(parse_cache("40 termo\n", 41), None);
+        assert_eq!(parse_cache("", 40), None);
+    }
+
+    #[test]
+    fn markup_is_stripped() {
+        let html = "<a href=\"/wiki/word\">word</a> &amp; <i>term</i> ";
+        assert_eq!(strip_markup(html), "word & term");
+    }
+}
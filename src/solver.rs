@@ -0,0 +1,915 @@
+//! Simple strategies for solving a game
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        OnceLock,
+    },
+};
+
+use crate::{diff, state::Guess, words, Match, Matches};
+
+/// Filters the dictionary down to the words that could still be the solution,
+/// given the guesses made so far.
+///
+/// A word is a candidate only if every guess would have been scored exactly the same
+/// against it, so this uses the same semantics as [`diff`].
+pub fn candidates<'a>(history: &[Guess<'_>], dict: &[&'a str]) -> Vec<&'a str> {
+    let len = history
+        .first()
+        .map_or(crate::WORD_LEN, |Guess(input, _)| input.chars().count());
+    dict.iter()
+        .copied()
+        // diff can only score words as long as the guesses
+        .filter(|word| word.chars().count() == len)
+        .filter(|word| {
+            history
+                .iter()
+                .all(|Guess(input, matches)| diff(input, word) == *matches)
+        })
+        .collect()
+}
+
+/// Sorts the candidates so the ones made of the most common letters come first.
+///
+/// Letters are counted once per candidate, among the candidates themselves, so the
+/// first words share the most letters with the rest. Ties keep their order.
+pub fn rank_by_letters(candidates: &mut [&str]) {
+    let mut counts = std::collections::HashMap::<char, usize>::new();
+    for word in candidates.iter() {
+        for c in distinct(word) {
+            *counts.entry(c).or_default() += 1;
+        }
+    }
+    candidates.sort_by_cached_key(|word| {
+        let score: usize = distinct(word).map(|c| counts[&c]).sum();
+        std::cmp::Reverse(score)
+    });
+}
+
+/// The letters each of the `len` positions could still hold, among the candidates,
+/// in alphabetical order. Every position is empty when there are no candidates
+pub fn possible_letters(candidates: &[&str], len: usize) -> Vec<Vec<char>> {
+    let mut letters = vec![Vec::new(); len];
+    for word in candidates {
+        for (position, c) in letters.iter_mut().zip(word.chars()) {
+            if !position.contains(&c) {
+                position.push(c);
+            }
+        }
+    }
+    for position in &mut letters {
+        position.sort_unstable();
+    }
+    letters
+}
+
+fn distinct(word: &str) -> impl Iterator<Item = char> + '_ {
+    word.char_indices()
+        .filter(move |&(i, c)| !word[..i].contains(c))
+        .map(|(_, c)| c)
+}
+
+/// A letter, optionally tied to a position, given as `c` or `c=1`.
+///
+/// Positions count from 1, as they're read off the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placement {
+    pub letter: char,
+    pub position: Option<usize>,
+}
+
+impl FromStr for Placement {
+    type Err = ConstraintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (letter, position) = match s.split_once('=') {
+            Some((letter, position)) => {
+                let position = position
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|p| (1..=5).contains(p))
+                    .ok_or_else(|| ConstraintError::InvalidPosition(position.to_owned()))?;
+                (letter, Some(position - 1))
+            }
+            None => (s, None),
+        };
+        let mut chars = letter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(letter), None) => Ok(Placement {
+                letter: letter_of(letter)?,
+                position,
+            }),
+            _ => Err(ConstraintError::InvalidLetter(letter.to_owned())),
+        }
+    }
+}
+
+fn letter_of(c: char) -> Result<char, ConstraintError> {
+    if c.is_ascii_alphabetic() {
+        Ok(c.to_ascii_lowercase())
+    } else {
+        Err(ConstraintError::InvalidLetter(c.to_string()))
+    }
+}
+
+/// Letters known to be in or out of the solution, given directly rather than
+/// derived from guesses
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Constraints {
+    green: [Option<char>; 5],
+    amber: Vec<Placement>,
+    absent: Vec<char>,
+}
+
+impl Constraints {
+    /// Requires the letter at the given position
+    pub fn green(mut self, placement: Placement) -> Result<Self, ConstraintError> {
+        let position = placement
+            .position
+            .ok_or(ConstraintError::MissingPosition(placement.letter))?;
+        match self.green[position] {
+            Some(other) if other != placement.letter => {
+                return Err(ConstraintError::Conflict(format!(
+                    "position {} can't be both {} and {}",
+                    position + 1,
+                    other,
+                    placement.letter
+                )))
+            }
+            _ => self.green[position] = Some(placement.letter),
+        }
+        self.check()
+    }
+
+    /// Requires the letter somewhere in the word, but not at the given position
+    pub fn amber(mut self, placement: Placement) -> Result<Self, ConstraintError> {
+        self.amber.push(placement);
+        self.check()
+    }
+
+    /// Rules out each of the letters
+    pub fn absent(mut self, letters: &str) -> Result<Self, ConstraintError> {
+        for c in letters.chars() {
+            self.absent.push(letter_of(c)?);
+        }
+        self.check()
+    }
+
+    fn check(self) -> Result<Self, ConstraintError> {
+        for &letter in &self.absent {
+            if self.green.contains(&Some(letter)) || self.amber.iter().any(|a| a.letter == letter) {
+                return Err(ConstraintError::Conflict(format!(
+                    "{} can't be both absent and in the word",
+                    letter
+                )));
+            }
+        }
+        for amber in &self.amber {
+            if let Some(position) = amber.position {
+                if self.green[position] == Some(amber.letter) {
+                    return Err(ConstraintError::Conflict(format!(
+                        "{} can't be both green and amber at position {}",
+                        amber.letter,
+                        position + 1
+                    )));
+                }
+            }
+        }
+        // amber letters must fit in the positions that aren't green
+        let mut amber = self.amber.iter().map(|a| a.letter).collect::<Vec<_>>();
+        amber.sort_unstable();
+        amber.dedup();
+        let open = self.green.iter().filter(|g| g.is_none()).count();
+        let placed = amber
+            .iter()
+            .filter(|&&letter| !self.green.contains(&Some(letter)))
+            .count();
+        if placed > open {
+            return Err(ConstraintError::Conflict(
+                "too many letters to fit in the word".to_owned(),
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Whether the word satisfies every constraint
+    pub fn matches(&self, word: &str) -> bool {
+        let letters = word.chars().collect::<Vec<_>>();
+        letters.len() == 5
+            && self
+                .green
+                .iter()
+                .zip(&letters)
+                .all(|(green, c)| green.is_none_or(|g| g == *c))
+            && self.amber.iter().all(|amber| {
+                letters.contains(&amber.letter)
+                    && amber.position.is_none_or(|p| letters[p] != amber.letter)
+            })
+            && !letters.iter().any(|c| self.absent.contains(c))
+    }
+
+    /// Filters the dictionary down to the words that satisfy every constraint
+    pub fn candidates<'a>(&self, dict: &[&'a str]) -> Vec<&'a str> {
+        dict.iter()
+            .copied()
+            .filter(|word| self.matches(word))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Reasons a set of [`Constraints`] could not be built
+pub enum ConstraintError {
+    /// Not a single ascii letter
+    InvalidLetter(String),
+    /// Not a position from 1 to 5
+    InvalidPosition(String),
+    /// A green letter was given without its position
+    MissingPosition(char),
+    /// No word could satisfy the constraints
+    Conflict(String),
+}
+
+impl fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstraintError::InvalidLetter(letter) => write!(f, "{} is not a letter", letter),
+            ConstraintError::InvalidPosition(position) => {
+                write!(f, "{} is not a position from 1 to 5", position)
+            }
+            ConstraintError::MissingPosition(letter) => {
+                write!(f, "green {} needs a position, eg {}=1", letter, letter)
+            }
+            ConstraintError::Conflict(reason) => write!(f, "conflicting constraints: {}", reason),
+        }
+    }
+}
+
+impl Error for ConstraintError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ArgEnum))]
+/// How the solver picks its next guess from the remaining candidates
+pub enum Strategy {
+    /// Always guess the first remaining candidate
+    Naive,
+    /// Guess the candidate that maximises the expected information gained
+    Entropy,
+}
+
+impl Strategy {
+    /// Picks the next guess out of the remaining candidates
+    pub fn best_guess<'a>(self, candidates: &[&'a str]) -> Option<&'a str> {
+        match self {
+            Strategy::Naive => candidates.first().copied(),
+            Strategy::Entropy => {
+                let scored = Candidates::new(candidates);
+                let total = candidates.len() as f64;
+                let mut best = None;
+                let mut best_entropy = f64::NEG_INFINITY;
+                for &guess in candidates {
+                    let entropy = entropy_of(&scored.buckets(guess), total);
+                    if entropy > best_entropy {
+                        best = Some(guess);
+                        best_entropy = entropy;
+                    }
+                }
+                best
+            }
+        }
+    }
+}
+
+/// How many alternatives to the best guess [`explain`] includes
+const ALTERNATIVES: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+/// A guess, scored by how much it narrows down the remaining candidates
+pub struct ScoredGuess {
+    pub word: &'static str,
+    /// Expected information gained, in bits
+    pub entropy: f64,
+    /// Expected number of candidates left after guessing this word
+    pub expected_remaining: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Why the solver suggests the guess it does
+pub struct HintExplanation {
+    /// Number of solutions still consistent with the guesses so far
+    pub remaining: usize,
+    /// The suggested guess first, followed by the next best alternatives
+    pub ranked: Vec<ScoredGuess>,
+}
+
+impl HintExplanation {
+    /// The suggested guess
+    pub fn best(&self) -> Option<&ScoredGuess> {
+        self.ranked.first()
+    }
+}
+
+/// Explains the guess the entropy strategy would make next, along with a few alternatives.
+///
+/// Only the remaining candidates are considered as guesses, which keeps this
+/// responsive on the full list. The opening explanation is cached since it's the slowest.
+pub fn explain(history: &[Guess<'_>]) -> HintExplanation {
+    static OPENING: OnceLock<HintExplanation> = OnceLock::new();
+    if history.is_empty() {
+        return OPENING
+            .get_or_init(|| explain_candidates(&candidates(&[], words::FINAL)))
+            .clone();
+    }
+    explain_candidates(&candidates(history, words::FINAL))
+}
+
+/// Every solution ranked as a first guess, by the information it can be expected to
+/// give about the solution. Only the solutions are ranked, as with the hints
+pub fn openers() -> &'static [ScoredGuess] {
+    static OPENERS: OnceLock<Vec<ScoredGuess>> = OnceLock::new();
+    OPENERS.get_or_init(|| rank_by_entropy(&candidates(&[], words::FINAL)))
+}
+
+fn explain_candidates(candidates: &[&'static str]) -> HintExplanation {
+    let mut ranked = rank_by_entropy(candidates);
+    ranked.truncate(ALTERNATIVES + 1);
+
+    HintExplanation {
+        remaining: candidates.len(),
+        ranked,
+    }
+}
+
+/// Scores every candidate as a guess, most informative first. Ties are alphabetical
+fn rank_by_entropy(candidates: &[&'static str]) -> Vec<ScoredGuess> {
+    let total = candidates.len() as f64;
+    let scored = Candidates::new(candidates);
+    let mut ranked: Vec<_> = candidates
+        .iter()
+        .map(|&word| {
+            let buckets = scored.buckets(word);
+            ScoredGuess {
+                word,
+                entropy: entropy_of(&buckets, total),
+                expected_remaining: buckets.iter().map(|&n| (n * n) as f64).sum::<f64>() / total,
+            }
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.entropy
+            .total_cmp(&a.entropy)
+            .then_with(|| a.word.cmp(b.word))
+    });
+    ranked
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ArgEnum))]
+/// How [`suggest`] orders the solutions that are still possible
+pub enum Ranking {
+    /// Most expected information gained first, as [`explain`] does
+    #[default]
+    Entropy,
+    /// Most common letters first, as [`rank_by_letters`] does. Much quicker
+    Letters,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The best few guesses, out of the solutions still possible
+pub struct Suggestions {
+    /// Number of solutions still consistent with the guesses so far
+    pub remaining: usize,
+    /// The best guesses, best first
+    pub words: Vec<&'static str>,
+}
+
+/// Suggests up to `n` of the solutions still possible after the guesses so far,
+/// ranked the given way
+pub fn suggest(history: &[Guess<'_>], ranking: Ranking, n: usize) -> Suggestions {
+    let mut candidates = candidates(history, words::FINAL);
+    let remaining = candidates.len();
+    let words = match ranking {
+        Ranking::Entropy => {
+            let ranked = rank_by_entropy(&candidates);
+            ranked
+                .into_iter()
+                .take(n)
+                .map(|scored| scored.word)
+                .collect()
+        }
+        Ranking::Letters => {
+            rank_by_letters(&mut candidates);
+            candidates.truncate(n);
+            candidates
+        }
+    };
+    Suggestions { remaining, words }
+}
+
+/// The candidates guesses are scored against. When they're all bundled solutions,
+/// they're looked up in the [bundled cache](PatternCache::bundled) instead of scored
+pub(crate) enum Candidates<'a> {
+    Cached(&'static PatternCache<'static>, Vec<usize>),
+    Words(&'a [&'a str]),
+}
+
+impl<'a> Candidates<'a> {
+    pub(crate) fn new(candidates: &'a [&'a str]) -> Self {
+        let cache = PatternCache::bundled();
+        let indices = candidates.iter().map(|word| cache.index(word));
+        match indices.collect::<Option<Vec<_>>>() {
+            Some(indices) => Candidates::Cached(cache, indices),
+            None => Candidates::Words(candidates),
+        }
+    }
+
+    /// Counts how many candidates would give each pattern for the guess
+    pub(crate) fn buckets(&self, guess: &str) -> [usize; 243] {
+        let mut buckets = [0usize; 243];
+        match self {
+            Candidates::Cached(cache, indices) => match cache.index(guess) {
+                Some(guess) => {
+                    let row = cache.row(guess);
+                    for &solution in indices {
+                        buckets[usize::from(row[solution])] += 1;
+                    }
+                }
+                // a guess from outside the cache still has to be scored
+                None => {
+                    for &solution in indices {
+                        buckets[usize::from(encode(&diff(guess, cache.words[solution])))] += 1;
+                    }
+                }
+            },
+            Candidates::Words(words) => {
+                for solution in words.iter() {
+                    buckets[usize::from(encode(&diff(guess, solution)))] += 1;
+                }
+            }
+        }
+        buckets
+    }
+}
+
+pub(crate) fn entropy_of(buckets: &[usize; 243], total: f64) -> f64 {
+    buckets
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Packs the matches into a single base 3 number, with the first letter's match as
+/// the most significant digit: wrong is 0, close is 1 and exact is 2.
+///
+/// # Panics
+///
+/// If there are more than five matches, which don't fit in a byte
+pub fn encode(matches: &Matches) -> u8 {
+    assert!(matches.0.len() <= 5, "only five matches fit in a byte");
+    matches.0.iter().fold(0, |acc, m| {
+        acc * 3
+            + match m {
+                Match::Wrong => 0,
+                Match::Close => 1,
+                Match::Exact => 2,
+            }
+    })
+}
+
+/// Unpacks `len` matches from a number packed by [`encode`]
+pub fn decode(mut code: u8, len: usize) -> Matches {
+    let mut matches = vec![Match::Wrong; len];
+    for m in matches.iter_mut().rev() {
+        *m = match code % 3 {
+            0 => Match::Wrong,
+            1 => Match::Close,
+            _ => Match::Exact,
+        };
+        code /= 3;
+    }
+    Matches(matches)
+}
+
+/// The pattern of every word in a list guessed against every other, packed by
+/// [`encode`], so the solver never scores the same pair twice.
+///
+/// Each guess's row is scored the first time it's needed, or all at once with
+/// [`precompute`](PatternCache::precompute). A row takes a byte for each word, so
+/// the bundled solutions take under 2MB once every row is filled in
+pub struct PatternCache<'a> {
+    words: Vec<&'a str>,
+    index: HashMap<&'a str, usize>,
+    rows: Vec<OnceLock<Box<[u8]>>>,
+}
+
+impl<'a> PatternCache<'a> {
+    /// Caches the patterns between the words, which must all be as long as each other
+    /// and no longer than five letters, for their patterns to fit in a byte. Returns
+    /// `None` if they aren't
+    pub fn new(words: &[&'a str]) -> Option<Self> {
+        let len = words.first().map_or(0, |word| word.chars().count());
+        if len > 5 || words.iter().any(|word| word.chars().count() != len) {
+            return None;
+        }
+        let mut index = HashMap::with_capacity(words.len());
+        for (i, &word) in words.iter().enumerate() {
+            index.entry(word).or_insert(i);
+        }
+        Some(Self {
+            words: words.to_vec(),
+            index,
+            rows: (0..words.len()).map(|_| OnceLock::new()).collect(),
+        })
+    }
+
+    /// The cache of the bundled solutions, which the solver looks patterns up in
+    /// whenever the candidates are all among them
+    pub fn bundled() -> &'static PatternCache<'static> {
+        static BUNDLED: OnceLock<PatternCache<'static>> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            PatternCache::new(words::FINAL).expect("the solutions are all five letters")
+        })
+    }
+
+    pub fn words(&self) -> &[&'a str] {
+        &self.words
+    }
+
+    /// Where the word is in the list, if it's in it
+    pub fn index(&self, word: &str) -> Option<usize> {
+        self.index.get(word).copied()
+    }
+
+    /// The pattern of the guess against every word, in the order of the list. The
+    /// guess is given by its [index](PatternCache::index)
+    pub fn row(&self, guess: usize) -> &[u8] {
+        self.rows[guess].get_or_init(|| {
+            let guess = self.words[guess];
+            let row = self
+                .words
+                .iter()
+                .map(|solution| encode(&diff(guess, solution)));
+            row.collect()
+        })
+    }
+
+    /// The pattern of the guess against the solution, both given by their index
+    pub fn pattern(&self, guess: usize, solution: usize) -> u8 {
+        self.row(guess)[solution]
+    }
+
+    /// Scores every row that hasn't been yet. With the `rayon` feature the rows are
+    /// scored in parallel
+    pub fn precompute(&self) {
+        #[cfg(feature = "rayon")]
+        (0..self.rows.len()).into_par_iter().for_each(|guess| {
+            self.row(guess);
+        });
+        #[cfg(not(feature = "rayon"))]
+        (0..self.rows.len()).for_each(|guess| {
+            self.row(guess);
+        });
+    }
+}
+
+/// Plays a game against `solution` with the given strategy, and returns the guesses
+/// it made, ending with the solution.
+///
+/// The solution should be in `dict`. If it isn't, the guesses stop once no word
+/// in `dict` fits them.
+pub fn play<'a>(strategy: Strategy, solution: &str, dict: &[&'a str]) -> Vec<&'a str> {
+    match strategy.best_guess(dict) {
+        Some(opener) => play_from(strategy, opener, solution, dict),
+        None => Vec::new(),
+    }
+}
+
+/// Plays a game against `solution`, starting with `opener`
+fn play_from<'a>(
+    strategy: Strategy,
+    opener: &'a str,
+    solution: &str,
+    dict: &[&'a str],
+) -> Vec<&'a str> {
+    let mut remaining = dict.to_vec();
+    let mut guesses = vec![opener];
+    let mut guess = opener;
+    while guess != solution {
+        let matches = diff(guess, solution);
+        remaining = candidates(&[Guess(guess, matches)], &remaining);
+        guess = match strategy.best_guess(&remaining) {
+            Some(guess) => guess,
+            None => break,
+        };
+        guesses.push(guess);
+    }
+    guesses
+}
+
+/// Plays a game against `solution`, starting with `opener`, and returns the
+/// number of guesses it took to solve.
+fn solve(strategy: Strategy, opener: &str, solution: &str, dict: &[&str]) -> usize {
+    play_from(strategy, opener, solution, dict).len()
+}
+
+/// Finds the `n` solutions that take the most guesses to solve using the given strategy
+pub fn hardest_words(strategy: Strategy, n: usize) -> Vec<(String, f64)> {
+    hardest_words_in(strategy, words::FINAL, n, |_, _| {})
+}
+
+/// Finds the `n` words in `dict` that take the most guesses to solve using the given strategy.
+///
+/// Every word in the dictionary is simulated, which can be slow, so `progress` is called
+/// with the number of words simulated so far and the total after each one.
+/// With the `rayon` feature the simulations run in parallel.
+pub fn hardest_words_in(
+    strategy: Strategy,
+    dict: &[&str],
+    n: usize,
+    progress: impl Fn(usize, usize) + Sync,
+) -> Vec<(String, f64)> {
+    let mut scores = benchmark(strategy, dict, progress).scores;
+    scores.truncate(n);
+    let scores = scores.into_iter();
+    scores
+        .map(|(word, guesses)| (word, guesses as f64))
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// How a strategy did against every word in a dictionary, from [`benchmark`]
+pub struct Benchmark {
+    /// The first guess of every game
+    pub opener: Option<String>,
+    /// Each word with the guesses it took, the most first. Ties are alphabetical
+    pub scores: Vec<(String, usize)>,
+}
+
+impl Benchmark {
+    /// Mean number of guesses per game, including the ones that would have been lost
+    pub fn average(&self) -> f64 {
+        let total = self
+            .scores
+            .iter()
+            .map(|(_, guesses)| guesses)
+            .sum::<usize>();
+        total as f64 / self.scores.len().max(1) as f64
+    }
+
+    /// Number of games that took more than `max_guesses`, so would have been lost
+    pub fn failures(&self, max_guesses: usize) -> usize {
+        let scores = self.scores.iter();
+        scores.filter(|(_, guesses)| *guesses > max_guesses).count()
+    }
+}
+
+/// Plays the strategy against every word in `dict`, keeping every score.
+///
+/// As with [`hardest_words_in`], `progress` is called after each word, and the games
+/// run in parallel with the `rayon` feature
+pub fn benchmark(
+    strategy: Strategy,
+    dict: &[&str],
+    progress: impl Fn(usize, usize) + Sync,
+) -> Benchmark {
+    let opener = match strategy.best_guess(dict) {
+        Some(opener) => opener,
+        None => {
+            return Benchmark {
+                opener: None,
+                scores: Vec::new(),
+            }
+        }
+    };
+
+    let done = AtomicUsize::new(0);
+    let simulate = |&solution: &&str| {
+        let guesses = solve(strategy, opener, solution, dict);
+        progress(done.fetch_add(1, Ordering::Relaxed) + 1, dict.len());
+        (solution.to_owned(), guesses)
+    };
+
+    #[cfg(feature = "rayon")]
+    let mut scores: Vec<_> = dict.par_iter().map(simulate).collect();
+    #[cfg(not(feature = "rayon"))]
+    let mut scores: Vec<_> = dict.iter().map(simulate).collect();
+
+    scores.sort_by(|(a, x), (b, y)| y.cmp(x).then_with(|| a.cmp(b)));
+    Benchmark {
+        opener: Some(opener.to_owned()),
+        scores,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        benchmark, candidates, decode, encode, explain, explain_candidates, hardest_words_in,
+        openers, play, possible_letters, rank_by_entropy, rank_by_letters, suggest, Candidates,
+        ConstraintError, Constraints, PatternCache, Ranking, Strategy,
+    };
+    use crate::{diff, state::Guess, words};
+    use test_case::test_case;
+
+    const FIXTURE: &[&str] = &["crane", "crate", "crave", "craze", "grace"];
+
+    #[test]
+    fn test_candidates_narrow_to_solution() {
+        let guesses = ["suite", "terra", "tempo"];
+        let scored = guesses.map(|guess| diff(guess, "termo"));
+        let history = guesses
+            .iter()
+            .zip(scored)
+            .map(|(&guess, matches)| Guess(guess, matches))
+            .collect::<Vec<_>>();
+        assert_eq!(candidates(&history, words::FINAL), ["termo"]);
+        // every guess so far is consistent with the solution
+        assert!(candidates(&history[..1], words::FINAL).contains(&"termo"));
+    }
+
+    // BYGYY is 01211 in base 3
+    #[test_case("sorte", "termo", 49; "mixed")]
+    #[test_case("termo", "termo", 242; "all exact")]
+    #[test_case("sagaz", "termo", 0; "all wrong")]
+    fn test_encode(guess: &str, solution: &str, code: u8) {
+        let matches = diff(guess, solution);
+        assert_eq!(encode(&matches), code);
+        assert_eq!(decode(code, 5), matches);
+    }
+
+    #[test]
+    fn test_pattern_cache() {
+        let cache = PatternCache::new(FIXTURE).unwrap();
+        for (g, guess) in FIXTURE.iter().enumerate() {
+            for (s, solution) in FIXTURE.iter().enumerate() {
+                assert_eq!(cache.pattern(g, s), encode(&diff(guess, solution)));
+            }
+        }
+        cache.precompute();
+        assert_eq!(cache.index("grace"), Some(4));
+        assert_eq!(cache.index("termo"), None);
+        assert!(PatternCache::new(&["crane", "cranes"]).is_none());
+        assert!(PatternCache::new(&["bananas"]).is_none());
+    }
+
+    #[test]
+    fn test_cached_buckets_match_scored_ones() {
+        let candidates = candidates(&[Guess("suite", diff("suite", "termo"))], words::FINAL);
+        let cached = Candidates::new(&candidates);
+        assert!(matches!(cached, Candidates::Cached(..)));
+        let scored = Candidates::Words(&candidates);
+        // a guess from the cache, and one from outside it
+        for guess in ["termo", "xyzzy"] {
+            assert_eq!(cached.buckets(guess), scored.buckets(guess));
+        }
+        assert!(matches!(Candidates::new(FIXTURE), Candidates::Words(_)));
+        assert_eq!(rank_by_entropy(&candidates).len(), candidates.len());
+    }
+
+    #[test]
+    fn test_rank_by_letters() {
+        // every letter but e of "eerie" is shared by fewer words, and repeats only count once
+        let mut ranked = ["eerie", "crane", "crate", "trace"];
+        rank_by_letters(&mut ranked);
+        assert_eq!(ranked, ["crate", "trace", "crane", "eerie"]);
+    }
+
+    #[test]
+    fn test_possible_letters() {
+        let letters = possible_letters(&["termo", "tempo", "terno"], 5);
+        assert_eq!(
+            letters,
+            [
+                vec!['t'],
+                vec!['e'],
+                vec!['m', 'r'],
+                vec!['m', 'n', 'p'],
+                vec!['o'],
+            ]
+        );
+        assert_eq!(possible_letters(&[], 2), [vec![], vec![]]);
+    }
+
+    #[test]
+    fn test_hardest_words_naive() {
+        let hardest = hardest_words_in(Strategy::Naive, FIXTURE, 2, |_, _| {});
+        assert_eq!(
+            hardest,
+            [("craze".to_owned(), 4.0), ("crave".to_owned(), 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_explain_ranks_by_entropy() {
+        let explanation = explain_candidates(FIXTURE);
+        assert_eq!(explanation.remaining, FIXTURE.len());
+        assert_eq!(explanation.ranked.len(), 4);
+        assert!(explanation
+            .ranked
+            .windows(2)
+            .all(|w| w[0].entropy >= w[1].entropy));
+
+        let best = explanation.best().unwrap();
+        assert_eq!(
+            Strategy::Entropy.best_guess(FIXTURE),
+            Some(best.word),
+            "explanation should agree with the strategy"
+        );
+        assert!(best.expected_remaining >= 1.0);
+    }
+
+    #[test]
+    fn test_openers_agree_with_the_opening_hint() {
+        let openers = openers();
+        assert_eq!(openers.len(), words::FINAL.len());
+        assert_eq!(explain(&[]).best(), openers.first());
+    }
+
+    #[test_case(Ranking::Entropy)]
+    #[test_case(Ranking::Letters)]
+    fn test_suggest(ranking: Ranking) {
+        let history = [Guess("suite", diff("suite", "termo"))];
+        let suggestions = suggest(&history, ranking, 5);
+        let remaining = candidates(&history, words::FINAL);
+        assert_eq!(suggestions.remaining, remaining.len());
+        assert_eq!(suggestions.words.len(), 5.min(remaining.len()));
+        assert!(suggestions
+            .words
+            .iter()
+            .all(|word| remaining.contains(word)));
+    }
+
+    #[test]
+    fn test_constraints() {
+        let constraints = Constraints::default()
+            .green("c=1".parse().unwrap())
+            .and_then(|c| c.amber("a=4".parse().unwrap()))
+            .and_then(|c| c.absent("nv"))
+            .unwrap();
+        assert_eq!(constraints.candidates(FIXTURE), ["crate", "craze"]);
+    }
+
+    #[test_case("g=0"; "position out of range")]
+    #[test_case("gr=1"; "not a letter")]
+    #[test_case("g"; "missing position")]
+    fn test_invalid_green(green: &str) {
+        assert!(green
+            .parse()
+            .and_then(|p| Constraints::default().green(p))
+            .is_err());
+    }
+
+    #[test]
+    fn test_conflicting_constraints() {
+        let absent_green = Constraints::default()
+            .absent("c")
+            .and_then(|c| c.green("c=1".parse().unwrap()));
+        assert!(matches!(absent_green, Err(ConstraintError::Conflict(_))));
+
+        let two_greens = Constraints::default()
+            .green("c=1".parse().unwrap())
+            .and_then(|c| c.green("g=1".parse().unwrap()));
+        assert!(matches!(two_greens, Err(ConstraintError::Conflict(_))));
+
+        let too_many = "abcdef"
+            .chars()
+            .try_fold(Constraints::default(), |c, letter| {
+                c.amber(letter.to_string().parse().unwrap())
+            });
+        assert!(matches!(too_many, Err(ConstraintError::Conflict(_))));
+    }
+
+    #[test_case(Strategy::Naive)]
+    #[test_case(Strategy::Entropy)]
+    fn test_hardest_words_covers_dict(strategy: Strategy) {
+        let hardest = hardest_words_in(strategy, FIXTURE, 10, |_, _| {});
+        assert_eq!(hardest.len(), FIXTURE.len());
+        assert!(hardest.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(hardest.iter().all(|&(_, n)| (1.0..=5.0).contains(&n)));
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let benchmark = benchmark(Strategy::Naive, FIXTURE, |_, _| {});
+        assert_eq!(benchmark.opener.as_deref(), Some("crane"));
+        // craze takes 4, crave 3, crate and grace 2, and crane 1
+        assert_eq!(benchmark.average(), 2.4);
+        assert_eq!(benchmark.failures(2), 2);
+        assert_eq!(benchmark.scores[0], ("craze".to_owned(), 4));
+        assert_eq!(
+            play(Strategy::Naive, "crave", FIXTURE),
+            ["crane", "crate", "crave"]
+        );
+    }
+}
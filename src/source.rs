@@ -0,0 +1,488 @@
+//! Where the daily solutions and the accepted guesses come from.
+//!
+//! The bundled lists are used by default, but a deployment with its own rules, such
+//! as a list read from a file or a solution picked by a server, can implement
+//! [`WordSource`] and start games with [`Game::from_source`](crate::game::Game::from_source).
+//!
+//! Besides the [`Bundled`] lists, the dailies can be [`Shuffled`] by a seed, so
+//! reading the bundled list in order doesn't give the coming days away, or come
+//! from a [`WordList`] of their own, read one word per line or from JSON.
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    error::Error,
+    fmt,
+    sync::Arc,
+};
+
+/// Decides the solution for each day, and which words can be guessed
+pub trait WordSource {
+    /// Gets the solution for the given day, counting from the first puzzle
+    fn solution_for_day(&self, day: usize) -> &str;
+
+    /// Determines whether the word can be guessed
+    fn is_acceptable(&self, word: &str) -> bool;
+
+    /// Finds the word that can be guessed in place of one typed without its
+    /// accents, for games that ignore accents. `folded` is already
+    /// [folded](crate::fold_accents). Sources that don't override this only accept
+    /// words typed with their accents
+    fn respell(&self, folded: &str) -> Option<String> {
+        let _ = folded;
+        None
+    }
+
+    /// Words that can be guessed which are a typo away from the word: one letter
+    /// changed, or two letters next to each other swapped. Sources that don't override
+    /// this find none
+    fn near_misses(&self, word: &str) -> Vec<String> {
+        let _ = word;
+        Vec::new()
+    }
+
+    /// Every letter the words are spelt with, once their accents are
+    /// [folded](crate::fold_accents), in alphabetical order. Sources that don't
+    /// override this are taken to be spelt with `a` to `z`
+    fn alphabet(&self) -> Vec<char> {
+        ('a'..='z').collect()
+    }
+}
+
+/// Whether a typo turns one word into the other, as a
+/// [near miss](WordSource::near_misses). A word isn't a near miss of itself
+pub fn near_miss(a: &str, b: &str) -> bool {
+    let (mut a, mut b) = (a.chars(), b.chars());
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) if x == y => continue,
+            // a changed letter, then the rest the same
+            // or the letter swapped with the next one
+            (Some(x), Some(y)) => {
+                return a.clone().eq(b.clone())
+                    || (a.next() == Some(y) && b.next() == Some(x) && a.eq(b))
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// A word source that can be shared between games
+pub type SharedSource = Arc<dyn WordSource + Send + Sync>;
+
+/// Gets the bundled lists for words of the given length, if there are any.
+///
+/// Only [`WORD_LEN`](crate::WORD_LEN) letter words are bundled. Other lengths need
+/// their own [`WordList`].
+pub fn bundled(len: usize) -> Option<SharedSource> {
+    (len == crate::WORD_LEN).then(|| Arc::new(Bundled) as SharedSource)
+}
+
+/// The word lists bundled in [`words`](crate::words)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bundled;
+
+impl WordSource for Bundled {
+    fn solution_for_day(&self, day: usize) -> &str {
+        crate::get_solution(day)
+    }
+
+    fn is_acceptable(&self, word: &str) -> bool {
+        crate::words::ACCEPT.contains(&word) || crate::words::FINAL.contains(&word)
+    }
+
+    fn respell(&self, folded: &str) -> Option<String> {
+        let words = crate::words::FINAL.iter().chain(crate::words::ACCEPT);
+        let word = words
+            .copied()
+            .find(|word| crate::fold_accents(word) == folded)?;
+        Some(word.to_owned())
+    }
+
+    /// Solutions come before the other accepted words, and words in both lists are
+    /// only offered once
+    fn near_misses(&self, word: &str) -> Vec<String> {
+        let mut near = crate::words::FINAL
+            .iter()
+            .filter(|solution| near_miss(word, solution))
+            .map(|&solution| solution.to_owned())
+            .collect::<Vec<_>>();
+        let accepted = crate::words::ACCEPT
+            .iter()
+            .filter(|listed| near_miss(word, listed) && !near.iter().any(|near| near == *listed))
+            .map(|&listed| listed.to_owned())
+            .collect::<Vec<_>>();
+        near.extend(accepted);
+        near
+    }
+}
+
+/// The bundled lists, with the solutions played in an order decided by a seed.
+///
+/// Everyone with the same seed gets the same word each day, which is the word of
+/// the [tournament](crate::tournament) round of that number with the seed. Every
+/// solution comes round once before any comes round again
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shuffled {
+    seed: String,
+}
+
+impl Shuffled {
+    pub fn new(seed: impl Into<String>) -> Self {
+        Self { seed: seed.into() }
+    }
+}
+
+impl WordSource for Shuffled {
+    fn solution_for_day(&self, day: usize) -> &str {
+        crate::tournament::solution(&self.seed, day)
+    }
+
+    fn is_acceptable(&self, word: &str) -> bool {
+        Bundled.is_acceptable(word)
+    }
+
+    fn respell(&self, folded: &str) -> Option<String> {
+        Bundled.respell(folded)
+    }
+
+    fn near_misses(&self, word: &str) -> Vec<String> {
+        Bundled.near_misses(word)
+    }
+}
+
+/// Solutions taken in order, one per day, from a list given at runtime
+#[derive(Clone, Debug)]
+pub struct WordList {
+    solutions: Vec<String>,
+    accepted: HashSet<String>,
+}
+
+impl WordList {
+    /// Creates a source from the solutions, in the order they're played, and any
+    /// other words that can be guessed. The solutions can always be guessed.
+    ///
+    /// Returns `None` if there are no solutions
+    pub fn new(
+        solutions: impl IntoIterator<Item = String>,
+        accepted: impl IntoIterator<Item = String>,
+    ) -> Option<Self> {
+        let solutions = solutions.into_iter().collect::<Vec<_>>();
+        if solutions.is_empty() {
+            return None;
+        }
+        let mut accepted = accepted.into_iter().collect::<HashSet<_>>();
+        accepted.extend(solutions.iter().cloned());
+        Some(Self {
+            solutions,
+            accepted,
+        })
+    }
+
+    /// Reads a dictionary with one word per line, which are both the solutions, in
+    /// order, and the accepted guesses.
+    ///
+    /// Words are lowercased and blank lines are skipped, as are words already listed,
+    /// so no solution comes round twice as often. Every word must be `len` letters long
+    pub fn parse(input: &str, len: usize) -> Result<Self, ParseWordListError> {
+        Self::new(parse_words(input, len)?, []).ok_or(ParseWordListError::Empty)
+    }
+
+    /// Reads a JSON object with the `solutions`, in the order they're played, and
+    /// any other `accepted` guesses, like
+    /// `{"solutions": ["termo", "sagaz"], "accepted": ["tarso"]}`.
+    ///
+    /// The words are checked as by [`WordList::parse`], with the `line` of an error
+    /// counting the words of its list from 1
+    #[cfg(feature = "serde")]
+    pub fn from_json(input: &str, len: usize) -> Result<Self, ParseWordListError> {
+        #[derive(serde::Deserialize)]
+        struct Lists {
+            solutions: Vec<String>,
+            #[serde(default)]
+            accepted: Vec<String>,
+        }
+
+        let lists: Lists =
+            serde_json::from_str(input).map_err(|err| ParseWordListError::Json(err.to_string()))?;
+        let solutions = parse_words(&lists.solutions.join("\n"), len)?;
+        let words = Self::new(solutions, []).ok_or(ParseWordListError::Empty)?;
+        words.with_accepted(&lists.accepted.join("\n"))
+    }
+
+    /// The solutions, in the order they're played
+    pub fn solutions(&self) -> &[String] {
+        &self.solutions
+    }
+
+    /// Also accepts the guesses in a list read like [`WordList::parse`], which must be
+    /// as long as the solutions. An empty list adds nothing
+    pub fn with_accepted(mut self, input: &str) -> Result<Self, ParseWordListError> {
+        let len = self.solutions[0].chars().count();
+        self.accepted.extend(parse_words(input, len)?);
+        Ok(self)
+    }
+}
+
+/// Reads one word per line, checking each is `len` letters, and dropping repeats
+fn parse_words(input: &str, len: usize) -> Result<Vec<String>, ParseWordListError> {
+    let mut words = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, line) in input.lines().enumerate() {
+        let word = line.trim().to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        if !crate::game::well_formed(&word) {
+            return Err(ParseWordListError::NotLetters { line: i + 1, word });
+        }
+        if word.chars().count() != len {
+            return Err(ParseWordListError::WrongLength { line: i + 1, word });
+        }
+        if seen.insert(word.clone()) {
+            words.push(word);
+        }
+    }
+    Ok(words)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a dictionary could not be read by [`WordList::parse`] or
+/// [`WordList::from_json`]
+pub enum ParseWordListError {
+    /// The word on this line isn't made of letters
+    NotLetters { line: usize, word: String },
+    /// The word on this line isn't as long as the game's words
+    WrongLength { line: usize, word: String },
+    /// There are no words to pick a solution from
+    Empty,
+    /// The JSON isn't an object with a list of solutions, with what's wrong with it
+    Json(String),
+}
+
+impl fmt::Display for ParseWordListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWordListError::NotLetters { line, word } => {
+                write!(f, "line {}: {} is not a word", line, word)
+            }
+            ParseWordListError::WrongLength { line, word } => {
+                write!(f, "line {}: {} is the wrong length", line, word)
+            }
+            ParseWordListError::Empty => write!(f, "no words in the dictionary"),
+            ParseWordListError::Json(err) => write!(f, "not a word list: {}", err),
+        }
+    }
+}
+
+impl Error for ParseWordListError {}
+
+impl WordSource for WordList {
+    fn solution_for_day(&self, day: usize) -> &str {
+        &self.solutions[day % self.solutions.len()]
+    }
+
+    fn is_acceptable(&self, word: &str) -> bool {
+        self.accepted.contains(word)
+    }
+
+    /// Solutions are looked through first, then the least of the other accepted
+    /// spellings, so it's the same one every time
+    fn respell(&self, folded: &str) -> Option<String> {
+        let solution = self
+            .solutions
+            .iter()
+            .find(|word| crate::fold_accents(word) == folded);
+        let respelled = solution.or_else(|| {
+            let accepted = self.accepted.iter();
+            accepted
+                .filter(|word| crate::fold_accents(word) == folded)
+                .min()
+        });
+        respelled.cloned()
+    }
+
+    /// Solutions come first, in the order they're played, then the other accepted
+    /// words in alphabetical order
+    fn near_misses(&self, word: &str) -> Vec<String> {
+        let mut near = self
+            .solutions
+            .iter()
+            .filter(|solution| near_miss(word, solution))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut others = self
+            .accepted
+            .iter()
+            .filter(|listed| near_miss(word, listed) && !near.contains(listed))
+            .cloned()
+            .collect::<Vec<_>>();
+        others.sort();
+        near.extend(others);
+        near
+    }
+
+    fn alphabet(&self) -> Vec<char> {
+        let mut letters = BTreeSet::new();
+        for word in &self.accepted {
+            letters.extend(crate::fold_accents(word).chars());
+        }
+        letters.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{near_miss, Bundled, ParseWordListError, Shuffled, WordList, WordSource};
+    use crate::words;
+
+    #[test]
+    fn bundled() {
+        assert_eq!(Bundled.solution_for_day(0), "termo");
+        assert!(Bundled.is_acceptable("sagaz"));
+        assert!(!Bundled.is_acceptable("xyzzy"));
+    }
+
+    #[test]
+    fn shuffled() {
+        let shuffled = Shuffled::new("abc");
+        assert_eq!(
+            shuffled.solution_for_day(1),
+            crate::tournament::solution("abc", 1)
+        );
+        assert_eq!(
+            Shuffled::new("abc").solution_for_day(1),
+            shuffled.solution_for_day(1)
+        );
+        let days = (0..words::FINAL.len()).map(|day| shuffled.solution_for_day(day));
+        // every solution comes round once, just not in the bundled order
+        assert_eq!(
+            days.clone().collect::<HashSet<_>>().len(),
+            words::FINAL.len()
+        );
+        assert!(days.zip(words::FINAL).any(|(day, bundled)| day != *bundled));
+        assert!(shuffled.is_acceptable("sagaz"));
+        assert!(!shuffled.is_acceptable("xyzzy"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn from_json() {
+        let list = WordList::from_json(
+            r#"{"solutions": ["Gato", "pato"], "accepted": ["rato"]}"#,
+            4,
+        )
+        .unwrap();
+        assert_eq!(list.solutions(), ["gato", "pato"]);
+        assert!(list.is_acceptable("rato"));
+        assert!(WordList::from_json(r#"{"solutions": ["gato"]}"#, 4).is_ok());
+
+        assert_eq!(
+            WordList::from_json(r#"{"solutions": ["gato", "ratos"]}"#, 4).unwrap_err(),
+            ParseWordListError::WrongLength {
+                line: 2,
+                word: "ratos".to_owned()
+            }
+        );
+        assert_eq!(
+            WordList::from_json(r#"{"solutions": []}"#, 4).unwrap_err(),
+            ParseWordListError::Empty
+        );
+        assert!(matches!(
+            WordList::from_json(r#"["gato"]"#, 4),
+            Err(ParseWordListError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn word_list() {
+        let list = WordList::new(
+            ["xyzzy".to_owned(), "plugh".to_owned()],
+            ["sagaz".to_owned()],
+        )
+        .unwrap();
+        assert_eq!(list.solution_for_day(3), "plugh");
+        assert!(list.is_acceptable("xyzzy"));
+        assert!(list.is_acceptable("sagaz"));
+        assert!(!list.is_acceptable("termo"));
+
+        assert!(WordList::new([], []).is_none());
+    }
+
+    #[test]
+    fn alphabets_are_spelt_without_accents() {
+        assert_eq!(Bundled.alphabet().len(), 26);
+        let list = WordList::parse(
+            "λέξεις
+λόγους
+",
+            6,
+        )
+        .unwrap();
+        let alphabet = list.alphabet().into_iter().collect::<String>();
+        assert_eq!(alphabet, "γειλξοςυ");
+    }
+
+    #[test]
+    fn near_misses() {
+        assert!(near_miss("craen", "crane"));
+        assert!(near_miss("crane", "crate"));
+        assert!(near_miss("leoes", "leões"));
+        assert!(!near_miss("crane", "crane"));
+        assert!(!near_miss("crane", "caner"));
+        assert!(!near_miss("crane", "cranes"));
+
+        assert!(Bundled.near_misses("temro").contains(&"termo".to_owned()));
+        // alias is both a solution and an accepted word
+        let near = Bundled.near_misses("alisa");
+        assert_eq!(near.iter().filter(|word| *word == "alias").count(), 1);
+        let list = WordList::new(
+            ["pato".to_owned(), "gato".to_owned()],
+            ["rato".to_owned(), "mato".to_owned(), "pata".to_owned()],
+        )
+        .unwrap();
+        assert_eq!(list.near_misses("xato"), ["pato", "gato", "mato", "rato"]);
+        assert_eq!(list.near_misses("ptao"), ["pato"]);
+    }
+
+    #[test]
+    fn parse() {
+        let list = WordList::parse("Gato\n\n  pato  \ngato\n", 4).unwrap();
+        assert_eq!(list.solution_for_day(0), "gato");
+        assert_eq!(list.solution_for_day(1), "pato");
+        // the repeated gato was dropped
+        assert_eq!(list.solution_for_day(2), "gato");
+        assert!(list.is_acceptable("pato"));
+
+        let list = list.with_accepted("rato\nMato\n").unwrap();
+        assert!(list.is_acceptable("mato"));
+        assert_eq!(list.solution_for_day(2), "gato");
+        assert_eq!(
+            list.with_accepted("ratos\n").unwrap_err(),
+            ParseWordListError::WrongLength {
+                line: 1,
+                word: "ratos".to_owned()
+            }
+        );
+
+        assert_eq!(
+            WordList::parse("gato\nratos\n", 4).unwrap_err(),
+            ParseWordListError::WrongLength {
+                line: 2,
+                word: "ratos".to_owned()
+            }
+        );
+        assert_eq!(
+            WordList::parse("g4to\n", 4).unwrap_err(),
+            ParseWordListError::NotLetters {
+                line: 1,
+                word: "g4to".to_owned()
+            }
+        );
+        assert_eq!(
+            WordList::parse("\n  \n", 4).unwrap_err(),
+            ParseWordListError::Empty
+        );
+    }
+}
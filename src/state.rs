@@ -1,17 +1,89 @@
-use std::fmt;
+use std::{borrow::Cow, error::Error, fmt, sync::Arc};
 
-use crate::{Matches, valid};
+use crate::{game::Score, Match, Matches};
+use crate::source::{Bundled, SharedSource};
 
 pub struct State {
     solution: String,
-    guesses: Vec<String>,
+    /// Decides which words can be guessed
+    source: SharedSource,
+    /// Alternative spellings that also count as the solution
+    aliases: Vec<String>,
+    /// Whether a word can be guessed more than once
+    allow_repeats: bool,
+    /// Whether guesses must use every hint revealed so far
+    hard_mode: bool,
+    /// Whether guesses must also fit everything else known, so a grey letter can't be
+    /// played again and an amber one can't go back where it was
+    ultra_hard: bool,
+    /// Whether letters match whatever their accents, so words can be typed without them
+    ignore_accents: bool,
+    /// The game is lost once this many guesses have missed
+    max_guesses: usize,
+    /// Each guess with how it matched, scored once when it's made so drawing the
+    /// board doesn't score every guess again
+    guesses: Vec<(String, Matches)>,
+    /// Whether the game was given up, which loses it whatever the guesses were
+    given_up: bool,
+    /// Picks how each guess matches, in place of a fixed solution
+    adversary: Option<Adversarial>,
+    /// Each letter given away by [`State::reveal`], with the number of guesses made
+    /// before it
+    revealed: Vec<(usize, Reveal)>,
+}
+
+/// Decides how each guess matches as it's made, keeping as many words possible as it
+/// can, instead of scoring guesses against a solution picked up front, as in Absurdle.
+///
+/// It only gives in once a single word is left and that's the guess.
+pub struct Adversarial {
+    /// Every word it could pick, before any guesses
+    words: &'static [&'static str],
+    /// The words that fit every guess so far
+    candidates: Vec<&'static str>,
+}
+
+impl Adversarial {
+    /// Callers must ensure there's at least one word
+    pub(crate) fn new(words: &'static [&'static str]) -> Self {
+        Self { words, candidates: words.to_vec() }
+    }
+
+    /// Number of words that still fit every guess
+    pub fn remaining(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Groups the candidates by how the guess would match them, and keeps the biggest
+    /// group. Ties go to the group with the fewest exact, then close, matches, so the
+    /// guess only wins when there's nothing else left
+    fn respond(&mut self, guess: &str) -> Matches {
+        let mut groups: Vec<(Matches, Vec<&'static str>)> = Vec::new();
+        for &word in &self.candidates {
+            let matches = crate::diff(guess, word);
+            match groups.iter_mut().find(|(m, _)| *m == matches) {
+                Some((_, words)) => words.push(word),
+                None => groups.push((matches, vec![word])),
+            }
+        }
+        let count = |Matches(m): &Matches, of| m.iter().filter(|&&m| m == of).count();
+        let (matches, words) = groups
+            .into_iter()
+            .rev()
+            .max_by_key(|(m, words)| {
+                let (exact, close) = (count(m, Match::Exact), count(m, Match::Close));
+                (words.len(), std::cmp::Reverse((exact, close)))
+            })
+            .expect("there's always a candidate left");
+        self.candidates = words;
+        matches
+    }
 }
 
 pub struct Guess<'a>(pub &'a str, pub Matches);
 
 pub struct StateIter<'a> {
-    solution: &'a str,
-    guesses: std::slice::Iter<'a, String>,
+    guesses: std::slice::Iter<'a, (String, Matches)>,
 }
 
 impl<'a> Iterator for StateIter<'a> {
@@ -20,64 +92,505 @@ impl<'a> Iterator for StateIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         self.guesses
             .next()
-            .map(|input| Guess(&*input, crate::diff(&*input, self.solution)))
+            .map(|(input, matches)| Guess(input, matches.clone()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.guesses.size_hint()
     }
 }
 
-pub struct GuessError;
+impl<'a> DoubleEndedIterator for StateIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.guesses
+            .next_back()
+            .map(|(input, matches)| Guess(input, matches.clone()))
+    }
+}
+
+impl<'a> ExactSizeIterator for StateIter<'a> {}
+
+/// A letter of the solution given away by [`State::reveal`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reveal {
+    /// The letter is somewhere in the solution
+    Present(char),
+    /// The letter is at this position, counting from 0
+    Placed(usize, char),
+}
+
+impl Reveal {
+    pub fn letter(self) -> char {
+        match self {
+            Reveal::Present(letter) | Reveal::Placed(_, letter) => letter,
+        }
+    }
+}
+
+/// Like `E is in the word` or `E is letter 2`, counting letters from 1
+impl fmt::Display for Reveal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = self.letter().to_uppercase();
+        match self {
+            Reveal::Present(_) => write!(f, "{} is in the word", letter),
+            Reveal::Placed(position, _) => write!(f, "{} is letter {}", letter, position + 1),
+        }
+    }
+}
+
+/// What's known for certain to be wrong about a guess, checked in ultra hard mode on
+/// top of hard mode's greens and ambers
+#[derive(Debug, Default)]
+struct Knowledge {
+    /// Letters known not to be at a position, from an amber or grey there
+    not_at: Vec<(char, usize)>,
+    /// The most copies of a letter the solution can have, once a grey has shown it
+    most: Vec<(char, usize)>,
+}
+
+impl Knowledge {
+    fn limit(&mut self, letter: char, most: usize) {
+        match self.most.iter_mut().find(|(c, _)| *c == letter) {
+            Some((_, known)) => *known = most.min(*known),
+            None => self.most.push((letter, most)),
+        }
+    }
+
+    fn check(&self, word: &str) -> Result<(), GuessError> {
+        let letters = word.chars().collect::<Vec<_>>();
+        for &(letter, most) in &self.most {
+            if letters.iter().filter(|&&l| l == letter).count() > most {
+                return Err(GuessError::ExcessLetter { letter, most });
+            }
+        }
+        for (position, &letter) in letters.iter().enumerate() {
+            if self.not_at.contains(&(letter, position)) {
+                return Err(GuessError::MisplacedLetter { letter, position });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Reasons a guess was rejected.
+///
+/// The [`Display`](fmt::Display) message is short enough to show a player as is,
+/// such as `need 5 letters, got 4` or `E must be letter 2`.
+pub enum GuessError {
+    /// The word is not in the word lists
+    NotInWordList,
+    /// The word is not as long as the solution. Lengths are in letters, not bytes
+    WrongLength { expected: usize, got: usize },
+    /// The word was already guessed, and repeats are not allowed
+    AlreadyGuessed,
+    /// In hard mode, the word doesn't use a revealed hint. The position is where a
+    /// green letter had to stay, or `None` for an amber letter that had to be reused
+    HardModeViolation { letter: char, position: Option<usize> },
+    /// In ultra hard mode, the word puts a letter where a guess already showed it isn't
+    MisplacedLetter { letter: char, position: usize },
+    /// In ultra hard mode, the word has more of a letter than a guess already showed
+    /// the solution has, which is none for a letter that was only ever grey
+    ExcessLetter { letter: char, most: usize },
+    /// The game was already won or lost
+    GameOver,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::NotInWordList => f.write_str("not in the word list"),
+            GuessError::WrongLength { expected, got } => {
+                write!(f, "need {} letters, got {}", expected, got)
+            }
+            GuessError::AlreadyGuessed => f.write_str("already guessed"),
+            // letters are shown uppercase, as they are on the board
+            GuessError::HardModeViolation { letter, position: Some(i) } => {
+                write!(f, "{} must be letter {}", letter.to_uppercase(), i + 1)
+            }
+            GuessError::HardModeViolation { letter, position: None } => {
+                write!(f, "must contain {}", letter.to_uppercase())
+            }
+            GuessError::MisplacedLetter { letter, position } => {
+                write!(f, "{} can't be letter {}", letter.to_uppercase(), position + 1)
+            }
+            GuessError::ExcessLetter { letter, most: 0 } => {
+                write!(f, "{} is not in the word", letter.to_uppercase())
+            }
+            GuessError::ExcessLetter { letter, most } => {
+                write!(f, "only {} {} in the word", most, letter.to_uppercase())
+            }
+            GuessError::GameOver => f.write_str("the game is over"),
+        }
+    }
+}
+
+impl Error for GuessError {}
 
 impl State {
-    pub fn new(solution: String) -> Self {
+    pub(crate) fn new(solution: String) -> Self {
         Self {
             solution,
-            guesses: Vec::with_capacity(6),
+            source: Arc::new(Bundled),
+            aliases: Vec::new(),
+            allow_repeats: true,
+            hard_mode: false,
+            ultra_hard: false,
+            ignore_accents: false,
+            max_guesses: crate::MAX_GUESSES,
+            guesses: Vec::with_capacity(crate::MAX_GUESSES),
+            given_up: false,
+            adversary: None,
+            revealed: Vec::new(),
+        }
+    }
+
+    /// A game with no solution until the adversary is forced into one, and no limit
+    /// on the guesses. The first candidate stands in as the solution until then
+    pub(crate) fn adversarial(words: &'static [&'static str]) -> Self {
+        let mut state = Self::new(words[0].to_owned());
+        state.max_guesses = usize::MAX;
+        state.adversary = Some(Adversarial::new(words));
+        state
+    }
+
+    /// Adds alternative spellings of the solution. Callers must ensure they're the same length,
+    /// and that they're added before any guesses, as guesses are scored when they're made
+    pub(crate) fn add_aliases(&mut self, aliases: impl IntoIterator<Item = String>) {
+        self.aliases.extend(aliases);
+    }
+
+    pub(crate) fn set_source(&mut self, source: SharedSource) {
+        self.source = source;
+    }
+
+    pub(crate) fn set_allow_repeats(&mut self, allow_repeats: bool) {
+        self.allow_repeats = allow_repeats;
+    }
+
+    pub(crate) fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    pub(crate) fn set_ultra_hard(&mut self, ultra_hard: bool) {
+        self.ultra_hard = ultra_hard;
+    }
+
+    pub(crate) fn set_ignore_accents(&mut self, ignore_accents: bool) {
+        self.ignore_accents = ignore_accents;
+    }
+
+    /// Callers must ensure it's at least 1
+    pub(crate) fn set_max_guesses(&mut self, max_guesses: usize) {
+        self.max_guesses = max_guesses;
+    }
+
+    /// Adversarial games start over from every candidate too
+    pub(crate) fn clear_guesses(&mut self) {
+        self.guesses.clear();
+        self.revealed.clear();
+        if let Some(adversary) = &mut self.adversary {
+            *adversary = Adversarial::new(adversary.words);
+            self.solution = adversary.words[0].to_owned();
+        }
+    }
+
+    /// Whether guesses must use every hint revealed so far, which they also must in
+    /// ultra hard mode
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode || self.ultra_hard
+    }
+
+    pub fn ultra_hard(&self) -> bool {
+        self.ultra_hard
+    }
+
+    pub fn ignore_accents(&self) -> bool {
+        self.ignore_accents
+    }
+
+    /// Whether a word can be guessed again, as it can by default
+    pub fn allows_repeats(&self) -> bool {
+        self.allow_repeats
+    }
+
+    /// The letters the word lists are spelt with, as
+    /// [`WordSource::alphabet`](crate::source::WordSource::alphabet) gives them
+    pub fn alphabet(&self) -> Vec<char> {
+        self.source.alphabet()
+    }
+
+    /// The word as it's compared: without its accents if they're
+    /// [ignored](State::ignore_accents), or as it is
+    pub fn fold<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        if self.ignore_accents {
+            Cow::Owned(crate::fold_accents(word))
+        } else {
+            Cow::Borrowed(word)
+        }
+    }
+
+    /// Number of guesses allowed before the game is lost, or `usize::MAX` if
+    /// [unlimited](State::unlimited)
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+
+    /// Whether the game goes on until it's won, as an adversarial one does
+    pub fn unlimited(&self) -> bool {
+        self.max_guesses == usize::MAX
+    }
+
+    /// Rows a board needs: one for each guess allowed, or with unlimited guesses, one
+    /// for each guess made and one more for the next while it's still being played
+    pub fn rows(&self) -> usize {
+        if self.unlimited() {
+            self.guesses.len() + usize::from(self.game_over().is_none())
+        } else {
+            self.max_guesses
         }
     }
 
+    /// What decides the matches, in an adversarial game
+    pub fn adversary(&self) -> Option<&Adversarial> {
+        self.adversary.as_ref()
+    }
+
     pub fn solution(&self) -> &str {
-        &*self.solution
+        &self.solution
+    }
+
+    /// Number of letters in the solution
+    pub fn word_len(&self) -> usize {
+        self.solution.chars().count()
+    }
+
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// Determines if the word is the solution or one of its aliases
+    pub fn is_solution(&self, word: &str) -> bool {
+        word == self.solution || self.aliases.iter().any(|alias| alias == word)
+    }
+
+    /// Scores the word against the solution. Aliases of the solution are an exact match
+    pub fn diff(&self, word: &str) -> Matches {
+        if self.aliases.iter().any(|alias| alias == word) {
+            Matches(vec![Match::Exact; self.word_len()])
+        } else {
+            crate::diff(&self.fold(word), &self.fold(&self.solution))
+        }
+    }
+
+    /// The listed spelling of a word typed without its accents, like `leões` for
+    /// `leoes`, when accents are ignored. Anything else is kept as it was typed
+    fn respell<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        if !self.ignore_accents || self.source.is_acceptable(word) || self.is_solution(word) {
+            return Cow::Borrowed(word);
+        }
+        let folded = crate::fold_accents(word);
+        let mut solutions = std::iter::once(&self.solution).chain(&self.aliases);
+        match solutions.find(|solution| crate::fold_accents(solution) == folded) {
+            Some(solution) => Cow::Owned(solution.clone()),
+            None => self.source.respell(&folded).map_or(Cow::Borrowed(word), Cow::Owned),
+        }
     }
 
     pub fn guesses(&self) -> StateIter<'_> {
         StateIter {
-            solution: &*self.solution(),
             guesses: self.guesses.iter(),
         }
     }
 
+    /// When accents are ignored, the word is guessed with its listed spelling, which
+    /// is the one the guesses give back
     pub fn guess(&mut self, word: &str) -> Result<Matches, GuessError> {
-        if valid(word) {
-            self.guesses.push(word.to_owned());
-            Ok(crate::diff(word, &*self.solution))
+        let word = &*self.respell(word);
+        self.check(word)?;
+        let matches = match &mut self.adversary {
+            Some(adversary) => {
+                let matches = adversary.respond(word);
+                self.solution = adversary.candidates[0].to_owned();
+                matches
+            }
+            None => self.diff(word),
+        };
+        self.guesses.push((word.to_owned(), matches.clone()));
+        Ok(matches)
+    }
+
+    /// Checks the word would be accepted as the next guess, without guessing it
+    pub fn check(&self, word: &str) -> Result<(), GuessError> {
+        let word = &*self.respell(word);
+        if self.game_over().is_some() {
+            return Err(GuessError::GameOver);
+        }
+        let (expected, got) = (self.word_len(), word.chars().count());
+        if got != expected {
+            return Err(GuessError::WrongLength { expected, got });
+        }
+        if !self.source.is_acceptable(word) && !self.is_solution(word) {
+            return Err(GuessError::NotInWordList);
+        }
+        if !self.allow_repeats && self.guesses.iter().any(|(guess, _)| guess == word) {
+            return Err(GuessError::AlreadyGuessed);
+        }
+        if self.hard_mode() {
+            self.check_hints(word)?;
+        }
+        if self.ultra_hard {
+            self.knowledge().check(&self.fold(word))?;
+        }
+        Ok(())
+    }
+
+    /// Up to `limit` words a typo away from the word, as
+    /// [near misses](crate::source::WordSource::near_misses), that could be guessed
+    /// next in its place
+    pub fn suggestions(&self, word: &str, limit: usize) -> Vec<String> {
+        let near = self.source.near_misses(word).into_iter();
+        near.filter(|listed| self.check(listed).is_ok())
+            .take(limit)
+            .collect()
+    }
+
+    /// Everything the guesses so far have shown about where the solution's letters
+    /// aren't, and how many of them there are at most
+    fn knowledge(&self) -> Knowledge {
+        let mut knowledge = Knowledge::default();
+        for Guess(input, Matches(matches)) in self.guesses() {
+            let letters = self.fold(input).chars().collect::<Vec<_>>();
+            for (i, (&c, m)) in letters.iter().zip(&matches).enumerate() {
+                if *m != Match::Exact {
+                    knowledge.not_at.push((c, i));
+                }
+                if *m == Match::Wrong {
+                    // a grey means the other copies in this guess are all there are
+                    let found = letters
+                        .iter()
+                        .zip(&matches)
+                        .filter(|&(&l, m)| l == c && *m != Match::Wrong)
+                        .count();
+                    knowledge.limit(c, found);
+                }
+            }
+        }
+        knowledge
+    }
+
+    /// Checks the word keeps every green letter in place, and reuses every amber letter.
+    ///
+    /// Letters are counted per guess, so a single amber `e` only needs one `e` again,
+    /// and a letter that was amber can be reused as a green.
+    fn check_hints(&self, word: &str) -> Result<(), GuessError> {
+        let letters = self.fold(word).chars().collect::<Vec<_>>();
+        for Guess(input, Matches(matches)) in self.guesses() {
+            let mut revealed = Vec::with_capacity(5);
+            for (i, (c, m)) in self.fold(input).chars().zip(matches).enumerate() {
+                match m {
+                    Match::Exact if letters.get(i) != Some(&c) => {
+                        return Err(GuessError::HardModeViolation { letter: c, position: Some(i) });
+                    }
+                    Match::Exact | Match::Close => revealed.push(c),
+                    Match::Wrong => {}
+                }
+            }
+            for &c in &revealed {
+                let needed = revealed.iter().filter(|&&r| r == c).count();
+                if letters.iter().filter(|&&l| l == c).count() < needed {
+                    return Err(GuessError::HardModeViolation { letter: c, position: None });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Gives away a letter of the solution: the first one that isn't placed yet, by a
+    /// green or an earlier reveal. A letter that isn't known to be in the solution
+    /// yet is only said to be in it, and revealing again places it.
+    ///
+    /// `None` once the game is over or every letter is placed, and in an adversarial
+    /// game, which has no solution to give away
+    pub fn reveal(&mut self) -> Option<Reveal> {
+        if self.game_over().is_some() || self.adversary.is_some() {
+            return None;
+        }
+        let fold = |letter: char| self.fold(&letter.to_string()).chars().next();
+        let placed = |i: usize| {
+            self.guesses().any(|Guess(_, Matches(m))| m.get(i) == Some(&Match::Exact))
+                || self.revealed().any(|r| matches!(r, Reveal::Placed(at, _) if at == i))
+        };
+        let known = |letter: char| {
+            self.guesses().any(|Guess(input, Matches(matches))| {
+                let mut letters = self.fold(input).chars().zip(matches).collect::<Vec<_>>();
+                letters.retain(|&(_, m)| m != Match::Wrong);
+                letters.iter().any(|&(l, _)| Some(l) == fold(letter))
+            }) || self.revealed().any(|r| fold(r.letter()) == fold(letter))
+        };
+        let (position, letter) = self.solution.chars().enumerate().find(|&(i, _)| !placed(i))?;
+        let reveal = if known(letter) {
+            Reveal::Placed(position, letter)
         } else {
-            Err(GuessError)
+            Reveal::Present(letter)
+        };
+        self.revealed.push((self.guesses.len(), reveal));
+        Some(reveal)
+    }
+
+    /// The letters given away by [`State::reveal`], in the order they were
+    pub fn revealed(&self) -> impl Iterator<Item = Reveal> + '_ {
+        self.revealed.iter().map(|&(_, reveal)| reveal)
+    }
+
+    /// How many guesses had been made before each reveal, in the order they were
+    pub(crate) fn revealed_after(&self) -> impl Iterator<Item = usize> + '_ {
+        self.revealed.iter().map(|&(after, _)| after)
+    }
+
+    /// Takes back the last guess, returning it, or `None` if there were none yet. An
+    /// adversarial game goes back to the words that fit the guesses left
+    pub(crate) fn undo(&mut self) -> Option<String> {
+        let (word, _) = self.guesses.pop()?;
+        if let Some(adversary) = &mut self.adversary {
+            *adversary = Adversarial::new(adversary.words);
+            for (guess, _) in &self.guesses {
+                adversary.respond(guess);
+            }
+            self.solution = adversary.candidates[0].to_owned();
         }
+        Some(word)
+    }
+
+    /// Ends the game as a loss, unless it's already over
+    pub fn give_up(&mut self) {
+        self.given_up |= self.game_over().is_none();
     }
 
+    /// `Some(true)` once won, `Some(false)` once lost, or `None` while still playing
     pub fn game_over(&self) -> Option<bool> {
-        let last = &self.guesses[self.guesses.len() - 1];
-        if last == &self.solution {
+        if self.given_up {
+            return Some(false);
+        }
+        let (last, _) = self.guesses.last()?;
+        if self.is_solution(last) {
             Some(true)
-        } else if self.guesses.len() >= 6 {
+        } else if self.guesses.len() >= self.max_guesses {
             Some(false)
         } else {
             None
         }
     }
 
-    pub fn display_score_card(&self, mut w: impl fmt::Write) -> fmt::Result {
-        let Self { solution, guesses } = self;
-        let n = guesses.len();
-        let score = if n < 6 || &guesses[5] == solution {
-            std::char::from_digit(n as u32, 10).ok_or(fmt::Error)?
+    /// The number of guesses taken, or [`Score::Failed`] if the game was lost
+    pub fn score(&self) -> Score {
+        let n = self.guesses.len();
+        if self.given_up {
+            Score::Failed
+        } else if n < self.max_guesses || self.is_solution(&self.guesses[n - 1].0) {
+            Score::Guesses(n as u8)
         } else {
-            'X'
-        };
-
-        write!(w, "{score}/6\n",)?;
-        for Guess(_, m) in self.guesses() {
-            write!(w, "\n{}", m)?;
+            Score::Failed
         }
-        Ok(())
     }
 }
@@ -0,0 +1,811 @@
+//! Results of the daily games played, and the statistics derived from them
+
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    error::Error,
+    fmt,
+    time::Duration,
+};
+
+use crate::{
+    game::{self, Elapsed, GameShare, GameType, Score},
+    Match, Matches,
+};
+
+/// Longest bar drawn in the guess distribution
+const BAR_WIDTH: usize = 20;
+
+/// The first line of [`Stats::to_csv`], naming the columns
+const CSV_HEADER: &str = "day,guesses,seconds";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// The result of every daily game played, one per day.
+///
+/// Only the results are kept, and everything else is derived from them, so the
+/// saved format is just one line per game: the day and the number of guesses,
+/// or `X` for a loss, then the seconds taken if the game was timed and the first
+/// guess if it was kept.
+pub struct Stats {
+    games: BTreeMap<usize, Option<u8>>,
+    /// The fastest time for each timed day
+    times: BTreeMap<usize, Duration>,
+    /// The first guess of each day's game, for the days it was kept
+    openers: BTreeMap<usize, String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// How the games that started with the same guess went, from [`Stats::openers`]
+pub struct OpenerStats {
+    pub word: String,
+    pub played: usize,
+    pub won: usize,
+    /// Mean number of guesses the wins took, or `None` if none were won
+    pub average_guesses: Option<f64>,
+}
+
+impl Stats {
+    /// Reads results saved by [`Display`](fmt::Display). Lines that can't be read are skipped
+    pub fn parse(saved: &str) -> Self {
+        let mut stats = Self::default();
+        let lines = saved.lines().filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let day = parts.next()?.parse().ok()?;
+            let guesses = match parts.next()? {
+                "X" => None,
+                n => Some(n.parse().ok()?),
+            };
+            // the seconds are left out of untimed games, so the opener can come first
+            let (mut seconds, mut opener) = (None, None);
+            for part in parts {
+                match part.parse() {
+                    Ok(n) => seconds = Some(n),
+                    Err(_) => opener = Some(part.to_owned()),
+                }
+            }
+            Some((day, guesses, seconds, opener))
+        });
+        for (day, guesses, seconds, opener) in lines {
+            stats.games.insert(day, guesses);
+            if let Some(seconds) = seconds {
+                stats.times.insert(day, Duration::from_secs(seconds));
+            }
+            if let Some(opener) = opener {
+                stats.openers.insert(day, opener);
+            }
+        }
+        stats
+    }
+
+    /// Records the result of the day's game, with `None` for a loss.
+    ///
+    /// Only the first result for each day counts, so replaying a day doesn't change it.
+    pub fn record(&mut self, day: usize, guesses: Option<u8>) {
+        self.games.entry(day).or_insert(guesses);
+    }
+
+    /// Records how long the day's game took, keeping the fastest. Only whole seconds
+    /// are kept, and days without a recorded result are ignored
+    pub fn record_time(&mut self, day: usize, time: Duration) {
+        if !self.games.contains_key(&day) {
+            return;
+        }
+        let time = Duration::from_secs(time.as_secs());
+        let fastest = self.times.entry(day).or_insert(time);
+        *fastest = time.min(*fastest);
+    }
+
+    /// Records the first guess of the day's game. As with [`Stats::record`] only the
+    /// first one counts, and days without a recorded result are ignored
+    pub fn record_opener(&mut self, day: usize, opener: &str) {
+        if self.games.contains_key(&day) {
+            let opener = opener.to_lowercase();
+            self.openers.entry(day).or_insert(opener);
+        }
+    }
+
+    /// Adds the days from `other` that aren't recorded here yet, for bringing in stats
+    /// from another machine, and returns how many there were.
+    ///
+    /// A day that's in both keeps the result recorded here, as with [`Stats::record`],
+    /// and the faster of the two times, and an opener only comes with the days added.
+    /// Streaks are worked out from the merged days
+    pub fn merge(&mut self, other: &Stats) -> usize {
+        let mut added = 0;
+        for (&day, &guesses) in &other.games {
+            if let Entry::Vacant(entry) = self.games.entry(day) {
+                entry.insert(guesses);
+                if let Some(opener) = other.openers.get(&day) {
+                    self.openers.insert(day, opener.clone());
+                }
+                added += 1;
+            }
+        }
+        for (&day, &time) in &other.times {
+            self.record_time(day, time);
+        }
+        added
+    }
+
+    /// Every game as a line of comma separated values, after a header naming the
+    /// columns: the day, the guesses or `X` for a loss, and the seconds if timed.
+    /// The openers are left out, for spreadsheets made from the earlier columns
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("{}\n", CSV_HEADER);
+        for (day, guesses) in &self.games {
+            let guesses = guesses.map_or("X".to_owned(), |n| n.to_string());
+            let seconds = self.times.get(day).map(|time| time.as_secs().to_string());
+            out.push_str(&format!(
+                "{},{},{}\n",
+                day,
+                guesses,
+                seconds.unwrap_or_default()
+            ));
+        }
+        out
+    }
+
+    /// Reads the output of [`Stats::to_csv`]. Unlike [`Stats::parse`], a line that
+    /// can't be read is an error, so nothing is lost without saying
+    pub fn parse_csv(input: &str) -> Result<Self, ParseStatsError> {
+        let mut stats = Self::default();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (i == 0 && line == CSV_HEADER) {
+                continue;
+            }
+            let error = ParseStatsError::Csv { line: i + 1 };
+            let mut fields = line.split(',').map(str::trim);
+            let day = fields
+                .next()
+                .and_then(|day| day.parse().ok())
+                .ok_or(error)?;
+            let guesses = match fields.next().ok_or(error)? {
+                "X" | "x" => None,
+                n => Some(n.parse().ok().filter(possible).ok_or(error)?),
+            };
+            let seconds = match fields.next() {
+                None | Some("") => None,
+                Some(seconds) => Some(seconds.parse().map_err(|_| error)?),
+            };
+            if fields.next().is_some() {
+                return Err(error);
+            }
+            stats.games.insert(day, guesses);
+            if let Some(seconds) = seconds {
+                stats.times.insert(day, Duration::from_secs(seconds));
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Reads the daily results out of shares pasted one after another, either this
+    /// game's own or official ones like `Wordle 1,234 4/6*`. Anything between them
+    /// that isn't a header or a row of squares, like names and times from a chat or a
+    /// message that starts with `Wordle ` without a score, is skipped.
+    ///
+    /// Hard mode is accepted but not kept, as the stats don't record it. A grid has to
+    /// agree with its score, which has to be out of at most 6 guesses, and only the
+    /// first share of each day counts
+    pub fn from_shares(input: &str) -> Result<Self, ParseStatsError> {
+        let mut stats = Self::default();
+        // the header line of the share being read, its result and its grid so far
+        let mut share: Option<(usize, SharedResult, Vec<Matches>)> = None;
+        for (i, line) in game::normalize(input).lines().enumerate() {
+            let line = line.trim();
+            let header = match line.split_once("Wordle ") {
+                Some(("", header)) => parse_official(header),
+                // a share copied out of a chat can have who sent it in front
+                Some((_, header)) => parse_official(header).flatten().map(Some),
+                None if line.starts_with(game::HEADER) => Some(parse_own(line)),
+                None => None,
+            };
+            match (header, &mut share) {
+                (Some(header), _) => {
+                    let header = header.ok_or(ParseStatsError::Header { line: i + 1 })?;
+                    if let Some(done) = share.replace((i + 1, header, Vec::new())) {
+                        stats.record_share(done)?;
+                    }
+                }
+                (None, Some((_, _, grid))) => grid.extend(game::parse_row(line)),
+                (None, None) => {}
+            }
+        }
+        if let Some(done) = share {
+            stats.record_share(done)?;
+        }
+        Ok(stats)
+    }
+
+    fn record_share(
+        &mut self,
+        (line, shared, grid): (usize, SharedResult, Vec<Matches>),
+    ) -> Result<(), ParseStatsError> {
+        let solved = |m: &Matches| m.0.iter().all(|&m| m == Match::Exact);
+        let rows = match shared.guesses {
+            Some(n) => n as usize,
+            None => shared.max_guesses,
+        };
+        let agrees = grid.len() == rows
+            && grid.last().is_some_and(solved) == shared.guesses.is_some()
+            && !grid[..rows.saturating_sub(1)].iter().any(solved);
+        if !grid.is_empty() && !agrees {
+            return Err(ParseStatsError::Grid { line });
+        }
+        self.record(shared.day, shared.guesses);
+        Ok(())
+    }
+
+    /// Every game as a JSON array of objects with the `day`, the `guesses` or null for
+    /// a loss, the `seconds` or null if it wasn't timed, and the `opener` if it was kept
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        let records = self.games.iter().map(|(&day, &guesses)| Record {
+            day,
+            guesses,
+            seconds: self.times.get(&day).map(Duration::as_secs),
+            opener: self.openers.get(&day).cloned(),
+        });
+        serde_json::to_string(&records.collect::<Vec<_>>()).expect("stats are always serializable")
+    }
+
+    /// Reads the output of [`Stats::to_json`]
+    #[cfg(feature = "serde")]
+    pub fn from_json(input: &str) -> Result<Self, serde_json::Error> {
+        let records: Vec<Record> = serde_json::from_str(input)?;
+        let mut stats = Self::default();
+        for Record {
+            day,
+            guesses,
+            seconds,
+            opener,
+        } in records
+        {
+            if let Some(n) = guesses.filter(|n| !possible(n)) {
+                return Err(serde::de::Error::custom(format!(
+                    "day {} took {} guesses, expected 1 to {}",
+                    day,
+                    n,
+                    crate::MAX_GUESSES
+                )));
+            }
+            stats.games.insert(day, guesses);
+            if let Some(seconds) = seconds {
+                stats.times.insert(day, Duration::from_secs(seconds));
+            }
+            if let Some(opener) = opener {
+                stats.openers.insert(day, opener);
+            }
+        }
+        Ok(stats)
+    }
+
+    /// The quickest of the timed games that were won
+    pub fn fastest_win(&self) -> Option<Duration> {
+        self.times
+            .iter()
+            .filter(|(day, _)| matches!(self.games.get(day), Some(Some(_))))
+            .map(|(_, &time)| time)
+            .min()
+    }
+
+    /// The result recorded for the day, if it was played
+    pub fn result(&self, day: usize) -> Option<Option<u8>> {
+        self.games.get(&day).copied()
+    }
+
+    /// The latest day with a recorded result. Dailies before it can't be recorded
+    /// any more, even where the clock says it's still that day, so moving to an
+    /// earlier time zone doesn't replay a day or fill in a missed one
+    pub fn last_day(&self) -> Option<usize> {
+        self.games.keys().next_back().copied()
+    }
+
+    /// The result of the day's daily, if it's been played. Without one, a later day
+    /// counts as the day's, as the day can't be played once it has been, like in a
+    /// time zone that's ahead
+    pub fn daily_result(&self, day: usize) -> Option<Option<u8>> {
+        if let Some(result) = self.result(day) {
+            return Some(result);
+        }
+        let last = self.last_day().filter(|&last| last >= day)?;
+        self.result(last)
+    }
+
+    /// The current streak, if it runs up to the day before and the day is still to
+    /// be played, so it ends unless the day is won
+    pub fn streak_at_risk(&self, day: usize) -> Option<usize> {
+        let streak = self.current_streak();
+        (streak > 0 && self.last_day()? + 1 == day).then_some(streak)
+    }
+
+    pub fn played(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn won(&self) -> usize {
+        self.games
+            .values()
+            .filter(|guesses| guesses.is_some())
+            .count()
+    }
+
+    /// Percentage of games won, rounded down
+    pub fn win_percentage(&self) -> usize {
+        (self.won() * 100).checked_div(self.played()).unwrap_or(0)
+    }
+
+    /// Mean number of guesses the wins took, or `None` before there are any
+    pub fn average_guesses(&self) -> Option<f64> {
+        let wins = self.games.values().flatten().map(|&n| n as usize);
+        let (count, total) = wins.fold((0, 0), |(count, total), n| (count + 1, total + n));
+        (count > 0).then(|| total as f64 / count as f64)
+    }
+
+    /// Wins in a row up to the last day played
+    pub fn current_streak(&self) -> usize {
+        self.streaks().last().unwrap_or(0)
+    }
+
+    pub fn max_streak(&self) -> usize {
+        self.streaks().max().unwrap_or(0)
+    }
+
+    /// The streak of wins leading up to each day played. A loss or a missed day
+    /// starts the streak over
+    fn streaks(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut last = None;
+        let mut streak = 0;
+        self.games.iter().map(move |(&day, guesses)| {
+            let follows = last.is_some_and(|last| last + 1 == day);
+            streak = match guesses {
+                Some(_) if follows => streak + 1,
+                Some(_) => 1,
+                None => 0,
+            };
+            last = Some(day);
+            streak
+        })
+    }
+
+    /// How the games went for each first guess, the most played first and ties in
+    /// alphabetical order. Only the days whose opener was kept count
+    pub fn openers(&self) -> Vec<OpenerStats> {
+        let mut openers = BTreeMap::<&str, Vec<Option<u8>>>::new();
+        for (day, opener) in &self.openers {
+            if let Some(&guesses) = self.games.get(day) {
+                openers.entry(opener).or_default().push(guesses);
+            }
+        }
+        let mut openers: Vec<_> = openers
+            .into_iter()
+            .map(|(word, games)| {
+                let wins = games.iter().flatten().map(|&n| n as usize);
+                let (won, total) = wins.fold((0, 0), |(won, total), n| (won + 1, total + n));
+                OpenerStats {
+                    word: word.to_owned(),
+                    played: games.len(),
+                    won,
+                    average_guesses: (won > 0).then(|| total as f64 / won as f64),
+                }
+            })
+            .collect();
+        // the sort is stable, so the alphabetical order is kept between ties
+        openers.sort_by_key(|opener| std::cmp::Reverse(opener.played));
+        openers
+    }
+
+    /// How many games were won in each number of guesses from 1 to 6, followed by
+    /// the number of losses
+    pub fn distribution(&self) -> [usize; 7] {
+        let mut distribution = [0; 7];
+        for guesses in self.games.values() {
+            let i = match guesses {
+                Some(n) => (*n as usize).clamp(1, 6) - 1,
+                None => 6,
+            };
+            distribution[i] += 1;
+        }
+        distribution
+    }
+
+    /// The statistics on one line, like `played 6, won 83%, current streak 1, max streak 2`
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "played {}, won {}%, current streak {}, max streak {}",
+            self.played(),
+            self.win_percentage(),
+            self.current_streak(),
+            self.max_streak(),
+        );
+        if let Some(fastest) = self.fastest_win() {
+            out.push_str(&format!(", fastest win {}", Elapsed(fastest)));
+        }
+        out
+    }
+
+    /// Lays out the statistics and the guess distribution for printing
+    pub fn render(&self) -> String {
+        let mut out = self.summary();
+        out.push('\n');
+        let distribution = self.distribution();
+        let most = distribution.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in distribution.iter().enumerate() {
+            let label = match i {
+                6 => 'X',
+                i => char::from(b'1' + i as u8),
+            };
+            // even an empty row gets a sliver of a bar, so it still reads as a row
+            let bar = (count * BAR_WIDTH).div_ceil(most).max(1);
+            out.push_str(&format!("\n{} {} {}", label, "█".repeat(bar), count));
+        }
+        out
+    }
+}
+
+/// A day's game in [`Stats::to_json`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Record {
+    day: usize,
+    guesses: Option<u8>,
+    #[serde(default)]
+    seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    opener: Option<String>,
+}
+
+/// A day's result, read from the first line of a share
+struct SharedResult {
+    day: usize,
+    /// `None` for a loss
+    guesses: Option<u8>,
+    max_guesses: usize,
+}
+
+/// The rest of an official share's first line after `Wordle `, like `1,234 4/6*`.
+/// Thousands are separated with commas, dots or spaces depending on the language.
+///
+/// `None` if it isn't a score at all, and `Some(None)` if it's a score that can't be
+/// a daily's, like `7/6` or `4/8`
+fn parse_official(header: &str) -> Option<Option<SharedResult>> {
+    let (day, score) = header.trim().rsplit_once(' ')?;
+    let day = day.trim();
+    if !day.starts_with(|c: char| c.is_ascii_digit())
+        || !day
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ',' | '.' | ' '))
+    {
+        return None;
+    }
+    let day = day.chars().filter(char::is_ascii_digit).collect::<String>();
+    let score = score.strip_suffix('*').unwrap_or(score);
+    let (guesses, max) = score.split_once('/')?;
+    let result = || {
+        let max_guesses = max
+            .parse()
+            .ok()
+            .filter(|&max| max > 0 && max <= crate::MAX_GUESSES)?;
+        let guesses = match guesses {
+            "X" => None,
+            n => Some(
+                n.parse()
+                    .ok()
+                    .filter(|&n| n > 0 && n as usize <= max_guesses)?,
+            ),
+        };
+        Some(SharedResult {
+            day: day.parse().ok()?,
+            guesses,
+            max_guesses,
+        })
+    };
+    Some(result())
+}
+
+/// The first line of this game's share, which has to be of a daily with a score
+fn parse_own(header: &str) -> Option<SharedResult> {
+    let share = GameShare::parse(header).ok()?;
+    let (day, score) = match (share.game_type(), share.score()?) {
+        (&GameType::Daily(day), score) if share.max_guesses() <= crate::MAX_GUESSES => (day, score),
+        _ => return None,
+    };
+    let guesses = match score {
+        Score::Guesses(n) => Some(n),
+        Score::Failed => None,
+    };
+    Some(SharedResult {
+        day,
+        guesses,
+        max_guesses: share.max_guesses(),
+    })
+}
+
+/// Whether a daily could have been won in this many guesses, for the imports
+fn possible(guesses: &u8) -> bool {
+    (1..=crate::MAX_GUESSES).contains(&usize::from(*guesses))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Reasons stats being imported couldn't be read. Lines count from 1
+pub enum ParseStatsError {
+    /// A line of a CSV file that isn't a day's result
+    Csv { line: usize },
+    /// The first line of a share that isn't a daily with a score
+    Header { line: usize },
+    /// A share whose grid doesn't agree with its score, by the line it starts on
+    Grid { line: usize },
+}
+
+impl fmt::Display for ParseStatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseStatsError::Csv { line } => write!(
+                f,
+                "line {}: expected a day, the guesses or X, and the seconds if timed",
+                line
+            ),
+            ParseStatsError::Header { line } => {
+                write!(f, "line {}: expected a daily share with a score", line)
+            }
+            ParseStatsError::Grid { line } => {
+                write!(f, "line {}: the grid doesn't match the score", line)
+            }
+        }
+    }
+}
+
+impl Error for ParseStatsError {}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (day, guesses) in &self.games {
+            match guesses {
+                Some(n) => write!(f, "{} {}", day, n)?,
+                None => write!(f, "{} X", day)?,
+            }
+            if let Some(time) = self.times.get(day) {
+                write!(f, " {}", time.as_secs())?;
+            }
+            match self.openers.get(day) {
+                Some(opener) => writeln!(f, " {}", opener)?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use test_case::test_case;
+
+    use super::{ParseStatsError, Stats};
+
+    fn fixture() -> Stats {
+        let mut stats = Stats::default();
+        for (day, guesses) in [
+            (1, Some(3)),
+            (2, Some(4)),
+            (3, None),
+            (4, Some(2)),
+            (5, Some(3)),
+        ] {
+            stats.record(day, guesses);
+        }
+        // missing day 6 breaks the streak
+        stats.record(7, Some(6));
+        stats
+    }
+
+    #[test]
+    fn aggregates() {
+        let stats = fixture();
+        assert_eq!(stats.played(), 6);
+        assert_eq!(stats.win_percentage(), 83);
+        assert_eq!(stats.current_streak(), 1);
+        assert_eq!(stats.max_streak(), 2);
+        assert_eq!(stats.distribution(), [0, 1, 2, 1, 0, 1, 1]);
+        assert_eq!(stats.average_guesses(), Some(3.6));
+        assert_eq!(Stats::default().average_guesses(), None);
+    }
+
+    #[test]
+    fn knows_whether_the_day_was_played() {
+        let stats = fixture();
+        assert_eq!(stats.daily_result(7), Some(Some(6)));
+        // a day that was played has its own result, and a missed one the later day's
+        assert_eq!(stats.daily_result(3), Some(None));
+        assert_eq!(stats.daily_result(6), Some(Some(6)));
+        assert_eq!(stats.daily_result(8), None);
+        assert_eq!(stats.streak_at_risk(8), Some(1));
+        // the streak already ended on a missed day, or is safe once the day is played
+        assert_eq!(stats.streak_at_risk(9), None);
+        assert_eq!(stats.streak_at_risk(7), None);
+        let mut lost = Stats::default();
+        lost.record(1, None);
+        assert_eq!(lost.streak_at_risk(2), None);
+    }
+
+    #[test]
+    fn first_result_counts() {
+        let mut stats = fixture();
+        stats.record(3, Some(1));
+        assert_eq!(stats.result(3), Some(None));
+        assert_eq!(stats.result(6), None);
+        assert_eq!(stats.last_day(), Some(7));
+        assert_eq!(Stats::default().last_day(), None);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let stats = fixture();
+        let saved = stats.to_string();
+        assert_eq!(saved, "1 3\n2 4\n3 X\n4 2\n5 3\n7 6\n");
+        assert_eq!(Stats::parse(&saved), stats);
+        assert_eq!(Stats::parse("junk\n1 3\n2 q\n"), Stats::parse("1 3\n"));
+    }
+
+    #[test]
+    fn averages_each_opener() {
+        let mut stats = fixture();
+        for (day, opener) in [(1, "SLATE"), (2, "crane"), (3, "slate"), (5, "slate")] {
+            stats.record_opener(day, opener);
+        }
+        // only the first opener of a day counts, and only on days played
+        stats.record_opener(1, "crane");
+        stats.record_opener(6, "crane");
+        let openers = stats.openers();
+        let summary = openers.iter().map(|opener| {
+            let average = opener.average_guesses;
+            (opener.word.as_str(), opener.played, opener.won, average)
+        });
+        assert_eq!(
+            summary.collect::<Vec<_>>(),
+            [("slate", 3, 2, Some(3.0)), ("crane", 1, 1, Some(4.0))]
+        );
+
+        stats.record_time(2, Duration::from_secs(47));
+        let saved = stats.to_string();
+        assert!(saved.starts_with("1 3 slate\n2 4 47 crane\n3 X slate\n4 2\n"));
+        assert_eq!(Stats::parse(&saved), stats);
+        let mut merged = Stats::default();
+        merged.record(1, Some(5));
+        merged.merge(&stats);
+        assert_eq!(merged.openers()[0].played, 2);
+    }
+
+    #[test]
+    fn merge_keeps_the_days_already_recorded() {
+        let mut stats = fixture();
+        stats.record_time(2, Duration::from_secs(60));
+        let mut other = Stats::default();
+        // day 3 was lost here, so the win elsewhere doesn't count twice
+        other.record(3, Some(2));
+        other.record(6, Some(4));
+        other.record(2, Some(4));
+        other.record_time(2, Duration::from_secs(30));
+        assert_eq!(stats.merge(&other), 1);
+        assert_eq!(stats.played(), 7);
+        assert_eq!(stats.result(3), Some(None));
+        assert_eq!(stats.fastest_win(), Some(Duration::from_secs(30)));
+        // day 6 joins the streaks either side of it
+        assert_eq!(stats.current_streak(), 4);
+        assert_eq!(stats.merge(&other), 0);
+    }
+
+    #[test]
+    fn csv_roundtrip() {
+        let mut stats = fixture();
+        stats.record_time(2, Duration::from_secs(47));
+        let csv = stats.to_csv();
+        assert!(csv.starts_with("day,guesses,seconds\n1,3,\n2,4,47\n3,X,\n"));
+        assert_eq!(Stats::parse_csv(&csv), Ok(stats));
+        assert_eq!(
+            Stats::parse_csv("1,3\n2 4"),
+            Err(ParseStatsError::Csv { line: 2 })
+        );
+        assert_eq!(
+            Stats::parse_csv("1,3,,\n"),
+            Err(ParseStatsError::Csv { line: 1 })
+        );
+        assert_eq!(
+            Stats::parse_csv("3,0,\n"),
+            Err(ParseStatsError::Csv { line: 1 })
+        );
+        assert_eq!(
+            Stats::parse_csv("1,3,\n3,200,\n"),
+            Err(ParseStatsError::Csv { line: 2 })
+        );
+    }
+
+    #[test]
+    fn results_from_shares() {
+        let input = "\
+[10:02] Ana: Wordle 1,234 3/6*
+
+⬛🟨⬛⬛⬛
+⬜🟩🟩⬛🟨
+🟩🟩🟩🟩🟩
+Wordle 512 X/6
+Wordle de hoje foi difícil
+joguei term.ooo #7 2/6
+
+🟨🟨⬛⬛⬛
+🟩🟩🟩🟩🟩
+Wordle 1.235 4/6";
+        let stats = Stats::from_shares(input).unwrap();
+        assert_eq!(stats.played(), 4);
+        assert_eq!(stats.result(1234), Some(Some(3)));
+        assert_eq!(stats.result(512), Some(None));
+        assert_eq!(stats.result(7), Some(Some(2)));
+        assert_eq!(stats.result(1235), Some(Some(4)));
+        assert_eq!(stats.current_streak(), 2);
+    }
+
+    #[test_case("Wordle 512 3/6\n🟩🟩🟩🟩🟩", ParseStatsError::Grid { line: 1 }; "too few rows")]
+    #[test_case("\nWordle 512 X/6\n⬛⬛⬛⬛⬛", ParseStatsError::Grid { line: 2 }; "loss")]
+    #[test_case("Wordle 512 7/6", ParseStatsError::Header { line: 1 }; "score over max")]
+    #[test_case("Wordle 512 4/8", ParseStatsError::Header { line: 1 }; "more than 6 guesses")]
+    #[test_case("joguei term.ooo #practice 3 2/6", ParseStatsError::Header { line: 1 }; "practice")]
+    fn shares_that_dont_add_up(input: &str, error: ParseStatsError) {
+        assert_eq!(Stats::from_shares(input), Err(error));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json_roundtrip() {
+        let mut stats = fixture();
+        stats.record_time(2, Duration::from_secs(47));
+        stats.record_opener(7, "slate");
+        let json = stats.to_json();
+        assert!(json.starts_with(
+            r#"[{"day":1,"guesses":3,"seconds":null},{"day":2,"guesses":4,"seconds":47},"#
+        ));
+        assert_eq!(Stats::from_json(&json).unwrap(), stats);
+        assert_eq!(
+            Stats::from_json(r#"[{"day":3,"guesses":null}]"#)
+                .unwrap()
+                .result(3),
+            Some(None)
+        );
+        for guesses in [0, 7] {
+            let json = format!(r#"[{{"day":3,"guesses":{}}}]"#, guesses);
+            assert!(Stats::from_json(&json).is_err());
+        }
+    }
+
+    #[test]
+    fn fastest_win() {
+        let mut stats = fixture();
+        stats.record_time(2, Duration::from_secs(95));
+        stats.record_time(2, Duration::from_millis(47_500));
+        stats.record_time(2, Duration::from_secs(60));
+        // losses and days not played don't count
+        stats.record_time(3, Duration::from_secs(10));
+        stats.record_time(6, Duration::from_secs(5));
+        assert_eq!(stats.fastest_win(), Some(Duration::from_secs(47)));
+
+        let saved = stats.to_string();
+        assert!(saved.starts_with("1 3\n2 4 47\n3 X 10\n"));
+        assert_eq!(Stats::parse(&saved), stats);
+        assert!(stats
+            .render()
+            .starts_with("played 6, won 83%, current streak 1, max streak 2, fastest win 00:47\n"));
+    }
+
+    #[test]
+    fn render() {
+        let expected = "played 6, won 83%, current streak 1, max streak 2
+
+1 █ 0
+2 ██████████ 1
+3 ████████████████████ 2
+4 ██████████ 1
+5 █ 0
+6 ██████████ 1
+X ██████████ 1";
+        assert_eq!(fixture().render(), expected);
+    }
+}
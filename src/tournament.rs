@@ -0,0 +1,100 @@
+//! Deterministic puzzle sequences for group competitions
+//!
+//! Everyone using the same seed plays the same solutions in the same order.
+//! The sequence is a shuffle of [`words::FINAL`], so no solution repeats until every
+//! word has been played. The shuffle only uses fixed-width integer arithmetic, so it's
+//! the same on every platform, but it will change if the word list does.
+
+use crate::words;
+
+/// Gets the index into [`words::FINAL`] of the solution for the given round
+pub fn solution_index(seed: &str, round: usize) -> usize {
+    let len = words::FINAL.len();
+    let mut order: Vec<usize> = (0..len).collect();
+
+    // fisher-yates, driven by splitmix64 seeded with the fnv-1a hash of the seed
+    let mut rng = SplitMix64(fnv1a(seed.as_bytes()));
+    for i in (1..len).rev() {
+        let j = (rng.next() % (i as u64 + 1)) as usize;
+        order.swap(i, j);
+    }
+
+    order[round % len]
+}
+
+/// Gets the solution for the given round
+pub fn solution(seed: &str, round: usize) -> &'static str {
+    words::FINAL[solution_index(seed, round)]
+}
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`, with every one equally likely. Draws from the top of the
+    /// range that would favour the smaller numbers are thrown away
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solution_index, SplitMix64};
+    use crate::words;
+    use test_case::test_case;
+
+    #[test_case("abc", 1, 920)]
+    #[test_case("abc", 3, 508)]
+    #[test_case("office", 1, 1121)]
+    fn test_solution_index(seed: &str, round: usize, index: usize) {
+        assert_eq!(solution_index(seed, round), index);
+    }
+
+    #[test]
+    fn test_rounds_dont_repeat() {
+        let mut seen = vec![false; words::FINAL.len()];
+        for round in 0..words::FINAL.len() {
+            let i = solution_index("abc", round);
+            assert!(!seen[i], "round {} repeats a solution", round);
+            seen[i] = true;
+        }
+    }
+
+    #[test]
+    fn test_below_is_in_range() {
+        let mut rng = SplitMix64(7);
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            seen[rng.below(3) as usize] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+        // only the top of the range is rejected, so nearly every draw is kept as is
+        assert_eq!(SplitMix64(1).below(1000), SplitMix64(1).next() % 1000);
+        // about half of all draws fall past the last whole multiple, and are drawn again
+        let n = u64::MAX / 2 + 2;
+        assert!((0..100).all(|seed| SplitMix64(seed).below(n) < n));
+    }
+}
@@ -0,0 +1,176 @@
+//! Spaced repetition over a deck of solutions that are easy to get stuck on
+
+use std::fmt;
+
+use crate::game::Score;
+
+/// Solutions that tend to take the most guesses, because of repeated or rare letters
+pub const DECK: &[&str] = &[
+    "ossos", "adaga", "urubu", "abada", "errar", "amada", "miojo", "texto", "oxala", "peixe",
+    "mexer", "exata", "jarra", "graxa", "pizza", "sagaz", "zerar", "vezes", "jejum", "jegue",
+    "leque", "feixe", "treze", "exodo", "arroz", "jarro", "vazia", "fuzue",
+];
+
+/// Guesses within which a solve counts as quick, moving the word up a level
+const QUICK: usize = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A word in the deck, and when it's next due
+pub struct Card {
+    pub word: String,
+    /// Raised by each quick solve and reset by a loss. Each level doubles the
+    /// number of reviews until the word is seen again
+    pub level: u32,
+    /// The review on which the word is next due
+    pub due: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// When each word of a deck is next due.
+///
+/// Time is counted in reviews rather than days, so practising more often
+/// doesn't make every word due at once.
+pub struct Schedule {
+    reviews: u64,
+    cards: Vec<Card>,
+}
+
+impl Schedule {
+    /// Starts a schedule where every word is due, in the order of the deck
+    pub fn new(deck: &[&str]) -> Self {
+        Self {
+            reviews: 0,
+            cards: deck
+                .iter()
+                .map(|&word| Card {
+                    word: word.to_owned(),
+                    level: 0,
+                    due: 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Restores the progress saved by [`Display`](fmt::Display) for the words in the deck.
+    ///
+    /// Words no longer in the deck are dropped, and lines that can't be read are
+    /// skipped, leaving their word as new.
+    pub fn parse(deck: &[&str], saved: &str) -> Self {
+        let mut schedule = Self::new(deck);
+        for line in saved.lines() {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("reviews"), Some(reviews), None) => {
+                    schedule.reviews = reviews.parse().unwrap_or_default();
+                }
+                (Some(word), Some(level), Some(due)) => {
+                    let card = schedule.cards.iter_mut().find(|card| card.word == word);
+                    if let (Some(card), Ok(level), Ok(due)) = (card, level.parse(), due.parse()) {
+                        card.level = level;
+                        card.due = due;
+                    }
+                }
+                _ => {}
+            }
+        }
+        schedule
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// The word to practise next: the one that's been due the longest, or the first
+    /// in the deck if several are
+    pub fn next(&self) -> Option<&str> {
+        self.cards
+            .iter()
+            .min_by_key(|card| card.due)
+            .map(|card| card.word.as_str())
+    }
+
+    /// Records how the word went and schedules it again.
+    ///
+    /// A quick solve moves the word up a level, a slow one keeps it where it is,
+    /// and a loss sends it back to the start so it comes up again on the next review.
+    pub fn review(&mut self, word: &str, score: Score) {
+        self.reviews += 1;
+        let card = match self.cards.iter_mut().find(|card| card.word == word) {
+            Some(card) => card,
+            None => return,
+        };
+        let interval = match score.guesses() {
+            Some(n) => {
+                if n <= QUICK {
+                    card.level += 1;
+                }
+                2 << card.level.min(16)
+            }
+            None => {
+                card.level = 0;
+                1
+            }
+        };
+        card.due = self.reviews + interval;
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "reviews {}", self.reviews)?;
+        for card in &self.cards {
+            writeln!(f, "{} {} {}", card.word, card.level, card.due)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schedule, DECK};
+    use crate::game::Score;
+
+    const FIXTURE: &[&str] = &["ossos", "adaga", "urubu"];
+
+    #[test]
+    fn deck_is_playable() {
+        for word in DECK {
+            assert!(crate::valid(word), "{} is not in the word lists", word);
+        }
+    }
+
+    #[test]
+    fn struggles_resurface_sooner() {
+        let mut schedule = Schedule::new(FIXTURE);
+        assert_eq!(schedule.next(), Some("ossos"));
+
+        schedule.review("ossos", Score::Guesses(2));
+        assert_eq!(schedule.next(), Some("adaga"));
+        schedule.review("adaga", Score::Failed);
+        assert_eq!(schedule.next(), Some("urubu"));
+        schedule.review("urubu", Score::Guesses(5));
+
+        // adaga was lost, so it's due straight away, while ossos waits for its level
+        assert_eq!(schedule.next(), Some("adaga"));
+        let due = schedule
+            .cards()
+            .iter()
+            .map(|card| card.due)
+            .collect::<Vec<_>>();
+        assert_eq!(due, [5, 3, 5]);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut schedule = Schedule::new(FIXTURE);
+        schedule.review("adaga", Score::Guesses(1));
+        let saved = schedule.to_string();
+        assert_eq!(saved, "reviews 1\nossos 0 0\nadaga 1 5\nurubu 0 0\n");
+        assert_eq!(Schedule::parse(FIXTURE, &saved), schedule);
+
+        // words dropped from the deck are forgotten, and new ones start fresh
+        let parsed = Schedule::parse(&["adaga", "errar"], &saved);
+        assert_eq!(parsed.next(), Some("errar"));
+        assert_eq!(parsed.cards()[0].level, 1);
+    }
+}
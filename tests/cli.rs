@@ -0,0 +1,357 @@
+//! Runs the binary, to check what it prints once a game is over
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+/// The binary, with a home of its own, so the config file and data of whoever runs
+/// the tests don't change what it does
+fn wordle() -> Command {
+    let home = std::env::temp_dir().join(format!("wordle-home-{}", std::process::id()));
+    let mut command = Command::new(env!("CARGO_BIN_EXE_wordle"));
+    command
+        .env("HOME", &home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_DATA_HOME", home.join(".local").join("share"));
+    command
+}
+
+#[test]
+fn guess_runs_print_only_the_share_to_stdout() {
+    let output = wordle()
+        .args(["--guess", "sagaz", "--guess", "termo", "custom", "termo"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "joguei term.ooo #custom 2/6\n\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩\n"
+    );
+}
+
+#[test]
+fn games_typed_in_end_with_the_share_on_stdout() {
+    use std::{io::Write, process::Stdio};
+
+    let mut child = wordle()
+        .args(["--no-tui", "custom", "termo"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"sagaz\ntermo\n").unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // the rows as they're guessed, with no colours or cursor moves in the pipe
+    assert!(!stdout.contains('\x1b'));
+    assert!(stdout.ends_with("\njoguei term.ooo #custom 2/6\n\n⬛⬛⬛⬛⬛\n🟩🟩🟩🟩🟩\n"));
+}
+
+#[test]
+fn the_score_is_out_of_the_guesses_allowed() {
+    let output = wordle()
+        .args(["--guesses", "8", "--guess", "termo", "custom", "termo"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("joguei term.ooo #custom 1/8\n"));
+}
+
+#[test]
+fn the_share_follows_its_template() {
+    let output = wordle()
+        .args([
+            "--share-template",
+            "Termo {day}: {score}\\n{grid}",
+            "--white-squares",
+        ])
+        .args(["--guess", "sagaz", "--guess", "termo", "custom", "termo"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "Termo custom: 2/6\n⬜⬜⬜⬜⬜\n🟩🟩🟩🟩🟩\n");
+}
+
+#[test]
+fn seed_codes_play_their_word() {
+    let output = wordle().args(["seed", "termo"]).output().unwrap();
+    assert!(output.status.success());
+    let code = String::from_utf8(output.stdout).unwrap();
+    let code = code.trim_end();
+    assert!(!code.to_lowercase().contains("termo"));
+
+    let output = wordle()
+        .args(["--guess", "termo", "play", code])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        format!("joguei term.ooo #seed {} 1/6\n\n🟩🟩🟩🟩🟩\n", code)
+    );
+}
+
+#[test]
+fn next_counts_down_to_midnight() {
+    let output = wordle()
+        .args(["--offset", "+00:00", "next"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let left = stdout.trim_end().rsplit(' ').next().unwrap();
+    let parts = left
+        .split(':')
+        .map(|part| part.parse::<u32>().unwrap())
+        .collect::<Vec<_>>();
+    assert!(stdout.starts_with("Termo "));
+    assert!(matches!(parts[..], [h, m, s] if h < 24 && m < 60 && s < 60));
+}
+
+#[test]
+fn status_says_whether_today_was_played() {
+    let dir = std::env::temp_dir().join(format!("wordle-status-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let status = || {
+        wordle()
+            .arg("--data-dir")
+            .arg(&dir)
+            .args(["--offset", "+00:00", "status"])
+            .output()
+            .unwrap()
+    };
+    let output = status();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "not played yet\n"
+    );
+
+    // a day that's ahead of today counts as today's
+    std::fs::write(dir.join("stats"), "99999 4\n").unwrap();
+    let output = status();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "played, 4/6\n");
+}
+
+#[test]
+fn profiles_are_listed_after_no_profile() {
+    let dir = std::env::temp_dir().join(format!("wordle-profiles-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("profiles").join("ana")).unwrap();
+    let output = wordle()
+        .arg("--data-dir")
+        .arg(&dir)
+        .arg("profiles")
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "(no profile)  streak 0, best 0\nana           streak 0, best 0\n"
+    );
+
+    let output = wordle()
+        .args(["--profile", "../ana", "profiles"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn imported_stats_are_exported_again() {
+    let dir = std::env::temp_dir().join(format!("wordle-stats-{}", std::process::id()));
+    let wordle = || {
+        let mut command = wordle();
+        command.arg("--data-dir").arg(&dir);
+        command
+    };
+    let csv = dir.join("export.csv");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&csv, "day,guesses,seconds\n1,3,\n2,X,40\n").unwrap();
+    let import = || {
+        wordle()
+            .args(["stats", "import"])
+            .arg(&csv)
+            .output()
+            .unwrap()
+    };
+    let output = import();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("imported 2 days, 0 were already played\n"));
+    // importing the same days again doesn't count them twice
+    let output = import();
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("imported 0 days, 2 were already played\n"));
+
+    let output = wordle().args(["stats", "export"]).output().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "day,guesses,seconds\n1,3,\n2,X,40\n"
+    );
+}
+
+#[test]
+fn dictionary_games_are_not_the_daily() {
+    use std::{io::Write, process::Stdio};
+
+    let dir = std::env::temp_dir().join(format!("wordle-dict-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("dict.txt"), "pizza\n").unwrap();
+    let mut child = wordle()
+        .arg("--data-dir")
+        .arg(&dir)
+        .arg("--dict")
+        .arg(dir.join("dict.txt"))
+        .arg("--no-tui")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(b"pizza\n").unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    let saved = ["stats", "progress", "share"].map(|file| dir.join(file).exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success());
+    // nothing's kept, and there are no stats to show after it
+    assert_eq!(saved, [false; 3]);
+    let printed = [output.stdout, output.stderr].concat();
+    assert!(!String::from_utf8(printed).unwrap().contains("streak"));
+}
+
+/// Plays `custom termo` with `--pipe`, a guess per line of `input`
+fn pipe(input: &str) -> std::process::Output {
+    use std::{io::Write, process::Stdio};
+
+    let mut child = wordle()
+        .args(["--pipe", "custom", "termo"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(input.as_bytes()).unwrap();
+    drop(stdin);
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn pipe_games_are_not_the_daily() {
+    use std::{io::Write, process::Stdio};
+
+    let dir = std::env::temp_dir().join(format!("wordle-pipe-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    // today's daily is already played, which isn't for the program to hear about
+    std::fs::write(dir.join("stats"), "99999 4\n").unwrap();
+    let mut child = wordle()
+        .arg("--data-dir")
+        .arg(&dir)
+        .arg("--pipe")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all("tarso\n".repeat(6).as_bytes()).unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    let stats = std::fs::read_to_string(dir.join("stats")).unwrap();
+    let saved = ["progress", "share"].map(|file| dir.join(file).exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+    // only the rows, and the solution if it wasn't tarso, go to the program
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.is_empty());
+    assert!(stdout
+        .lines()
+        .all(|line| line.len() == 5 || line.starts_with("LOST ")));
+    assert_eq!(stats, "99999 4\n");
+    assert_eq!(saved, [false; 2]);
+}
+
+#[test]
+fn pipe_games_end_once_won() {
+    let output = pipe("sagaz\nxyzzy\ntermo\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "BBBBB\nINVALID not in the word list\nGGGGG\n"
+    );
+}
+
+#[test]
+fn pipe_games_end_once_lost() {
+    // more guesses than are allowed, so nothing asks for the last one
+    let output = pipe(&"sagaz\n".repeat(7));
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, format!("{}LOST TERMO\n", "BBBBB\n".repeat(6)));
+}
+
+#[test]
+fn too_many_boards_is_a_usage_error() {
+    for args in [&["--boards", "100000"][..], &["multi", "--boards", "0"]] {
+        let output = wordle().args(args).output().unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("expected a number of boards from 1 to 8"));
+    }
+}
+
+#[test]
+fn random_games_dont_ignore_the_dictionary() {
+    let dict = std::env::temp_dir().join(format!("wordle-random-{}.txt", std::process::id()));
+    std::fs::write(&dict, "пицца\n").unwrap();
+    let outputs = ["practice", "speedrun", "marathon"].map(|mode| {
+        wordle()
+            .arg("--dict")
+            .arg(&dict)
+            .arg(mode)
+            .output()
+            .unwrap()
+    });
+    std::fs::remove_file(&dict).unwrap();
+    for output in outputs {
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("--dict can't be used"));
+    }
+}
+
+#[test]
+fn replay_speeds_are_checked() {
+    for speed in ["NaN", "inf", "0", "-1"].map(|speed| format!("--speed={}", speed)) {
+        let output = wordle()
+            .args(["replay", &speed, "missing.replay"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains("expected a speed above 0"));
+    }
+}
+
+#[test]
+fn guesses_given_up_front_dont_warn_about_the_board() {
+    let output = wordle()
+        .args(["--guess", "termo", "custom", "termo"])
+        .env("TERM", "dumb")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("the board is printed"), "{}", stderr);
+}
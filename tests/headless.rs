@@ -0,0 +1,38 @@
+//! Plays whole games through the library alone, as a server or test harness would
+
+use cl_wordle::prelude::*;
+
+#[test]
+fn scripted_loss() {
+    let mut game = Game::custom("termo".to_owned()).unwrap();
+    let script = ["suite", "terra", "sexto", "tempo", "metro", "turma"];
+
+    let mut rows = Vec::new();
+    for word in script {
+        assert_eq!(game.game_over(), None);
+        rows.push(game.guess(word).unwrap());
+    }
+    assert_eq!(game.game_over(), Some(false));
+    assert_eq!(game.guess("termo"), Err(GuessError::GameOver));
+
+    let share = game.share();
+    assert_eq!(share.grid(), rows);
+    assert_eq!(share.score(), Some(Score::Failed));
+    assert_eq!(
+        share.to_string(),
+        "joguei term.ooo #custom X/6\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩⬛⬛\n⬛🟩⬛🟨🟩\n🟩🟩🟨⬛🟩\n🟨🟩🟨🟨🟩\n🟩⬛🟩🟩⬛"
+    );
+}
+
+#[test]
+fn scripted_win() {
+    let mut game = Game::custom("termo".to_owned()).unwrap();
+    assert_eq!(game.guess("xxxxx"), Err(GuessError::NotInWordList));
+    assert_eq!(game.guess("suite").unwrap().to_string(), "⬛⬛⬛🟨🟨");
+    game.guess("termo").unwrap();
+    assert_eq!(game.game_over(), Some(true));
+    assert_eq!(
+        game.share().to_string(),
+        "joguei term.ooo #custom 2/6\n\n⬛⬛⬛🟨🟨\n🟩🟩🟩🟩🟩"
+    );
+}